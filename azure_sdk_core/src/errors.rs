@@ -1,6 +1,7 @@
 use crate::{enumerations::ParsingError, range::ParseError};
 use http::header::ToStrError;
 use hyper::{self, body, Body, StatusCode};
+use std::future::Future;
 use std::io::Error as IOError;
 use std::num;
 use std::num::ParseIntError;
@@ -336,7 +337,7 @@ impl From<failure::Error> for AzureError {
 
 #[inline]
 pub async fn extract_status_headers_and_body(
-    resp: hyper::client::ResponseFuture,
+    resp: impl Future<Output = Result<hyper::Response<Body>, hyper::Error>>,
 ) -> Result<(hyper::StatusCode, hyper::HeaderMap, body::Bytes), AzureError> {
     let res = resp.await?;
     let (head, body) = res.into_parts();
@@ -349,7 +350,7 @@ pub async fn extract_status_headers_and_body(
 
 #[inline]
 pub async fn check_status_extract_headers_and_body(
-    resp: hyper::client::ResponseFuture,
+    resp: impl Future<Output = Result<hyper::Response<Body>, hyper::Error>>,
     expected_status_code: hyper::StatusCode,
 ) -> Result<(hyper::HeaderMap, body::Bytes), AzureError> {
     let (status, headers, body) = extract_status_headers_and_body(resp).await?;
@@ -366,7 +367,7 @@ pub async fn check_status_extract_headers_and_body(
 
 #[inline]
 pub async fn check_status_extract_headers_and_body_as_string(
-    resp: hyper::client::ResponseFuture,
+    resp: impl Future<Output = Result<hyper::Response<Body>, hyper::Error>>,
     expected_status_code: hyper::StatusCode,
 ) -> Result<(hyper::HeaderMap, String), AzureError> {
     let (headers, body) = check_status_extract_headers_and_body(resp, expected_status_code).await?;
@@ -376,7 +377,7 @@ pub async fn check_status_extract_headers_and_body_as_string(
 
 #[inline]
 pub async fn extract_status_and_body(
-    resp: hyper::client::ResponseFuture,
+    resp: impl Future<Output = Result<hyper::Response<Body>, hyper::Error>>,
 ) -> Result<(StatusCode, String), AzureError> {
     let res = resp.await?;
     let status = res.status();
@@ -386,7 +387,7 @@ pub async fn extract_status_and_body(
 
 #[inline]
 pub async fn extract_location_status_and_body(
-    resp: hyper::client::ResponseFuture,
+    resp: impl Future<Output = Result<hyper::Response<Body>, hyper::Error>>,
 ) -> Result<(http::StatusCode, String, String), AzureError> {
     let res = resp.await?;
     let status = res.status();
@@ -400,7 +401,7 @@ pub async fn extract_location_status_and_body(
 
 #[inline]
 pub async fn check_status_extract_body(
-    resp: hyper::client::ResponseFuture,
+    resp: impl Future<Output = Result<hyper::Response<Body>, hyper::Error>>,
     expected_status_code: hyper::StatusCode,
 ) -> Result<String, AzureError> {
     let (status, body) = extract_status_and_body(resp).await?;
@@ -27,6 +27,7 @@ pub const SERVER_ENCRYPTED: &str = "x-ms-server-encrypted";
 pub const BLOB_TYPE: &str = "x-ms-blob-type";
 pub const CONTENT_CRC64: &str = "x-ms-content-crc64";
 pub const BLOB_CONTENT_LENGTH: &str = "x-ms-blob-content-length";
+pub const BLOB_CONTENT_MD5: &str = "x-ms-blob-content-md5";
 pub const BLOB_ACCESS_TIER: &str = "x-ms-access-tier";
 pub const BLOB_SEQUENCE_NUMBER: &str = "x-ms-blob-sequence-number";
 pub const IF_SEQUENCE_NUMBER_LE: &str = "x-ms-if-sequence-number-le";
@@ -39,6 +40,7 @@ pub const DELETE_SNAPSHOTS: &str = "x-ms-delete-snapshots";
 pub const SKU_NAME: &str = "x-ms-sku-name";
 pub const ACCOUNT_KIND: &str = "x-ms-account-kind";
 pub const APPEND_POSITION: &str = "x-ms-blob-condition-appendpos";
+pub const MAX_SIZE: &str = "x-ms-blob-condition-maxsize";
 pub const CACHE_CONTROL: &str = "x-ms-blob-cache-control";
 pub const CONTENT_DISPOSITION: &str = "x-ms-blob-content-disposition";
 pub const ACTIVITY_ID: &str = "x-ms-activity-id";
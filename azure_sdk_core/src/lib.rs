@@ -131,11 +131,11 @@ pub trait ClientRequestIdOption<'a> {
 
 pub trait AppendPositionSupport {
     type O;
-    fn with_append_position(self, append_position: u32) -> Self::O;
+    fn with_append_position(self, append_position: u64) -> Self::O;
 }
 
 pub trait AppendPositionOption {
-    fn append_position(&self) -> Option<u32>;
+    fn append_position(&self) -> Option<u64>;
 
     #[must_use]
     fn add_header(&self, mut builder: Builder) -> Builder {
@@ -146,6 +146,23 @@ pub trait AppendPositionOption {
     }
 }
 
+pub trait MaxSizeSupport {
+    type O;
+    fn with_max_size(self, max_size: u64) -> Self::O;
+}
+
+pub trait MaxSizeOption {
+    fn max_size(&self) -> Option<u64>;
+
+    #[must_use]
+    fn add_header(&self, mut builder: Builder) -> Builder {
+        if let Some(max_size) = self.max_size() {
+            builder = builder.header(MAX_SIZE, max_size);
+        }
+        builder
+    }
+}
+
 pub trait ContentDispositionSupport<'a> {
     type O;
     fn with_content_disposition(self, content_disposition: &'a str) -> Self::O;
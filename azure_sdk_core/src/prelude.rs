@@ -19,11 +19,12 @@ pub use crate::{
     IncludeUncommittedBlobsSupport, IsSynchronousOption, IsSynchronousSupport,
     LeaseBreakPeriodOption, LeaseBreakPeriodRequired, LeaseBreakPeriodSupport,
     LeaseDurationRequired, LeaseDurationSupport, LeaseIdOption, LeaseIdRequired, LeaseIdSupport,
-    MaxResultsOption, MaxResultsSupport, MetadataOption, MetadataSupport, NextMarkerOption,
-    NextMarkerSupport, PageBlobLengthRequired, PageBlobLengthSupport, PrefixOption, PrefixSupport,
-    ProposedLeaseIdOption, ProposedLeaseIdRequired, ProposedLeaseIdSupport, RangeOption,
-    RangeRequired, RangeSupport, SequenceNumberConditionOption, SequenceNumberConditionSupport,
-    SequenceNumberOption, SequenceNumberSupport, SnapshotOption, SnapshotRequired, SnapshotSupport,
+    MaxResultsOption, MaxResultsSupport, MaxSizeOption, MaxSizeSupport, MetadataOption,
+    MetadataSupport, NextMarkerOption, NextMarkerSupport, PageBlobLengthRequired,
+    PageBlobLengthSupport, PrefixOption, PrefixSupport, ProposedLeaseIdOption,
+    ProposedLeaseIdRequired, ProposedLeaseIdSupport, RangeOption, RangeRequired, RangeSupport,
+    SequenceNumberConditionOption, SequenceNumberConditionSupport, SequenceNumberOption,
+    SequenceNumberSupport, SnapshotOption, SnapshotRequired, SnapshotSupport,
     SourceContentMD5Option, SourceContentMD5Support, SourceUrlRequired, SourceUrlSupport,
     StoredAccessPolicy, StoredAccessPolicyList, TimeoutOption, TimeoutSupport, UserAgentOption,
     UserAgentSupport,
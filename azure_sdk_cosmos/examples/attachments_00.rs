@@ -1,5 +1,6 @@
 use azure_sdk_core::prelude::*;
 use azure_sdk_cosmos::prelude::*;
+use futures::stream::TryStreamExt;
 use std::borrow::Cow;
 use std::error::Error;
 #[macro_use]
@@ -133,6 +134,25 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     println!("create slug == {:#?}", resp);
 
+    println!("replacing the content of the slug attachment");
+    let replace_resp = attachment_client
+        .replace_attachment_media()
+        .with_content_type("text/plain")
+        .execute_with_reader(futures::io::Cursor::new(b"GGGGGG".to_vec()))
+        .await?;
+    println!("replace media == {:#?}", replace_resp);
+
+    let get_resp = attachment_client.get_attachment_media().execute().await?;
+    println!("get media == {:#?}", get_resp);
+    let media = get_resp
+        .media
+        .try_fold(Vec::new(), |mut media, chunk| async move {
+            media.extend_from_slice(&chunk);
+            Ok(media)
+        })
+        .await?;
+    println!("media == {:?}", std::str::from_utf8(&media)?);
+
     println!("deleting");
     let resp_delete = attachment_client
         .delete()
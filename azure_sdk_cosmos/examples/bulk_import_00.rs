@@ -0,0 +1,62 @@
+use azure_sdk_cosmos::prelude::*;
+use std::error::Error;
+#[macro_use]
+extern crate serde_derive;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct MySampleStruct {
+    id: String,
+    a_string: String,
+    a_number: u64,
+}
+
+// This example imports the specified number of documents in a collection partitioned
+// on /id.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+    let count = std::env::args()
+        .nth(3)
+        .map(|count| count.parse::<u64>())
+        .unwrap_or(Ok(1000))?;
+
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+    let client = ClientBuilder::new(account, authorization_token)?;
+    let client = client.with_database_client(database_name);
+    let client = client.with_collection_client(collection_name);
+
+    let documents = (0..count)
+        .map(|i| MySampleStruct {
+            id: format!("bulk{}", i),
+            a_string: "Something here".to_owned(),
+            a_number: i,
+        })
+        .collect::<Vec<_>>();
+
+    let response = client
+        .bulk_executor()
+        .with_is_upsert(true)
+        .import(&documents)
+        .await?;
+
+    println!(
+        "imported {} documents consuming {} RU ({} requests throttled)",
+        response.results.len() - response.failed().count(),
+        response.charge,
+        response.throttled_requests
+    );
+    for failed in response.failed() {
+        println!("{} failed: {:?}", failed.id, failed.result);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use azure_sdk_cosmos::prelude::*;
+use futures::stream::StreamExt;
+use std::error::Error;
+
+// This example reads the changes of a collection since the checkpoint saved in the
+// file passed as third parameter (if present) and saves the new checkpoint after
+// each batch of documents.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+    let checkpoint_file = std::env::args()
+        .nth(3)
+        .expect("please specify the checkpoint file as third command line parameter");
+
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+    let client = ClientBuilder::new(account, authorization_token)?;
+    let client = client.into_database_client(database);
+    let client = client.into_collection_client(collection);
+
+    let continuation = match std::fs::read_to_string(&checkpoint_file) {
+        Ok(s) => serde_json::from_str(&s)?,
+        Err(_) => ChangeFeedContinuation::from_beginning(),
+    };
+
+    let builder = client
+        .read_change_feed()
+        .with_change_feed_continuation(&continuation)
+        .with_max_item_count(100);
+    let mut stream = Box::pin(builder.stream::<serde_json::Value>());
+
+    while let Some(response) = stream.next().await {
+        let response = response?;
+        println!(
+            "partition key range {:?}: {} changes ({} RUs)",
+            response.partition_key_range_id,
+            response.documents.len(),
+            response.charge
+        );
+        for document in response.documents {
+            println!("{:#?}", document.document);
+        }
+
+        std::fs::write(
+            &checkpoint_file,
+            serde_json::to_string(&response.continuation)?,
+        )?;
+    }
+
+    Ok(())
+}
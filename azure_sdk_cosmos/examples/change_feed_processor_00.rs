@@ -0,0 +1,56 @@
+use azure_sdk_cosmos::change_feed::ChangeFeedProcessor;
+use azure_sdk_cosmos::prelude::*;
+use azure_sdk_cosmos::responses::ReadChangeFeedResponse;
+use std::error::Error;
+
+// Run several instances of this example (with different instance names) to see the
+// partition key ranges of the collection being spread among them. The lease collection
+// must exist and be partitioned on /id.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+    let lease_collection = std::env::args()
+        .nth(3)
+        .expect("please specify lease collection name as third command line parameter");
+    let instance_name = std::env::args()
+        .nth(4)
+        .expect("please specify instance name as fourth command line parameter");
+
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+    let client = ClientBuilder::new(account, authorization_token)?;
+    let database_client = client.with_database_client(database);
+    let collection_client = database_client.with_collection_client(collection);
+    let lease_collection_client = database_client.with_collection_client(lease_collection);
+
+    let processor =
+        ChangeFeedProcessor::new(&collection_client, &lease_collection_client, instance_name)
+            .with_lease_expiration(30)
+            .with_max_item_count(100);
+
+    loop {
+        let processed_changes = processor
+            .process_changes(
+                |response: ReadChangeFeedResponse<serde_json::Value>| async move {
+                    println!(
+                        "partition key range {:?}: {} changes",
+                        response.partition_key_range_id,
+                        response.documents.len()
+                    );
+                    Ok(())
+                },
+            )
+            .await?;
+        println!("{:?}", processed_changes);
+
+        tokio::time::delay_for(std::time::Duration::from_secs(5)).await;
+    }
+}
@@ -0,0 +1,75 @@
+use azure_sdk_cosmos::prelude::*;
+use futures::stream::StreamExt;
+use std::error::Error;
+
+// This example lists the conflicts of a collection of a multi-region write account,
+// prints the conflicting version of each document and then deletes the conflict.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+    let client = ClientBuilder::new(account, authorization_token)?;
+    let database_client = client.with_database_client(database_name);
+    let collection_client = database_client.with_collection_client(collection_name);
+
+    let collection = collection_client
+        .get_collection()
+        .execute()
+        .await?
+        .collection;
+    println!(
+        "conflict resolution policy == {:?}",
+        collection.conflict_resolution_policy
+    );
+
+    let mut conflicts = Vec::new();
+    let builder = collection_client.list_conflicts();
+    let mut stream = Box::pin(builder.stream());
+    while let Some(response) = stream.next().await {
+        conflicts.extend(response?.conflicts);
+    }
+
+    for conflict in conflicts {
+        let content: serde_json::Value = conflict.content()?;
+        println!(
+            "{:?} conflict on {}: {:#?}",
+            conflict.operation_type, conflict.resource_id, content
+        );
+
+        // the conflict belongs to the partition of the conflicting document
+        let mut partition_keys = PartitionKeys::new();
+        for path in &collection.parition_key.paths {
+            let value = path
+                .split('/')
+                .skip(1)
+                .fold(&content, |value, segment| &value[segment]);
+            partition_keys.push(value)?;
+        }
+
+        let conflict_client = collection_client.with_conflict_client(&conflict.id);
+        let response = conflict_client
+            .get_conflict()
+            .with_partition_keys(&partition_keys)
+            .execute()
+            .await?;
+        assert_eq!(response.conflict.id, conflict.id);
+
+        conflict_client
+            .delete_conflict()
+            .with_partition_keys(&partition_keys)
+            .execute()
+            .await?;
+    }
+
+    Ok(())
+}
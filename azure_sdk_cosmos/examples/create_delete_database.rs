@@ -63,6 +63,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
             indexing_mode: IndexingMode::Consistent,
             included_paths: vec![ip],
             excluded_paths: vec![],
+            ..Default::default()
         };
 
         let create_collection_response = db_client
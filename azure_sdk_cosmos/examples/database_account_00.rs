@@ -0,0 +1,43 @@
+use azure_sdk_cosmos::prelude::*;
+use std::error::Error;
+
+// This example prints the regions of the account and then lists the databases reading
+// from the preferred regions passed on the command line (for example "West Europe").
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let preferred_locations = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+    let client = ClientBuilder::new_with_preferred_locations(
+        account,
+        authorization_token,
+        preferred_locations,
+    )?;
+
+    let database_account = client.get_database_account().execute().await?;
+    for location in &database_account.database_account.writable_locations {
+        println!(
+            "writable: {} ({})",
+            location.name, location.database_account_endpoint
+        );
+    }
+    for location in &database_account.database_account.readable_locations {
+        println!(
+            "readable: {} ({})",
+            location.name, location.database_account_endpoint
+        );
+    }
+
+    let databases = client.list_databases().execute().await?;
+    println!("databases == {:#?}", databases.databases);
+    println!(
+        "read endpoints == {:?}",
+        client.location_cache().read_endpoints()
+    );
+
+    Ok(())
+}
@@ -101,6 +101,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 indexing_mode: IndexingMode::Consistent,
                 included_paths: vec![ip],
                 excluded_paths: vec![],
+                ..Default::default()
             };
 
             // Notice here we specify the expected performance level.
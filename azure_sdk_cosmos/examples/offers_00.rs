@@ -0,0 +1,79 @@
+use azure_sdk_cosmos::prelude::*;
+use azure_sdk_cosmos::{Param, Query};
+use futures::stream::StreamExt;
+use std::error::Error;
+
+// This example lists the throughput offers of the account and, if a new throughput is
+// passed as third parameter, changes the throughput of the specified collection.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+    let throughput = std::env::args().nth(3);
+
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+    let client = ClientBuilder::new(account, authorization_token)?;
+
+    let builder = client.list_offers();
+    let mut stream = Box::pin(builder.stream());
+    while let Some(response) = stream.next().await {
+        for offer in response?.offers {
+            println!(
+                "offer {} for {}: {:?} RU/s",
+                offer.id,
+                offer.resource,
+                offer.throughput()
+            );
+        }
+    }
+
+    let collection = client
+        .with_database_client(database_name)
+        .with_collection_client(collection_name)
+        .get_collection()
+        .execute()
+        .await?
+        .collection;
+
+    let query = Query::with_params(
+        "SELECT * FROM root r WHERE r.offerResourceId = @rid",
+        vec![Param::new("@rid", collection.rid.as_str())],
+    );
+    let response = client.query_offers().with_query(&query).execute().await?;
+    let offer =
+        response.offers.into_iter().next().expect(
+            "the collection has no offer, its throughput might be shared with the database",
+        );
+    println!("collection offer == {:#?}", offer);
+
+    if let Some(throughput) = throughput {
+        // read it again to showcase get_offer
+        let offer = client
+            .get_offer()
+            .with_offer_id(&offer.rid)
+            .execute()
+            .await?
+            .offer;
+
+        let offer = offer.with_throughput(throughput.parse()?)?;
+        let response = client
+            .replace_offer()
+            .with_throughput_offer(&offer)
+            .execute()
+            .await?;
+        println!(
+            "throughput changed to {:?} RU/s",
+            response.offer.throughput()
+        );
+    }
+
+    Ok(())
+}
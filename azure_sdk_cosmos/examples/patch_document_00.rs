@@ -0,0 +1,45 @@
+use azure_sdk_cosmos::prelude::*;
+use std::error::Error;
+
+// This example decrements the stock of a product without reading it first. The patch is
+// applied only if there is still stock available. The collection must be partitioned
+// on /id.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+    let document_id = std::env::args()
+        .nth(3)
+        .expect("please specify document id as third command line parameter");
+
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+    let client = ClientBuilder::new(account, authorization_token)?;
+    let database_client = client.with_database_client(database_name);
+    let collection_client = database_client.with_collection_client(collection_name);
+
+    let mut partition_keys = PartitionKeys::new();
+    partition_keys.push(&document_id)?;
+
+    let response = collection_client
+        .patch_document()
+        .with_document_id(&document_id)
+        .with_partition_keys(&partition_keys)
+        .with_condition("FROM c WHERE c.stock > 0")
+        .execute_with_operations(&[
+            PatchOperation::increment("/stock", -1),
+            PatchOperation::set("/lastSold", chrono::Utc::now().to_rfc3339()),
+        ])
+        .await?;
+
+    println!("patched, charge == {}", response.charge);
+
+    Ok(())
+}
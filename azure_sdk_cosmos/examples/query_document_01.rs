@@ -0,0 +1,53 @@
+use azure_sdk_cosmos::prelude::*;
+use azure_sdk_cosmos::Query;
+use std::error::Error;
+
+// This example runs the query passed as third parameter with the client side query
+// pipeline, so that queries like
+// "SELECT * FROM c ORDER BY c.a_number DESC OFFSET 10 LIMIT 5" or
+// "SELECT VALUE AVG(c.a_number) FROM c" return the right results on partitioned
+// collections.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+    let query = std::env::args()
+        .nth(3)
+        .expect("please specify requested query");
+
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+
+    let client = ClientBuilder::new(account, authorization_token)?;
+    let client = client.with_database_client(database_name);
+    let client = client.with_collection_client(collection_name);
+
+    let query = Query::new(&query);
+
+    let response = client
+        .query_documents()
+        .with_query(&query)
+        .with_max_item_count(100)
+        .execute_cross_partition::<serde_json::Value>()
+        .await?;
+
+    println!("query plan == {:#?}", response.query_plan);
+    println!(
+        "{} results from partition key ranges {:?} ({} RUs)",
+        response.results.len(),
+        response.partition_key_range_ids,
+        response.charge()
+    );
+    for result in response.results {
+        println!("{}", result);
+    }
+
+    Ok(())
+}
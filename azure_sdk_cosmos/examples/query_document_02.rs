@@ -0,0 +1,55 @@
+use azure_sdk_cosmos::prelude::*;
+use azure_sdk_cosmos::responses::QueryDocumentsResponse;
+use azure_sdk_cosmos::sql::{array_contains, Operand, Path, Select};
+use std::error::Error;
+#[macro_use]
+extern crate serde_derive;
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct MySampleStructOwned {
+    id: String,
+    a_string: String,
+    a_number: u64,
+    tags: Vec<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+
+    let client = ClientBuilder::new(account, authorization_token)?;
+    let client = client.with_database_client(database_name);
+    let client = client.with_collection_client(collection_name);
+
+    // the paths are checked against the serde representation of MySampleStructOwned
+    let a_number = Path::checked::<MySampleStructOwned, _>("c", "a_number")?;
+    let tags = Path::checked::<MySampleStructOwned, _>("c", "tags")?;
+
+    let query = Select::from("c")
+        .filter(a_number.clone().greater_than(100))
+        .filter(array_contains(tags, "blue"))
+        .order_by_desc(a_number)
+        .build();
+    println!("query == {} {:?}", query.text(), query.parameters());
+
+    let respo: QueryDocumentsResponse<MySampleStructOwned> = client
+        .query_documents()
+        .with_query(&query.query())
+        .with_query_cross_partition(true)
+        .execute()
+        .await?;
+    println!("as items == {:?}", respo);
+
+    Ok(())
+}
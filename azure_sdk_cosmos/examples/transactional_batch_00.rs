@@ -0,0 +1,78 @@
+use azure_sdk_cosmos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Order {
+    id: String,
+    customer: String,
+    total: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Customer {
+    id: String,
+    customer: String,
+    orders: u64,
+}
+
+// This example atomically creates an order and increments the order count of its
+// customer. Both documents live in the logical partition of the customer. The collection
+// must be partitioned on /customer.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let database_name = std::env::args()
+        .nth(1)
+        .expect("please specify database name as first command line parameter");
+    let collection_name = std::env::args()
+        .nth(2)
+        .expect("please specify collection name as second command line parameter");
+
+    let master_key =
+        std::env::var("COSMOS_MASTER_KEY").expect("Set env variable COSMOS_MASTER_KEY first!");
+    let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
+
+    let authorization_token = AuthorizationToken::new_master(&master_key)?;
+    let client = ClientBuilder::new(account, authorization_token)?;
+    let database_client = client.with_database_client(database_name);
+    let collection_client = database_client.with_collection_client(collection_name);
+
+    let customer = Customer {
+        id: "customer.42".to_owned(),
+        customer: "42".to_owned(),
+        orders: 0,
+    };
+    let order = Order {
+        id: "order.1".to_owned(),
+        customer: "42".to_owned(),
+        total: 100,
+    };
+
+    let mut batch = TransactionalBatch::new();
+    batch
+        .upsert(&customer)?
+        .create(&order)?
+        .patch(&customer.id, &[PatchOperation::increment("/orders", 1)])
+        .read(&customer.id);
+
+    let mut partition_keys = PartitionKeys::new();
+    partition_keys.push(&customer.customer)?;
+
+    let response = collection_client
+        .transactional_batch()
+        .with_partition_keys(&partition_keys)
+        .with_transactional_batch(&batch)
+        .execute()
+        .await?;
+
+    if response.is_successful() {
+        let customer: Option<Customer> = response.results[3].document()?;
+        println!("committed, customer is now {:?}", customer);
+    } else {
+        for result in response.results {
+            println!("{:?}", result.status_code);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// Additive increase, multiplicative decrease of the number of concurrent requests sent to
+// a partition key range: one more request every time a full window of requests completes
+// without being throttled, half of them as soon as one is throttled.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CongestionControl {
+    concurrency: usize,
+    max_concurrency: usize,
+    completed_in_window: usize,
+}
+
+impl CongestionControl {
+    pub(crate) fn new(initial_concurrency: usize, max_concurrency: usize) -> Self {
+        let max_concurrency = max_concurrency.max(1);
+        Self {
+            concurrency: initial_concurrency.max(1).min(max_concurrency),
+            max_concurrency,
+            completed_in_window: 0,
+        }
+    }
+
+    pub(crate) fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    pub(crate) fn on_completed(&mut self) {
+        self.completed_in_window += 1;
+        if self.completed_in_window >= self.concurrency {
+            self.completed_in_window = 0;
+            self.concurrency = (self.concurrency + 1).min(self.max_concurrency);
+        }
+    }
+
+    pub(crate) fn on_throttled(&mut self) {
+        self.completed_in_window = 0;
+        self.concurrency = (self.concurrency / 2).max(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn congestion_control() {
+        let mut congestion_control = CongestionControl::new(2, 4);
+        assert_eq!(congestion_control.concurrency(), 2);
+
+        congestion_control.on_completed();
+        assert_eq!(congestion_control.concurrency(), 2);
+        congestion_control.on_completed();
+        assert_eq!(congestion_control.concurrency(), 3);
+        for _ in 0..10 {
+            congestion_control.on_completed();
+        }
+        assert_eq!(congestion_control.concurrency(), 4);
+
+        congestion_control.on_throttled();
+        assert_eq!(congestion_control.concurrency(), 2);
+        congestion_control.on_throttled();
+        congestion_control.on_throttled();
+        assert_eq!(congestion_control.concurrency(), 1);
+
+        assert_eq!(CongestionControl::new(10, 5).concurrency(), 5);
+        assert_eq!(CongestionControl::new(0, 0).concurrency(), 1);
+    }
+}
@@ -0,0 +1,337 @@
+use super::congestion_control::CongestionControl;
+use crate::effective_partition_key::{effective_partition_key, find_partition_key_range};
+use crate::prelude::*;
+use crate::responses::CreateDocumentResponse;
+use crate::DocumentAttributes;
+use azure_sdk_core::errors::AzureError;
+use futures::future::join_all;
+use futures::stream::{FuturesUnordered, StreamExt};
+use hyper::StatusCode;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+
+/// Creates (or upserts) many documents in a collection, loading every partition key range
+/// in parallel. Obtained with `CollectionClient::bulk_executor`.
+///
+/// Each partition key range starts with `initial_concurrency` concurrent requests and
+/// adds one every time they all complete without being throttled, up to
+/// `max_concurrency`. A throttled request halves the concurrency of its range and, once
+/// the retry policy of the client gives up, the document is queued again (up to
+/// `max_attempts` times).
+#[derive(Debug, Clone)]
+pub struct BulkExecutor<'a, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    collection_client: &'a dyn CollectionClient<C, D>,
+    is_upsert: bool,
+    initial_concurrency: usize,
+    max_concurrency: usize,
+    max_attempts: u32,
+}
+
+/// Outcome of a bulk import. The results are in the same order as the documents.
+#[derive(Debug)]
+pub struct BulkImportResponse {
+    pub results: Vec<BulkImportResult>,
+    /// Request units consumed by the whole import.
+    pub charge: f64,
+    /// Number of requests throttled (429 - Too Many Requests) during the import.
+    pub throttled_requests: u32,
+}
+
+#[derive(Debug)]
+pub struct BulkImportResult {
+    pub id: String,
+    pub partition_key_range_id: String,
+    pub charge: f64,
+    pub result: Result<DocumentAttributes, AzureError>,
+}
+
+impl BulkImportResponse {
+    pub fn is_successful(&self) -> bool {
+        self.results.iter().all(|result| result.result.is_ok())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &BulkImportResult> {
+        self.results.iter().filter(|result| result.result.is_err())
+    }
+}
+
+#[derive(Debug)]
+struct PendingDocument {
+    index: usize,
+    id: String,
+    document: Value,
+    partition_keys: PartitionKeys,
+    attempts: u32,
+}
+
+#[derive(Debug, Default)]
+struct RangeOutcome {
+    results: Vec<(usize, BulkImportResult)>,
+    charge: f64,
+    throttled_requests: u32,
+}
+
+impl<'a, C, D> BulkExecutor<'a, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    pub fn new(collection_client: &'a dyn CollectionClient<C, D>) -> Self {
+        Self {
+            collection_client,
+            is_upsert: false,
+            initial_concurrency: 5,
+            max_concurrency: 50,
+            max_attempts: 10,
+        }
+    }
+
+    /// Replaces the documents that already exist instead of failing with 409 - Conflict.
+    pub fn with_is_upsert(self, is_upsert: bool) -> Self {
+        Self { is_upsert, ..self }
+    }
+
+    /// Concurrent requests sent to each partition key range at the start. Defaults to 5.
+    pub fn with_initial_concurrency(self, initial_concurrency: usize) -> Self {
+        Self {
+            initial_concurrency,
+            ..self
+        }
+    }
+
+    /// Upper bound of the concurrent requests sent to each partition key range. Defaults
+    /// to 50.
+    pub fn with_max_concurrency(self, max_concurrency: usize) -> Self {
+        Self {
+            max_concurrency,
+            ..self
+        }
+    }
+
+    /// How many times a throttled document is sent before giving up. Defaults to 10.
+    pub fn with_max_attempts(self, max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..self
+        }
+    }
+
+    /// Imports the documents, which must have a string `id`. An error is returned only if
+    /// the import could not start (for example if the partition key ranges cannot be read):
+    /// the failure of single documents, including the ones without an id, is reported in
+    /// their `BulkImportResult`.
+    pub async fn import<T>(&self, documents: &[T]) -> Result<BulkImportResponse, AzureError>
+    where
+        T: Serialize,
+    {
+        let partition_key = self
+            .collection_client
+            .get_collection()
+            .execute()
+            .await?
+            .collection
+            .parition_key;
+        let partition_key_ranges = self
+            .collection_client
+            .get_partition_key_ranges()
+            .execute()
+            .await?
+            .partition_key_ranges;
+
+        // the documents that cannot be sent are reported without being sent
+        let mut rejected = Vec::new();
+        let mut ranges: BTreeMap<String, VecDeque<PendingDocument>> = BTreeMap::new();
+        for (index, document) in documents.iter().enumerate() {
+            let document = serde_json::to_value(document)?;
+            let id = match document.get("id").and_then(Value::as_str) {
+                Some(id) => id.to_owned(),
+                None => {
+                    rejected.push((
+                        index,
+                        BulkImportResult {
+                            id: String::new(),
+                            partition_key_range_id: String::new(),
+                            charge: 0.0,
+                            result: Err(AzureError::InputParametersError(format!(
+                                "document {} has no string id",
+                                index
+                            ))),
+                        },
+                    ));
+                    continue;
+                }
+            };
+
+            let mut partition_keys = PartitionKeys::new();
+            for path in &partition_key.paths {
+                partition_keys.push(partition_key_component(&document, path))?;
+            }
+
+            let effective_partition_key = effective_partition_key(&partition_key, &partition_keys)?;
+            let partition_key_range_id =
+                match find_partition_key_range(&partition_key_ranges, &effective_partition_key) {
+                    Some(partition_key_range) => partition_key_range.id.clone(),
+                    None => {
+                        rejected.push((
+                            index,
+                            BulkImportResult {
+                                result: Err(AzureError::GenericErrorWithText(format!(
+                                    "no partition key range contains the effective partition \
+                                     key {} of document {}",
+                                    effective_partition_key, id
+                                ))),
+                                id,
+                                partition_key_range_id: String::new(),
+                                charge: 0.0,
+                            },
+                        ));
+                        continue;
+                    }
+                };
+
+            ranges
+                .entry(partition_key_range_id)
+                .or_default()
+                .push_back(PendingDocument {
+                    index,
+                    id,
+                    document,
+                    partition_keys,
+                    attempts: 0,
+                });
+        }
+
+        let outcomes = join_all(ranges.into_iter().map(|(partition_key_range_id, queue)| {
+            self.import_range(partition_key_range_id, queue)
+        }))
+        .await;
+
+        let mut results = rejected;
+        let mut charge = 0.0;
+        let mut throttled_requests = 0;
+        for outcome in outcomes {
+            results.extend(outcome.results);
+            charge += outcome.charge;
+            throttled_requests += outcome.throttled_requests;
+        }
+        results.sort_by_key(|(index, _)| *index);
+
+        Ok(BulkImportResponse {
+            results: results.into_iter().map(|(_, result)| result).collect(),
+            charge,
+            throttled_requests,
+        })
+    }
+
+    async fn import_range(
+        &self,
+        partition_key_range_id: String,
+        mut queue: VecDeque<PendingDocument>,
+    ) -> RangeOutcome {
+        let mut outcome = RangeOutcome::default();
+        let mut congestion_control =
+            CongestionControl::new(self.initial_concurrency, self.max_concurrency);
+        let mut in_flight = FuturesUnordered::new();
+
+        loop {
+            while in_flight.len() < congestion_control.concurrency() {
+                match queue.pop_front() {
+                    Some(pending) => in_flight.push(self.create_document(pending)),
+                    None => break,
+                }
+            }
+
+            let (mut pending, result) = match in_flight.next().await {
+                Some(completed) => completed,
+                None => return outcome,
+            };
+            pending.attempts += 1;
+
+            let result = match result {
+                Ok(response) => {
+                    if response.retry_count() > 0 {
+                        outcome.throttled_requests += response.retry_count();
+                        congestion_control.on_throttled();
+                    } else {
+                        congestion_control.on_completed();
+                    }
+                    outcome.charge += response.charge;
+                    BulkImportResult {
+                        id: pending.id,
+                        partition_key_range_id: partition_key_range_id.clone(),
+                        charge: response.charge,
+                        result: Ok(response.document_attributes),
+                    }
+                }
+                Err(AzureError::UnexpectedHTTPResult(error))
+                    if error.status_code() == StatusCode::TOO_MANY_REQUESTS
+                        && pending.attempts < self.max_attempts =>
+                {
+                    debug!(
+                        "document {} throttled by partition key range {}, queued again",
+                        pending.id, partition_key_range_id
+                    );
+                    outcome.throttled_requests += 1;
+                    congestion_control.on_throttled();
+                    queue.push_back(pending);
+                    continue;
+                }
+                Err(error) => BulkImportResult {
+                    id: pending.id,
+                    partition_key_range_id: partition_key_range_id.clone(),
+                    charge: 0.0,
+                    result: Err(error),
+                },
+            };
+            outcome.results.push((pending.index, result));
+        }
+    }
+
+    async fn create_document(
+        &self,
+        pending: PendingDocument,
+    ) -> (PendingDocument, Result<CreateDocumentResponse, AzureError>) {
+        let result = self
+            .collection_client
+            .create_document()
+            .with_partition_keys(&pending.partition_keys)
+            .with_is_upsert(self.is_upsert)
+            .execute_with_document(&pending.document)
+            .await;
+        (pending, result)
+    }
+}
+
+// Value of the property at `path` (for example `/address/city`). Missing properties are
+// sent as an empty object, meaning undefined.
+pub(crate) fn partition_key_component(document: &Value, path: &str) -> Value {
+    path.split('/')
+        .skip(1)
+        .map(|property| property.trim_matches('"'))
+        .try_fold(document, |value, property| value.get(property))
+        .cloned()
+        .unwrap_or_else(|| Value::Object(serde_json::Map::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn partition_key_components() {
+        let document = json!({ "id": "1", "address": { "city": "Milan" }, "n": 3 });
+        assert_eq!(partition_key_component(&document, "/id"), json!("1"));
+        assert_eq!(
+            partition_key_component(&document, "/address/city"),
+            json!("Milan")
+        );
+        assert_eq!(partition_key_component(&document, "/n"), json!(3));
+        assert_eq!(partition_key_component(&document, "/missing"), json!({}));
+    }
+}
@@ -0,0 +1,11 @@
+//! Bulk ingestion of documents.
+//!
+//! The documents are grouped by the partition key range they belong to and every range
+//! is loaded independently, with a number of concurrent requests that grows while the
+//! range keeps up and is halved as soon as it throttles the requests.
+mod congestion_control;
+mod executor;
+
+#[cfg(feature = "test_utils")]
+pub(crate) use self::executor::partition_key_component;
+pub use self::executor::{BulkExecutor, BulkImportResponse, BulkImportResult};
@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+/// Checkpoint of a change feed, one etag per partition key range.
+///
+/// The etag returned by a change feed read is the logical sequence number reached in that
+/// partition key range. Passing it back as `If-None-Match` resumes the feed right after
+/// the last change received. This struct is serializable so it can be persisted between
+/// runs (for example with `serde_json::to_string`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChangeFeedContinuation {
+    #[serde(rename = "startFromNow", default)]
+    start_from_now: bool,
+    #[serde(rename = "etags", default)]
+    etags: BTreeMap<String, String>,
+}
+
+impl ChangeFeedContinuation {
+    /// Reads every partition key range from the first change retained by the collection.
+    pub fn from_beginning() -> Self {
+        Self::default()
+    }
+
+    /// Skips the existing changes of the partition key ranges without a checkpoint: only
+    /// the changes made after the first read will be returned.
+    pub fn from_now() -> Self {
+        Self {
+            start_from_now: true,
+            etags: BTreeMap::new(),
+        }
+    }
+
+    pub fn etag(&self, partition_key_range_id: &str) -> Option<&str> {
+        self.etags
+            .get(partition_key_range_id)
+            .map(|etag| etag.as_str())
+    }
+
+    pub fn set_etag<P, E>(&mut self, partition_key_range_id: P, etag: E)
+    where
+        P: Into<String>,
+        E: Into<String>,
+    {
+        self.etags
+            .insert(partition_key_range_id.into(), etag.into());
+    }
+
+    pub fn partition_key_range_ids(&self) -> impl Iterator<Item = &str> {
+        self.etags.keys().map(|id| id.as_str())
+    }
+
+    /// Value of the `If-None-Match` header for the specified partition key range.
+    pub(crate) fn if_none_match(&self, partition_key_range_id: &str) -> Option<&str> {
+        match self.etag(partition_key_range_id) {
+            Some(etag) => Some(etag),
+            None if self.start_from_now => Some("*"),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn if_none_match() {
+        let mut continuation = ChangeFeedContinuation::from_beginning();
+        assert_eq!(continuation.if_none_match("0"), None);
+        continuation.set_etag("0", "\"42\"");
+        assert_eq!(continuation.if_none_match("0"), Some("\"42\""));
+
+        let mut continuation = ChangeFeedContinuation::from_now();
+        assert_eq!(continuation.if_none_match("0"), Some("*"));
+        continuation.set_etag("0", "\"42\"");
+        assert_eq!(continuation.if_none_match("0"), Some("\"42\""));
+        assert_eq!(continuation.if_none_match("1"), Some("*"));
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let mut continuation = ChangeFeedContinuation::from_now();
+        continuation.set_etag("0", "\"42\"");
+        continuation.set_etag("1", "\"7\"");
+
+        let serialized = serde_json::to_string(&continuation).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"startFromNow":true,"etags":{"0":"\"42\"","1":"\"7\""}}"#
+        );
+
+        let deserialized: ChangeFeedContinuation = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, continuation);
+        assert_eq!(
+            deserialized.partition_key_range_ids().collect::<Vec<_>>(),
+            vec!["0", "1"]
+        );
+    }
+}
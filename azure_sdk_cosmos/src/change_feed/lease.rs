@@ -0,0 +1,289 @@
+use crate::{Document, PartitionKeyRange};
+use std::collections::HashMap;
+
+/// Lease document stored in the lease collection, one per partition key range of the
+/// monitored collection. The lease collection must be partitioned on `/id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lease {
+    pub id: String,
+    /// Database of the monitored collection.
+    #[serde(default)]
+    pub database: String,
+    /// Name of the monitored collection.
+    #[serde(default)]
+    pub collection: String,
+    #[serde(rename = "partitionKeyRangeId")]
+    pub partition_key_range_id: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Change feed etag reached by the last checkpoint.
+    #[serde(rename = "continuationToken", default)]
+    pub continuation: Option<String>,
+    /// Last time (in seconds since the Unix epoch) the owner renewed the lease.
+    #[serde(default)]
+    pub timestamp: i64,
+}
+
+impl Lease {
+    pub fn new<I, D, C, P>(id: I, database: D, collection: C, partition_key_range_id: P) -> Self
+    where
+        I: Into<String>,
+        D: Into<String>,
+        C: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            database: database.into(),
+            collection: collection.into(),
+            partition_key_range_id: partition_key_range_id.into(),
+            owner: None,
+            continuation: None,
+            timestamp: 0,
+        }
+    }
+
+    /// A lease is alive when it has an owner that renewed it within `expiration` seconds.
+    pub fn is_alive(&self, now: i64, expiration: i64) -> bool {
+        self.owner.is_some() && now - self.timestamp < expiration
+    }
+
+    /// Lease of a partition key range created by the split of the range of this lease: it
+    /// resumes the change feed from the last checkpoint of this lease.
+    pub fn child<I, P>(&self, id: I, partition_key_range_id: P) -> Self
+    where
+        I: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            continuation: self.continuation.clone(),
+            ..Self::new(
+                id,
+                self.database.as_str(),
+                self.collection.as_str(),
+                partition_key_range_id,
+            )
+        }
+    }
+}
+
+/// The partition key ranges without a lease. The ranges coming from the split of a range
+/// that still has a lease are left out: they take over that lease, and its checkpoint,
+/// as soon as its owner finds out about the split.
+pub(crate) fn ranges_without_lease<'r>(
+    leases: &[Document<Lease>],
+    partition_key_ranges: &'r [PartitionKeyRange],
+) -> Vec<&'r PartitionKeyRange> {
+    let has_lease = |partition_key_range_id: &str| {
+        leases
+            .iter()
+            .any(|lease| lease.document.partition_key_range_id == partition_key_range_id)
+    };
+
+    partition_key_ranges
+        .iter()
+        .filter(|partition_key_range| {
+            !has_lease(&partition_key_range.id)
+                && !partition_key_range
+                    .parents
+                    .iter()
+                    .any(|parent| has_lease(parent))
+        })
+        .collect()
+}
+
+/// The current partition key ranges created by the split (possibly more than once) of
+/// the specified one.
+pub(crate) fn child_ranges<'r>(
+    partition_key_range_id: &str,
+    partition_key_ranges: &'r [PartitionKeyRange],
+) -> Vec<&'r PartitionKeyRange> {
+    partition_key_ranges
+        .iter()
+        .filter(|partition_key_range| {
+            partition_key_range
+                .parents
+                .iter()
+                .any(|parent| parent == partition_key_range_id)
+        })
+        .collect()
+}
+
+/// Picks the leases `owner` should acquire to get its fair share of the partition key
+/// ranges. Expired leases are preferred; if there are none and `owner` is still below
+/// its share, a single lease is taken from the most loaded instance (if it holds more
+/// than its share). This is the same strategy used by the .NET change feed processor.
+pub(crate) fn leases_to_acquire<'l>(
+    leases: &'l [Document<Lease>],
+    owner: &str,
+    now: i64,
+    expiration: i64,
+) -> Vec<&'l Document<Lease>> {
+    let mut leases_per_owner: HashMap<&str, usize> = HashMap::new();
+    leases_per_owner.insert(owner, 0);
+    for lease in leases
+        .iter()
+        .filter(|l| l.document.is_alive(now, expiration))
+    {
+        // is_alive guarantees the owner is there
+        if let Some(lease_owner) = lease.document.owner.as_deref() {
+            *leases_per_owner.entry(lease_owner).or_insert(0) += 1;
+        }
+    }
+
+    let owned = leases_per_owner[owner];
+    let target = leases.len().div_ceil(leases_per_owner.len());
+    if owned >= target {
+        return Vec::new();
+    }
+
+    let expired = leases
+        .iter()
+        .filter(|l| !l.document.is_alive(now, expiration))
+        .take(target - owned)
+        .collect::<Vec<_>>();
+    if !expired.is_empty() {
+        return expired;
+    }
+
+    let most_loaded = leases_per_owner
+        .iter()
+        .filter(|(lease_owner, count)| **lease_owner != owner && **count > target)
+        .max_by_key(|(_, count)| **count);
+
+    match most_loaded {
+        Some((most_loaded, _)) => leases
+            .iter()
+            .find(|l| l.document.owner.as_deref() == Some(*most_loaded))
+            .into_iter()
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000;
+    const EXPIRATION: i64 = 60;
+
+    fn lease(range: &str, owner: Option<&str>, timestamp: i64) -> Document<Lease> {
+        let mut lease = Lease::new(format!("lease.{}", range), "db", "coll", range);
+        lease.owner = owner.map(|owner| owner.to_owned());
+        lease.timestamp = timestamp;
+        Document::new(lease)
+    }
+
+    fn partition_key_range(id: &str, parents: &[&str]) -> PartitionKeyRange {
+        PartitionKeyRange {
+            rid: String::new(),
+            id: id.to_owned(),
+            etag: String::new(),
+            min_exclusive: String::new(),
+            max_exclusive: String::new(),
+            rid_prefix: 0,
+            _self: String::new(),
+            throughput_fraction: 1,
+            status: "online".to_owned(),
+            parents: parents.iter().map(|parent| (*parent).to_owned()).collect(),
+            ts: 0,
+        }
+    }
+
+    fn ranges(leases: Vec<&Document<Lease>>) -> Vec<&str> {
+        leases
+            .into_iter()
+            .map(|l| l.document.partition_key_range_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn acquire_all_when_alone() {
+        let leases = vec![lease("0", None, 0), lease("1", Some("b"), NOW - 100)];
+        assert_eq!(
+            ranges(leases_to_acquire(&leases, "a", NOW, EXPIRATION)),
+            vec!["0", "1"]
+        );
+    }
+
+    #[test]
+    fn acquire_fair_share() {
+        let leases = vec![
+            lease("0", None, 0),
+            lease("1", None, 0),
+            lease("2", Some("b"), NOW - 10),
+            lease("3", None, 0),
+        ];
+        assert_eq!(
+            ranges(leases_to_acquire(&leases, "a", NOW, EXPIRATION)),
+            vec!["0", "1"]
+        );
+
+        let leases = vec![
+            lease("0", Some("a"), NOW - 10),
+            lease("1", Some("a"), NOW - 10),
+            lease("2", Some("b"), NOW - 10),
+            lease("3", None, 0),
+        ];
+        assert!(leases_to_acquire(&leases, "a", NOW, EXPIRATION).is_empty());
+    }
+
+    #[test]
+    fn steal_from_most_loaded() {
+        let leases = vec![
+            lease("0", Some("b"), NOW - 10),
+            lease("1", Some("b"), NOW - 10),
+            lease("2", Some("b"), NOW - 10),
+            lease("3", Some("c"), NOW - 10),
+        ];
+        assert_eq!(
+            ranges(leases_to_acquire(&leases, "a", NOW, EXPIRATION)),
+            vec!["0"]
+        );
+
+        let leases = vec![
+            lease("0", Some("b"), NOW - 10),
+            lease("1", Some("c"), NOW - 10),
+        ];
+        assert!(leases_to_acquire(&leases, "a", NOW, EXPIRATION).is_empty());
+    }
+
+    #[test]
+    fn split_ranges_take_over_the_parent_lease() {
+        // range 0 has been split into 2 and 3, range 1 into 4 (and others) and then 4 into 6
+        let partition_key_ranges = vec![
+            partition_key_range("2", &["0"]),
+            partition_key_range("3", &["0"]),
+            partition_key_range("6", &["1", "4"]),
+            partition_key_range("7", &[]),
+        ];
+        let ids = |ranges: Vec<&PartitionKeyRange>| {
+            ranges
+                .into_iter()
+                .map(|partition_key_range| partition_key_range.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let leases = vec![lease("0", Some("a"), NOW - 10), lease("4", None, 0)];
+        assert_eq!(
+            ids(ranges_without_lease(&leases, &partition_key_ranges)),
+            vec!["7"]
+        );
+        assert_eq!(
+            ids(child_ranges("0", &partition_key_ranges)),
+            vec!["2", "3"]
+        );
+        assert_eq!(ids(child_ranges("1", &partition_key_ranges)), vec!["6"]);
+        assert_eq!(ids(child_ranges("4", &partition_key_ranges)), vec!["6"]);
+        assert!(child_ranges("7", &partition_key_ranges).is_empty());
+
+        let mut parent = leases[0].document.clone();
+        parent.continuation = Some("\"42\"".to_owned());
+        let child = parent.child("lease.2", "2");
+        assert_eq!(child.partition_key_range_id, "2");
+        assert_eq!(child.collection, "coll");
+        assert_eq!(child.continuation.as_deref(), Some("\"42\""));
+        assert_eq!(child.owner, None);
+    }
+}
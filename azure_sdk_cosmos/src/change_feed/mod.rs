@@ -0,0 +1,7 @@
+mod continuation;
+mod lease;
+mod processor;
+
+pub use self::continuation::ChangeFeedContinuation;
+pub use self::lease::Lease;
+pub use self::processor::{ChangeFeedProcessor, ProcessedChanges};
@@ -0,0 +1,408 @@
+use super::lease::{child_ranges, leases_to_acquire, ranges_without_lease, Lease};
+use crate::clients::CollectionStruct;
+use crate::prelude::*;
+use crate::responses::ReadChangeFeedResponse;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use futures::stream::StreamExt;
+use hyper::StatusCode;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::future::Future;
+
+/// Distributes the partition key ranges of a collection among several instances, storing
+/// ownership and checkpoints in a lease collection (like the .NET `ChangeFeedProcessor`).
+///
+/// Every instance must have a unique name and call `process_changes` periodically: each
+/// call balances the leases, renews the owned ones and hands the new changes of their
+/// partition key ranges to the handler, checkpointing after every batch. When a partition
+/// key range is split, its lease is replaced by the leases of the new ranges, which resume
+/// from its last checkpoint. The lease collection must be partitioned on `/id` and should
+/// not contain other documents.
+#[derive(Debug)]
+pub struct ChangeFeedProcessor<'a, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    feed_collection_client: &'a dyn CollectionClient<C, D>,
+    lease_collection_client: &'a dyn CollectionClient<C, D>,
+    instance_name: Cow<'a, str>,
+    lease_prefix: Cow<'a, str>,
+    lease_expiration: i64,
+    max_item_count: i32,
+    start_from_now: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessedChanges {
+    pub partition_key_range_ids: Vec<String>,
+    pub documents: usize,
+}
+
+impl<'a, C, D> ChangeFeedProcessor<'a, C, D>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+{
+    pub fn new<IntoCowStr>(
+        feed_collection_client: &'a dyn CollectionClient<C, D>,
+        lease_collection_client: &'a dyn CollectionClient<C, D>,
+        instance_name: IntoCowStr,
+    ) -> Self
+    where
+        IntoCowStr: Into<Cow<'a, str>>,
+    {
+        let lease_prefix = format!(
+            "{}.{}.",
+            feed_collection_client.database_client().database_name(),
+            feed_collection_client.collection_name()
+        );
+
+        Self {
+            feed_collection_client,
+            lease_collection_client,
+            instance_name: instance_name.into(),
+            lease_prefix: Cow::Owned(lease_prefix),
+            lease_expiration: 60,
+            max_item_count: -1,
+            start_from_now: false,
+        }
+    }
+
+    /// Prefix of the lease ids. Defaults to `<database>.<collection>.`.
+    pub fn with_lease_prefix<IntoCowStr>(self, lease_prefix: IntoCowStr) -> Self
+    where
+        IntoCowStr: Into<Cow<'a, str>>,
+    {
+        Self {
+            lease_prefix: lease_prefix.into(),
+            ..self
+        }
+    }
+
+    /// Seconds after which a lease that has not been renewed can be taken by another
+    /// instance. Defaults to 60.
+    pub fn with_lease_expiration(self, lease_expiration: i64) -> Self {
+        Self {
+            lease_expiration,
+            ..self
+        }
+    }
+
+    pub fn with_max_item_count(self, max_item_count: i32) -> Self {
+        Self {
+            max_item_count,
+            ..self
+        }
+    }
+
+    /// Ignores the changes made before a lease is checkpointed for the first time.
+    pub fn with_start_from_now(self, start_from_now: bool) -> Self {
+        Self {
+            start_from_now,
+            ..self
+        }
+    }
+
+    pub fn instance_name(&self) -> &str {
+        &self.instance_name
+    }
+
+    pub async fn process_changes<T, F, Fut>(
+        &self,
+        mut handler: F,
+    ) -> Result<ProcessedChanges, AzureError>
+    where
+        T: DeserializeOwned,
+        F: FnMut(ReadChangeFeedResponse<T>) -> Fut,
+        Fut: Future<Output = Result<(), AzureError>>,
+    {
+        let leases = self.ensure_leases().await?;
+        let now = chrono::Utc::now().timestamp();
+
+        let mut owned = leases
+            .iter()
+            .filter(|lease| {
+                lease.document.is_alive(now, self.lease_expiration)
+                    && lease.document.owner.as_deref() == Some(self.instance_name())
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for lease in leases_to_acquire(&leases, &self.instance_name, now, self.lease_expiration) {
+            let mut lease = lease.clone();
+            lease.document.owner = Some(self.instance_name.to_string());
+            if let Some(lease) = self.replace_lease(lease).await? {
+                debug!(
+                    "{} acquired lease {}",
+                    self.instance_name, lease.document.id
+                );
+                owned.push(lease);
+            }
+        }
+
+        let mut processed_changes = ProcessedChanges::default();
+        for lease in owned {
+            let partition_key_range_id = lease.document.partition_key_range_id.clone();
+            if let Some(documents) = self.process_lease(lease, &mut handler).await? {
+                processed_changes.documents += documents;
+                processed_changes
+                    .partition_key_range_ids
+                    .push(partition_key_range_id);
+            }
+        }
+
+        Ok(processed_changes)
+    }
+
+    // Reads the changes of the lease partition key range up to its current end.
+    // Returns None if the lease has been lost in the meantime.
+    async fn process_lease<T, F, Fut>(
+        &self,
+        mut lease: Document<Lease>,
+        handler: &mut F,
+    ) -> Result<Option<usize>, AzureError>
+    where
+        T: DeserializeOwned,
+        F: FnMut(ReadChangeFeedResponse<T>) -> Fut,
+        Fut: Future<Output = Result<(), AzureError>>,
+    {
+        let partition_key_range_id = lease.document.partition_key_range_id.clone();
+        let mut documents = 0;
+
+        loop {
+            let mut continuation = if self.start_from_now {
+                ChangeFeedContinuation::from_now()
+            } else {
+                ChangeFeedContinuation::from_beginning()
+            };
+            if let Some(etag) = &lease.document.continuation {
+                continuation.set_etag(partition_key_range_id.as_str(), etag.as_str());
+            }
+
+            let response = match self
+                .feed_collection_client
+                .read_change_feed()
+                .with_partition_range_id(&partition_key_range_id)
+                .with_change_feed_continuation(&continuation)
+                .with_max_item_count(self.max_item_count)
+                .execute::<T>()
+                .await
+            {
+                Ok(response) => response,
+                // the partition key range is gone: it could have been split
+                Err(AzureError::UnexpectedHTTPResult(err))
+                    if err.status_code() == StatusCode::GONE =>
+                {
+                    if self.split_lease(&lease).await? {
+                        return Ok(Some(documents));
+                    }
+                    return Err(AzureError::UnexpectedHTTPResult(err));
+                }
+                Err(err) => return Err(err),
+            };
+
+            let etag = response.etag.clone();
+            let has_changes = !response.documents.is_empty();
+            if has_changes {
+                documents += response.documents.len();
+                handler(response).await?;
+            }
+
+            // the checkpoint renews the lease too
+            lease.document.continuation = Some(etag);
+            lease = match self.replace_lease(lease).await? {
+                Some(lease) => lease,
+                None => {
+                    debug!(
+                        "{} lost lease for partition key range {}",
+                        self.instance_name, partition_key_range_id
+                    );
+                    return Ok(None);
+                }
+            };
+
+            if !has_changes {
+                return Ok(Some(documents));
+            }
+        }
+    }
+
+    // Creates the missing leases and returns every lease of this processor.
+    async fn ensure_leases(&self) -> Result<Vec<Document<Lease>>, AzureError> {
+        let partition_key_ranges = self
+            .feed_collection_client
+            .get_partition_key_ranges()
+            .execute()
+            .await?
+            .partition_key_ranges;
+        let leases = self.list_leases().await?;
+
+        let mut created = false;
+        for partition_key_range in ranges_without_lease(&leases, &partition_key_ranges) {
+            let lease = Document::new(Lease::new(
+                self.lease_id(&partition_key_range.id),
+                self.feed_collection_client
+                    .database_client()
+                    .database_name(),
+                self.feed_collection_client.collection_name(),
+                partition_key_range.id.as_str(),
+            ));
+            self.create_lease(&lease).await?;
+            created = true;
+        }
+
+        if created {
+            self.list_leases().await
+        } else {
+            Ok(leases)
+        }
+    }
+
+    // Replaces the lease of a split partition key range with the leases of the ranges it
+    // has been split into. Returns false if the partition key range has not been split.
+    async fn split_lease(&self, lease: &Document<Lease>) -> Result<bool, AzureError> {
+        let partition_key_ranges = self
+            .feed_collection_client
+            .get_partition_key_ranges()
+            .execute()
+            .await?
+            .partition_key_ranges;
+
+        let children = child_ranges(
+            &lease.document.partition_key_range_id,
+            &partition_key_ranges,
+        );
+        if children.is_empty() {
+            return Ok(false);
+        }
+
+        for partition_key_range in children {
+            let child = Document::new(lease.document.child(
+                self.lease_id(&partition_key_range.id),
+                partition_key_range.id.as_str(),
+            ));
+            self.create_lease(&child).await?;
+        }
+        debug!(
+            "{} split lease for partition key range {}",
+            self.instance_name, lease.document.partition_key_range_id
+        );
+
+        self.delete_lease(lease).await?;
+        Ok(true)
+    }
+
+    async fn create_lease(&self, lease: &Document<Lease>) -> Result<(), AzureError> {
+        match self
+            .lease_collection_client
+            .create_document()
+            .with_partition_keys(&(&lease.document.id).into())
+            .execute_with_document(lease)
+            .await
+        {
+            Ok(_) => Ok(()),
+            // another instance created it first
+            Err(AzureError::UnexpectedHTTPResult(err))
+                if err.status_code() == StatusCode::CONFLICT =>
+            {
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // Deletes the lease unless another instance has modified or deleted it since it was read.
+    async fn delete_lease(&self, lease: &Document<Lease>) -> Result<(), AzureError> {
+        let collection_client = CollectionStruct::new(
+            Cow::Borrowed(self.lease_collection_client.database_client()),
+            Cow::Borrowed(self.lease_collection_client.collection_name()),
+        );
+        let document_client = collection_client
+            .with_document_client(lease.document.id.as_str(), (&lease.document.id).into());
+
+        let response = document_client
+            .delete_document()
+            .with_if_match_condition(IfMatchCondition::Match(lease.document_attributes.etag()))
+            .execute()
+            .await;
+
+        match response {
+            Ok(_) => Ok(()),
+            Err(AzureError::UnexpectedHTTPResult(err))
+                if err.status_code() == StatusCode::PRECONDITION_FAILED
+                    || err.status_code() == StatusCode::NOT_FOUND =>
+            {
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn list_leases(&self) -> Result<Vec<Document<Lease>>, AzureError> {
+        let builder = self.lease_collection_client.list_documents();
+        let mut stream = Box::pin(builder.stream::<Lease>());
+
+        let mut leases = Vec::new();
+        while let Some(response) = stream.next().await {
+            leases.extend(
+                response?
+                    .documents
+                    .into_iter()
+                    .filter(|lease| self.is_own_lease(&lease.document)),
+            );
+        }
+
+        Ok(leases)
+    }
+
+    fn lease_id(&self, partition_key_range_id: &str) -> String {
+        format!("{}{}", self.lease_prefix, partition_key_range_id)
+    }
+
+    // Whether the lease belongs to this processor: the prefix alone is not enough as
+    // the prefix of a collection can be the start of the prefix of another one.
+    fn is_own_lease(&self, lease: &Lease) -> bool {
+        lease.database
+            == self
+                .feed_collection_client
+                .database_client()
+                .database_name()
+            && lease.collection == self.feed_collection_client.collection_name()
+            && lease.id == self.lease_id(&lease.partition_key_range_id)
+    }
+
+    // Replaces the lease only if nobody else has modified it since it was read.
+    // Returns None if the lease has been modified or deleted by another instance.
+    async fn replace_lease(
+        &self,
+        mut lease: Document<Lease>,
+    ) -> Result<Option<Document<Lease>>, AzureError> {
+        lease.document.timestamp = chrono::Utc::now().timestamp();
+        let etag = lease.document_attributes.etag().to_owned();
+
+        let response = self
+            .lease_collection_client
+            .replace_document()
+            .with_document_id(&lease.document.id)
+            .with_partition_keys(&(&lease.document.id).into())
+            .with_if_match_condition(IfMatchCondition::Match(&etag))
+            .execute_with_document(&lease)
+            .await;
+
+        match response {
+            Ok(response) => {
+                lease.document_attributes = response.document_attributes;
+                Ok(Some(lease))
+            }
+            Err(AzureError::UnexpectedHTTPResult(err))
+                if err.status_code() == StatusCode::PRECONDITION_FAILED
+                    || err.status_code() == StatusCode::NOT_FOUND =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
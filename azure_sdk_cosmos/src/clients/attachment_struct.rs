@@ -148,4 +148,14 @@ where
     fn get(&self) -> requests::GetAttachmentBuilder<'_, '_, C, D, COLL, DOC> {
         requests::GetAttachmentBuilder::new(self)
     }
+
+    fn get_attachment_media(&self) -> requests::GetAttachmentMediaBuilder<'_, '_, C, D, COLL, DOC> {
+        requests::GetAttachmentMediaBuilder::new(self)
+    }
+
+    fn replace_attachment_media(
+        &self,
+    ) -> requests::ReplaceAttachmentMediaBuilder<'_, '_, C, D, COLL, DOC, No> {
+        requests::ReplaceAttachmentMediaBuilder::new(self)
+    }
 }
@@ -2,9 +2,10 @@ use crate::clients::*;
 use crate::requests;
 use crate::{
     CollectionClient, CosmosClient, DatabaseClient, HasCosmosClient, HasDatabaseClient,
-    HasHyperClient, IntoDocumentClient, IntoStoredProcedureClient, IntoTriggerClient,
-    IntoUserDefinedFunctionClient, PartitionKeys, UserDefinedFunctionStruct, WithDocumentClient,
-    WithStoredProcedureClient, WithTriggerClient, WithUserDefinedFunctionClient,
+    HasHyperClient, IntoConflictClient, IntoDocumentClient, IntoStoredProcedureClient,
+    IntoTriggerClient, IntoUserDefinedFunctionClient, PartitionKeys, UserDefinedFunctionStruct,
+    WithConflictClient, WithDocumentClient, WithStoredProcedureClient, WithTriggerClient,
+    WithUserDefinedFunctionClient,
 };
 use azure_sdk_core::No;
 use std::borrow::Cow;
@@ -105,10 +106,22 @@ where
         requests::ReplaceDocumentBuilder::new(self)
     }
 
+    fn patch_document(&self) -> requests::PatchDocumentBuilder<'_, '_, C, D, No, No> {
+        requests::PatchDocumentBuilder::new(self)
+    }
+
     fn query_documents(&self) -> requests::QueryDocumentsBuilder<'_, '_, C, D, No> {
         requests::QueryDocumentsBuilder::new(self)
     }
 
+    fn read_change_feed(&self) -> requests::ReadChangeFeedBuilder<'_, '_, C, D> {
+        requests::ReadChangeFeedBuilder::new(self)
+    }
+
+    fn transactional_batch(&self) -> requests::TransactionalBatchBuilder<'_, '_, C, D, No, No> {
+        requests::TransactionalBatchBuilder::new(self)
+    }
+
     fn list_stored_procedures(&self) -> requests::ListStoredProceduresBuilder<'_, '_, C, D> {
         requests::ListStoredProceduresBuilder::new(self)
     }
@@ -119,6 +132,10 @@ where
         requests::ListUserDefinedFunctionsBuilder::new(self)
     }
 
+    fn list_conflicts(&self) -> requests::ListConflictsBuilder<'_, '_, C, D> {
+        requests::ListConflictsBuilder::new(self)
+    }
+
     fn list_triggers(&self) -> requests::ListTriggersBuilder<'_, '_, C, D> {
         requests::ListTriggersBuilder::new(self)
     }
@@ -126,6 +143,10 @@ where
     fn get_partition_key_ranges(&self) -> requests::GetPartitionKeyRangesBuilder<'_, '_, C, D> {
         requests::GetPartitionKeyRangesBuilder::new(self)
     }
+
+    fn bulk_executor(&self) -> crate::bulk::BulkExecutor<'_, C, D> {
+        crate::bulk::BulkExecutor::new(self)
+    }
 }
 
 impl<'a, 'b, C, D> IntoDocumentClient<'b, C, D, Self, DocumentStruct<'a, 'b, C, D, Self>>
@@ -267,3 +288,37 @@ where
         StoredProcedureStruct::new(Cow::Owned(self), stored_procedure_name.into())
     }
 }
+
+impl<'a, C, D> WithConflictClient<'a, C, D, Self, ConflictStruct<'a, C, D, Self>>
+    for CollectionStruct<'a, C, D>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+{
+    fn with_conflict_client<IntoCowStr>(
+        &'a self,
+        conflict_name: IntoCowStr,
+    ) -> ConflictStruct<'a, C, D, Self>
+    where
+        IntoCowStr: Into<Cow<'a, str>>,
+    {
+        ConflictStruct::new(Cow::Borrowed(self), conflict_name.into())
+    }
+}
+
+impl<'a, C, D> IntoConflictClient<'a, C, D, Self, ConflictStruct<'a, C, D, Self>>
+    for CollectionStruct<'a, C, D>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+{
+    fn into_conflict_client<IntoCowStr>(
+        self,
+        conflict_name: IntoCowStr,
+    ) -> ConflictStruct<'a, C, D, Self>
+    where
+        IntoCowStr: Into<Cow<'a, str>>,
+    {
+        ConflictStruct::new(Cow::Owned(self), conflict_name.into())
+    }
+}
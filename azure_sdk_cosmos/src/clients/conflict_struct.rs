@@ -0,0 +1,102 @@
+use crate::requests;
+use crate::traits::*;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct ConflictStruct<'a, C, D, COLL>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+    COLL: CollectionClient<C, D> + Clone,
+{
+    collection_client: Cow<'a, COLL>,
+    conflict_name: Cow<'a, str>,
+    p_c: PhantomData<C>,
+    p_d: PhantomData<D>,
+}
+
+impl<'a, C, D, COLL> ConflictStruct<'a, C, D, COLL>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+    COLL: CollectionClient<C, D> + Clone,
+{
+    pub(crate) fn new(collection_client: Cow<'a, COLL>, conflict_name: Cow<'a, str>) -> Self {
+        Self {
+            collection_client,
+            conflict_name,
+            p_c: PhantomData {},
+            p_d: PhantomData {},
+        }
+    }
+}
+
+impl<'a, C, D, COLL> HasHyperClient for ConflictStruct<'a, C, D, COLL>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+    COLL: CollectionClient<C, D> + Clone,
+{
+    #[inline]
+    fn hyper_client(
+        &self,
+    ) -> &hyper::Client<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>> {
+        self.collection_client.hyper_client()
+    }
+}
+
+impl<'a, C, D, COLL> HasCosmosClient<C> for ConflictStruct<'a, C, D, COLL>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+    COLL: CollectionClient<C, D> + Clone,
+{
+    #[inline]
+    fn cosmos_client(&self) -> &C {
+        self.collection_client.cosmos_client()
+    }
+}
+
+impl<'a, C, D, COLL> HasDatabaseClient<C, D> for ConflictStruct<'a, C, D, COLL>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+    COLL: CollectionClient<C, D> + Clone,
+{
+    #[inline]
+    fn database_client(&self) -> &D {
+        self.collection_client.database_client()
+    }
+}
+
+impl<'a, C, D, COLL> HasCollectionClient<C, D, COLL> for ConflictStruct<'a, C, D, COLL>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+    COLL: CollectionClient<C, D> + Clone,
+{
+    #[inline]
+    fn collection_client(&self) -> &COLL {
+        &self.collection_client
+    }
+}
+
+impl<'a, C, D, COLL> ConflictClient<C, D, COLL> for ConflictStruct<'a, C, D, COLL>
+where
+    C: CosmosClient + Clone,
+    D: DatabaseClient<C> + Clone,
+    COLL: CollectionClient<C, D> + Clone,
+{
+    fn conflict_name(&self) -> &str {
+        &self.conflict_name
+    }
+
+    fn get_conflict(&self) -> requests::GetConflictBuilder<'_, '_, C, D, COLL> {
+        requests::GetConflictBuilder::new(self)
+    }
+
+    fn delete_conflict(&self) -> requests::DeleteConflictBuilder<'_, '_, C, D, COLL> {
+        requests::DeleteConflictBuilder::new(self)
+    }
+}
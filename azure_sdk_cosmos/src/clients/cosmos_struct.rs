@@ -2,8 +2,9 @@ use crate::clients::DatabaseStruct;
 use crate::headers::*;
 use crate::requests;
 use crate::{
-    AuthorizationToken, CosmosClient, HasHyperClient, IntoDatabaseClient, ResourceType,
-    WithDatabaseClient,
+    AuthorizationToken, CosmosClient, DiagnosticsHandler, HasHyperClient, IntoDatabaseClient,
+    LocationCache, RequestDiagnostics, ResourceTokenRefresher, ResourceType, RetryPolicy,
+    SessionContainer, WithDatabaseClient,
 };
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::No;
@@ -37,18 +38,77 @@ where
     account: Cow<'a, str>,
     auth_token: AuthorizationToken,
     cosmos_uri_builder: CUB,
+    retry_policy: RetryPolicy,
+    location_cache: LocationCache,
+    session_container: SessionContainer,
+    diagnostics_handler: Option<DiagnosticsHandler>,
+    resource_token_refresher: Option<ResourceTokenRefresher>,
 }
 
 impl<'a, CUB> CosmosStruct<'a, CUB>
 where
     CUB: CosmosUriBuilder + Clone,
 {
+    /// A copy of the client authorized with `auth_token`. The resource token refresher of
+    /// the client, if any, is kept and replaces `auth_token` once the server rejects it.
     pub fn with_auth_token(&self, auth_token: AuthorizationToken) -> Self {
         Self {
-            hyper_client: self.hyper_client.clone(),
-            account: self.account.clone(),
             auth_token,
-            cosmos_uri_builder: self.cosmos_uri_builder.clone(),
+            resource_token_refresher: self
+                .resource_token_refresher
+                .as_ref()
+                .map(ResourceTokenRefresher::without_auth_token),
+            ..self.clone()
+        }
+    }
+
+    pub fn with_retry_policy(&self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy,
+            ..self.clone()
+        }
+    }
+
+    /// Enables the discovery of the regions of the account: reads are sent to the first
+    /// available region of `preferred_locations` (for example `["West Europe", "North
+    /// Europe"]`) and requests fail over to the other regions when one is unavailable.
+    /// See [`LocationCache`](crate::LocationCache).
+    pub fn with_preferred_locations<I, S>(&self, preferred_locations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            location_cache: LocationCache::new(
+                preferred_locations.into_iter().map(Into::into).collect(),
+            ),
+            ..self.clone()
+        }
+    }
+
+    /// Calls `diagnostics_handler` with the diagnostics of every request sent by the
+    /// client, for example to export the request units consumed by each operation. See
+    /// [`DiagnosticsHandler`](crate::DiagnosticsHandler).
+    pub fn with_diagnostics_handler<F>(&self, diagnostics_handler: F) -> Self
+    where
+        F: Fn(&RequestDiagnostics) + Send + Sync + 'static,
+    {
+        Self {
+            diagnostics_handler: Some(DiagnosticsHandler::new(diagnostics_handler)),
+            ..self.clone()
+        }
+    }
+
+    /// Refreshes the resource token of the client when the server rejects it, usually
+    /// because it expired. See
+    /// [`ResourceTokenBroker::client_for`](crate::ResourceTokenBroker::client_for).
+    pub fn with_resource_token_refresher(
+        &self,
+        resource_token_refresher: ResourceTokenRefresher,
+    ) -> Self {
+        Self {
+            resource_token_refresher: Some(resource_token_refresher),
+            ..self.clone()
         }
     }
 }
@@ -103,6 +163,10 @@ impl CosmosUriBuilder for CustomCosmosUri {
     }
 }
 
+/// Creates the `CosmosStruct` clients. The options of a client are set on the client
+/// itself, for example `ClientBuilder::new(account, auth_token)?.with_retry_policy(..)`:
+/// `new_with_preferred_locations` is a shortcut for the preferred locations, which are
+/// usually known when the client is built.
 pub struct ClientBuilder {}
 
 impl ClientBuilder {
@@ -123,9 +187,29 @@ impl ClientBuilder {
             account: account,
             auth_token,
             cosmos_uri_builder,
+            retry_policy: RetryPolicy::default(),
+            location_cache: LocationCache::default(),
+            session_container: SessionContainer::default(),
+            diagnostics_handler: None,
+            resource_token_refresher: None,
         })
     }
 
+    /// Like `new`, with the preferred locations of the client set. See
+    /// [`CosmosStruct::with_preferred_locations`](CosmosStruct::with_preferred_locations).
+    pub fn new_with_preferred_locations<'a, IntoCowStr, I, S>(
+        account: IntoCowStr,
+        auth_token: AuthorizationToken,
+        preferred_locations: I,
+    ) -> Result<CosmosStruct<'a, DefaultCosmosUri>, AzureError>
+    where
+        IntoCowStr: Into<Cow<'a, str>>,
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Ok(Self::new(account, auth_token)?.with_preferred_locations(preferred_locations))
+    }
+
     pub fn new_china<'a, IntoCowStr>(
         account: IntoCowStr,
         auth_token: AuthorizationToken,
@@ -142,6 +226,11 @@ impl ClientBuilder {
             account,
             auth_token,
             cosmos_uri_builder,
+            retry_policy: RetryPolicy::default(),
+            location_cache: LocationCache::default(),
+            session_container: SessionContainer::default(),
+            diagnostics_handler: None,
+            resource_token_refresher: None,
         })
     }
 
@@ -160,6 +249,11 @@ impl ClientBuilder {
             account: account.into(),
             auth_token,
             cosmos_uri_builder: CustomCosmosUri { uri },
+            retry_policy: RetryPolicy::default(),
+            location_cache: LocationCache::default(),
+            session_container: SessionContainer::default(),
+            diagnostics_handler: None,
+            resource_token_refresher: None,
         })
     }
 
@@ -181,6 +275,11 @@ impl ClientBuilder {
             cosmos_uri_builder: CustomCosmosUri {
                 uri: format!("https://{}:{}", address, port),
             },
+            retry_policy: RetryPolicy::default(),
+            location_cache: LocationCache::default(),
+            session_container: SessionContainer::default(),
+            diagnostics_handler: None,
+            resource_token_refresher: None,
         })
     }
 }
@@ -207,6 +306,26 @@ where
         requests::ListDatabasesBuilder::new(self)
     }
 
+    fn list_offers(&self) -> requests::ListOffersBuilder<'_> {
+        requests::ListOffersBuilder::new(self)
+    }
+
+    fn get_offer(&self) -> requests::GetOfferBuilder<'_, '_, No> {
+        requests::GetOfferBuilder::new(self)
+    }
+
+    fn query_offers(&self) -> requests::QueryOffersBuilder<'_, '_, No> {
+        requests::QueryOffersBuilder::new(self)
+    }
+
+    fn replace_offer(&self) -> requests::ReplaceOfferBuilder<'_, '_, No> {
+        requests::ReplaceOfferBuilder::new(self)
+    }
+
+    fn get_database_account(&self) -> requests::GetDatabaseAccountBuilder<'_> {
+        requests::GetDatabaseAccountBuilder::new(self)
+    }
+
     #[inline]
     fn prepare_request(
         &self,
@@ -217,17 +336,51 @@ where
         let time = format!("{}", chrono::Utc::now().format(TIME_FORMAT));
 
         let auth = {
-            let resource_link = generate_resource_link(&uri_path);
+            let resource_link = match resource_type {
+                ResourceType::Offers => Cow::Owned(generate_offer_resource_link(uri_path)),
+                ResourceType::Media => Cow::Owned(generate_media_resource_link(uri_path)),
+                _ => Cow::Borrowed(generate_resource_link(&uri_path)),
+            };
+            // the token refreshed after the server rejected the one of the client
+            let refreshed_token = self
+                .resource_token_refresher
+                .as_ref()
+                .and_then(ResourceTokenRefresher::auth_token);
             generate_authorization(
-                &self.auth_token,
+                refreshed_token.as_ref().unwrap_or(&self.auth_token),
                 &http_method,
                 resource_type,
-                resource_link,
+                &resource_link,
                 &time,
             )
         };
         self.prepare_request_with_signature(uri_path, http_method, &time, &auth)
     }
+
+    #[inline]
+    fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    #[inline]
+    fn location_cache(&self) -> &LocationCache {
+        &self.location_cache
+    }
+
+    #[inline]
+    fn session_container(&self) -> &SessionContainer {
+        &self.session_container
+    }
+
+    #[inline]
+    fn diagnostics_handler(&self) -> Option<&DiagnosticsHandler> {
+        self.diagnostics_handler.as_ref()
+    }
+
+    #[inline]
+    fn resource_token_refresher(&self) -> Option<&ResourceTokenRefresher> {
+        self.resource_token_refresher.as_ref()
+    }
 }
 
 impl<'a, CUB> IntoDatabaseClient<'a, Self, DatabaseStruct<'a, Self>> for CosmosStruct<'a, CUB>
@@ -338,6 +491,17 @@ fn generate_authorization(
     form_urlencoded::byte_serialize(&str_unencoded.as_bytes()).collect::<String>()
 }
 
+// The authorization header of a resource token, which does not depend on the request.
+pub(crate) fn resource_token_authorization(signature: &str) -> String {
+    generate_authorization(
+        &AuthorizationToken::Resource(signature.to_owned()),
+        &hyper::Method::GET,
+        ResourceType::Documents,
+        "",
+        "",
+    )
+}
+
 fn encode_str_to_sign(str_to_sign: &str, key: &[u8]) -> String {
     let key = hmac::Key::new(ring::hmac::HMAC_SHA256, key);
     let sig = hmac::sign(&key, str_to_sign.as_bytes());
@@ -384,6 +548,10 @@ fn string_to_sign(
             ResourceType::PartitionKeyRanges => "pkranges",
             ResourceType::UserDefinedFunctions => "udfs",
             ResourceType::Triggers => "triggers",
+            ResourceType::Offers => "offers",
+            ResourceType::Conflicts => "conflicts",
+            ResourceType::DatabaseAccount => "",
+            ResourceType::Media => "media",
         },
         resource_link,
         time.to_lowercase()
@@ -402,6 +570,7 @@ fn generate_resource_link(u: &str) -> &str {
         "pkranges",
         "udfs",
         "triggers",
+        "conflicts",
     ];
 
     // store the element only if it does not end with dbs, colls or docs
@@ -428,6 +597,21 @@ fn generate_resource_link(u: &str) -> &str {
     p
 }
 
+// Offers are signed with their resource id alone, in lowercase (and with an empty
+// link when listing or querying them).
+fn generate_offer_resource_link(u: &str) -> String {
+    u.trim_start_matches("offers")
+        .trim_start_matches('/')
+        .to_lowercase()
+}
+
+// Like offers, the media of the attachments are addressed and signed by resource id.
+fn generate_media_resource_link(u: &str) -> String {
+    u.trim_start_matches("media")
+        .trim_start_matches('/')
+        .to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -523,4 +707,18 @@ mon, 01 jan 1900 01:00:00 gmt
         );
         assert_eq!(generate_resource_link("dbs/test_db/colls"), "dbs/test_db");
     }
+
+    #[test]
+    fn generate_offer_resource_link_00() {
+        assert_eq!(generate_offer_resource_link("offers"), "");
+        assert_eq!(generate_offer_resource_link("offers/Z8ag"), "z8ag");
+    }
+
+    #[test]
+    fn generate_media_resource_link_00() {
+        assert_eq!(
+            generate_media_resource_link("media/Bz1VAOUQ3wABAAAAAAAAAAEiGSY="),
+            "bz1vaouq3wabaaaaaaaaaaeigsy="
+        );
+    }
 }
@@ -1,5 +1,6 @@
 mod attachment_struct;
 mod collection_struct;
+mod conflict_struct;
 mod cosmos_struct;
 mod database_struct;
 mod document_struct;
@@ -10,6 +11,7 @@ mod user_defined_function_struct;
 mod user_struct;
 pub use attachment_struct::AttachmentStruct;
 pub use collection_struct::CollectionStruct;
+pub use conflict_struct::ConflictStruct;
 pub use cosmos_struct::*;
 pub use database_struct::DatabaseStruct;
 pub use document_struct::DocumentStruct;
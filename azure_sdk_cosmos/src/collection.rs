@@ -1,8 +1,12 @@
+use crate::TimeToLive;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq)]
 pub enum KeyKind {
     Hash,
     Range,
     Spatial,
+    /// Hierarchical partition keys: up to three paths, each one hashed on its own.
+    MultiHash,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq)]
@@ -58,6 +62,9 @@ impl std::convert::From<String> for ExcludedPath {
 pub struct PartitionKey {
     pub paths: Vec<String>,
     pub kind: KeyKind,
+    /// Version of the hash function: 1 (the default) or 2, needed for long partition keys.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub version: Option<u32>,
 }
 
 impl std::default::Default for PartitionKey {
@@ -65,6 +72,7 @@ impl std::default::Default for PartitionKey {
         Self {
             paths: vec![],
             kind: KeyKind::Hash,
+            version: None,
         }
     }
 }
@@ -77,6 +85,7 @@ where
         Self {
             paths: vec![t.as_ref().to_owned()],
             kind: KeyKind::Hash,
+            version: None,
         }
     }
 }
@@ -91,6 +100,129 @@ pub struct IndexingPolicy {
     pub included_paths: Vec<IncludedPath>,
     #[serde(rename = "excludedPaths")]
     pub excluded_paths: Vec<ExcludedPath>,
+    /// Indexes on several paths, needed by the queries ordering by more than one path.
+    #[serde(rename = "compositeIndexes")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub composite_indexes: Vec<CompositeIndex>,
+    #[serde(rename = "spatialIndexes")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub spatial_indexes: Vec<SpatialIndex>,
+}
+
+impl std::default::Default for IndexingPolicy {
+    fn default() -> Self {
+        Self {
+            automatic: true,
+            indexing_mode: IndexingMode::Consistent,
+            included_paths: vec![],
+            excluded_paths: vec![],
+            composite_indexes: vec![],
+            spatial_indexes: vec![],
+        }
+    }
+}
+
+impl IndexingPolicy {
+    pub fn with_composite_indexes(self, composite_indexes: Vec<CompositeIndex>) -> Self {
+        Self {
+            composite_indexes,
+            ..self
+        }
+    }
+
+    pub fn with_spatial_indexes(self, spatial_indexes: Vec<SpatialIndex>) -> Self {
+        Self {
+            spatial_indexes,
+            ..self
+        }
+    }
+}
+
+/// The paths of a composite index, in order. A query can use the index if its `ORDER BY`
+/// has the same paths with the same orders, or all of them reversed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq)]
+#[serde(transparent)]
+pub struct CompositeIndex {
+    pub paths: Vec<CompositePath>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq)]
+pub struct CompositePath {
+    #[serde(rename = "path")]
+    pub path: String,
+    #[serde(rename = "order")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub order: Option<CompositePathOrder>,
+}
+
+impl<T> std::convert::From<T> for CompositePath
+where
+    T: AsRef<str>,
+{
+    fn from(t: T) -> Self {
+        Self {
+            path: t.as_ref().to_owned(),
+            order: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq)]
+pub enum CompositePathOrder {
+    #[serde(rename = "ascending")]
+    Ascending,
+    #[serde(rename = "descending")]
+    Descending,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq)]
+pub struct SpatialIndex {
+    /// Path of the GeoJSON values, for example `/location/*`.
+    #[serde(rename = "path")]
+    pub path: String,
+    #[serde(rename = "types")]
+    pub types: Vec<SpatialType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialOrd, PartialEq)]
+pub enum SpatialType {
+    Point,
+    LineString,
+    Polygon,
+    MultiPolygon,
+}
+
+/// Paths whose values must be unique among the documents with the same partition key.
+/// The policy is set when the collection is created and cannot be changed afterwards.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialOrd, PartialEq)]
+pub struct UniqueKeyPolicy {
+    #[serde(rename = "uniqueKeys")]
+    pub unique_keys: Vec<UniqueKey>,
+}
+
+/// A combination of paths that must be unique, for example `["/firstName", "/lastName"]`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq)]
+pub struct UniqueKey {
+    #[serde(rename = "paths")]
+    pub paths: Vec<String>,
+}
+
+/// How the conflicts of a multi-region write account are resolved.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq)]
+#[serde(tag = "mode")]
+pub enum ConflictResolutionPolicy {
+    /// The write with the highest value in `path` (`/_ts` by default) wins.
+    LastWriterWins {
+        #[serde(rename = "conflictResolutionPath", default)]
+        path: String,
+    },
+    /// Conflicts are resolved by the stored procedure at `procedure` (for example
+    /// `dbs/db/colls/coll/sprocs/resolver`). If empty, the conflicts are written to the
+    /// conflicts feed of the collection and must be resolved manually.
+    Custom {
+        #[serde(rename = "conflictResolutionProcedure", default)]
+        procedure: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq)]
@@ -100,6 +232,16 @@ pub struct Collection {
     pub indexing_policy: IndexingPolicy,
     #[serde(rename = "partitionKey")]
     pub parition_key: PartitionKey,
+    #[serde(rename = "conflictResolutionPolicy")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub conflict_resolution_policy: Option<ConflictResolutionPolicy>,
+    /// Time to live of the documents. If `None` the documents never expire.
+    #[serde(rename = "defaultTtl")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default_ttl: Option<TimeToLive>,
+    #[serde(rename = "uniqueKeyPolicy")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub unique_key_policy: Option<UniqueKeyPolicy>,
     #[serde(rename = "_rid")]
     pub rid: String,
     #[serde(rename = "_ts")]
@@ -126,6 +268,9 @@ impl Collection {
             id: id.to_owned(),
             indexing_policy,
             parition_key: PartitionKey::default(),
+            conflict_resolution_policy: None,
+            default_ttl: None,
+            unique_key_policy: None,
             rid: "".to_owned(),
             ts: 0,
             _self: "".to_owned(),
@@ -160,3 +305,109 @@ impl CollectionName for String {
         self.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_conflict_resolution_policy() {
+        let policy = ConflictResolutionPolicy::LastWriterWins {
+            path: "/_ts".to_owned(),
+        };
+        assert_eq!(
+            serde_json::to_string(&policy).unwrap(),
+            r#"{"mode":"LastWriterWins","conflictResolutionPath":"/_ts"}"#
+        );
+
+        let policy: ConflictResolutionPolicy = serde_json::from_str(
+            r#"{"mode":"Custom","conflictResolutionPath":"","conflictResolutionProcedure":"dbs/db/colls/coll/sprocs/resolver"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            policy,
+            ConflictResolutionPolicy::Custom {
+                procedure: "dbs/db/colls/coll/sprocs/resolver".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_indexing_and_unique_key_policies() {
+        let collection: Collection = serde_json::from_str(
+            r#"{
+                "id": "cities",
+                "indexingPolicy": {
+                    "indexingMode": "consistent",
+                    "automatic": true,
+                    "includedPaths": [{ "path": "/*" }],
+                    "excludedPaths": [{ "path": "/\"_etag\"/?" }],
+                    "compositeIndexes": [
+                        [
+                            { "path": "/country", "order": "ascending" },
+                            { "path": "/population", "order": "descending" }
+                        ]
+                    ],
+                    "spatialIndexes": [
+                        { "path": "/location/*", "types": ["Point", "Polygon", "MultiPolygon", "LineString"] }
+                    ]
+                },
+                "partitionKey": { "paths": ["/country"], "kind": "Hash" },
+                "uniqueKeyPolicy": { "uniqueKeys": [{ "paths": ["/name", "/zip"] }] },
+                "_rid": "AAAAAA==",
+                "_ts": 1602936435,
+                "_self": "dbs/AAAAAA==/colls/AAAAAA==/",
+                "_etag": "\"00000000-0000-0000-0000-000000000000\"",
+                "_docs": "docs/",
+                "_sprocs": "sprocs/",
+                "_triggers": "triggers/",
+                "_udfs": "udfs/",
+                "_conflicts": "conflicts/"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            collection.indexing_policy.composite_indexes,
+            vec![CompositeIndex {
+                paths: vec![
+                    CompositePath {
+                        path: "/country".to_owned(),
+                        order: Some(CompositePathOrder::Ascending),
+                    },
+                    CompositePath {
+                        path: "/population".to_owned(),
+                        order: Some(CompositePathOrder::Descending),
+                    },
+                ]
+            }]
+        );
+        assert_eq!(
+            collection.indexing_policy.spatial_indexes[0].types,
+            vec![
+                SpatialType::Point,
+                SpatialType::Polygon,
+                SpatialType::MultiPolygon,
+                SpatialType::LineString
+            ]
+        );
+        assert_eq!(
+            collection.unique_key_policy,
+            Some(UniqueKeyPolicy {
+                unique_keys: vec![UniqueKey {
+                    paths: vec!["/name".to_owned(), "/zip".to_owned()]
+                }]
+            })
+        );
+
+        let serialized = serde_json::to_value(&collection).unwrap();
+        assert_eq!(
+            serialized["indexingPolicy"]["compositeIndexes"][0][1],
+            serde_json::json!({ "path": "/population", "order": "descending" })
+        );
+        assert_eq!(
+            serde_json::from_value::<Collection>(serialized).unwrap(),
+            collection
+        );
+    }
+}
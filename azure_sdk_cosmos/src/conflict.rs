@@ -0,0 +1,73 @@
+use serde::de::DeserializeOwned;
+
+/// Operation that caused the conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictOperation {
+    Create,
+    Replace,
+    Delete,
+}
+
+/// Conflict that was not resolved automatically. It can be found in the conflicts feed of
+/// the collection until it is deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Conflict {
+    pub id: String,
+    #[serde(rename = "_rid")]
+    pub rid: String,
+    #[serde(rename = "_ts")]
+    pub ts: u64,
+    #[serde(rename = "_self")]
+    pub _self: String,
+    #[serde(rename = "_etag")]
+    pub etag: String,
+    /// Kind of the conflicting resource (for example `document`).
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    #[serde(rename = "operationType")]
+    pub operation_type: ConflictOperation,
+    /// Resource id (`_rid`) of the conflicting resource.
+    #[serde(rename = "resourceId")]
+    pub resource_id: String,
+    /// The conflicting version of the resource, serialized as JSON.
+    #[serde(default)]
+    pub content: String,
+}
+
+impl Conflict {
+    /// Deserializes the conflicting version of the resource.
+    pub fn content<T>(&self) -> Result<T, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_str(&self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_conflict() {
+        let body = r#"{
+            "id": "K1MGAK0ZEwA=",
+            "_rid": "K1MGAK0ZEwA=",
+            "_self": "dbs/K1MGAA==/colls/K1MGAK0ZEwA=/conflicts/K1MGAK0ZEwA=/",
+            "_etag": "\"00000000-0000-0000-0000-000000000000\"",
+            "_ts": 1589112233,
+            "resourceId": "K1MGAK0ZEwABAAAAAAAAAA==",
+            "resourceType": "document",
+            "operationType": "replace",
+            "content": "{\"id\":\"doc\",\"value\":42}"
+        }"#;
+
+        let conflict: Conflict = serde_json::from_str(body).unwrap();
+        assert_eq!(conflict.operation_type, ConflictOperation::Replace);
+        assert_eq!(conflict.resource_type, "document");
+
+        let content: serde_json::Value = conflict.content().unwrap();
+        assert_eq!(content["value"], 42);
+    }
+}
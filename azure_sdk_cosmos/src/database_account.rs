@@ -0,0 +1,81 @@
+/// Properties of the database account, as returned by `GET /`. For more details see
+/// [https://docs.microsoft.com/en-us/rest/api/cosmos-db/get-a-database-account](https://docs.microsoft.com/en-us/rest/api/cosmos-db/get-a-database-account).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseAccount {
+    pub id: String,
+    #[serde(rename = "_rid")]
+    pub rid: String,
+    #[serde(rename = "_self", default)]
+    pub _self: String,
+    /// Regions accepting writes. Unless multiple write locations are enabled only the
+    /// first one does.
+    #[serde(rename = "writableLocations", default)]
+    pub writable_locations: Vec<DatabaseAccountLocation>,
+    #[serde(rename = "readableLocations", default)]
+    pub readable_locations: Vec<DatabaseAccountLocation>,
+    #[serde(rename = "enableMultipleWriteLocations", default)]
+    pub enable_multiple_write_locations: bool,
+    #[serde(
+        rename = "userConsistencyPolicy",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub user_consistency_policy: Option<UserConsistencyPolicy>,
+}
+
+/// A region of the database account and the endpoint to use to reach it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseAccountLocation {
+    /// Name of the region, for example `West Europe`.
+    pub name: String,
+    #[serde(rename = "databaseAccountEndpoint")]
+    pub database_account_endpoint: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserConsistencyPolicy {
+    #[serde(rename = "defaultConsistencyLevel")]
+    pub default_consistency_level: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_database_account() {
+        let json = r#"{
+            "_self": "",
+            "id": "myaccount",
+            "_rid": "myaccount.documents.azure.com",
+            "media": "//media/",
+            "addresses": "//addresses/",
+            "_dbs": "//dbs/",
+            "writableLocations": [
+                { "name": "West Europe", "databaseAccountEndpoint": "https://myaccount-westeurope.documents.azure.com:443/" }
+            ],
+            "readableLocations": [
+                { "name": "West Europe", "databaseAccountEndpoint": "https://myaccount-westeurope.documents.azure.com:443/" },
+                { "name": "North Europe", "databaseAccountEndpoint": "https://myaccount-northeurope.documents.azure.com:443/" }
+            ],
+            "enableMultipleWriteLocations": false,
+            "userConsistencyPolicy": { "defaultConsistencyLevel": "Session" }
+        }"#;
+
+        let account: DatabaseAccount = serde_json::from_str(json).unwrap();
+        assert_eq!(account.id, "myaccount");
+        assert_eq!(account.writable_locations.len(), 1);
+        assert_eq!(account.readable_locations[1].name, "North Europe");
+        assert_eq!(
+            account.readable_locations[1].database_account_endpoint,
+            "https://myaccount-northeurope.documents.azure.com:443/"
+        );
+        assert!(!account.enable_multiple_write_locations);
+        assert_eq!(
+            account
+                .user_consistency_policy
+                .unwrap()
+                .default_consistency_level,
+            "Session"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use crate::from_headers::{activity_id_from_headers, request_charge_from_headers};
+use crate::headers::*;
+use crate::resource_quota::resource_quotas_from_str;
+use crate::session_container::execute_with_session_token;
+use crate::{CosmosClient, ResourceQuota};
+use azure_sdk_core::errors::{AzureError, UnexpectedHTTPResult};
+use hyper::body::Bytes;
+use hyper::{Body, HeaderMap, Request, Response, StatusCode};
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// What happened while serving a request: its cost, where it was sent and how long it
+/// took. Every response carries the diagnostics of the request that produced it.
+///
+/// The values missing from the response (for example the session token of a request
+/// that does not use one) are left empty instead of failing the request.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+pub struct Diagnostics {
+    /// Request units consumed, `None` if the response does not tell.
+    pub charge: Option<f64>,
+    pub activity_id: Option<uuid::Uuid>,
+    pub session_token: Option<String>,
+    /// Endpoint that served the request, for example
+    /// `https://myaccount-westeurope.documents.azure.com:443/`.
+    pub endpoint: Option<String>,
+    /// Region that served the request. Known only when the regions of the account are
+    /// discovered (see `CosmosStruct::with_preferred_locations`).
+    pub region: Option<String>,
+    /// Retries made because the request was throttled.
+    pub retry_count: u32,
+    /// Time spent waiting before the retries.
+    pub throttled_time: Duration,
+    /// Time elapsed from sending the request to receiving the headers of the response,
+    /// retries and failovers included.
+    pub latency: Duration,
+    pub resource_quota: Vec<ResourceQuota>,
+    pub resource_usage: Vec<ResourceQuota>,
+}
+
+impl Diagnostics {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let quotas = |name: &str| {
+            header(name)
+                .and_then(|value| resource_quotas_from_str(value).ok())
+                .unwrap_or_default()
+        };
+
+        Self {
+            charge: request_charge_from_headers(headers).ok(),
+            activity_id: activity_id_from_headers(headers).ok(),
+            session_token: header(HEADER_SESSION_TOKEN).map(str::to_owned),
+            endpoint: None,
+            region: None,
+            retry_count: 0,
+            throttled_time: Duration::default(),
+            latency: Duration::default(),
+            resource_quota: quotas(HEADER_RESOURCE_QUOTA),
+            resource_usage: quotas(HEADER_RESOURCE_USAGE),
+        }
+    }
+
+    // The diagnostics of a response: the ones sent by the service in the headers along
+    // with the ones collected by the client while sending the request, which are kept in
+    // the extensions of the response.
+    pub(crate) fn from_response(response: &Response<Body>) -> Self {
+        let client_diagnostics = response
+            .extensions()
+            .get::<Diagnostics>()
+            .cloned()
+            .unwrap_or_default();
+
+        Self {
+            endpoint: client_diagnostics.endpoint,
+            region: client_diagnostics.region,
+            retry_count: client_diagnostics.retry_count,
+            throttled_time: client_diagnostics.throttled_time,
+            latency: client_diagnostics.latency,
+            ..Self::from_headers(response.headers())
+        }
+    }
+}
+
+// The diagnostics collected by the client for the response, added to its extensions if
+// missing.
+pub(crate) fn diagnostics_mut(response: &mut Response<Body>) -> &mut Diagnostics {
+    let extensions = response.extensions_mut();
+    if extensions.get::<Diagnostics>().is_none() {
+        extensions.insert(Diagnostics::default());
+    }
+    extensions
+        .get_mut::<Diagnostics>()
+        .expect("the diagnostics have just been added")
+}
+
+// Like `extract_status_headers_and_body` of azure_sdk_core, returning the diagnostics of
+// the response as well.
+pub(crate) async fn extract_status_headers_body_and_diagnostics(
+    response: impl Future<Output = Result<Response<Body>, hyper::Error>>,
+) -> Result<(StatusCode, HeaderMap, Bytes, Diagnostics), AzureError> {
+    let response = response.await?;
+    let diagnostics = Diagnostics::from_response(&response);
+    let (head, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body).await?;
+
+    Ok((head.status, head.headers, body, diagnostics))
+}
+
+// Like `check_status_extract_headers_and_body` of azure_sdk_core, returning the diagnostics
+// of the response as well.
+pub(crate) async fn check_status_extract_headers_body_and_diagnostics(
+    response: impl Future<Output = Result<Response<Body>, hyper::Error>>,
+    expected_status_code: StatusCode,
+) -> Result<(HeaderMap, Bytes, Diagnostics), AzureError> {
+    let (status, headers, body, diagnostics) =
+        extract_status_headers_body_and_diagnostics(response).await?;
+    if status == expected_status_code {
+        Ok((headers, body, diagnostics))
+    } else {
+        Err(
+            UnexpectedHTTPResult::new(expected_status_code, status, std::str::from_utf8(&body)?)
+                .into(),
+        )
+    }
+}
+
+/// The diagnostics of a request, as passed to the `DiagnosticsHandler` of the client.
+#[derive(Debug, Clone)]
+pub struct RequestDiagnostics {
+    pub method: hyper::Method,
+    /// Path of the resource, for example `/dbs/db/colls/coll/docs/doc`.
+    pub path: String,
+    pub status_code: StatusCode,
+    pub diagnostics: Diagnostics,
+}
+
+/// Function called with the diagnostics of every request sent by the client, whatever
+/// its outcome, for example to export the request units consumed by each operation.
+/// Requests that do not get a response (because the service cannot be reached) are not
+/// reported. Set with `CosmosStruct::with_diagnostics_handler`.
+#[derive(Clone)]
+pub struct DiagnosticsHandler(Arc<dyn Fn(&RequestDiagnostics) + Send + Sync>);
+
+impl DiagnosticsHandler {
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&RequestDiagnostics) + Send + Sync + 'static,
+    {
+        Self(Arc::new(handler))
+    }
+
+    pub(crate) fn call(&self, request_diagnostics: &RequestDiagnostics) {
+        (self.0)(request_diagnostics)
+    }
+}
+
+impl fmt::Debug for DiagnosticsHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DiagnosticsHandler")
+    }
+}
+
+// Sends the request measuring its latency, which is recorded in the diagnostics of the
+// response, and passes the diagnostics to the handler of the client.
+pub(crate) async fn execute_with_diagnostics<C>(
+    cosmos_client: &C,
+    request: Request<Body>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    C: CosmosClient + ?Sized,
+{
+    let method = request.method().clone();
+    let path = request.uri().path().to_owned();
+
+    let start = Instant::now();
+    let mut response = execute_with_session_token(cosmos_client, request).await?;
+    let latency = start.elapsed();
+
+    diagnostics_mut(&mut response).latency = latency;
+
+    if let Some(diagnostics_handler) = cosmos_client.diagnostics_handler() {
+        diagnostics_handler.call(&RequestDiagnostics {
+            method,
+            path,
+            status_code: response.status(),
+            diagnostics: Diagnostics::from_response(&response),
+        });
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::header::HeaderValue;
+
+    #[test]
+    fn diagnostics_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(HEADER_REQUEST_CHARGE, HeaderValue::from_static("5.71"));
+        headers.insert(
+            HEADER_ACTIVITY_ID,
+            HeaderValue::from_static("a0c5b0b6-4fe6-4c8f-8d7b-4c1a3a0c9f53"),
+        );
+        headers.insert(HEADER_SESSION_TOKEN, HeaderValue::from_static("0:1#12"));
+        headers.insert(
+            HEADER_RESOURCE_QUOTA,
+            HeaderValue::from_static("documentSize=10240;documentsCount=-1;"),
+        );
+        headers.insert(
+            HEADER_RESOURCE_USAGE,
+            HeaderValue::from_static("documentSize=0;documentsCount=4;"),
+        );
+
+        let diagnostics = Diagnostics::from_headers(&headers);
+        assert_eq!(diagnostics.charge, Some(5.71));
+        assert_eq!(
+            diagnostics.activity_id.unwrap().to_string(),
+            "a0c5b0b6-4fe6-4c8f-8d7b-4c1a3a0c9f53"
+        );
+        assert_eq!(diagnostics.session_token.as_deref(), Some("0:1#12"));
+        assert_eq!(
+            diagnostics.resource_usage,
+            vec![
+                ResourceQuota::DocumentSize(0),
+                ResourceQuota::DocumentsCount(4)
+            ]
+        );
+
+        let diagnostics = Diagnostics::from_headers(&HeaderMap::new());
+        assert_eq!(diagnostics.charge, None);
+        assert_eq!(diagnostics, Diagnostics::default());
+    }
+
+    #[test]
+    fn diagnostics_from_response() {
+        let mut response = Response::new(Body::empty());
+        response
+            .headers_mut()
+            .insert(HEADER_REQUEST_CHARGE, HeaderValue::from_static("2.5"));
+        assert_eq!(Diagnostics::from_response(&response).retry_count, 0);
+
+        let diagnostics = diagnostics_mut(&mut response);
+        diagnostics.region = Some("West Europe".to_owned());
+        diagnostics.retry_count = 2;
+        diagnostics.throttled_time = Duration::from_millis(300);
+        diagnostics.latency = Duration::from_micros(41250);
+
+        let diagnostics = Diagnostics::from_response(&response);
+        assert_eq!(diagnostics.charge, Some(2.5));
+        assert_eq!(diagnostics.region.as_deref(), Some("West Europe"));
+        assert_eq!(diagnostics.retry_count, 2);
+        assert_eq!(diagnostics.throttled_time, Duration::from_millis(300));
+        assert_eq!(diagnostics.latency, Duration::from_micros(41250));
+    }
+}
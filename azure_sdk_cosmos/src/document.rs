@@ -1,4 +1,4 @@
-use crate::DocumentAttributes;
+use crate::{DocumentAttributes, TimeToLive};
 use azure_sdk_core::errors::AzureError;
 use hyper::header::HeaderMap;
 use serde::de::DeserializeOwned;
@@ -24,6 +24,12 @@ impl<T> Document<T> {
             document: t,
         }
     }
+
+    /// Sets the time to live of the document, overriding the default of its collection.
+    pub fn with_ttl(mut self, ttl: TimeToLive) -> Self {
+        self.document_attributes.set_ttl(Some(ttl));
+        self
+    }
 }
 
 impl DocumentName for &str {
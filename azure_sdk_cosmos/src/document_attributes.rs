@@ -1,3 +1,4 @@
+use crate::TimeToLive;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::IfMatchCondition;
 use http::HeaderMap;
@@ -14,6 +15,11 @@ pub struct DocumentAttributes {
     pub etag: String,
     #[serde(rename = "_attachments")]
     pub attachments: String,
+    /// Time to live of the document, overriding the default of its collection. If `None`
+    /// the default applies.
+    #[serde(rename = "ttl")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ttl: Option<TimeToLive>,
 }
 
 impl DocumentAttributes {
@@ -37,6 +43,10 @@ impl DocumentAttributes {
         &self.attachments
     }
 
+    pub fn ttl(&self) -> Option<TimeToLive> {
+        self.ttl
+    }
+
     pub fn set_rid<T>(&mut self, value: T)
     where
         T: Into<String>,
@@ -68,6 +78,10 @@ impl DocumentAttributes {
     {
         self.attachments = value.into();
     }
+
+    pub fn set_ttl(&mut self, value: Option<TimeToLive>) {
+        self.ttl = value;
+    }
 }
 
 impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DocumentAttributes {
@@ -96,6 +110,7 @@ mod tests {
             _self: "_self".to_owned(),
             etag: "etag".to_owned(),
             attachments: "attachments".to_owned(),
+            ttl: None,
         };
 
         a.set_attachments("new_attachments".to_owned());
@@ -0,0 +1,444 @@
+//! Effective partition keys: the hashes of the partition key values that determine the
+//! partition key range a document belongs to.
+//!
+//! The components of a partition key are JSON values as sent in the
+//! `x-ms-documentdb-partitionkey` header, an empty object standing for an undefined value.
+//! The resulting hex strings can be compared lexicographically with the boundaries of the
+//! partition key ranges (see [`PartitionKeyRange::contains`](crate::PartitionKeyRange::contains)).
+use crate::collection::{KeyKind, PartitionKey};
+use crate::{PartitionKeyRange, PartitionKeys};
+use azure_sdk_core::errors::AzureError;
+use serde_json::Value;
+
+// Markers of the binary encoding of the partition key components.
+const MARKER_UNDEFINED: u8 = 0x00;
+const MARKER_NULL: u8 = 0x01;
+const MARKER_FALSE: u8 = 0x02;
+const MARKER_TRUE: u8 = 0x03;
+const MARKER_NUMBER: u8 = 0x05;
+const MARKER_STRING: u8 = 0x08;
+
+// Strings are truncated to this many characters before being hashed by the V1 scheme and
+// to this many bytes in its effective partition key.
+const V1_MAX_STRING_LENGTH: usize = 100;
+
+/// Effective partition key of `partition_keys` in a collection partitioned according to
+/// `partition_key`, choosing the hashing scheme from its kind and version.
+///
+/// Hierarchical partition keys (`KeyKind::MultiHash`) can be given only the first
+/// components: the result is then the prefix shared by the effective partition keys of
+/// every document starting with them.
+pub fn effective_partition_key(
+    partition_key: &PartitionKey,
+    partition_keys: &PartitionKeys,
+) -> Result<String, AzureError> {
+    let components: Vec<Value> = serde_json::from_str(&partition_keys.to_json())?;
+
+    match partition_key.kind {
+        KeyKind::MultiHash if components.len() <= partition_key.paths.len() => {
+            Ok(effective_partition_key_multi_hash(&components))
+        }
+        KeyKind::Hash if components.len() == partition_key.paths.len() => {
+            if components.is_empty() {
+                // collections without partition key have a single range
+                Ok(String::new())
+            } else if partition_key.version == Some(2) {
+                Ok(effective_partition_key_v2(&components))
+            } else {
+                Ok(effective_partition_key_v1(&components))
+            }
+        }
+        KeyKind::Hash | KeyKind::MultiHash => Err(AzureError::GenericErrorWithText(format!(
+            "{} partition key components given for the partition key paths {:?}",
+            components.len(),
+            partition_key.paths
+        ))),
+        _ => Err(AzureError::GenericErrorWithText(format!(
+            "cannot hash partition keys of kind {:?}",
+            partition_key.kind
+        ))),
+    }
+}
+
+/// Effective partition key with the version 1 scheme (the default one): the MurmurHash3
+/// 32 bits hash of the components followed by the components themselves. Strings are
+/// truncated to 100 characters.
+pub fn effective_partition_key_v1(components: &[Value]) -> String {
+    let components = components
+        .iter()
+        .map(|component| match component {
+            Value::String(s) if s.chars().count() > V1_MAX_STRING_LENGTH => {
+                Value::String(s.chars().take(V1_MAX_STRING_LENGTH).collect())
+            }
+            component => component.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut hashed = Vec::new();
+    for component in &components {
+        write_for_hashing(component, 0x00, &mut hashed);
+    }
+    let hash = murmur_hash3_32(&hashed, 0);
+
+    let mut encoded = Vec::new();
+    write_for_binary_encoding(&Value::from(f64::from(hash)), &mut encoded);
+    for component in &components {
+        write_for_binary_encoding(component, &mut encoded);
+    }
+    to_hex(&encoded)
+}
+
+/// Effective partition key with the version 2 scheme: the MurmurHash3 128 bits hash of
+/// the components.
+pub fn effective_partition_key_v2(components: &[Value]) -> String {
+    let mut hashed = Vec::new();
+    for component in components {
+        write_for_hashing(component, 0xFF, &mut hashed);
+    }
+    let (low, high) = murmur_hash3_128(&hashed, 0);
+
+    let mut hash = Vec::with_capacity(16);
+    hash.extend_from_slice(&high.to_be_bytes());
+    hash.extend_from_slice(&low.to_be_bytes());
+    // the two most significant bits are reserved
+    hash[0] &= 0x3F;
+    to_hex(&hash)
+}
+
+/// Effective partition key of hierarchical partition keys: the version 2 hashes of every
+/// component, one after the other.
+pub fn effective_partition_key_multi_hash(components: &[Value]) -> String {
+    components
+        .iter()
+        .map(|component| effective_partition_key_v2(std::slice::from_ref(component)))
+        .collect()
+}
+
+/// The partition key range containing the effective partition key.
+pub fn find_partition_key_range<'a>(
+    partition_key_ranges: &'a [PartitionKeyRange],
+    effective_partition_key: &str,
+) -> Option<&'a PartitionKeyRange> {
+    partition_key_ranges
+        .iter()
+        .find(|partition_key_range| partition_key_range.contains(effective_partition_key))
+}
+
+fn is_undefined(component: &Value) -> bool {
+    match component {
+        Value::Object(object) => object.is_empty(),
+        _ => false,
+    }
+}
+
+fn write_for_hashing(component: &Value, string_suffix: u8, buffer: &mut Vec<u8>) {
+    match component {
+        Value::Bool(true) => buffer.push(MARKER_TRUE),
+        Value::Bool(false) => buffer.push(MARKER_FALSE),
+        Value::Null => buffer.push(MARKER_NULL),
+        Value::Number(number) => {
+            buffer.push(MARKER_NUMBER);
+            buffer.extend_from_slice(&number.as_f64().unwrap_or_default().to_le_bytes());
+        }
+        Value::String(s) => {
+            buffer.push(MARKER_STRING);
+            buffer.extend_from_slice(s.as_bytes());
+            buffer.push(string_suffix);
+        }
+        component if is_undefined(component) => buffer.push(MARKER_UNDEFINED),
+        // arrays and objects cannot be partition keys
+        _ => buffer.push(MARKER_UNDEFINED),
+    }
+}
+
+fn write_for_binary_encoding(component: &Value, buffer: &mut Vec<u8>) {
+    match component {
+        Value::Bool(true) => buffer.push(MARKER_TRUE),
+        Value::Bool(false) => buffer.push(MARKER_FALSE),
+        Value::Null => buffer.push(MARKER_NULL),
+        Value::Number(number) => {
+            buffer.push(MARKER_NUMBER);
+            write_number(number.as_f64().unwrap_or_default(), buffer);
+        }
+        Value::String(s) => {
+            buffer.push(MARKER_STRING);
+            let bytes = s.as_bytes();
+            for byte in bytes.iter().take(V1_MAX_STRING_LENGTH) {
+                buffer.push(if *byte < 0xFF { byte + 1 } else { *byte });
+            }
+            if bytes.len() < V1_MAX_STRING_LENGTH {
+                buffer.push(0x00);
+            }
+        }
+        _ => buffer.push(MARKER_UNDEFINED),
+    }
+}
+
+// Numbers are encoded so that their encodings sort like them: the first byte holds the
+// 8 most significant bits, the following ones 7 bits each followed by a 1 bit, except
+// for the last one that ends with a 0 bit.
+fn write_number(number: f64, buffer: &mut Vec<u8>) {
+    let bits = number.to_bits();
+    let mut payload = if bits & 0x8000_0000_0000_0000 == 0 {
+        bits ^ 0x8000_0000_0000_0000
+    } else {
+        (!bits).wrapping_add(1)
+    };
+
+    buffer.push((payload >> 56) as u8);
+    payload <<= 8;
+
+    let mut byte_to_write = 0u8;
+    let mut first_iteration = true;
+    while payload != 0 {
+        if first_iteration {
+            first_iteration = false;
+        } else {
+            buffer.push(byte_to_write);
+        }
+        byte_to_write = (payload >> 56) as u8 | 0x01;
+        payload <<= 7;
+    }
+    buffer.push(byte_to_write & 0xFE);
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+fn murmur_hash3_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mut h1 = seed;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k1 = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k1 = k1.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        h1 ^= k1;
+        h1 = h1.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let mut k1 = 0u32;
+        for (i, byte) in tail.iter().enumerate() {
+            k1 |= u32::from(*byte) << (8 * i);
+        }
+        h1 ^= k1.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+
+    h1 ^= data.len() as u32;
+    h1 ^= h1 >> 16;
+    h1 = h1.wrapping_mul(0x85eb_ca6b);
+    h1 ^= h1 >> 13;
+    h1 = h1.wrapping_mul(0xc2b2_ae35);
+    h1 ^= h1 >> 16;
+    h1
+}
+
+// MurmurHash3_x64_128, returns the low and the high 64 bits.
+fn murmur_hash3_128(data: &[u8], seed: u64) -> (u64, u64) {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+
+    fn fmix64(mut k: u64) -> u64 {
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        k ^= k >> 33;
+        k
+    }
+
+    fn read_u64(bytes: &[u8]) -> u64 {
+        bytes
+            .iter()
+            .enumerate()
+            .fold(0, |k, (i, byte)| k | (u64::from(*byte) << (8 * i)))
+    }
+
+    let mut h1 = seed;
+    let mut h2 = seed;
+    let mut chunks = data.chunks_exact(16);
+    for chunk in &mut chunks {
+        let k1 = read_u64(&chunk[..8]);
+        let k2 = read_u64(&chunk[8..]);
+
+        h1 ^= k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dc_e729);
+
+        h2 ^= k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x3849_5ab5);
+    }
+
+    let tail = chunks.remainder();
+    if tail.len() > 8 {
+        h2 ^= read_u64(&tail[8..])
+            .wrapping_mul(C2)
+            .rotate_left(33)
+            .wrapping_mul(C1);
+    }
+    if !tail.is_empty() {
+        h1 ^= read_u64(&tail[..tail.len().min(8)])
+            .wrapping_mul(C1)
+            .rotate_left(31)
+            .wrapping_mul(C2);
+    }
+
+    h1 ^= data.len() as u64;
+    h2 ^= data.len() as u64;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix64(h1);
+    h2 = fmix64(h2);
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    (h1, h2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn effective_partition_keys() {
+        assert_eq!(
+            effective_partition_key_v1(&[json!("")]),
+            "05C1CF33970FF80800"
+        );
+        assert_eq!(
+            effective_partition_key_v1(&[json!("partitionKey")]),
+            "05C1E1B3D9CD2608716273756A756A706F4C667A00"
+        );
+        assert_eq!(
+            effective_partition_key_v1(&[json!(5.0)]),
+            "05C1D9C1C5517C05C014"
+        );
+
+        assert_eq!(
+            effective_partition_key_v2(&[json!("")]),
+            "32E9366E637A71B4E710384B2F4970A0"
+        );
+        assert_eq!(
+            effective_partition_key_v2(&[json!("partitionKey")]),
+            "013AEFCF77FA271571CF665A58C933F1"
+        );
+        assert_eq!(
+            effective_partition_key_v2(&[json!(5.0)]),
+            "19C08621B135968252FB34B4CF66F811"
+        );
+    }
+
+    #[test]
+    fn effective_partition_keys_of_every_type() {
+        let long_string = json!("a".repeat(1024));
+        let undefined = json!({});
+
+        assert_eq!(
+            effective_partition_key_v1(&[json!(null)]),
+            "05C1ED45D7475601"
+        );
+        assert_eq!(
+            effective_partition_key_v1(&[json!(true)]),
+            "05C1D7C5A903D803"
+        );
+        assert_eq!(
+            effective_partition_key_v1(&[json!(false)]),
+            "05C1DB857D857C02"
+        );
+        assert_eq!(
+            effective_partition_key_v1(std::slice::from_ref(&undefined)),
+            "05C1D529E345DC00"
+        );
+        assert_eq!(
+            effective_partition_key_v1(std::slice::from_ref(&long_string)),
+            format!("05C1EB5921F70608{}", "62".repeat(100))
+        );
+
+        assert_eq!(
+            effective_partition_key_v2(&[json!(null)]),
+            "378867E4430E67857ACE5C908374FE16"
+        );
+        assert_eq!(
+            effective_partition_key_v2(&[json!(true)]),
+            "0E711127C5B5A8E4726AC6DD306A3E59"
+        );
+        assert_eq!(
+            effective_partition_key_v2(&[json!(false)]),
+            "2FE1BE91E90A3439635E0E9E37361EF2"
+        );
+        assert_eq!(
+            effective_partition_key_v2(&[undefined]),
+            "11622DAA78F835834610ABE56EFF5CB5"
+        );
+        assert_eq!(
+            effective_partition_key_v2(&[long_string]),
+            "332BDF5512AE49615F32C7D98C2DB86C"
+        );
+    }
+
+    #[test]
+    fn effective_partition_key_from_definition() {
+        let mut partition_keys = PartitionKeys::new();
+        partition_keys.push("partitionKey").unwrap();
+
+        let mut partition_key = PartitionKey::from("/pk");
+        assert_eq!(
+            effective_partition_key(&partition_key, &partition_keys).unwrap(),
+            "05C1E1B3D9CD2608716273756A756A706F4C667A00"
+        );
+        partition_key.version = Some(2);
+        assert_eq!(
+            effective_partition_key(&partition_key, &partition_keys).unwrap(),
+            "013AEFCF77FA271571CF665A58C933F1"
+        );
+
+        let hierarchical = PartitionKey {
+            paths: vec!["/tenant".to_owned(), "/user".to_owned()],
+            kind: KeyKind::MultiHash,
+            version: Some(2),
+        };
+        assert_eq!(
+            effective_partition_key(&hierarchical, &partition_keys).unwrap(),
+            "013AEFCF77FA271571CF665A58C933F1"
+        );
+        partition_keys.push("").unwrap();
+        assert_eq!(
+            effective_partition_key(&hierarchical, &partition_keys).unwrap(),
+            "013AEFCF77FA271571CF665A58C933F132E9366E637A71B4E710384B2F4970A0"
+        );
+        // too many components
+        assert!(effective_partition_key(&partition_key, &partition_keys).is_err());
+    }
+
+    #[test]
+    fn find_range() {
+        let partition_key_ranges: Vec<PartitionKeyRange> = serde_json::from_str(
+            r#"[
+                {"_rid": "r0", "id": "0", "_etag": "", "minInclusive": "", "maxExclusive": "1FFFFFFFFFFFFF", "ridPrefix": 0, "_self": "", "throughputFraction": 1, "status": "online", "_ts": 0},
+                {"_rid": "r1", "id": "1", "_etag": "", "minInclusive": "1FFFFFFFFFFFFF", "maxExclusive": "FF", "ridPrefix": 1, "_self": "", "throughputFraction": 1, "status": "online", "_ts": 0}
+            ]"#,
+        )
+        .unwrap();
+
+        let find = |effective_partition_key: &str| {
+            find_partition_key_range(&partition_key_ranges, effective_partition_key)
+                .map(|partition_key_range| partition_key_range.id.as_str())
+        };
+        assert_eq!(find(""), Some("0"));
+        assert_eq!(find("013AEFCF77FA271571CF665A58C933F1"), Some("0"));
+        assert_eq!(find("1FFFFFFFFFFFFF"), Some("1"));
+        assert_eq!(find("32E9366E637A71B4E710384B2F4970A0"), Some("1"));
+        assert_eq!(find("FF"), None);
+    }
+}
@@ -13,6 +13,7 @@ pub(crate) const HEADER_ACTIVITY_ID: &str = "x-ms-activity-id"; // [String]
 pub(crate) const HEADER_DOCUMENTDB_PARTITIONRANGEID: &str = "x-ms-documentdb-partitionkeyrangeid"; // [String]
 pub(crate) const HEADER_DOCUMENTDB_PARTITIONKEY: &str = "x-ms-documentdb-partitionkey"; // [String]
 pub(crate) const HEADER_NUMBER_OF_READ_REGIONS: &str = "x-ms-number-of-read-regions";
+pub(crate) const HEADER_SUB_STATUS: &str = "x-ms-substatus"; // [u32]
 pub(crate) const HEADER_REQUEST_CHARGE: &str = "x-ms-request-charge"; // [f64]
 pub(crate) const HEADER_OFFER_THROUGHPUT: &str = "x-ms-offer-throughput"; // [u64]
 pub(crate) const HEADER_OFFER_TYPE: &str = "x-ms-offer-type"; // [&str]
@@ -46,3 +47,11 @@ pub(crate) const HEADER_COSMOS_QUORUM_ACKED_LLSN: &str = "x-ms-cosmos-quorum-ack
 pub(crate) const HEADER_ROLE: &str = "x-ms-xp-role"; // [u64]
 pub(crate) const HEADER_MAX_MEDIA_STORAGE_USAGE_MB: &str = "x-ms-max-media-storage-usage-mb"; // [u64]
 pub(crate) const HEADER_MEDIA_STORAGE_USAGE_MB: &str = "x-ms-media-storage-usage-mb"; // [u64]
+pub(crate) const HEADER_RETRY_AFTER_MS: &str = "x-ms-retry-after-ms"; // [u64]
+pub(crate) const HEADER_COSMOS_IS_QUERY_PLAN_REQUEST: &str = "x-ms-cosmos-is-query-plan-request"; // [bool]
+pub(crate) const HEADER_COSMOS_SUPPORTED_QUERY_FEATURES: &str =
+    "x-ms-cosmos-supported-query-features"; // [String]
+pub(crate) const HEADER_COSMOS_QUERY_VERSION: &str = "x-ms-cosmos-query-version"; // [String]
+pub(crate) const HEADER_COSMOS_IS_BATCH_REQUEST: &str = "x-ms-cosmos-is-batch-request"; // [bool]
+pub(crate) const HEADER_COSMOS_BATCH_ATOMIC: &str = "x-ms-cosmos-batch-atomic"; // [bool]
+pub(crate) const HEADER_COSMOS_BATCH_ORDERED: &str = "x-ms-cosmos-batch-ordered"; // [bool]
@@ -11,45 +11,69 @@ extern crate azure_sdk_core;
 
 pub mod attachment;
 mod authorization_token;
+pub mod bulk;
+pub mod change_feed;
 pub mod clients;
 pub mod collection;
+mod conflict;
 mod consistency_level;
 mod database;
+mod database_account;
+mod diagnostics;
 mod document;
 mod document_attributes;
+pub mod effective_partition_key;
 mod errors;
 pub(crate) mod from_headers;
 mod headers;
 mod indexing_directive;
+mod location_cache;
+pub mod migration;
 pub mod offer;
 mod partition_key_range;
 mod partition_keys;
+mod patch_operation;
 mod permission;
 mod permission_resource;
 mod permission_token;
 pub mod prelude;
 mod query;
+pub mod query_pipeline;
 mod requests;
 mod resource;
 mod resource_quota;
+mod resource_token_broker;
 pub mod responses;
+mod retry_policy;
+mod session_container;
+pub mod sql;
 pub mod stored_procedure;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
+mod time_to_live;
 mod to_json_vector;
 mod traits;
+mod transactional_batch;
 pub mod trigger;
 mod user;
 mod user_defined_function;
 
 pub use self::attachment::Attachment;
 pub use self::authorization_token::*;
-use self::collection::IndexingPolicy;
+pub use self::change_feed::ChangeFeedContinuation;
+use self::collection::{ConflictResolutionPolicy, IndexingPolicy, UniqueKeyPolicy};
+pub use self::conflict::{Conflict, ConflictOperation};
 pub use self::consistency_level::ConsistencyLevel;
 pub use self::database::{Database, DatabaseName};
+pub use self::database_account::{DatabaseAccount, DatabaseAccountLocation, UserConsistencyPolicy};
+pub use self::diagnostics::{Diagnostics, DiagnosticsHandler, RequestDiagnostics};
 pub use self::document::{Document, DocumentName};
 pub use self::document_attributes::DocumentAttributes;
 pub use self::indexing_directive::IndexingDirective;
-pub use self::offer::Offer;
+pub use self::location_cache::LocationCache;
+pub use self::offer::{Offer, ThroughputOffer};
 pub use self::partition_key_range::PartitionKeyRange;
+pub use self::patch_operation::PatchOperation;
 pub use self::permission::{Permission, PermissionMode, PermissionName};
 pub use self::permission_resource::PermissionResource;
 pub use self::permission_token::PermissionToken;
@@ -57,7 +81,12 @@ pub use self::query::{Param, ParamDef, Query};
 pub use self::requests::*;
 pub use self::resource::Resource;
 pub use self::resource_quota::ResourceQuota;
+pub use self::resource_token_broker::{ResourceGrant, ResourceTokenBroker, ResourceTokenRefresher};
+pub use self::retry_policy::RetryPolicy;
+pub use self::session_container::SessionContainer;
+pub use self::time_to_live::TimeToLive;
 pub use self::traits::*;
+pub use self::transactional_batch::{TransactionalBatch, TRANSACTIONAL_BATCH_MAX_OPERATIONS};
 pub use self::trigger::{Trigger, TriggerName};
 use crate::clients::*;
 use crate::collection::Collection;
@@ -82,6 +111,10 @@ pub enum ResourceType {
     PartitionKeyRanges,
     UserDefinedFunctions,
     Triggers,
+    Offers,
+    Conflicts,
+    DatabaseAccount,
+    Media,
 }
 
 pub trait CosmosClientRequired<'a> {
@@ -260,6 +293,18 @@ pub trait ContinuationOption<'a> {
     }
 }
 
+pub trait ChangeFeedContinuationSupport<'a> {
+    type O;
+    fn with_change_feed_continuation(
+        self,
+        change_feed_continuation: &'a ChangeFeedContinuation,
+    ) -> Self::O;
+}
+
+pub trait ChangeFeedContinuationOption<'a> {
+    fn change_feed_continuation(&self) -> Option<&'a ChangeFeedContinuation>;
+}
+
 pub trait IndexingDirectiveSupport {
     type O;
     fn with_indexing_directive(self, indexing_directive: IndexingDirective) -> Self::O;
@@ -455,6 +500,15 @@ where
     fn stored_procedure_client(&self) -> &'a dyn StoredProcedureClient<C, D, COLL>;
 }
 
+pub trait ConflictClientRequired<'a, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    fn conflict_client(&self) -> &'a dyn ConflictClient<C, D, COLL>;
+}
+
 pub trait UserDefinedFunctionClientRequired<'a, C, D, COLL>
 where
     C: CosmosClient,
@@ -527,6 +581,24 @@ pub trait OfferSupport {
     fn with_offer(self, offer: Offer) -> Self::O;
 }
 
+pub trait OfferIdRequired<'a> {
+    fn offer_id(&self) -> &'a str;
+}
+
+pub trait OfferIdSupport<'a> {
+    type O;
+    fn with_offer_id(self, offer_id: &'a str) -> Self::O;
+}
+
+pub trait ThroughputOfferRequired<'a> {
+    fn throughput_offer(&self) -> &'a ThroughputOffer;
+}
+
+pub trait ThroughputOfferSupport<'a> {
+    type O;
+    fn with_throughput_offer(self, throughput_offer: &'a ThroughputOffer) -> Self::O;
+}
+
 pub trait CollectionNameRequired<'a> {
     fn collection_name(&self) -> &'a dyn CollectionName;
 }
@@ -554,11 +626,59 @@ pub trait IndexingPolicySupport<'a> {
     fn with_indexing_policy(self, offer: &'a IndexingPolicy) -> Self::O;
 }
 
+pub trait TransactionalBatchRequired<'a> {
+    fn transactional_batch(&self) -> &'a TransactionalBatch;
+}
+
+pub trait TransactionalBatchSupport<'a> {
+    type O;
+    fn with_transactional_batch(self, transactional_batch: &'a TransactionalBatch) -> Self::O;
+}
+
+pub trait ConflictResolutionPolicyOption<'a> {
+    fn conflict_resolution_policy(&self) -> Option<&'a ConflictResolutionPolicy>;
+}
+
+pub trait ConflictResolutionPolicySupport<'a> {
+    type O;
+    fn with_conflict_resolution_policy(
+        self,
+        conflict_resolution_policy: &'a ConflictResolutionPolicy,
+    ) -> Self::O;
+}
+
+pub trait UniqueKeyPolicyOption<'a> {
+    fn unique_key_policy(&self) -> Option<&'a UniqueKeyPolicy>;
+}
+
+pub trait UniqueKeyPolicySupport<'a> {
+    type O;
+    fn with_unique_key_policy(self, unique_key_policy: &'a UniqueKeyPolicy) -> Self::O;
+}
+
+pub trait DefaultTtlOption {
+    fn default_ttl(&self) -> Option<TimeToLive>;
+}
+
+pub trait DefaultTtlSupport {
+    type O;
+    fn with_default_ttl(self, default_ttl: TimeToLive) -> Self::O;
+}
+
 //pub trait CollectionSupport<'a> {
 //    type O;
 //    fn with_collection(self, collection: &'a str) -> Self::O;
 //}
 
+pub trait ConditionOption<'a> {
+    fn condition(&self) -> Option<&'a str>;
+}
+
+pub trait ConditionSupport<'a> {
+    type O;
+    fn with_condition(self, condition: &'a str) -> Self::O;
+}
+
 pub trait DocumentIdRequired<'a> {
     fn document_id(&self) -> &'a str;
 }
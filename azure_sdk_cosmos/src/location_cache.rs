@@ -0,0 +1,453 @@
+use crate::diagnostics::diagnostics_mut;
+use crate::headers::*;
+use crate::retry_policy::{copy_request, execute_with_retry_policy, is_replayable};
+use crate::{CosmosClient, DatabaseAccount, DatabaseAccountLocation, ResourceType};
+use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use hyper::{Body, Request, Response, StatusCode, Uri};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+// How long the regions of the account are trusted before being read again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+// How long a region that failed is tried only after the other ones.
+const UNAVAILABLE_EXPIRATION: Duration = Duration::from_secs(5 * 60);
+
+// Sub-statuses of a 403 meaning that the region cannot serve the request anymore
+// (rather than an authorization failure that would happen in every region).
+const SUB_STATUS_WRITE_FORBIDDEN: &str = "3";
+const SUB_STATUS_DATABASE_ACCOUNT_NOT_FOUND: &str = "1008";
+
+/// Regions of the database account and the order in which the client tries them.
+///
+/// Endpoint discovery is enabled by setting the preferred locations (see
+/// `CosmosStruct::with_preferred_locations`): the client then reads the database account
+/// to discover its regions and sends reads to the first available preferred region and
+/// writes to the write region (or to the first preferred one if the account accepts
+/// writes in multiple regions). A region answering with 503 - Service Unavailable or with
+/// a 403 - Forbidden because it cannot accept writes anymore, or that cannot be reached,
+/// is set aside for five minutes and the request is sent to the next region. The global
+/// endpoint is always tried last. Clones share the discovered regions.
+#[derive(Debug, Clone, Default)]
+pub struct LocationCache {
+    preferred_locations: Arc<Vec<String>>,
+    state: Arc<RwLock<LocationState>>,
+}
+
+#[derive(Debug, Default)]
+struct LocationState {
+    read_locations: Vec<DatabaseAccountLocation>,
+    write_locations: Vec<DatabaseAccountLocation>,
+    enable_multiple_write_locations: bool,
+    last_refresh: Option<Instant>,
+    unavailable_endpoints: HashMap<String, Instant>,
+}
+
+impl LocationCache {
+    pub fn new(preferred_locations: Vec<String>) -> Self {
+        Self {
+            preferred_locations: Arc::new(preferred_locations),
+            state: Arc::default(),
+        }
+    }
+
+    /// Names of the regions in order of preference, for example `["West Europe", "North Europe"]`.
+    pub fn preferred_locations(&self) -> &[String] {
+        &self.preferred_locations
+    }
+
+    /// Endpoints tried, in order, for reads. Empty until the regions are discovered.
+    pub fn read_endpoints(&self) -> Vec<String> {
+        let state = self.state.read().unwrap();
+        self.order_endpoints(&state, &state.read_locations, true)
+    }
+
+    /// Endpoints tried, in order, for writes. Empty until the regions are discovered.
+    pub fn write_endpoints(&self) -> Vec<String> {
+        let state = self.state.read().unwrap();
+        self.order_endpoints(
+            &state,
+            &state.write_locations,
+            state.enable_multiple_write_locations,
+        )
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.preferred_locations.is_empty()
+    }
+
+    pub(crate) fn needs_refresh(&self) -> bool {
+        match self.state.read().unwrap().last_refresh {
+            Some(last_refresh) => last_refresh.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        }
+    }
+
+    pub(crate) fn update(&self, database_account: &DatabaseAccount) {
+        let mut state = self.state.write().unwrap();
+        state.read_locations = database_account.readable_locations.clone();
+        state.write_locations = database_account.writable_locations.clone();
+        state.enable_multiple_write_locations = database_account.enable_multiple_write_locations;
+        state.last_refresh = Some(Instant::now());
+    }
+
+    // Keeps the known regions: they will be read again after the refresh interval.
+    pub(crate) fn refresh_failed(&self) {
+        self.state.write().unwrap().last_refresh = Some(Instant::now());
+    }
+
+    pub(crate) fn invalidate(&self) {
+        self.state.write().unwrap().last_refresh = None;
+    }
+
+    // Name of the region reached through the endpoint.
+    pub(crate) fn location_name(&self, endpoint: &str) -> Option<String> {
+        let state = self.state.read().unwrap();
+        state
+            .read_locations
+            .iter()
+            .chain(state.write_locations.iter())
+            .find(|location| location.database_account_endpoint == endpoint)
+            .map(|location| location.name.clone())
+    }
+
+    pub(crate) fn mark_unavailable(&self, endpoint: &str) {
+        self.state
+            .write()
+            .unwrap()
+            .unavailable_endpoints
+            .insert(endpoint.to_owned(), Instant::now());
+    }
+
+    fn order_endpoints(
+        &self,
+        state: &LocationState,
+        locations: &[DatabaseAccountLocation],
+        use_preferred_locations: bool,
+    ) -> Vec<String> {
+        let mut ordered: Vec<&DatabaseAccountLocation> = Vec::with_capacity(locations.len());
+        if use_preferred_locations {
+            for preferred_location in self.preferred_locations.iter() {
+                if let Some(location) = locations
+                    .iter()
+                    .find(|location| location.name.eq_ignore_ascii_case(preferred_location))
+                {
+                    ordered.push(location);
+                }
+            }
+        }
+        for location in locations {
+            if !ordered.contains(&location) {
+                ordered.push(location);
+            }
+        }
+
+        let is_unavailable = |endpoint: &str| {
+            state
+                .unavailable_endpoints
+                .get(endpoint)
+                .map(|since| since.elapsed() < UNAVAILABLE_EXPIRATION)
+                .unwrap_or(false)
+        };
+
+        let (available, unavailable): (Vec<_>, Vec<_>) = ordered
+            .into_iter()
+            .map(|location| location.database_account_endpoint.clone())
+            .partition(|endpoint| !is_unavailable(endpoint));
+        available.into_iter().chain(unavailable).collect()
+    }
+}
+
+// Sends the request to the regions of the account, in order of preference, until one of
+// them is able to serve it. Without preferred locations the request is sent as is.
+pub(crate) async fn execute_with_failover<C>(
+    cosmos_client: &C,
+    request: Request<Body>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    C: CosmosClient + ?Sized,
+{
+    let location_cache = cosmos_client.location_cache();
+    if !location_cache.is_enabled() {
+        let endpoint = endpoint_of(request.uri());
+        let response = execute_with_retry_policy(
+            cosmos_client.hyper_client(),
+            cosmos_client.retry_policy(),
+            request,
+        )
+        .await?;
+        return Ok(with_contacted_endpoint(response, &endpoint, None));
+    }
+
+    if location_cache.needs_refresh() {
+        match read_database_account(cosmos_client).await {
+            Ok(database_account) => location_cache.update(&database_account),
+            Err(error) => {
+                warn!("cannot discover the regions of the account: {}", error);
+                location_cache.refresh_failed();
+            }
+        }
+    }
+
+    let (parts, body) = request.into_parts();
+    let endpoints = if is_read(&parts) {
+        location_cache.read_endpoints()
+    } else {
+        location_cache.write_endpoints()
+    };
+
+    if !is_replayable(&body) {
+        // a streamed body can be sent only once: to the preferred region, without failover
+        let mut request = Request::from_parts(parts, body);
+        let region = match endpoints.first() {
+            Some(endpoint) => {
+                *request.uri_mut() = with_endpoint(request.uri(), endpoint);
+                location_cache.location_name(endpoint)
+            }
+            None => None,
+        };
+        let contacted_endpoint = endpoint_of(request.uri());
+        let response = execute_with_retry_policy(
+            cosmos_client.hyper_client(),
+            cosmos_client.retry_policy(),
+            request,
+        )
+        .await?;
+        return Ok(with_contacted_endpoint(
+            response,
+            &contacted_endpoint,
+            region,
+        ));
+    }
+
+    // the body must be buffered as it could be sent to more than one region
+    let body = hyper::body::to_bytes(body).await?;
+    // None stands for the global endpoint the request was prepared for
+    let endpoints = endpoints
+        .into_iter()
+        .map(Some)
+        .chain(std::iter::once(None))
+        .collect::<Vec<_>>();
+
+    for (index, endpoint) in endpoints.iter().enumerate() {
+        let mut request = copy_request(&parts, &body);
+        if let Some(endpoint) = endpoint {
+            *request.uri_mut() = with_endpoint(&parts.uri, endpoint);
+        }
+
+        let contacted_endpoint = endpoint_of(request.uri());
+        let region = endpoint
+            .as_ref()
+            .and_then(|endpoint| location_cache.location_name(endpoint));
+        let result = execute_with_retry_policy(
+            cosmos_client.hyper_client(),
+            cosmos_client.retry_policy(),
+            request,
+        )
+        .await
+        .map(|response| with_contacted_endpoint(response, &contacted_endpoint, region));
+
+        let endpoint = match endpoint {
+            Some(endpoint) if index + 1 < endpoints.len() => endpoint,
+            _ => return result,
+        };
+
+        match &result {
+            Ok(response) if is_region_unavailable(response) => {
+                debug!(
+                    "{} answered {}, failing over to the next region",
+                    endpoint,
+                    response.status()
+                );
+                // the write region could have changed
+                if response.status() == StatusCode::FORBIDDEN {
+                    location_cache.invalidate();
+                }
+            }
+            Err(error) if error.is_connect() => {
+                debug!(
+                    "cannot connect to {} ({}), failing over to the next region",
+                    endpoint, error
+                );
+            }
+            _ => return result,
+        }
+        location_cache.mark_unavailable(endpoint);
+    }
+
+    unreachable!("the global endpoint is always tried")
+}
+
+async fn read_database_account<C>(cosmos_client: &C) -> Result<DatabaseAccount, AzureError>
+where
+    C: CosmosClient + ?Sized,
+{
+    let request = cosmos_client
+        .prepare_request("", hyper::Method::GET, ResourceType::DatabaseAccount)
+        .body(Body::empty())?;
+
+    let future_response = execute_with_retry_policy(
+        cosmos_client.hyper_client(),
+        cosmos_client.retry_policy(),
+        request,
+    );
+    let (_, body) = check_status_extract_headers_and_body(future_response, StatusCode::OK).await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+// Queries are sent with a POST but can be served by any region.
+fn is_read(parts: &http::request::Parts) -> bool {
+    match parts.method {
+        hyper::Method::GET | hyper::Method::HEAD => true,
+        hyper::Method::POST => parts
+            .headers
+            .get(http::header::CONTENT_TYPE)
+            .map(|content_type| content_type == "application/query+json")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+fn is_region_unavailable(response: &Response<Body>) -> bool {
+    match response.status() {
+        StatusCode::SERVICE_UNAVAILABLE => true,
+        StatusCode::FORBIDDEN => response
+            .headers()
+            .get(HEADER_SUB_STATUS)
+            .and_then(|sub_status| sub_status.to_str().ok())
+            .map(|sub_status| {
+                sub_status == SUB_STATUS_WRITE_FORBIDDEN
+                    || sub_status == SUB_STATUS_DATABASE_ACCOUNT_NOT_FOUND
+            })
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+// Replaces the scheme and the authority of the uri with the ones of the regional endpoint.
+// The signature of the request does not depend on them so it stays valid.
+fn with_endpoint(uri: &Uri, endpoint: &str) -> Uri {
+    let endpoint = match endpoint.parse::<Uri>() {
+        Ok(endpoint) => endpoint,
+        Err(_) => return uri.clone(),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.scheme = endpoint.scheme().cloned();
+    parts.authority = endpoint.authority().cloned();
+    Uri::from_parts(parts).unwrap_or_else(|_| uri.clone())
+}
+
+// Scheme and authority of the uri, for example `https://account.documents.azure.com:443/`.
+fn endpoint_of(uri: &Uri) -> String {
+    match (uri.scheme_str(), uri.authority()) {
+        (Some(scheme), Some(authority)) => format!("{}://{}/", scheme, authority),
+        _ => String::new(),
+    }
+}
+
+// Records in the diagnostics of the response where the request was sent.
+fn with_contacted_endpoint(
+    mut response: Response<Body>,
+    endpoint: &str,
+    region: Option<String>,
+) -> Response<Body> {
+    let diagnostics = diagnostics_mut(&mut response);
+    diagnostics.endpoint = Some(endpoint.to_owned());
+    diagnostics.region = region;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(name: &str) -> DatabaseAccountLocation {
+        DatabaseAccountLocation {
+            name: name.to_owned(),
+            database_account_endpoint: format!(
+                "https://account-{}.documents.azure.com:443/",
+                name.replace(' ', "").to_lowercase()
+            ),
+        }
+    }
+
+    fn database_account(enable_multiple_write_locations: bool) -> DatabaseAccount {
+        DatabaseAccount {
+            id: "account".to_owned(),
+            rid: "account.documents.azure.com".to_owned(),
+            _self: "".to_owned(),
+            writable_locations: vec![location("East US"), location("West Europe")],
+            readable_locations: vec![
+                location("East US"),
+                location("West Europe"),
+                location("North Europe"),
+            ],
+            enable_multiple_write_locations,
+            user_consistency_policy: None,
+        }
+    }
+
+    #[test]
+    fn endpoints_follow_preferred_locations() {
+        let location_cache =
+            LocationCache::new(vec!["North Europe".to_owned(), "west europe".to_owned()]);
+        assert!(location_cache.needs_refresh());
+        assert!(location_cache.read_endpoints().is_empty());
+
+        location_cache.update(&database_account(false));
+        assert!(!location_cache.needs_refresh());
+        assert_eq!(
+            location_cache.read_endpoints(),
+            vec![
+                "https://account-northeurope.documents.azure.com:443/",
+                "https://account-westeurope.documents.azure.com:443/",
+                "https://account-eastus.documents.azure.com:443/",
+            ]
+        );
+        // a single write region: the order of the account is kept
+        assert_eq!(
+            location_cache.write_endpoints()[0],
+            "https://account-eastus.documents.azure.com:443/"
+        );
+
+        location_cache.update(&database_account(true));
+        assert_eq!(
+            location_cache.write_endpoints()[0],
+            "https://account-westeurope.documents.azure.com:443/"
+        );
+    }
+
+    #[test]
+    fn unavailable_endpoints_are_tried_last() {
+        let location_cache = LocationCache::new(vec!["North Europe".to_owned()]);
+        location_cache.update(&database_account(false));
+        location_cache.mark_unavailable("https://account-northeurope.documents.azure.com:443/");
+        assert_eq!(
+            location_cache
+                .location_name("https://account-northeurope.documents.azure.com:443/")
+                .as_deref(),
+            Some("North Europe")
+        );
+        assert_eq!(
+            location_cache.read_endpoints(),
+            vec![
+                "https://account-eastus.documents.azure.com:443/",
+                "https://account-westeurope.documents.azure.com:443/",
+                "https://account-northeurope.documents.azure.com:443/",
+            ]
+        );
+    }
+
+    #[test]
+    fn with_endpoint_keeps_path() {
+        let uri: Uri = "https://account.documents.azure.com/dbs/db/colls/coll/docs"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            with_endpoint(&uri, "https://account-westeurope.documents.azure.com:443/"),
+            "https://account-westeurope.documents.azure.com:443/dbs/db/colls/coll/docs"
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! Declarative schema migrations.
+//!
+//! A `SchemaSpec` describes the databases, collections and server-side scripts an
+//! application expects. `SchemaMigration` compares it with the account and creates,
+//! replaces or deletes what differs; in dry run it only prints the plan.
+mod plan;
+mod runner;
+mod spec;
+
+pub use self::plan::{MigrationPlan, MigrationStep};
+pub use self::runner::SchemaMigration;
+pub use self::spec::{
+    CollectionSpec, DatabaseSpec, SchemaSpec, StoredProcedureSpec, TriggerSpec,
+    UserDefinedFunctionSpec,
+};
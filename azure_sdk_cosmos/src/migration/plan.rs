@@ -0,0 +1,784 @@
+use super::spec::*;
+use crate::collection::{Collection, IndexingPolicy, UniqueKeyPolicy};
+use crate::stored_procedure::StoredProcedure;
+use crate::trigger::Trigger;
+use crate::user_defined_function::UserDefinedFunction;
+use crate::ThroughputOffer;
+use azure_sdk_core::errors::AzureError;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// Path the service adds to the excluded paths of every indexing policy.
+const ETAG_PATH: &str = "/\"_etag\"/?";
+
+/// A change to make to the account to match the spec.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationStep {
+    CreateDatabase {
+        database: String,
+    },
+    DeleteDatabase {
+        database: String,
+    },
+    CreateCollection {
+        database: String,
+        collection: CollectionSpec,
+    },
+    /// Replaces the indexing policy and the time to live of the collection. The indexing
+    /// policy of the spec is the live one if the spec does not manage it.
+    ReplaceCollection {
+        database: String,
+        collection: CollectionSpec,
+        changes: Vec<&'static str>,
+    },
+    DeleteCollection {
+        database: String,
+        collection: String,
+    },
+    ReplaceThroughput {
+        database: String,
+        collection: String,
+        offer: ThroughputOffer,
+        throughput: u64,
+    },
+    CreateStoredProcedure {
+        database: String,
+        collection: String,
+        stored_procedure: StoredProcedureSpec,
+    },
+    ReplaceStoredProcedure {
+        database: String,
+        collection: String,
+        stored_procedure: StoredProcedureSpec,
+    },
+    DeleteStoredProcedure {
+        database: String,
+        collection: String,
+        stored_procedure: String,
+    },
+    CreateTrigger {
+        database: String,
+        collection: String,
+        trigger: TriggerSpec,
+    },
+    ReplaceTrigger {
+        database: String,
+        collection: String,
+        trigger: TriggerSpec,
+    },
+    DeleteTrigger {
+        database: String,
+        collection: String,
+        trigger: String,
+    },
+    CreateUserDefinedFunction {
+        database: String,
+        collection: String,
+        user_defined_function: UserDefinedFunctionSpec,
+    },
+    ReplaceUserDefinedFunction {
+        database: String,
+        collection: String,
+        user_defined_function: UserDefinedFunctionSpec,
+    },
+    DeleteUserDefinedFunction {
+        database: String,
+        collection: String,
+        user_defined_function: String,
+    },
+}
+
+impl fmt::Display for MigrationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationStep::CreateDatabase { database } => write!(f, "create database {}", database),
+            MigrationStep::DeleteDatabase { database } => write!(f, "delete database {}", database),
+            MigrationStep::CreateCollection {
+                database,
+                collection,
+            } => {
+                write!(
+                    f,
+                    "create collection {}/{} partitioned by {}",
+                    database,
+                    collection.id,
+                    collection.partition_key.paths.join(", ")
+                )?;
+                if let Some(throughput) = collection.throughput {
+                    write!(f, " with {} RU/s", throughput)?;
+                }
+                Ok(())
+            }
+            MigrationStep::ReplaceCollection {
+                database,
+                collection,
+                changes,
+            } => write!(
+                f,
+                "replace collection {}/{}: {}",
+                database,
+                collection.id,
+                changes.join(", ")
+            ),
+            MigrationStep::DeleteCollection {
+                database,
+                collection,
+            } => write!(f, "delete collection {}/{}", database, collection),
+            MigrationStep::ReplaceThroughput {
+                database,
+                collection,
+                offer,
+                throughput,
+            } => {
+                write!(f, "replace throughput of {}/{}: ", database, collection)?;
+                match offer.throughput() {
+                    Some(current) => write!(f, "{} -> {} RU/s", current, throughput),
+                    None => write!(f, "{} RU/s", throughput),
+                }
+            }
+            MigrationStep::CreateStoredProcedure {
+                database,
+                collection,
+                stored_procedure,
+            } => write!(
+                f,
+                "create stored procedure {}/{}/{}",
+                database, collection, stored_procedure.id
+            ),
+            MigrationStep::ReplaceStoredProcedure {
+                database,
+                collection,
+                stored_procedure,
+            } => write!(
+                f,
+                "replace stored procedure {}/{}/{}",
+                database, collection, stored_procedure.id
+            ),
+            MigrationStep::DeleteStoredProcedure {
+                database,
+                collection,
+                stored_procedure,
+            } => write!(
+                f,
+                "delete stored procedure {}/{}/{}",
+                database, collection, stored_procedure
+            ),
+            MigrationStep::CreateTrigger {
+                database,
+                collection,
+                trigger,
+            } => write!(
+                f,
+                "create trigger {}/{}/{}",
+                database, collection, trigger.id
+            ),
+            MigrationStep::ReplaceTrigger {
+                database,
+                collection,
+                trigger,
+            } => write!(
+                f,
+                "replace trigger {}/{}/{}",
+                database, collection, trigger.id
+            ),
+            MigrationStep::DeleteTrigger {
+                database,
+                collection,
+                trigger,
+            } => write!(f, "delete trigger {}/{}/{}", database, collection, trigger),
+            MigrationStep::CreateUserDefinedFunction {
+                database,
+                collection,
+                user_defined_function,
+            } => write!(
+                f,
+                "create user defined function {}/{}/{}",
+                database, collection, user_defined_function.id
+            ),
+            MigrationStep::ReplaceUserDefinedFunction {
+                database,
+                collection,
+                user_defined_function,
+            } => write!(
+                f,
+                "replace user defined function {}/{}/{}",
+                database, collection, user_defined_function.id
+            ),
+            MigrationStep::DeleteUserDefinedFunction {
+                database,
+                collection,
+                user_defined_function,
+            } => write!(
+                f,
+                "delete user defined function {}/{}/{}",
+                database, collection, user_defined_function
+            ),
+        }
+    }
+}
+
+/// The steps bringing the account to the state of the spec, in the order they are
+/// applied. Displayed one step per line.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MigrationPlan {
+    pub steps: Vec<MigrationStep>,
+}
+
+impl MigrationPlan {
+    /// True if the account already matches the spec.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl fmt::Display for MigrationPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.steps.is_empty() {
+            return writeln!(f, "nothing to do");
+        }
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+// What the account contains. The collections are read only for the databases of the
+// spec, their offer and scripts only for the collections of the spec.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LiveState {
+    pub databases: Vec<LiveDatabase>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LiveDatabase {
+    pub id: String,
+    pub collections: Vec<LiveCollection>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LiveCollection {
+    pub collection: Collection,
+    pub offer: Option<ThroughputOffer>,
+    pub stored_procedures: Vec<StoredProcedure>,
+    pub triggers: Vec<Trigger>,
+    pub user_defined_functions: Vec<UserDefinedFunction>,
+}
+
+impl LiveCollection {
+    pub fn new(collection: Collection) -> Self {
+        Self {
+            collection,
+            offer: None,
+            stored_procedures: Vec::new(),
+            triggers: Vec::new(),
+            user_defined_functions: Vec::new(),
+        }
+    }
+}
+
+// Compares the spec with the account. With `prune` whatever the spec does not declare is
+// deleted: the other databases, the other collections of its databases and the other
+// scripts of its collections.
+pub(crate) fn plan(
+    spec: &SchemaSpec,
+    live: &LiveState,
+    prune: bool,
+) -> Result<MigrationPlan, AzureError> {
+    check_unique_ids("database", spec.databases.iter().map(|d| d.id.as_str()))?;
+
+    let mut steps = Vec::new();
+    for database in &spec.databases {
+        check_unique_ids(
+            "collection",
+            database.collections.iter().map(|c| c.id.as_str()),
+        )?;
+
+        let live_database = live.databases.iter().find(|live| live.id == database.id);
+        if live_database.is_none() {
+            steps.push(MigrationStep::CreateDatabase {
+                database: database.id.clone(),
+            });
+        }
+        let live_collections = live_database
+            .map(|live| live.collections.as_slice())
+            .unwrap_or_default();
+
+        for collection in &database.collections {
+            let live_collection = live_collections
+                .iter()
+                .find(|live| live.collection.id == collection.id);
+            plan_collection(&mut steps, &database.id, collection, live_collection, prune)?;
+        }
+
+        if prune {
+            for live in live_collections {
+                if !database
+                    .collections
+                    .iter()
+                    .any(|collection| collection.id == live.collection.id)
+                {
+                    steps.push(MigrationStep::DeleteCollection {
+                        database: database.id.clone(),
+                        collection: live.collection.id.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    if prune {
+        for live in &live.databases {
+            if !spec.databases.iter().any(|database| database.id == live.id) {
+                steps.push(MigrationStep::DeleteDatabase {
+                    database: live.id.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(MigrationPlan { steps })
+}
+
+fn plan_collection(
+    steps: &mut Vec<MigrationStep>,
+    database: &str,
+    spec: &CollectionSpec,
+    live: Option<&LiveCollection>,
+    prune: bool,
+) -> Result<(), AzureError> {
+    check_unique_ids(
+        "stored procedure",
+        spec.stored_procedures.iter().map(|s| s.id.as_str()),
+    )?;
+    check_unique_ids("trigger", spec.triggers.iter().map(|t| t.id.as_str()))?;
+    check_unique_ids(
+        "user defined function",
+        spec.user_defined_functions.iter().map(|u| u.id.as_str()),
+    )?;
+
+    let (stored_procedures, triggers, user_defined_functions) = match live {
+        Some(live) => {
+            plan_collection_changes(steps, database, spec, live)?;
+            (
+                live.stored_procedures.as_slice(),
+                live.triggers.as_slice(),
+                live.user_defined_functions.as_slice(),
+            )
+        }
+        None => {
+            steps.push(MigrationStep::CreateCollection {
+                database: database.to_owned(),
+                collection: spec.clone(),
+            });
+            (&[][..], &[][..], &[][..])
+        }
+    };
+
+    for stored_procedure in &spec.stored_procedures {
+        let existing = stored_procedures
+            .iter()
+            .find(|existing| existing.id == stored_procedure.id);
+        let (database, collection, stored_procedure) = (
+            database.to_owned(),
+            spec.id.clone(),
+            stored_procedure.clone(),
+        );
+        match existing {
+            None => steps.push(MigrationStep::CreateStoredProcedure {
+                database,
+                collection,
+                stored_procedure,
+            }),
+            Some(existing) if existing.body != stored_procedure.body => {
+                steps.push(MigrationStep::ReplaceStoredProcedure {
+                    database,
+                    collection,
+                    stored_procedure,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for trigger in &spec.triggers {
+        let existing = triggers.iter().find(|existing| existing.id == trigger.id);
+        let (database, collection, trigger) =
+            (database.to_owned(), spec.id.clone(), trigger.clone());
+        match existing {
+            None => steps.push(MigrationStep::CreateTrigger {
+                database,
+                collection,
+                trigger,
+            }),
+            Some(existing)
+                if existing.body != trigger.body
+                    || existing.trigger_type != trigger.trigger_type
+                    || existing.trigger_operation != trigger.trigger_operation =>
+            {
+                steps.push(MigrationStep::ReplaceTrigger {
+                    database,
+                    collection,
+                    trigger,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for user_defined_function in &spec.user_defined_functions {
+        let existing = user_defined_functions
+            .iter()
+            .find(|existing| existing.id == user_defined_function.id);
+        let (database, collection, user_defined_function) = (
+            database.to_owned(),
+            spec.id.clone(),
+            user_defined_function.clone(),
+        );
+        match existing {
+            None => steps.push(MigrationStep::CreateUserDefinedFunction {
+                database,
+                collection,
+                user_defined_function,
+            }),
+            Some(existing) if existing.body != user_defined_function.body => {
+                steps.push(MigrationStep::ReplaceUserDefinedFunction {
+                    database,
+                    collection,
+                    user_defined_function,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    if prune {
+        for existing in stored_procedures {
+            if !spec.stored_procedures.iter().any(|s| s.id == existing.id) {
+                steps.push(MigrationStep::DeleteStoredProcedure {
+                    database: database.to_owned(),
+                    collection: spec.id.clone(),
+                    stored_procedure: existing.id.clone(),
+                });
+            }
+        }
+        for existing in triggers {
+            if !spec.triggers.iter().any(|t| t.id == existing.id) {
+                steps.push(MigrationStep::DeleteTrigger {
+                    database: database.to_owned(),
+                    collection: spec.id.clone(),
+                    trigger: existing.id.clone(),
+                });
+            }
+        }
+        for existing in user_defined_functions {
+            if !spec
+                .user_defined_functions
+                .iter()
+                .any(|u| u.id == existing.id)
+            {
+                steps.push(MigrationStep::DeleteUserDefinedFunction {
+                    database: database.to_owned(),
+                    collection: spec.id.clone(),
+                    user_defined_function: existing.id.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// The settings of an existing collection: the partition key and the unique keys cannot
+// be changed without recreating the collection (and losing its documents) so they are
+// reported as errors.
+fn plan_collection_changes(
+    steps: &mut Vec<MigrationStep>,
+    database: &str,
+    spec: &CollectionSpec,
+    live: &LiveCollection,
+) -> Result<(), AzureError> {
+    let collection = &live.collection;
+    if spec.partition_key.paths != collection.parition_key.paths {
+        return Err(AzureError::GenericErrorWithText(format!(
+            "collection {}/{} is partitioned by {:?} instead of {:?}: the partition key \
+             cannot be changed",
+            database, spec.id, collection.parition_key.paths, spec.partition_key.paths
+        )));
+    }
+    let unique_keys = |policy: &Option<UniqueKeyPolicy>| {
+        policy
+            .as_ref()
+            .map(|policy| policy.unique_keys.clone())
+            .unwrap_or_default()
+    };
+    if unique_keys(&spec.unique_key_policy) != unique_keys(&collection.unique_key_policy) {
+        return Err(AzureError::GenericErrorWithText(format!(
+            "collection {}/{} has different unique keys: they cannot be changed",
+            database, spec.id
+        )));
+    }
+
+    let mut changes = Vec::new();
+    let indexing_policy = match &spec.indexing_policy {
+        Some(indexing_policy) => {
+            if !same_indexing_policy(indexing_policy, &collection.indexing_policy) {
+                changes.push("indexing policy");
+            }
+            indexing_policy.clone()
+        }
+        None => collection.indexing_policy.clone(),
+    };
+    if spec.default_ttl != collection.default_ttl {
+        changes.push("default time to live");
+    }
+    if !changes.is_empty() {
+        steps.push(MigrationStep::ReplaceCollection {
+            database: database.to_owned(),
+            collection: CollectionSpec {
+                indexing_policy: Some(indexing_policy),
+                ..spec.clone()
+            },
+            changes,
+        });
+    }
+
+    if let Some(throughput) = spec.throughput {
+        let offer = live.offer.as_ref().ok_or_else(|| {
+            AzureError::GenericErrorWithText(format!(
+                "collection {}/{} has no throughput of its own",
+                database, spec.id
+            ))
+        })?;
+        if offer.autoscale_max_throughput().is_some() {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "collection {}/{} has autoscale throughput, which cannot be set manually",
+                database, spec.id
+            )));
+        }
+        if offer.throughput() != Some(throughput) {
+            steps.push(MigrationStep::ReplaceThroughput {
+                database: database.to_owned(),
+                collection: spec.id.clone(),
+                offer: offer.clone(),
+                throughput,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// The service completes the indexing policies it stores (with the system paths it always
+// excludes and the default indexes of the included paths) so only what a spec can set is
+// compared, ignoring the order of the paths.
+fn same_indexing_policy(desired: &IndexingPolicy, live: &IndexingPolicy) -> bool {
+    let included = |policy: &IndexingPolicy| {
+        policy
+            .included_paths
+            .iter()
+            .map(|included| included.path.clone())
+            .collect::<BTreeSet<_>>()
+    };
+    let excluded = |policy: &IndexingPolicy| {
+        policy
+            .excluded_paths
+            .iter()
+            .map(|excluded| excluded.path.clone())
+            .filter(|path| path != ETAG_PATH)
+            .collect::<BTreeSet<_>>()
+    };
+
+    desired.automatic == live.automatic
+        && desired.indexing_mode == live.indexing_mode
+        && included(desired) == included(live)
+        && excluded(desired) == excluded(live)
+        && desired.composite_indexes == live.composite_indexes
+        && desired.spatial_indexes == live.spatial_indexes
+}
+
+fn check_unique_ids<'a>(kind: &str, ids: impl Iterator<Item = &'a str>) -> Result<(), AzureError> {
+    let mut seen = BTreeSet::new();
+    for id in ids {
+        if !seen.insert(id) {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "the spec declares the {} {} more than once",
+                kind, id
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::{IncludedPath, IndexingMode};
+    use crate::trigger::{TriggerOperation, TriggerType};
+
+    fn indexing_policy(included: &str) -> IndexingPolicy {
+        IndexingPolicy {
+            automatic: true,
+            indexing_mode: IndexingMode::Consistent,
+            included_paths: vec![IncludedPath {
+                path: included.to_owned(),
+                indexes: None,
+            }],
+            excluded_paths: vec![],
+            composite_indexes: vec![],
+            spatial_indexes: vec![],
+        }
+    }
+
+    fn live_collection(id: &str, partition_key: &str) -> LiveCollection {
+        let mut collection = Collection::new(id, indexing_policy("/*"));
+        collection.parition_key = partition_key.into();
+        LiveCollection::new(collection)
+    }
+
+    fn stored_procedure(id: &str, body: &str) -> StoredProcedure {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "body": body,
+            "_rid": "",
+            "_ts": 0,
+            "_self": "",
+            "_etag": "",
+        }))
+        .unwrap()
+    }
+
+    fn trigger(id: &str, body: &str, trigger_type: TriggerType) -> Trigger {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "body": body,
+            "triggerType": trigger_type,
+            "triggerOperation": TriggerOperation::Create,
+            "_rid": "",
+            "_ts": 0,
+            "_self": "",
+            "_etag": "",
+        }))
+        .unwrap()
+    }
+
+    fn live(collections: Vec<LiveCollection>) -> LiveState {
+        LiveState {
+            databases: vec![LiveDatabase {
+                id: "shop".to_owned(),
+                collections,
+            }],
+        }
+    }
+
+    #[test]
+    fn plan_creates_what_is_missing() {
+        let spec = SchemaSpec::default().with_database(
+            DatabaseSpec::new("shop").with_collection(
+                CollectionSpec::new("orders", "/customerId")
+                    .with_throughput(1000)
+                    .with_stored_procedure("archive", "function () {}"),
+            ),
+        );
+
+        let plan = plan(&spec, &LiveState::default(), false).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "create database shop\n\
+             create collection shop/orders partitioned by /customerId with 1000 RU/s\n\
+             create stored procedure shop/orders/archive\n"
+        );
+    }
+
+    #[test]
+    fn plan_is_empty_when_up_to_date() {
+        let spec = SchemaSpec::default().with_database(
+            DatabaseSpec::new("shop").with_collection(
+                CollectionSpec::new("orders", "/customerId")
+                    .with_indexing_policy(indexing_policy("/*"))
+                    .with_stored_procedure("archive", "function () {}"),
+            ),
+        );
+        let mut orders = live_collection("orders", "/customerId");
+        // added by the service to every policy
+        orders.collection.indexing_policy.excluded_paths = vec![crate::collection::ExcludedPath {
+            path: ETAG_PATH.to_owned(),
+        }];
+        orders
+            .stored_procedures
+            .push(stored_procedure("archive", "function () {}"));
+
+        let plan = plan(&spec, &live(vec![orders]), true).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.to_string(), "nothing to do\n");
+    }
+
+    #[test]
+    fn plan_replaces_what_changed() {
+        let spec = SchemaSpec::default().with_database(
+            DatabaseSpec::new("shop").with_collection(
+                CollectionSpec::new("orders", "/customerId")
+                    .with_indexing_policy(indexing_policy("/status/?"))
+                    .with_default_ttl(crate::TimeToLive::Seconds(3600))
+                    .with_stored_procedure("archive", "function () { return; }")
+                    .with_trigger(TriggerSpec::new(
+                        "validate",
+                        "function () {}",
+                        TriggerType::Post,
+                        TriggerOperation::Create,
+                    )),
+            ),
+        );
+        let mut orders = live_collection("orders", "/customerId");
+        orders
+            .stored_procedures
+            .push(stored_procedure("archive", "function () {}"));
+        orders
+            .triggers
+            .push(trigger("validate", "function () {}", TriggerType::Pre));
+
+        let plan = plan(&spec, &live(vec![orders]), false).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "replace collection shop/orders: indexing policy, default time to live\n\
+             replace stored procedure shop/orders/archive\n\
+             replace trigger shop/orders/validate\n"
+        );
+    }
+
+    #[test]
+    fn plan_deletes_only_when_pruning() {
+        let spec = SchemaSpec::default().with_database(
+            DatabaseSpec::new("shop").with_collection(CollectionSpec::new("orders", "/customerId")),
+        );
+        let mut orders = live_collection("orders", "/customerId");
+        orders
+            .stored_procedures
+            .push(stored_procedure("archive", "function () {}"));
+        let mut live = live(vec![orders, live_collection("carts", "/customerId")]);
+        live.databases.push(LiveDatabase {
+            id: "legacy".to_owned(),
+            collections: vec![],
+        });
+
+        assert!(plan(&spec, &live, false).unwrap().is_empty());
+        assert_eq!(
+            plan(&spec, &live, true).unwrap().to_string(),
+            "delete stored procedure shop/orders/archive\n\
+             delete collection shop/carts\n\
+             delete database legacy\n"
+        );
+    }
+
+    #[test]
+    fn plan_rejects_what_cannot_be_migrated() {
+        let spec = SchemaSpec::default().with_database(
+            DatabaseSpec::new("shop").with_collection(CollectionSpec::new("orders", "/customerId")),
+        );
+        let live = live(vec![live_collection("orders", "/id")]);
+        assert!(plan(&spec, &live, false).is_err());
+
+        let spec = SchemaSpec::default()
+            .with_database(DatabaseSpec::new("shop"))
+            .with_database(DatabaseSpec::new("shop"));
+        assert!(plan(&spec, &LiveState::default(), false).is_err());
+    }
+}
@@ -0,0 +1,443 @@
+use super::plan::{plan, LiveCollection, LiveDatabase, LiveState, MigrationPlan, MigrationStep};
+use super::spec::{CollectionSpec, DatabaseSpec, SchemaSpec};
+use crate::clients::{
+    CollectionStruct, DatabaseStruct, StoredProcedureStruct, TriggerStruct,
+    UserDefinedFunctionStruct,
+};
+use crate::collection::{IncludedPath, IndexingMode, IndexingPolicy};
+use crate::prelude::*;
+use crate::ThroughputOffer;
+use azure_sdk_core::errors::AzureError;
+use futures::stream::StreamExt;
+use std::borrow::Cow;
+
+// Throughput of the collections created without one in the spec.
+const MIN_THROUGHPUT: u64 = 400;
+
+/// Brings the databases of an account to the state described by a `SchemaSpec`.
+///
+/// `migrate` reads the account with the list builders, plans the changes with `plan` and
+/// applies them in order. A failed step stops the migration: as the plan is computed
+/// again at every run, the migration can be resumed by running it again.
+///
+/// ```no_run
+/// # async fn example(client: azure_sdk_cosmos::clients::CosmosStruct<'_, azure_sdk_cosmos::clients::DefaultCosmosUri>)
+/// # -> Result<(), azure_sdk_core::errors::AzureError> {
+/// use azure_sdk_cosmos::migration::{CollectionSpec, DatabaseSpec, SchemaMigration, SchemaSpec};
+///
+/// let spec = SchemaSpec::default().with_database(
+///     DatabaseSpec::new("shop")
+///         .with_collection(CollectionSpec::new("orders", "/customerId").with_throughput(1000)),
+/// );
+/// // prints the plan without changing the account
+/// SchemaMigration::new(&client, &spec).with_dry_run(true).migrate().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SchemaMigration<'a, C>
+where
+    C: CosmosClient + Clone,
+{
+    cosmos_client: &'a C,
+    spec: &'a SchemaSpec,
+    dry_run: bool,
+    prune: bool,
+}
+
+impl<'a, C> SchemaMigration<'a, C>
+where
+    C: CosmosClient + Clone,
+{
+    pub fn new(cosmos_client: &'a C, spec: &'a SchemaSpec) -> Self {
+        Self {
+            cosmos_client,
+            spec,
+            dry_run: false,
+            prune: false,
+        }
+    }
+
+    /// In dry run `migrate` prints the plan to the standard output instead of applying it.
+    pub fn with_dry_run(self, dry_run: bool) -> Self {
+        Self { dry_run, ..self }
+    }
+
+    /// Deletes what the spec does not declare: the other databases of the account, the
+    /// other collections of its databases and the other scripts of its collections.
+    /// Disabled by default.
+    pub fn with_prune(self, prune: bool) -> Self {
+        Self { prune, ..self }
+    }
+
+    /// Compares the spec with the account without changing it. Fails if the spec is
+    /// invalid or if a collection cannot be migrated in place, for example because its
+    /// partition key changed.
+    pub async fn plan(&self) -> Result<MigrationPlan, AzureError> {
+        let live = self.read_live_state().await?;
+        plan(self.spec, &live, self.prune)
+    }
+
+    /// Plans the migration and, unless in dry run, applies it. Returns the plan.
+    pub async fn migrate(&self) -> Result<MigrationPlan, AzureError> {
+        let plan = self.plan().await?;
+        if self.dry_run {
+            print!("{}", plan);
+            return Ok(plan);
+        }
+
+        for step in &plan.steps {
+            info!("migration: {}", step);
+            if let Err(error) = self.apply(step).await {
+                warn!("migration step \"{}\" failed: {}", step, error);
+                return Err(error);
+            }
+        }
+        Ok(plan)
+    }
+
+    async fn read_live_state(&self) -> Result<LiveState, AzureError> {
+        let mut databases = Vec::new();
+        let builder = self.cosmos_client.list_databases();
+        let mut stream = Box::pin(builder.stream());
+        while let Some(response) = stream.next().await {
+            databases.extend(response?.databases);
+        }
+
+        let mut offers = Vec::new();
+        let manages_throughput = self
+            .spec
+            .databases
+            .iter()
+            .flat_map(|database| &database.collections)
+            .any(|collection| collection.throughput.is_some());
+        if manages_throughput {
+            let builder = self.cosmos_client.list_offers();
+            let mut stream = Box::pin(builder.stream());
+            while let Some(response) = stream.next().await {
+                offers.extend(response?.offers);
+            }
+        }
+
+        let mut live = LiveState::default();
+        for database in databases {
+            let collections = match self.spec.databases.iter().find(|d| d.id == database.id) {
+                Some(spec) => self.read_collections(spec, &offers).await?,
+                None => Vec::new(),
+            };
+            live.databases.push(LiveDatabase {
+                id: database.id,
+                collections,
+            });
+        }
+        Ok(live)
+    }
+
+    async fn read_collections(
+        &self,
+        spec: &DatabaseSpec,
+        offers: &[ThroughputOffer],
+    ) -> Result<Vec<LiveCollection>, AzureError> {
+        let database_client = self.database_client(&spec.id);
+
+        let mut collections = Vec::new();
+        let builder = database_client.list_collections();
+        let mut stream = Box::pin(builder.stream());
+        while let Some(response) = stream.next().await {
+            collections.extend(response?.collections);
+        }
+
+        let mut live = Vec::new();
+        for collection in collections {
+            let id = collection.id.clone();
+            let mut live_collection = LiveCollection::new(collection);
+            if !spec.collections.iter().any(|spec| spec.id == id) {
+                live.push(live_collection);
+                continue;
+            }
+
+            live_collection.offer = offers
+                .iter()
+                .find(|offer| offer.offer_resource_id == live_collection.collection.rid)
+                .cloned();
+
+            let collection_client =
+                CollectionStruct::new(Cow::Borrowed(&database_client), Cow::Borrowed(&id));
+            let builder = collection_client.list_stored_procedures();
+            let mut stream = Box::pin(builder.stream());
+            while let Some(response) = stream.next().await {
+                live_collection
+                    .stored_procedures
+                    .extend(response?.stored_procedures);
+            }
+            let builder = collection_client.list_triggers();
+            let mut stream = Box::pin(builder.stream());
+            while let Some(response) = stream.next().await {
+                live_collection.triggers.extend(response?.triggers);
+            }
+            let builder = collection_client.list_user_defined_functions();
+            let mut stream = Box::pin(builder.stream());
+            while let Some(response) = stream.next().await {
+                live_collection
+                    .user_defined_functions
+                    .extend(response?.user_defined_functions);
+            }
+
+            live.push(live_collection);
+        }
+        Ok(live)
+    }
+
+    async fn apply(&self, step: &MigrationStep) -> Result<(), AzureError> {
+        match step {
+            MigrationStep::CreateDatabase { database } => {
+                self.cosmos_client
+                    .create_database()
+                    .with_database_name(database)
+                    .execute()
+                    .await?;
+            }
+            MigrationStep::DeleteDatabase { database } => {
+                self.database_client(database)
+                    .delete_database()
+                    .execute()
+                    .await?;
+            }
+            MigrationStep::CreateCollection {
+                database,
+                collection,
+            } => {
+                let database_client = self.database_client(database);
+                let indexing_policy = indexing_policy(collection);
+                let builder = database_client
+                    .create_collection()
+                    .with_offer(Offer::Throughput(
+                        collection.throughput.unwrap_or(MIN_THROUGHPUT),
+                    ))
+                    .with_collection_name(&collection.id)
+                    .with_indexing_policy(&indexing_policy)
+                    .with_partition_key(&collection.partition_key);
+                let builder = match collection.default_ttl {
+                    Some(default_ttl) => builder.with_default_ttl(default_ttl),
+                    None => builder,
+                };
+                let builder = match &collection.unique_key_policy {
+                    Some(unique_key_policy) => builder.with_unique_key_policy(unique_key_policy),
+                    None => builder,
+                };
+                builder.execute().await?;
+            }
+            MigrationStep::ReplaceCollection {
+                database,
+                collection,
+                ..
+            } => {
+                let database_client = self.database_client(database);
+                let collection_client = CollectionStruct::new(
+                    Cow::Borrowed(&database_client),
+                    Cow::Borrowed(&collection.id),
+                );
+                let indexing_policy = indexing_policy(collection);
+                let builder = collection_client
+                    .replace_collection()
+                    .with_partition_key(&collection.partition_key)
+                    .with_indexing_policy(&indexing_policy);
+                let builder = match collection.default_ttl {
+                    Some(default_ttl) => builder.with_default_ttl(default_ttl),
+                    None => builder,
+                };
+                let builder = match &collection.unique_key_policy {
+                    Some(unique_key_policy) => builder.with_unique_key_policy(unique_key_policy),
+                    None => builder,
+                };
+                builder.execute().await?;
+            }
+            MigrationStep::DeleteCollection {
+                database,
+                collection,
+            } => {
+                let database_client = self.database_client(database);
+                CollectionStruct::new(Cow::Borrowed(&database_client), Cow::Borrowed(collection))
+                    .delete_collection()
+                    .execute()
+                    .await?;
+            }
+            MigrationStep::ReplaceThroughput {
+                offer, throughput, ..
+            } => {
+                self.cosmos_client
+                    .replace_offer()
+                    .with_throughput_offer(&offer.with_throughput(*throughput)?)
+                    .execute()
+                    .await?;
+            }
+            MigrationStep::CreateStoredProcedure {
+                database,
+                collection,
+                stored_procedure,
+            }
+            | MigrationStep::ReplaceStoredProcedure {
+                database,
+                collection,
+                stored_procedure,
+            } => {
+                let database_client = self.database_client(database);
+                let collection_client = CollectionStruct::new(
+                    Cow::Borrowed(&database_client),
+                    Cow::Borrowed(collection),
+                );
+                let stored_procedure_client = StoredProcedureStruct::new(
+                    Cow::Borrowed(&collection_client),
+                    Cow::Borrowed(&stored_procedure.id),
+                );
+                if let MigrationStep::CreateStoredProcedure { .. } = step {
+                    stored_procedure_client
+                        .create_stored_procedure()
+                        .with_body(&stored_procedure.body)
+                        .execute()
+                        .await?;
+                } else {
+                    stored_procedure_client
+                        .replace_stored_procedure()
+                        .with_body(&stored_procedure.body)
+                        .execute()
+                        .await?;
+                }
+            }
+            MigrationStep::DeleteStoredProcedure {
+                database,
+                collection,
+                stored_procedure,
+            } => {
+                let database_client = self.database_client(database);
+                let collection_client = CollectionStruct::new(
+                    Cow::Borrowed(&database_client),
+                    Cow::Borrowed(collection),
+                );
+                StoredProcedureStruct::new(
+                    Cow::Borrowed(&collection_client),
+                    Cow::Borrowed(stored_procedure),
+                )
+                .delete_stored_procedure()
+                .execute()
+                .await?;
+            }
+            MigrationStep::CreateTrigger {
+                database,
+                collection,
+                trigger,
+            }
+            | MigrationStep::ReplaceTrigger {
+                database,
+                collection,
+                trigger,
+            } => {
+                let database_client = self.database_client(database);
+                let collection_client = CollectionStruct::new(
+                    Cow::Borrowed(&database_client),
+                    Cow::Borrowed(collection),
+                );
+                let trigger_client = TriggerStruct::new(
+                    Cow::Borrowed(&collection_client),
+                    Cow::Borrowed(&trigger.id),
+                );
+                let builder = if let MigrationStep::CreateTrigger { .. } = step {
+                    trigger_client.create_trigger()
+                } else {
+                    trigger_client.replace_trigger()
+                };
+                builder
+                    .with_trigger_type(trigger.trigger_type)
+                    .with_trigger_operation(trigger.trigger_operation)
+                    .with_body(&trigger.body)
+                    .execute()
+                    .await?;
+            }
+            MigrationStep::DeleteTrigger {
+                database,
+                collection,
+                trigger,
+            } => {
+                let database_client = self.database_client(database);
+                let collection_client = CollectionStruct::new(
+                    Cow::Borrowed(&database_client),
+                    Cow::Borrowed(collection),
+                );
+                TriggerStruct::new(Cow::Borrowed(&collection_client), Cow::Borrowed(trigger))
+                    .delete_trigger()
+                    .execute()
+                    .await?;
+            }
+            MigrationStep::CreateUserDefinedFunction {
+                database,
+                collection,
+                user_defined_function,
+            }
+            | MigrationStep::ReplaceUserDefinedFunction {
+                database,
+                collection,
+                user_defined_function,
+            } => {
+                let database_client = self.database_client(database);
+                let collection_client = CollectionStruct::new(
+                    Cow::Borrowed(&database_client),
+                    Cow::Borrowed(collection),
+                );
+                let user_defined_function_client = UserDefinedFunctionStruct::new(
+                    Cow::Borrowed(&collection_client),
+                    Cow::Borrowed(&user_defined_function.id),
+                );
+                let builder = if let MigrationStep::CreateUserDefinedFunction { .. } = step {
+                    user_defined_function_client.create_user_defined_function()
+                } else {
+                    user_defined_function_client.replace_user_defined_function()
+                };
+                builder
+                    .with_body(&user_defined_function.body)
+                    .execute()
+                    .await?;
+            }
+            MigrationStep::DeleteUserDefinedFunction {
+                database,
+                collection,
+                user_defined_function,
+            } => {
+                let database_client = self.database_client(database);
+                let collection_client = CollectionStruct::new(
+                    Cow::Borrowed(&database_client),
+                    Cow::Borrowed(collection),
+                );
+                UserDefinedFunctionStruct::new(
+                    Cow::Borrowed(&collection_client),
+                    Cow::Borrowed(user_defined_function),
+                )
+                .delete_user_defined_function()
+                .execute()
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn database_client<'b>(&'b self, database: &'b str) -> DatabaseStruct<'b, C> {
+        DatabaseStruct::new(Cow::Borrowed(self.cosmos_client), Cow::Borrowed(database))
+    }
+}
+
+// The indexing policy of the spec or, if missing, the one the service would choose.
+fn indexing_policy(collection: &CollectionSpec) -> IndexingPolicy {
+    collection
+        .indexing_policy
+        .clone()
+        .unwrap_or_else(|| IndexingPolicy {
+            automatic: true,
+            indexing_mode: IndexingMode::Consistent,
+            included_paths: vec![IncludedPath {
+                path: "/*".to_owned(),
+                indexes: None,
+            }],
+            excluded_paths: vec![],
+            composite_indexes: vec![],
+            spatial_indexes: vec![],
+        })
+}
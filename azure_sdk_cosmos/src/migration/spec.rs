@@ -0,0 +1,219 @@
+use crate::collection::{IndexingPolicy, PartitionKey, UniqueKeyPolicy};
+use crate::trigger::{TriggerOperation, TriggerType};
+use crate::TimeToLive;
+
+/// The desired state of the databases of an account. It can be built in code or
+/// deserialized, for example from a JSON file kept with the application.
+///
+/// ```
+/// use azure_sdk_cosmos::migration::{CollectionSpec, DatabaseSpec, SchemaSpec};
+///
+/// let spec: SchemaSpec = serde_json::from_str(
+///     r#"{
+///         "databases": [{
+///             "id": "shop",
+///             "collections": [{
+///                 "id": "orders",
+///                 "partitionKey": { "paths": ["/customerId"], "kind": "Hash" },
+///                 "throughput": 400,
+///                 "storedProcedures": [{ "id": "archive", "body": "function () {}" }]
+///             }]
+///         }]
+///     }"#,
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     spec,
+///     SchemaSpec::default().with_database(DatabaseSpec::new("shop").with_collection(
+///         CollectionSpec::new("orders", "/customerId")
+///             .with_throughput(400)
+///             .with_stored_procedure("archive", "function () {}")
+///     ))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SchemaSpec {
+    #[serde(default)]
+    pub databases: Vec<DatabaseSpec>,
+}
+
+impl SchemaSpec {
+    pub fn with_database(mut self, database: DatabaseSpec) -> Self {
+        self.databases.push(database);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseSpec {
+    pub id: String,
+    #[serde(default)]
+    pub collections: Vec<CollectionSpec>,
+}
+
+impl DatabaseSpec {
+    pub fn new<S: Into<String>>(id: S) -> Self {
+        Self {
+            id: id.into(),
+            collections: Vec::new(),
+        }
+    }
+
+    pub fn with_collection(mut self, collection: CollectionSpec) -> Self {
+        self.collections.push(collection);
+        self
+    }
+}
+
+/// A collection and its server-side scripts.
+///
+/// The optional settings are managed only when specified: without an indexing policy the
+/// collection is created with the default one and its policy is never replaced, without
+/// a throughput the collection is created with the minimum one and its offer is never
+/// replaced. The time to live is always managed: `None` disables it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionSpec {
+    pub id: String,
+    #[serde(rename = "partitionKey")]
+    pub partition_key: PartitionKey,
+    #[serde(rename = "indexingPolicy")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub indexing_policy: Option<IndexingPolicy>,
+    /// Throughput of the collection, in request units per second.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub throughput: Option<u64>,
+    #[serde(rename = "defaultTtl")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default_ttl: Option<TimeToLive>,
+    /// Can be set only when the collection is created.
+    #[serde(rename = "uniqueKeyPolicy")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub unique_key_policy: Option<UniqueKeyPolicy>,
+    #[serde(rename = "storedProcedures")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub stored_procedures: Vec<StoredProcedureSpec>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub triggers: Vec<TriggerSpec>,
+    #[serde(rename = "userDefinedFunctions")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub user_defined_functions: Vec<UserDefinedFunctionSpec>,
+}
+
+impl CollectionSpec {
+    pub fn new<S, P>(id: S, partition_key: P) -> Self
+    where
+        S: Into<String>,
+        P: Into<PartitionKey>,
+    {
+        Self {
+            id: id.into(),
+            partition_key: partition_key.into(),
+            indexing_policy: None,
+            throughput: None,
+            default_ttl: None,
+            unique_key_policy: None,
+            stored_procedures: Vec::new(),
+            triggers: Vec::new(),
+            user_defined_functions: Vec::new(),
+        }
+    }
+
+    pub fn with_indexing_policy(self, indexing_policy: IndexingPolicy) -> Self {
+        Self {
+            indexing_policy: Some(indexing_policy),
+            ..self
+        }
+    }
+
+    pub fn with_throughput(self, throughput: u64) -> Self {
+        Self {
+            throughput: Some(throughput),
+            ..self
+        }
+    }
+
+    pub fn with_default_ttl(self, default_ttl: TimeToLive) -> Self {
+        Self {
+            default_ttl: Some(default_ttl),
+            ..self
+        }
+    }
+
+    pub fn with_unique_key_policy(self, unique_key_policy: UniqueKeyPolicy) -> Self {
+        Self {
+            unique_key_policy: Some(unique_key_policy),
+            ..self
+        }
+    }
+
+    pub fn with_stored_procedure<S, B>(mut self, id: S, body: B) -> Self
+    where
+        S: Into<String>,
+        B: Into<String>,
+    {
+        self.stored_procedures.push(StoredProcedureSpec {
+            id: id.into(),
+            body: body.into(),
+        });
+        self
+    }
+
+    pub fn with_trigger(mut self, trigger: TriggerSpec) -> Self {
+        self.triggers.push(trigger);
+        self
+    }
+
+    pub fn with_user_defined_function<S, B>(mut self, id: S, body: B) -> Self
+    where
+        S: Into<String>,
+        B: Into<String>,
+    {
+        self.user_defined_functions.push(UserDefinedFunctionSpec {
+            id: id.into(),
+            body: body.into(),
+        });
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredProcedureSpec {
+    pub id: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerSpec {
+    pub id: String,
+    pub body: String,
+    #[serde(rename = "triggerType")]
+    pub trigger_type: TriggerType,
+    #[serde(rename = "triggerOperation")]
+    pub trigger_operation: TriggerOperation,
+}
+
+impl TriggerSpec {
+    pub fn new<S, B>(
+        id: S,
+        body: B,
+        trigger_type: TriggerType,
+        trigger_operation: TriggerOperation,
+    ) -> Self
+    where
+        S: Into<String>,
+        B: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            body: body.into(),
+            trigger_type,
+            trigger_operation,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserDefinedFunctionSpec {
+    pub id: String,
+    pub body: String,
+}
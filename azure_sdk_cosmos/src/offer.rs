@@ -1,3 +1,5 @@
+use azure_sdk_core::errors::AzureError;
+
 /// This specifies the collection performance level. It can either be custom or fixed. For
 /// more details see
 /// [https://docs.microsoft.com/en-us/rest/api/cosmos-db/create-a-collection](https://docs.microsoft.com/en-us/rest/api/cosmos-db/create-a-collection).
@@ -8,3 +10,199 @@ pub enum Offer {
     S2,
     S3,
 }
+
+/// Throughput offer of a database or a collection, as returned by the offers API. For
+/// more details see
+/// [https://docs.microsoft.com/en-us/rest/api/cosmos-db/offers](https://docs.microsoft.com/en-us/rest/api/cosmos-db/offers).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputOffer {
+    pub id: String,
+    #[serde(rename = "_rid")]
+    pub rid: String,
+    #[serde(rename = "offerVersion")]
+    pub offer_version: String,
+    #[serde(rename = "offerType")]
+    pub offer_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<OfferContent>,
+    /// Self link of the database or collection the offer applies to.
+    pub resource: String,
+    #[serde(rename = "offerResourceId")]
+    pub offer_resource_id: String,
+    #[serde(rename = "_self")]
+    pub _self: String,
+    #[serde(rename = "_etag")]
+    pub etag: String,
+    #[serde(rename = "_ts")]
+    pub ts: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfferContent {
+    #[serde(rename = "offerThroughput", default)]
+    pub offer_throughput: u64,
+    #[serde(
+        rename = "offerIsRUPerMinuteThroughputEnabled",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub offer_is_ru_per_minute_throughput_enabled: Option<bool>,
+    #[serde(
+        rename = "offerAutopilotSettings",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub offer_autopilot_settings: Option<AutopilotSettings>,
+}
+
+/// Autoscale settings: the throughput scales between 10% of `max_throughput` and
+/// `max_throughput`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutopilotSettings {
+    #[serde(rename = "maxThroughput")]
+    pub max_throughput: u64,
+}
+
+impl ThroughputOffer {
+    pub fn throughput(&self) -> Option<u64> {
+        self.content
+            .as_ref()
+            .map(|content| content.offer_throughput)
+    }
+
+    /// Maximum throughput of an autoscale offer, `None` for a manual one.
+    pub fn autoscale_max_throughput(&self) -> Option<u64> {
+        self.content
+            .as_ref()
+            .and_then(|content| content.offer_autopilot_settings.as_ref())
+            .map(|autopilot_settings| autopilot_settings.max_throughput)
+    }
+
+    /// Returns a copy of the offer with the (manual) throughput changed, ready to be passed
+    /// to `replace_offer`. Only V2 offers can be changed this way. Autoscale offers are
+    /// refused: change them with `with_autoscale_max_throughput`.
+    pub fn with_throughput(&self, throughput: u64) -> Result<Self, AzureError> {
+        if self.offer_version != "V2" {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "offer {} is {}: only V2 offers have a throughput",
+                self.id, self.offer_version
+            )));
+        }
+        if self.autoscale_max_throughput().is_some() {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "offer {} is autoscale: its throughput cannot be set manually",
+                self.id
+            )));
+        }
+
+        let content = match &self.content {
+            Some(content) => OfferContent {
+                offer_throughput: throughput,
+                ..content.clone()
+            },
+            None => OfferContent {
+                offer_throughput: throughput,
+                offer_is_ru_per_minute_throughput_enabled: None,
+                offer_autopilot_settings: None,
+            },
+        };
+
+        Ok(Self {
+            content: Some(content),
+            ..self.clone()
+        })
+    }
+
+    /// Returns a copy of the autoscale offer with its maximum throughput changed, ready to
+    /// be passed to `replace_offer`. Manual offers are refused: change them with
+    /// `with_throughput`.
+    pub fn with_autoscale_max_throughput(&self, max_throughput: u64) -> Result<Self, AzureError> {
+        let content = match &self.content {
+            Some(content) if content.offer_autopilot_settings.is_some() => OfferContent {
+                offer_autopilot_settings: Some(AutopilotSettings { max_throughput }),
+                ..content.clone()
+            },
+            _ => {
+                return Err(AzureError::GenericErrorWithText(format!(
+                    "offer {} is not autoscale: its maximum throughput cannot be set",
+                    self.id
+                )))
+            }
+        };
+
+        Ok(Self {
+            content: Some(content),
+            ..self.clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFFER: &str = r#"{
+        "offerVersion": "V2",
+        "offerType": "Invalid",
+        "content": {
+            "offerThroughput": 400,
+            "offerIsRUPerMinuteThroughputEnabled": false
+        },
+        "resource": "dbs/rgkVAA==/colls/rgkVAMHcJww=/",
+        "offerResourceId": "rgkVAMHcJww=",
+        "id": "Z8ag",
+        "_rid": "Z8ag",
+        "_self": "offers/Z8ag/",
+        "_etag": "\"00005800-0000-0000-0000-5ade3c540000\"",
+        "_ts": 1524513876
+    }"#;
+
+    #[test]
+    fn parse_and_change_throughput() {
+        let offer: ThroughputOffer = serde_json::from_str(OFFER).unwrap();
+        assert_eq!(offer.throughput(), Some(400));
+        assert_eq!(offer.offer_resource_id, "rgkVAMHcJww=");
+
+        assert_eq!(offer.autoscale_max_throughput(), None);
+        assert!(offer.with_autoscale_max_throughput(4000).is_err());
+
+        let v1_offer = ThroughputOffer {
+            offer_version: "V1".to_owned(),
+            offer_type: "S1".to_owned(),
+            content: None,
+            ..offer.clone()
+        };
+        assert!(v1_offer.with_throughput(1000).is_err());
+
+        let offer = offer.with_throughput(1000).unwrap();
+        let serialized = serde_json::to_value(&offer).unwrap();
+        assert_eq!(serialized["content"]["offerThroughput"], 1000);
+        assert_eq!(
+            serialized["content"]["offerIsRUPerMinuteThroughputEnabled"],
+            false
+        );
+        assert!(serialized["content"]
+            .get("offerAutopilotSettings")
+            .is_none());
+    }
+
+    #[test]
+    fn change_autoscale_max_throughput() {
+        let mut offer: ThroughputOffer = serde_json::from_str(OFFER).unwrap();
+        offer.content = Some(OfferContent {
+            offer_throughput: 400,
+            offer_is_ru_per_minute_throughput_enabled: None,
+            offer_autopilot_settings: Some(AutopilotSettings {
+                max_throughput: 4000,
+            }),
+        });
+        assert_eq!(offer.autoscale_max_throughput(), Some(4000));
+        assert!(offer.with_throughput(1000).is_err());
+
+        let offer = offer.with_autoscale_max_throughput(6000).unwrap();
+        let serialized = serde_json::to_value(&offer).unwrap();
+        assert_eq!(
+            serialized["content"]["offerAutopilotSettings"]["maxThroughput"],
+            6000
+        );
+        assert_eq!(serialized["content"]["offerThroughput"], 400);
+    }
+}
@@ -5,6 +5,7 @@ pub struct PartitionKeyRange {
     pub id: String,
     #[serde(rename = "_etag")]
     pub etag: String,
+    /// Inclusive minimum of the range despite its name, see `min_inclusive`.
     #[serde(rename = "minInclusive")]
     pub min_exclusive: String,
     #[serde(rename = "maxExclusive")]
@@ -15,7 +16,25 @@ pub struct PartitionKeyRange {
     #[serde(rename = "throughputFraction")]
     pub throughput_fraction: u64,
     pub status: String,
-    // TODO: parents
+    /// Ids of the partition key ranges this range comes from, after one or more splits.
+    #[serde(default)]
+    pub parents: Vec<String>,
     #[serde(rename = "_ts")]
     pub ts: u64,
 }
+
+impl PartitionKeyRange {
+    /// Minimum effective partition key of the range, which belongs to it.
+    pub fn min_inclusive(&self) -> &str {
+        &self.min_exclusive
+    }
+
+    /// Whether the effective partition key (see
+    /// [`effective_partition_key`](crate::effective_partition_key::effective_partition_key))
+    /// belongs to this range. Effective partition keys are hex strings that can be
+    /// compared lexicographically.
+    pub fn contains(&self, effective_partition_key: &str) -> bool {
+        self.min_inclusive() <= effective_partition_key
+            && effective_partition_key < self.max_exclusive.as_str()
+    }
+}
@@ -0,0 +1,105 @@
+use serde_json::Value;
+
+/// A single operation of a partial document update, as defined by JSON Patch
+/// ([RFC 6902](https://tools.ietf.org/html/rfc6902)) with the Cosmos DB `set` and
+/// `incr` extensions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Adds the value, inserting it at the specified index if the target is an array.
+    Add { path: String, value: Value },
+    /// Removes the target, failing if it does not exist.
+    Remove { path: String },
+    /// Replaces the target, failing if it does not exist.
+    Replace { path: String, value: Value },
+    /// Like `Add` but it replaces the target if it exists (even in arrays).
+    Set { path: String, value: Value },
+    /// Increments the target number by the specified (possibly negative) value.
+    #[serde(rename = "incr")]
+    Increment { path: String, value: Value },
+    /// Moves the value at `from` to `path`.
+    Move { from: String, path: String },
+}
+
+impl PatchOperation {
+    pub fn add<P, V>(path: P, value: V) -> Self
+    where
+        P: Into<String>,
+        V: Into<Value>,
+    {
+        PatchOperation::Add {
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn remove<P>(path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        PatchOperation::Remove { path: path.into() }
+    }
+
+    pub fn replace<P, V>(path: P, value: V) -> Self
+    where
+        P: Into<String>,
+        V: Into<Value>,
+    {
+        PatchOperation::Replace {
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn set<P, V>(path: P, value: V) -> Self
+    where
+        P: Into<String>,
+        V: Into<Value>,
+    {
+        PatchOperation::Set {
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn increment<P, V>(path: P, value: V) -> Self
+    where
+        P: Into<String>,
+        V: Into<Value>,
+    {
+        PatchOperation::Increment {
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn move_from<F, P>(from: F, path: P) -> Self
+    where
+        F: Into<String>,
+        P: Into<String>,
+    {
+        PatchOperation::Move {
+            from: from.into(),
+            path: path.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_patch_operations() {
+        let operations = vec![
+            PatchOperation::set("/color", "red"),
+            PatchOperation::increment("/count", -1),
+            PatchOperation::remove("/tags/0"),
+            PatchOperation::move_from("/old", "/new"),
+        ];
+        assert_eq!(
+            serde_json::to_string(&operations).unwrap(),
+            r#"[{"op":"set","path":"/color","value":"red"},{"op":"incr","path":"/count","value":-1},{"op":"remove","path":"/tags/0"},{"op":"move","from":"/old","path":"/new"}]"#
+        );
+    }
+}
@@ -1,6 +1,8 @@
 pub use crate::clients::{ClientBuilder, CosmosStruct};
 pub use crate::collection::{
-    Collection, DataType, IncludedPath, IncludedPathIndex, IndexingMode, IndexingPolicy, KeyKind,
+    Collection, CompositeIndex, CompositePath, CompositePathOrder, ConflictResolutionPolicy,
+    DataType, IncludedPath, IncludedPathIndex, IndexingMode, IndexingPolicy, KeyKind, SpatialIndex,
+    SpatialType, UniqueKey, UniqueKeyPolicy,
 };
 pub use crate::database::DatabaseName;
 pub use crate::document::Document;
@@ -8,29 +10,35 @@ pub use crate::query::Query;
 pub use crate::responses::{QueryDocumentsResponse, QueryDocumentsResponseRaw, QueryResult};
 pub use crate::{
     AIMOption, AIMSupport, AllowTentativeWritesOption, AllowTentativeWritesSupport,
-    AttachmentClient, AttachmentClientRequired, AuthorizationToken, CollectionClient,
+    AttachmentClient, AttachmentClientRequired, AuthorizationToken, ChangeFeedContinuation,
+    ChangeFeedContinuationOption, ChangeFeedContinuationSupport, CollectionClient,
     CollectionClientRequired, CollectionNameRequired, CollectionNameSupport, CollectionRequired,
-    CollectionSupport, ConsistencyLevel, ConsistencyLevelOption, ConsistencyLevelSupport,
-    ContinuationOption, ContinuationSupport, CosmosClient, CosmosClientRequired, DatabaseClient,
-    DatabaseClientRequired, DatabaseNameRequired, DatabaseNameSupport, DocumentClient,
+    CollectionSupport, ConditionOption, ConditionSupport, ConflictClient, ConflictClientRequired,
+    ConflictResolutionPolicyOption, ConflictResolutionPolicySupport, ConsistencyLevel,
+    ConsistencyLevelOption, ConsistencyLevelSupport, ContinuationOption, ContinuationSupport,
+    CosmosClient, CosmosClientRequired, DatabaseClient, DatabaseClientRequired,
+    DatabaseNameRequired, DatabaseNameSupport, DefaultTtlOption, DefaultTtlSupport, DocumentClient,
     DocumentIdRequired, DocumentIdSupport, ExpirySecondsOption, ExpirySecondsSupport,
     HasStoredProcedureClient, IndexingDirective, IndexingDirectiveOption, IndexingDirectiveSupport,
     IndexingPolicyRequired, IndexingPolicySupport, IntoAttachmentClient, IntoCollectionClient,
-    IntoDatabaseClient, IntoDocumentClient, IntoPermissionClient, IntoStoredProcedureClient,
-    IntoUserClient, IsUpsertOption, IsUpsertSupport, MaxItemCountOption, MaxItemCountSupport,
-    MediaRequired, MediaSupport, Offer, OfferRequired, OfferSupport,
-    ParallelizeCrossPartitionQueryOption, ParallelizeCrossPartitionQuerySupport, ParametersOption,
-    ParametersSupport, PartitionKeyOption, PartitionKeyRequired, PartitionKeySupport,
-    PartitionKeys, PartitionKeysOption, PartitionKeysRequired, PartitionKeysSupport,
-    PartitionRangeIdOption, PartitionRangeIdSupport, PermissionClient, PermissionClientRequired,
-    QueryCrossPartitionOption, QueryCrossPartitionSupport, QueryRequired, QuerySupport,
+    IntoConflictClient, IntoDatabaseClient, IntoDocumentClient, IntoPermissionClient,
+    IntoStoredProcedureClient, IntoUserClient, IsUpsertOption, IsUpsertSupport, MaxItemCountOption,
+    MaxItemCountSupport, MediaRequired, MediaSupport, Offer, OfferIdRequired, OfferIdSupport,
+    OfferRequired, OfferSupport, ParallelizeCrossPartitionQueryOption,
+    ParallelizeCrossPartitionQuerySupport, ParametersOption, ParametersSupport, PartitionKeyOption,
+    PartitionKeyRequired, PartitionKeySupport, PartitionKeys, PartitionKeysOption,
+    PartitionKeysRequired, PartitionKeysSupport, PartitionRangeIdOption, PartitionRangeIdSupport,
+    PatchOperation, PermissionClient, PermissionClientRequired, QueryCrossPartitionOption,
+    QueryCrossPartitionSupport, QueryRequired, QuerySupport, RetryPolicy,
     StoredProcedureBodyRequired, StoredProcedureBodySupport, StoredProcedureClient,
     StoredProcedureClientRequired, StoredProcedureNameRequired, StoredProcedureNameSupport,
-    TriggerBodyRequired, TriggerBodySupport, TriggerClient, TriggerClientRequired,
-    TriggerOperationRequired, TriggerOperationSupport, TriggerTypeRequired, TriggerTypeSupport,
-    UserClient, UserClientRequired, UserDefinedFunctionBodyRequired,
+    ThroughputOffer, ThroughputOfferRequired, ThroughputOfferSupport, TimeToLive,
+    TransactionalBatch, TransactionalBatchRequired, TransactionalBatchSupport, TriggerBodyRequired,
+    TriggerBodySupport, TriggerClient, TriggerClientRequired, TriggerOperationRequired,
+    TriggerOperationSupport, TriggerTypeRequired, TriggerTypeSupport, UniqueKeyPolicyOption,
+    UniqueKeyPolicySupport, UserClient, UserClientRequired, UserDefinedFunctionBodyRequired,
     UserDefinedFunctionBodySupport, UserDefinedFunctionClient, UserDefinedFunctionClientRequired,
     UserName, UserNameRequired, UserNameSupport, WithAttachmentClient, WithCollectionClient,
-    WithDatabaseClient, WithDocumentClient, WithPermissionClient, WithStoredProcedureClient,
-    WithTriggerClient, WithUserClient, WithUserDefinedFunctionClient,
+    WithConflictClient, WithDatabaseClient, WithDocumentClient, WithPermissionClient,
+    WithStoredProcedureClient, WithTriggerClient, WithUserClient, WithUserDefinedFunctionClient,
 };
@@ -0,0 +1,566 @@
+//! Client side execution of cross partition queries.
+//!
+//! The gateway can only answer cross partition queries that do not need to combine the
+//! results of different partitions. For the other ones (ORDER BY, TOP, OFFSET/LIMIT,
+//! DISTINCT and aggregates) the SDK asks for the query plan, sends the rewritten query to
+//! every partition key range and merges the partial results here.
+mod query_plan;
+pub use self::query_plan::{Aggregate, DistinctType, QueryInfo, QueryPlan, QueryRange, SortOrder};
+use azure_sdk_core::errors::AzureError;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{HashSet, VecDeque};
+
+// Merges the results of every partition key range (in partition key range order)
+// according to the query plan.
+pub(crate) fn merge_results(
+    query_info: &QueryInfo,
+    partitions: Vec<Vec<Value>>,
+) -> Result<Vec<Value>, AzureError> {
+    let results: Box<dyn Iterator<Item = Value>> = if !query_info.order_by.is_empty() {
+        Box::new(merge_order_by(&query_info.order_by, partitions)?)
+    } else if !query_info.aggregates.is_empty() {
+        Box::new(merge_aggregates(&query_info.aggregates, partitions)?.into_iter())
+    } else if has_aggregates_by_alias(query_info) {
+        Box::new(merge_aggregates_by_alias(query_info, partitions)?.into_iter())
+    } else {
+        Box::new(partitions.into_iter().flatten())
+    };
+
+    let results: Box<dyn Iterator<Item = Value>> = if query_info.distinct_type == DistinctType::None
+    {
+        results
+    } else {
+        let mut seen = HashSet::new();
+        Box::new(results.filter(move |result| seen.insert(result.to_string())))
+    };
+
+    let results = results.skip(query_info.offset.unwrap_or(0) as usize);
+    let results: Vec<Value> = match query_info.limit.or(query_info.top) {
+        Some(limit) => results.take(limit as usize).collect(),
+        None => results.collect(),
+    };
+
+    Ok(results)
+}
+
+// Whether the results read so far from a partition key range are all the ones it can
+// contribute to the merged results, so that its next pages do not need to be read. With
+// TOP or OFFSET/LIMIT, and without DISTINCT or aggregates, a partition key range never
+// contributes more than offset + limit results.
+pub(crate) fn is_partition_complete(query_info: &QueryInfo, results: &[Value]) -> bool {
+    if query_info.distinct_type != DistinctType::None
+        || !query_info.aggregates.is_empty()
+        || has_aggregates_by_alias(query_info)
+    {
+        return false;
+    }
+    let limit = match query_info.limit.or(query_info.top) {
+        Some(limit) => limit,
+        None => return false,
+    };
+
+    // the ORDER BY results without payload are dropped by the merge
+    let count = if query_info.order_by.is_empty() {
+        results.len()
+    } else {
+        results
+            .iter()
+            .filter(|result| result.get("payload").is_some())
+            .count()
+    };
+    count as u64 >= query_info.offset.unwrap_or(0) + limit
+}
+
+fn has_aggregates_by_alias(query_info: &QueryInfo) -> bool {
+    query_info
+        .group_by_alias_to_aggregate_type
+        .values()
+        .any(Option::is_some)
+}
+
+// Rewritten ORDER BY queries return items like
+// {"_rid": "...", "orderByItems": [{"item": <value>}, ...], "payload": <result>}.
+// Every partition returns them already sorted, so they are merged lazily taking the
+// smallest head among the partitions; equal items keep the partition key range order.
+fn merge_order_by(
+    sort_orders: &[SortOrder],
+    partitions: Vec<Vec<Value>>,
+) -> Result<OrderByMerge<'_>, AzureError> {
+    let mut heads = Vec::with_capacity(partitions.len());
+    for partition in partitions {
+        let mut items = VecDeque::with_capacity(partition.len());
+        for item in partition {
+            let mut item = match item {
+                Value::Object(item) => item,
+                _ => return Err(unexpected_item("ORDER BY", &item)),
+            };
+            let order_by_items = match item.remove("orderByItems") {
+                Some(Value::Array(order_by_items)) => order_by_items,
+                _ => return Err(unexpected_item("ORDER BY", &Value::Object(item))),
+            };
+            // a missing payload means the projection is undefined for this document
+            if let Some(payload) = item.remove("payload") {
+                items.push_back((order_by_items, payload));
+            }
+        }
+        heads.push(items);
+    }
+
+    Ok(OrderByMerge {
+        sort_orders,
+        partitions: heads,
+    })
+}
+
+struct OrderByMerge<'a> {
+    sort_orders: &'a [SortOrder],
+    // the order by items and the payload of the items not merged yet
+    partitions: Vec<VecDeque<(Vec<Value>, Value)>>,
+}
+
+impl<'a> OrderByMerge<'a> {
+    fn compare(&self, a: &[Value], b: &[Value]) -> Ordering {
+        self.sort_orders
+            .iter()
+            .zip(a.iter().zip(b.iter()))
+            .map(|(sort_order, (a, b))| {
+                let ordering = compare_values(a.get("item"), b.get("item"));
+                match sort_order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            })
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<'a> Iterator for OrderByMerge<'a> {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        let mut next: Option<usize> = None;
+        for (index, partition) in self.partitions.iter().enumerate() {
+            let head = match partition.front() {
+                Some((order_by_items, _)) => order_by_items,
+                None => continue,
+            };
+            next = match next {
+                Some(current)
+                    if self.compare(head, &self.partitions[current].front()?.0)
+                        != Ordering::Less =>
+                {
+                    Some(current)
+                }
+                _ => Some(index),
+            };
+        }
+        self.partitions[next?]
+            .pop_front()
+            .map(|(_, payload)| payload)
+    }
+}
+
+// Cosmos DB sorts undefined < null < booleans < numbers < strings.
+fn compare_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    fn type_order(value: Option<&Value>) -> u8 {
+        match value {
+            None => 0,
+            Some(Value::Null) => 1,
+            Some(Value::Bool(_)) => 2,
+            Some(Value::Number(_)) => 3,
+            Some(Value::String(_)) => 4,
+            Some(Value::Array(_)) => 5,
+            Some(Value::Object(_)) => 6,
+        }
+    }
+
+    match (a, b) {
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        _ => type_order(a).cmp(&type_order(b)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Accumulator {
+    Average { sum: f64, count: f64 },
+    Count(f64),
+    Max(Option<Value>),
+    Min(Option<Value>),
+    Sum(Option<f64>),
+}
+
+impl Accumulator {
+    fn new(aggregate: Aggregate) -> Self {
+        match aggregate {
+            Aggregate::Average => Accumulator::Average {
+                sum: 0.0,
+                count: 0.0,
+            },
+            Aggregate::Count => Accumulator::Count(0.0),
+            Aggregate::Max => Accumulator::Max(None),
+            Aggregate::Min => Accumulator::Min(None),
+            Aggregate::Sum => Accumulator::Sum(None),
+        }
+    }
+
+    fn add(&mut self, item: &Value) -> Result<(), AzureError> {
+        match self {
+            Accumulator::Average { sum, count } => {
+                // averages are returned as {"sum": <number>, "count": <number>}
+                if let (Some(partial_sum), Some(partial_count)) = (
+                    item.get("sum").and_then(Value::as_f64),
+                    item.get("count").and_then(Value::as_f64),
+                ) {
+                    *sum += partial_sum;
+                    *count += partial_count;
+                }
+            }
+            Accumulator::Count(count) => {
+                *count += item
+                    .as_f64()
+                    .ok_or_else(|| unexpected_item("COUNT", item))?
+            }
+            Accumulator::Sum(sum) => {
+                let partial_sum = item.as_f64().ok_or_else(|| unexpected_item("SUM", item))?;
+                *sum = Some(sum.unwrap_or(0.0) + partial_sum);
+            }
+            Accumulator::Max(max) => {
+                if max.is_none() || compare_values(Some(item), max.as_ref()) == Ordering::Greater {
+                    *max = Some(item.clone());
+                }
+            }
+            Accumulator::Min(min) => {
+                if min.is_none() || compare_values(Some(item), min.as_ref()) == Ordering::Less {
+                    *min = Some(item.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // None means undefined (for example the MAX of an empty set).
+    fn result(self) -> Option<Value> {
+        match self {
+            Accumulator::Average { sum, count } if count > 0.0 => Some(number(sum / count)),
+            Accumulator::Average { .. } => None,
+            Accumulator::Count(count) => Some(number(count)),
+            Accumulator::Sum(sum) => sum.map(number),
+            Accumulator::Max(value) | Accumulator::Min(value) => value,
+        }
+    }
+}
+
+// Whole numbers are returned as integers so they can be deserialized as such.
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        Value::from(value as i64)
+    } else {
+        Value::from(value)
+    }
+}
+
+// Rewritten aggregate queries return, for every partition, items like
+// [{"item": <partial aggregate>}, ...] with one element per aggregate. The item is missing
+// if the partial aggregate is undefined.
+fn merge_aggregates(
+    aggregates: &[Aggregate],
+    partitions: Vec<Vec<Value>>,
+) -> Result<Vec<Value>, AzureError> {
+    let mut accumulators = aggregates
+        .iter()
+        .map(|aggregate| Accumulator::new(*aggregate))
+        .collect::<Vec<_>>();
+
+    for item in partitions.into_iter().flatten() {
+        let partial_aggregates = match item {
+            Value::Array(partial_aggregates) => partial_aggregates,
+            item => vec![item],
+        };
+
+        for (accumulator, partial_aggregate) in accumulators.iter_mut().zip(partial_aggregates) {
+            let partial_aggregate = match partial_aggregate {
+                Value::Object(mut item) => item.remove("item"),
+                partial_aggregate => Some(partial_aggregate),
+            };
+            if let Some(partial_aggregate) = partial_aggregate {
+                accumulator.add(&partial_aggregate)?;
+            }
+        }
+    }
+
+    Ok(accumulators
+        .into_iter()
+        .filter_map(Accumulator::result)
+        .collect())
+}
+
+// Rewritten aggregate queries that are not SELECT VALUE (for example
+// `SELECT COUNT(1) AS n, MAX(c.v) FROM c`) return, for every partition, items like
+// {"payload": {"n": {"item": <partial aggregate>}, "$1": {"item": ...}}}. The partial
+// aggregates are merged alias by alias into a single item; the undefined ones are left out.
+fn merge_aggregates_by_alias(
+    query_info: &QueryInfo,
+    partitions: Vec<Vec<Value>>,
+) -> Result<Vec<Value>, AzureError> {
+    let mut aliases = query_info.group_by_aliases.clone();
+    if aliases.is_empty() {
+        aliases = query_info
+            .group_by_alias_to_aggregate_type
+            .keys()
+            .cloned()
+            .collect();
+        aliases.sort();
+    }
+
+    let mut accumulators = aliases
+        .into_iter()
+        .map(
+            |alias| match query_info.group_by_alias_to_aggregate_type.get(&alias) {
+                Some(Some(aggregate)) => Ok((alias, Accumulator::new(*aggregate))),
+                _ => Err(AzureError::GenericErrorWithText(format!(
+                    "{} is not an aggregate: projections mixing aggregates and other values \
+                     are not supported by the query pipeline",
+                    alias
+                ))),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for item in partitions.into_iter().flatten() {
+        let mut item = match item {
+            Value::Object(item) => item,
+            item => return Err(unexpected_item("aggregate", &item)),
+        };
+        let mut payload = match item.remove("payload") {
+            Some(Value::Object(payload)) => payload,
+            Some(payload) => return Err(unexpected_item("aggregate", &payload)),
+            None => item,
+        };
+
+        for (alias, accumulator) in accumulators.iter_mut() {
+            let partial_aggregate = match payload.remove(alias.as_str()) {
+                Some(Value::Object(mut item)) => item.remove("item"),
+                partial_aggregate => partial_aggregate,
+            };
+            if let Some(partial_aggregate) = partial_aggregate {
+                accumulator.add(&partial_aggregate)?;
+            }
+        }
+    }
+
+    Ok(vec![Value::Object(
+        accumulators
+            .into_iter()
+            .filter_map(|(alias, accumulator)| accumulator.result().map(|result| (alias, result)))
+            .collect(),
+    )])
+}
+
+fn unexpected_item(operation: &str, item: &Value) -> AzureError {
+    AzureError::GenericErrorWithText(format!("unexpected {} partial result: {}", operation, item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn query_info(query_info: Value) -> QueryInfo {
+        serde_json::from_value(query_info).unwrap()
+    }
+
+    fn order_by_item(value: Value) -> Value {
+        json!({ "_rid": "rid", "orderByItems": [{ "item": value.clone() }], "payload": { "v": value } })
+    }
+
+    #[test]
+    fn parse_query_plan() {
+        let query_plan: QueryPlan = serde_json::from_str(
+            r#"{"partitionedQueryExecutionInfoVersion":2,"queryInfo":{"distinctType":"None","top":10,"offset":null,"limit":null,"orderBy":["Descending"],"orderByExpressions":["c.v"],"groupByExpressions":[],"groupByAliases":[],"aggregates":[],"groupByAliasToAggregateType":{},"rewrittenQuery":"SELECT TOP 10 c._rid, [{\"item\": c.v}] AS orderByItems, c AS payload\nFROM c\nWHERE ({documentdb-formattableorderbyquery-filter})\nORDER BY c.v DESC","hasSelectValue":false},"queryRanges":[{"min":"","max":"FF","isMinInclusive":true,"isMaxInclusive":false}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(query_plan.query_info.top, Some(10));
+        assert_eq!(query_plan.query_info.order_by, vec![SortOrder::Descending]);
+        assert_eq!(
+            query_plan.rewritten_query().unwrap(),
+            "SELECT TOP 10 c._rid, [{\"item\": c.v}] AS orderByItems, c AS payload\nFROM c\nWHERE (true)\nORDER BY c.v DESC"
+        );
+        assert_eq!(query_plan.query_ranges[0].max, "FF");
+    }
+
+    #[test]
+    fn merge_order_by_and_top() {
+        let query_info = query_info(json!({
+            "distinctType": "None",
+            "top": 4,
+            "orderBy": ["Descending"],
+        }));
+        let partitions = vec![
+            vec![order_by_item(json!(9)), order_by_item(json!(4))],
+            vec![
+                order_by_item(json!(7)),
+                order_by_item(json!(5)),
+                order_by_item(json!(1)),
+            ],
+            vec![order_by_item(json!("z")), order_by_item(Value::Null)],
+        ];
+
+        assert_eq!(
+            merge_results(&query_info, partitions).unwrap(),
+            vec![
+                json!({"v": "z"}),
+                json!({"v": 9}),
+                json!({"v": 7}),
+                json!({"v": 5})
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_order_by_keeps_partition_order_of_equal_items() {
+        let query_info = query_info(json!({
+            "distinctType": "None",
+            "orderBy": ["Ascending"],
+        }));
+        let item = |value: Value, partition: u32| json!({ "_rid": "rid", "orderByItems": [{ "item": value }], "payload": { "p": partition } });
+        let partitions = vec![
+            vec![item(json!(1), 0), item(json!(3), 0)],
+            vec![item(json!(1), 1), item(json!(2), 1), item(json!(3), 1)],
+        ];
+
+        assert_eq!(
+            merge_results(&query_info, partitions).unwrap(),
+            vec![
+                json!({"p": 0}),
+                json!({"p": 1}),
+                json!({"p": 1}),
+                json!({"p": 0}),
+                json!({"p": 1})
+            ]
+        );
+    }
+
+    #[test]
+    fn partition_complete_after_offset_plus_limit() {
+        let offset_limit = query_info(json!({
+            "distinctType": "None",
+            "offset": 2,
+            "limit": 3,
+            "orderBy": ["Ascending"],
+        }));
+        let items = (0..5).map(|i| order_by_item(json!(i))).collect::<Vec<_>>();
+        assert!(!is_partition_complete(&offset_limit, &items[..4]));
+        assert!(is_partition_complete(&offset_limit, &items));
+        // results without payload do not count
+        let mut undefined = items[..4].to_vec();
+        undefined.push(json!({ "_rid": "rid", "orderByItems": [{}] }));
+        assert!(!is_partition_complete(&offset_limit, &undefined));
+
+        let top = query_info(json!({"distinctType": "None", "top": 1}));
+        assert!(is_partition_complete(&top, &[json!({"a": 1})]));
+
+        let distinct = query_info(json!({"distinctType": "Unordered", "top": 1}));
+        assert!(!is_partition_complete(&distinct, &[json!({"a": 1})]));
+
+        let unbounded = query_info(json!({"distinctType": "None"}));
+        assert!(!is_partition_complete(&unbounded, &[json!({"a": 1})]));
+    }
+
+    #[test]
+    fn merge_aggregates_across_partitions() {
+        let partitions = |items: Vec<Value>| items.into_iter().map(|item| vec![item]).collect();
+
+        let count = query_info(json!({"distinctType": "None", "aggregates": ["Count"]}));
+        assert_eq!(
+            merge_results(
+                &count,
+                partitions(vec![json!([{"item": 3}]), json!([{"item": 0}])])
+            )
+            .unwrap(),
+            vec![json!(3)]
+        );
+
+        let average = query_info(json!({"distinctType": "None", "aggregates": ["Average"]}));
+        assert_eq!(
+            merge_results(
+                &average,
+                partitions(vec![
+                    json!([{"item": {"sum": 6, "count": 3}}]),
+                    json!([{"item": {"sum": 1.5, "count": 1}}]),
+                    json!([{}]),
+                ])
+            )
+            .unwrap(),
+            vec![json!(1.875)]
+        );
+
+        let max = query_info(json!({"distinctType": "None", "aggregates": ["Max"]}));
+        assert_eq!(
+            merge_results(
+                &max,
+                partitions(vec![json!([{"item": 3}]), json!([{"item": "a"}])])
+            )
+            .unwrap(),
+            vec![json!("a")]
+        );
+
+        let min = query_info(json!({"distinctType": "None", "aggregates": ["Min"]}));
+        assert!(
+            merge_results(&min, partitions(vec![json!([{}]), json!([{}])]))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn merge_aggregates_by_alias_across_partitions() {
+        let by_alias = query_info(json!({
+            "distinctType": "None",
+            "groupByAliases": ["n", "$1", "$2"],
+            "groupByAliasToAggregateType": {"n": "Count", "$1": "Max", "$2": "Min"},
+        }));
+        let partitions = vec![
+            vec![json!({"payload": {"n": {"item": 2}, "$1": {"item": 5}, "$2": {}}})],
+            vec![json!({"payload": {"n": {"item": 0}, "$1": {}, "$2": {}}})],
+            vec![json!({"payload": {"n": {"item": 3}, "$1": {"item": 8}, "$2": {}}})],
+        ];
+
+        assert_eq!(
+            merge_results(&by_alias, partitions).unwrap(),
+            vec![json!({"n": 5, "$1": 8})]
+        );
+
+        let mixed = query_info(json!({
+            "distinctType": "None",
+            "groupByAliases": ["n", "v"],
+            "groupByAliasToAggregateType": {"n": "Count", "v": null},
+        }));
+        assert!(merge_results(&mixed, vec![]).is_err());
+    }
+
+    #[test]
+    fn merge_distinct_with_offset_limit() {
+        let query_info = query_info(json!({
+            "distinctType": "Unordered",
+            "offset": 1,
+            "limit": 2,
+        }));
+        let partitions = vec![
+            vec![json!({"a": 1}), json!({"a": 2})],
+            vec![json!({"a": 1}), json!({"a": 3}), json!({"a": 4})],
+        ];
+
+        assert_eq!(
+            merge_results(&query_info, partitions).unwrap(),
+            vec![json!({"a": 2}), json!({"a": 3})]
+        );
+    }
+}
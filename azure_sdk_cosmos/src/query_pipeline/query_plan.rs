@@ -0,0 +1,111 @@
+use crate::PartitionKeyRange;
+use std::collections::HashMap;
+
+/// Execution plan of a query as returned by the gateway. It tells how the query must be
+/// rewritten before sending it to every partition key range and how to merge the partial
+/// results.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct QueryPlan {
+    #[serde(rename = "partitionedQueryExecutionInfoVersion")]
+    pub version: u32,
+    #[serde(rename = "queryInfo")]
+    pub query_info: QueryInfo,
+    #[serde(rename = "queryRanges")]
+    pub query_ranges: Vec<QueryRange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct QueryInfo {
+    #[serde(rename = "distinctType")]
+    pub distinct_type: DistinctType,
+    pub top: Option<u64>,
+    pub offset: Option<u64>,
+    pub limit: Option<u64>,
+    #[serde(rename = "orderBy", default)]
+    pub order_by: Vec<SortOrder>,
+    #[serde(rename = "orderByExpressions", default)]
+    pub order_by_expressions: Vec<String>,
+    #[serde(rename = "groupByExpressions", default)]
+    pub group_by_expressions: Vec<String>,
+    #[serde(default)]
+    pub aggregates: Vec<Aggregate>,
+    #[serde(rename = "groupByAliases", default)]
+    pub group_by_aliases: Vec<String>,
+    /// Aggregate computed by every alias of the projection, if any. Filled when the query
+    /// is not SELECT VALUE, for example `SELECT COUNT(1) AS n FROM c`.
+    #[serde(rename = "groupByAliasToAggregateType", default)]
+    pub group_by_alias_to_aggregate_type: HashMap<String, Option<Aggregate>>,
+    #[serde(rename = "rewrittenQuery", default)]
+    pub rewritten_query: String,
+    #[serde(rename = "hasSelectValue", default)]
+    pub has_select_value: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum DistinctType {
+    None,
+    Ordered,
+    Unordered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum Aggregate {
+    Average,
+    Count,
+    Max,
+    Min,
+    Sum,
+}
+
+/// Range of effective partition keys targeted by the query.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct QueryRange {
+    pub min: String,
+    pub max: String,
+    #[serde(rename = "isMinInclusive")]
+    pub is_min_inclusive: bool,
+    #[serde(rename = "isMaxInclusive")]
+    pub is_max_inclusive: bool,
+}
+
+impl QueryRange {
+    /// Effective partition keys are hex strings so they can be compared lexicographically.
+    /// Partition key ranges always include their minimum and exclude their maximum.
+    pub fn overlaps(&self, partition_key_range: &PartitionKeyRange) -> bool {
+        let range_min = partition_key_range.min_inclusive();
+        let range_max = partition_key_range.max_exclusive.as_str();
+
+        let starts_before_range_end = self.min.as_str() < range_max;
+        let ends_after_range_start = if self.is_max_inclusive {
+            self.max.as_str() >= range_min
+        } else {
+            self.max.as_str() > range_min
+        };
+
+        starts_before_range_end && ends_after_range_start
+    }
+}
+
+// The placeholder the gateway leaves in rewritten ORDER BY queries to resume them from
+// the last item seen. As the pipeline drains every partition key range it is always true.
+const ORDER_BY_FILTER_PLACEHOLDER: &str = "{documentdb-formattableorderbyquery-filter}";
+
+impl QueryPlan {
+    pub(crate) fn rewritten_query(&self) -> Option<String> {
+        if self.query_info.rewritten_query.is_empty() {
+            None
+        } else {
+            Some(
+                self.query_info
+                    .rewritten_query
+                    .replace(ORDER_BY_FILTER_PLACEHOLDER, "true"),
+            )
+        }
+    }
+}
@@ -1,9 +1,13 @@
 use crate::collection::CollectionName;
-use crate::collection::{Collection, IndexingPolicy, PartitionKey};
+use crate::collection::{
+    Collection, ConflictResolutionPolicy, IndexingPolicy, PartitionKey, UniqueKeyPolicy,
+};
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateCollectionResponse;
+use crate::TimeToLive;
 use crate::{Offer, ResourceType};
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use hyper::StatusCode;
@@ -34,6 +38,9 @@ pub struct CreateCollectionBuilder<
     collection_name: Option<&'a dyn CollectionName>,
     indexing_policy: Option<&'a IndexingPolicy>,
     partition_key: Option<&'a PartitionKey>,
+    conflict_resolution_policy: Option<&'a ConflictResolutionPolicy>,
+    default_ttl: Option<TimeToLive>,
+    unique_key_policy: Option<&'a UniqueKeyPolicy>,
     user_agent: Option<&'a str>,
     activity_id: Option<&'a str>,
     consistency_level: Option<ConsistencyLevel<'a>>,
@@ -57,6 +64,9 @@ where
             indexing_policy: None,
             p_partition_key: PhantomData {},
             partition_key: None,
+            conflict_resolution_policy: None,
+            default_ttl: None,
+            unique_key_policy: None,
             user_agent: None,
             activity_id: None,
             consistency_level: None,
@@ -146,6 +156,74 @@ where
     }
 }
 
+impl<'a, C, OfferSet, CollectionNameSet, IndexingPolicySet, PartitionKeySet>
+    ConflictResolutionPolicyOption<'a>
+    for CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >
+where
+    OfferSet: ToAssign,
+    CollectionNameSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    PartitionKeySet: ToAssign,
+    C: CosmosClient,
+{
+    #[inline]
+    fn conflict_resolution_policy(&self) -> Option<&'a ConflictResolutionPolicy> {
+        self.conflict_resolution_policy
+    }
+}
+
+impl<'a, C, OfferSet, CollectionNameSet, IndexingPolicySet, PartitionKeySet> DefaultTtlOption
+    for CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >
+where
+    OfferSet: ToAssign,
+    CollectionNameSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    PartitionKeySet: ToAssign,
+    C: CosmosClient,
+{
+    #[inline]
+    fn default_ttl(&self) -> Option<TimeToLive> {
+        self.default_ttl
+    }
+}
+
+impl<'a, C, OfferSet, CollectionNameSet, IndexingPolicySet, PartitionKeySet>
+    UniqueKeyPolicyOption<'a>
+    for CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >
+where
+    OfferSet: ToAssign,
+    CollectionNameSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    PartitionKeySet: ToAssign,
+    C: CosmosClient,
+{
+    #[inline]
+    fn unique_key_policy(&self) -> Option<&'a UniqueKeyPolicy> {
+        self.unique_key_policy
+    }
+}
+
 impl<'a, C, OfferSet, CollectionNameSet, IndexingPolicySet, PartitionKeySet> UserAgentOption<'a>
     for CreateCollectionBuilder<
         'a,
@@ -236,6 +314,9 @@ where
             collection_name: self.collection_name,
             indexing_policy: self.indexing_policy,
             partition_key: self.partition_key,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: self.user_agent,
             activity_id: self.activity_id,
             consistency_level: self.consistency_level,
@@ -265,6 +346,9 @@ where
             collection_name: Some(collection_name),
             indexing_policy: self.indexing_policy,
             partition_key: self.partition_key,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: self.user_agent,
             activity_id: self.activity_id,
             consistency_level: self.consistency_level,
@@ -294,6 +378,9 @@ where
             collection_name: self.collection_name,
             indexing_policy: Some(indexing_policy),
             partition_key: self.partition_key,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: self.user_agent,
             activity_id: self.activity_id,
             consistency_level: self.consistency_level,
@@ -323,6 +410,155 @@ where
             collection_name: self.collection_name,
             indexing_policy: self.indexing_policy,
             partition_key: Some(partition_key),
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, C, OfferSet, CollectionNameSet, IndexingPolicySet, PartitionKeySet>
+    ConflictResolutionPolicySupport<'a>
+    for CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >
+where
+    OfferSet: ToAssign,
+    CollectionNameSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    PartitionKeySet: ToAssign,
+    C: CosmosClient,
+{
+    type O = CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >;
+
+    #[inline]
+    fn with_conflict_resolution_policy(
+        self,
+        conflict_resolution_policy: &'a ConflictResolutionPolicy,
+    ) -> Self::O {
+        CreateCollectionBuilder {
+            database_client: self.database_client,
+            p_offer: PhantomData {},
+            p_collection_name: PhantomData {},
+            p_indexing_policy: PhantomData {},
+            p_partition_key: PhantomData {},
+            offer: self.offer,
+            collection_name: self.collection_name,
+            indexing_policy: self.indexing_policy,
+            partition_key: self.partition_key,
+            conflict_resolution_policy: Some(conflict_resolution_policy),
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, C, OfferSet, CollectionNameSet, IndexingPolicySet, PartitionKeySet> DefaultTtlSupport
+    for CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >
+where
+    OfferSet: ToAssign,
+    CollectionNameSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    PartitionKeySet: ToAssign,
+    C: CosmosClient,
+{
+    type O = CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >;
+
+    #[inline]
+    fn with_default_ttl(self, default_ttl: TimeToLive) -> Self::O {
+        CreateCollectionBuilder {
+            database_client: self.database_client,
+            p_offer: PhantomData {},
+            p_collection_name: PhantomData {},
+            p_indexing_policy: PhantomData {},
+            p_partition_key: PhantomData {},
+            offer: self.offer,
+            collection_name: self.collection_name,
+            indexing_policy: self.indexing_policy,
+            partition_key: self.partition_key,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: Some(default_ttl),
+            unique_key_policy: self.unique_key_policy,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, C, OfferSet, CollectionNameSet, IndexingPolicySet, PartitionKeySet>
+    UniqueKeyPolicySupport<'a>
+    for CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >
+where
+    OfferSet: ToAssign,
+    CollectionNameSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    PartitionKeySet: ToAssign,
+    C: CosmosClient,
+{
+    type O = CreateCollectionBuilder<
+        'a,
+        C,
+        OfferSet,
+        CollectionNameSet,
+        IndexingPolicySet,
+        PartitionKeySet,
+    >;
+
+    #[inline]
+    fn with_unique_key_policy(self, unique_key_policy: &'a UniqueKeyPolicy) -> Self::O {
+        CreateCollectionBuilder {
+            database_client: self.database_client,
+            p_offer: PhantomData {},
+            p_collection_name: PhantomData {},
+            p_indexing_policy: PhantomData {},
+            p_partition_key: PhantomData {},
+            offer: self.offer,
+            collection_name: self.collection_name,
+            indexing_policy: self.indexing_policy,
+            partition_key: self.partition_key,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: Some(unique_key_policy),
             user_agent: self.user_agent,
             activity_id: self.activity_id,
             consistency_level: self.consistency_level,
@@ -367,6 +603,9 @@ where
             collection_name: self.collection_name,
             indexing_policy: self.indexing_policy,
             partition_key: self.partition_key,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: Some(user_agent),
             activity_id: self.activity_id,
             consistency_level: self.consistency_level,
@@ -411,6 +650,9 @@ where
             collection_name: self.collection_name,
             indexing_policy: self.indexing_policy,
             partition_key: self.partition_key,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: self.user_agent,
             activity_id: Some(activity_id),
             consistency_level: self.consistency_level,
@@ -456,6 +698,9 @@ where
             collection_name: self.collection_name,
             indexing_policy: self.indexing_policy,
             partition_key: self.partition_key,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: self.user_agent,
             activity_id: self.activity_id,
             consistency_level: Some(consistency_level),
@@ -490,6 +735,9 @@ where
             self.indexing_policy().to_owned(),
         );
         collection.parition_key = self.partition_key().to_owned();
+        collection.conflict_resolution_policy = self.conflict_resolution_policy().cloned();
+        collection.default_ttl = self.default_ttl();
+        collection.unique_key_policy = self.unique_key_policy().cloned();
 
         let body = serde_json::to_string(&collection)?;
         debug!("body == {}", body);
@@ -497,12 +745,12 @@ where
         let req = req.body(hyper::Body::from(body))?;
         debug!("\nreq == {:?}", req);
 
-        let (headers, body) = check_status_extract_headers_and_body(
-            self.database_client.hyper_client().request(req),
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.database_client.cosmos_client().execute_request(req),
             StatusCode::CREATED,
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
@@ -1,7 +1,8 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateDatabaseResponse;
 use crate::ResourceType;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use hyper::StatusCode;
@@ -176,10 +177,11 @@ impl<'a> CreateDatabaseBuilder<'a, Yes> {
 
         debug!("create database request prepared == {:?}", request);
 
-        let future_response = self.cosmos_client().hyper_client().request(request);
-        let (headers, body) =
-            check_status_extract_headers_and_body(future_response, StatusCode::CREATED).await?;
+        let future_response = self.cosmos_client().execute_request(request);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::CREATED)
+                .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
@@ -1,7 +1,8 @@
+use crate::diagnostics::extract_status_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateDocumentResponse;
 use crate::ResourceType;
-use azure_sdk_core::errors::{extract_status_headers_and_body, AzureError, UnexpectedHTTPResult};
+use azure_sdk_core::errors::{AzureError, UnexpectedHTTPResult};
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use chrono::{DateTime, Utc};
@@ -473,9 +474,11 @@ where
         let serialized = serde_json::to_string(document)?;
         let req = req.body(hyper::Body::from(serialized))?;
 
-        let (status_code, headers, whole_body) =
-            extract_status_headers_and_body(self.collection_client.hyper_client().request(req))
-                .await?;
+        let (status_code, headers, whole_body, diagnostics) =
+            extract_status_headers_body_and_diagnostics(
+                self.collection_client.cosmos_client().execute_request(req),
+            )
+            .await?;
 
         debug!("status_core == {:?}", status_code);
         debug!("headers == {:?}", headers);
@@ -499,6 +502,6 @@ where
             .into());
         }
 
-        CreateDocumentResponse::try_from((status_code, &headers, &whole_body as &[u8]))
+        CreateDocumentResponse::try_from((status_code, &headers, &whole_body as &[u8], diagnostics))
     }
 }
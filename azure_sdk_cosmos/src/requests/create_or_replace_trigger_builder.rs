@@ -1,7 +1,8 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateTriggerResponse;
 use crate::trigger::*;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use hyper::StatusCode;
@@ -417,8 +418,10 @@ where
         let request = serde_json::to_string(&request)?;
         let request = req.body(hyper::Body::from(request))?;
 
-        let (headers, body) = check_status_extract_headers_and_body(
-            self.trigger_client().hyper_client().request(request),
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.trigger_client()
+                .cosmos_client()
+                .execute_request(request),
             if self.is_create() {
                 StatusCode::CREATED
             } else {
@@ -427,6 +430,6 @@ where
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
@@ -1,6 +1,7 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateUserDefinedFunctionResponse;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use hyper::StatusCode;
@@ -257,10 +258,10 @@ where
         let request = serde_json::to_string(&request)?;
         let request = req.body(hyper::Body::from(request))?;
 
-        let (headers, body) = check_status_extract_headers_and_body(
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
             self.user_defined_function_client()
-                .hyper_client()
-                .request(request),
+                .cosmos_client()
+                .execute_request(request),
             match self.is_create {
                 true => StatusCode::CREATED,
                 false => StatusCode::OK,
@@ -268,6 +269,6 @@ where
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
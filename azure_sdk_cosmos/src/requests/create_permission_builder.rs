@@ -1,8 +1,9 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreatePermissionResponse;
 use crate::ResourceType;
 use crate::{PermissionMode, PermissionResource};
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use hyper::StatusCode;
 use std::convert::TryInto;
@@ -211,6 +212,7 @@ where
             ResourceType::Permissions,
         );
 
+        let request = ExpirySecondsOption::add_header(self, request);
         let request = UserAgentOption::add_header(self, request);
         let request = ActivityIdOption::add_header(self, request);
         let request = ConsistencyLevelOption::add_header(self, request);
@@ -237,12 +239,14 @@ where
         let request = request.body(hyper::Body::from(request_body))?;
         debug!("\nrequest == {:#?}", request);
 
-        let (headers, body) = check_status_extract_headers_and_body(
-            self.permission_client.hyper_client().request(request),
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.permission_client
+                .cosmos_client()
+                .execute_request(request),
             StatusCode::CREATED,
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
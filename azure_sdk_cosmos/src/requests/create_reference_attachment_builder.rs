@@ -1,5 +1,6 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use hyper::StatusCode;
@@ -324,8 +325,8 @@ where
         let req = req.body(hyper::Body::from(request))?;
         debug!("req == {:#?}", req);
 
-        let (headers, whole_body) = check_status_extract_headers_and_body(
-            self.attachment_client.hyper_client().request(req),
+        let (headers, whole_body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.attachment_client.cosmos_client().execute_request(req),
             StatusCode::CREATED,
         )
         .await?;
@@ -333,6 +334,6 @@ where
         debug!("\nheaders == {:?}", headers);
         debug!("\nwhole body == {:#?}", whole_body);
 
-        Ok((&headers, &whole_body as &[u8]).try_into()?)
+        Ok((&headers, &whole_body as &[u8], diagnostics).try_into()?)
     }
 }
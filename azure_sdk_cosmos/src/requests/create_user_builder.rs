@@ -1,6 +1,7 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateUserResponse;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use hyper::StatusCode;
 use std::convert::TryInto;
@@ -163,12 +164,12 @@ where
         let req = req.body(hyper::Body::from(request_body))?;
         debug!("\nreq == {:?}", req);
 
-        let (headers, body) = check_status_extract_headers_and_body(
-            self.user_client.hyper_client().request(req),
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.user_client.cosmos_client().execute_request(req),
             StatusCode::CREATED,
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
@@ -1,5 +1,6 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use hyper::StatusCode;
 use std::convert::TryInto;
@@ -229,8 +230,8 @@ where
 
         debug!("req == {:#?}", req);
 
-        let (headers, whole_body) = check_status_extract_headers_and_body(
-            self.attachment_client.hyper_client().request(req),
+        let (headers, whole_body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.attachment_client.cosmos_client().execute_request(req),
             StatusCode::NO_CONTENT,
         )
         .await?;
@@ -238,6 +239,6 @@ where
         debug!("\nheaders == {:?}", headers);
         debug!("\nwhole body == {:#?}", whole_body);
 
-        Ok((&headers, &whole_body as &[u8]).try_into()?)
+        Ok((&headers, &whole_body as &[u8], diagnostics).try_into()?)
     }
 }
@@ -1,7 +1,8 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::DeleteDocumentResponse;
 use crate::DocumentClientRequired;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::modify_conditions::IfMatchCondition;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{IfMatchConditionOption, IfMatchConditionSupport};
@@ -294,12 +295,12 @@ where
         let req = req.body(hyper::Body::empty())?;
         debug!("{:?}", req);
 
-        let (headers, body) = check_status_extract_headers_and_body(
-            self.document_client.hyper_client().request(req),
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.document_client.cosmos_client().execute_request(req),
             StatusCode::NO_CONTENT,
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
@@ -1,6 +1,7 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::DeleteUserDefinedFunctionResponse;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use hyper::StatusCode;
 use std::convert::TryInto;
@@ -173,14 +174,14 @@ where
 
         let request = request.body(hyper::Body::empty())?;
 
-        let (headers, body) = check_status_extract_headers_and_body(
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
             self.user_defined_function_client()
-                .hyper_client()
-                .request(request),
+                .cosmos_client()
+                .execute_request(request),
             StatusCode::NO_CONTENT,
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
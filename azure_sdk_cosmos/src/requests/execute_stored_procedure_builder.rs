@@ -1,7 +1,8 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::ExecuteStoredProcedureResponse;
 use crate::stored_procedure::Parameters;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use hyper::StatusCode;
 use serde::de::DeserializeOwned;
@@ -301,14 +302,14 @@ where
 
         let request = request.body(hyper::Body::from(body))?;
 
-        let (headers, body) = check_status_extract_headers_and_body(
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
             self.stored_procedure_client()
-                .hyper_client()
-                .request(request),
+                .cosmos_client()
+                .execute_request(request),
             StatusCode::OK,
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
@@ -0,0 +1,176 @@
+use crate::prelude::*;
+use crate::responses::{GetAttachmentMediaResponse, MediaStream};
+use crate::Diagnostics;
+use azure_sdk_core::errors::{AzureError, UnexpectedHTTPResult};
+use azure_sdk_core::prelude::*;
+use futures::stream::TryStreamExt;
+use hyper::StatusCode;
+use std::convert::TryInto;
+
+#[derive(Debug, Clone)]
+pub struct GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    attachment_client: &'a dyn AttachmentClient<C, D, COLL, DOC>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+}
+
+impl<'a, 'b, C, D, COLL, DOC> GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    pub(crate) fn new(
+        attachment_client: &'a dyn AttachmentClient<C, D, COLL, DOC>,
+    ) -> GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC> {
+        GetAttachmentMediaBuilder {
+            attachment_client,
+            user_agent: None,
+            activity_id: None,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC> AttachmentClientRequired<'a, C, D, COLL, DOC>
+    for GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    fn attachment_client(&self) -> &'a dyn AttachmentClient<C, D, COLL, DOC> {
+        self.attachment_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b, C, D, COLL, DOC> UserAgentOption<'b>
+    for GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC> ActivityIdOption<'b>
+    for GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC> UserAgentSupport<'b>
+    for GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    type O = GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>;
+
+    #[inline]
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        GetAttachmentMediaBuilder {
+            attachment_client: self.attachment_client,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC> ActivityIdSupport<'b>
+    for GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    type O = GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>;
+
+    #[inline]
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        GetAttachmentMediaBuilder {
+            attachment_client: self.attachment_client,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b, C, D, COLL, DOC> GetAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    /// Downloads the content of the attachment as a stream of bytes. The attachment is
+    /// read first to find its media: the content of attachments referencing external
+    /// media must be downloaded from their own location.
+    pub async fn execute(&self) -> Result<GetAttachmentMediaResponse, AzureError> {
+        let attachment = self.attachment_client.get().execute().await?.attachment;
+
+        let mut req = self
+            .attachment_client
+            .prepare_media_request(&attachment.media, hyper::Method::GET)?;
+
+        // add trait headers
+        req = UserAgentOption::add_header(self, req);
+        req = ActivityIdOption::add_header(self, req);
+
+        let req = req.body(hyper::Body::empty())?;
+
+        debug!("req == {:#?}", req);
+
+        let response = self
+            .attachment_client
+            .cosmos_client()
+            .execute_request(req)
+            .await?;
+
+        let status = response.status();
+        if status != StatusCode::OK {
+            let body = hyper::body::to_bytes(response.into_body()).await?;
+            return Err(AzureError::UnexpectedHTTPResult(UnexpectedHTTPResult::new(
+                StatusCode::OK,
+                status,
+                std::str::from_utf8(&body)?,
+            )));
+        }
+
+        let diagnostics = Diagnostics::from_response(&response);
+        let (parts, body) = response.into_parts();
+        debug!("\nheaders == {:?}", parts.headers);
+
+        let media: MediaStream = Box::pin(body.map_err(AzureError::from));
+        (&parts.headers, media, diagnostics).try_into()
+    }
+}
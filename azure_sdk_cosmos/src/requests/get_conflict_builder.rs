@@ -0,0 +1,219 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::GetConflictResponse;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use hyper::StatusCode;
+use std::convert::TryInto;
+
+#[derive(Debug, Clone)]
+pub struct GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    conflict_client: &'a dyn ConflictClient<C, D, COLL>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+    consistency_level: Option<ConsistencyLevel<'b>>,
+    partition_keys: Option<&'b PartitionKeys>,
+}
+
+impl<'a, 'b, C, D, COLL> GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    #[inline]
+    pub(crate) fn new(
+        conflict_client: &'a dyn ConflictClient<C, D, COLL>,
+    ) -> GetConflictBuilder<'a, 'b, C, D, COLL> {
+        GetConflictBuilder {
+            conflict_client,
+            user_agent: None,
+            activity_id: None,
+            consistency_level: None,
+            partition_keys: None,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL> ConflictClientRequired<'a, C, D, COLL>
+    for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    #[inline]
+    fn conflict_client(&self) -> &'a dyn ConflictClient<C, D, COLL> {
+        self.conflict_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b, C, D, COLL> UserAgentOption<'b> for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    #[inline]
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, C, D, COLL> ActivityIdOption<'b> for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    #[inline]
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b, C, D, COLL> ConsistencyLevelOption<'b> for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    #[inline]
+    fn consistency_level(&self) -> Option<ConsistencyLevel<'b>> {
+        self.consistency_level.clone()
+    }
+}
+
+impl<'a, 'b, C, D, COLL> PartitionKeysOption<'b> for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    #[inline]
+    fn partition_keys(&self) -> Option<&'b PartitionKeys> {
+        self.partition_keys
+    }
+}
+
+impl<'a, 'b, C, D, COLL> UserAgentSupport<'b> for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    type O = GetConflictBuilder<'a, 'b, C, D, COLL>;
+
+    #[inline]
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        GetConflictBuilder {
+            conflict_client: self.conflict_client,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            partition_keys: self.partition_keys,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL> ActivityIdSupport<'b> for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    type O = GetConflictBuilder<'a, 'b, C, D, COLL>;
+
+    #[inline]
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        GetConflictBuilder {
+            conflict_client: self.conflict_client,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+            consistency_level: self.consistency_level,
+            partition_keys: self.partition_keys,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL> ConsistencyLevelSupport<'b> for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    type O = GetConflictBuilder<'a, 'b, C, D, COLL>;
+
+    #[inline]
+    fn with_consistency_level(self, consistency_level: ConsistencyLevel<'b>) -> Self::O {
+        GetConflictBuilder {
+            conflict_client: self.conflict_client,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: Some(consistency_level),
+            partition_keys: self.partition_keys,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL> PartitionKeysSupport<'b> for GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    type O = GetConflictBuilder<'a, 'b, C, D, COLL>;
+
+    #[inline]
+    fn with_partition_keys(self, partition_keys: &'b PartitionKeys) -> Self::O {
+        GetConflictBuilder {
+            conflict_client: self.conflict_client,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            partition_keys: Some(partition_keys),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b, C, D, COLL> GetConflictBuilder<'a, 'b, C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    pub async fn execute(&self) -> Result<GetConflictResponse, AzureError> {
+        trace!("GetConflictBuilder::execute called");
+
+        let request = self
+            .conflict_client
+            .prepare_request_with_conflict_name(hyper::Method::GET);
+
+        // add trait headers
+        let request = UserAgentOption::add_header(self, request);
+        let request = ActivityIdOption::add_header(self, request);
+        let request = ConsistencyLevelOption::add_header(self, request);
+        let request = PartitionKeysOption::add_header(self, request);
+
+        let request = request.body(hyper::Body::empty())?;
+
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.conflict_client()
+                .cosmos_client()
+                .execute_request(request),
+            StatusCode::OK,
+        )
+        .await?;
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+}
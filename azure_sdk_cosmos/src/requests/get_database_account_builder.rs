@@ -0,0 +1,95 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::GetDatabaseAccountResponse;
+use crate::ResourceType;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use hyper::StatusCode;
+use std::convert::TryInto;
+
+#[derive(Debug, Clone)]
+pub struct GetDatabaseAccountBuilder<'a> {
+    cosmos_client: &'a dyn CosmosClient,
+    user_agent: Option<&'a str>,
+    activity_id: Option<&'a str>,
+}
+
+impl<'a> GetDatabaseAccountBuilder<'a> {
+    pub(crate) fn new(cosmos_client: &'a dyn CosmosClient) -> GetDatabaseAccountBuilder<'a> {
+        GetDatabaseAccountBuilder {
+            cosmos_client,
+            user_agent: None,
+            activity_id: None,
+        }
+    }
+}
+
+impl<'a> CosmosClientRequired<'a> for GetDatabaseAccountBuilder<'a> {
+    fn cosmos_client(&self) -> &'a dyn CosmosClient {
+        self.cosmos_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a> UserAgentOption<'a> for GetDatabaseAccountBuilder<'a> {
+    fn user_agent(&self) -> Option<&'a str> {
+        self.user_agent
+    }
+}
+
+impl<'a> ActivityIdOption<'a> for GetDatabaseAccountBuilder<'a> {
+    fn activity_id(&self) -> Option<&'a str> {
+        self.activity_id
+    }
+}
+
+impl<'a> UserAgentSupport<'a> for GetDatabaseAccountBuilder<'a> {
+    type O = GetDatabaseAccountBuilder<'a>;
+
+    fn with_user_agent(self, user_agent: &'a str) -> Self::O {
+        GetDatabaseAccountBuilder {
+            cosmos_client: self.cosmos_client,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a> ActivityIdSupport<'a> for GetDatabaseAccountBuilder<'a> {
+    type O = GetDatabaseAccountBuilder<'a>;
+
+    fn with_activity_id(self, activity_id: &'a str) -> Self::O {
+        GetDatabaseAccountBuilder {
+            cosmos_client: self.cosmos_client,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a> GetDatabaseAccountBuilder<'a> {
+    pub async fn execute(&self) -> Result<GetDatabaseAccountResponse, AzureError> {
+        trace!("GetDatabaseAccountBuilder::execute called");
+
+        let request = self.cosmos_client.prepare_request(
+            "",
+            hyper::Method::GET,
+            ResourceType::DatabaseAccount,
+        );
+
+        let request = UserAgentOption::add_header(self, request);
+        let request = ActivityIdOption::add_header(self, request);
+
+        let request = request.body(hyper::Body::empty())?;
+
+        let future_response = self.cosmos_client.execute_request(request);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::OK)
+                .await?;
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+}
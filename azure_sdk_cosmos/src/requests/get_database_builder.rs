@@ -1,6 +1,7 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::GetDatabaseResponse;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use hyper::StatusCode;
 use std::convert::TryInto;
@@ -137,10 +138,14 @@ where
 
         trace!("request prepared == {:?}", request);
 
-        let future_response = self.database_client().hyper_client().request(request);
-        let (headers, body) =
-            check_status_extract_headers_and_body(future_response, StatusCode::OK).await?;
+        let future_response = self
+            .database_client()
+            .cosmos_client()
+            .execute_request(request);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::OK)
+                .await?;
 
-        Ok(((&headers, &body as &[u8])).try_into()?)
+        Ok(((&headers, &body as &[u8], diagnostics)).try_into()?)
     }
 }
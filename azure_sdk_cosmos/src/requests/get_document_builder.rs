@@ -1,7 +1,8 @@
+use crate::diagnostics::extract_status_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::GetDocumentResponse;
 use crate::DocumentClientRequired;
-use azure_sdk_core::errors::{extract_status_headers_and_body, AzureError, UnexpectedHTTPResult};
+use azure_sdk_core::errors::{AzureError, UnexpectedHTTPResult};
 use azure_sdk_core::modify_conditions::IfMatchCondition;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{IfMatchConditionOption, IfMatchConditionSupport};
@@ -253,9 +254,11 @@ where
 
         let req = req.body(hyper::Body::empty())?;
 
-        let (status_code, headers, whole_body) =
-            extract_status_headers_and_body(self.document_client.hyper_client().request(req))
-                .await?;
+        let (status_code, headers, whole_body, diagnostics) =
+            extract_status_headers_body_and_diagnostics(
+                self.document_client.cosmos_client().execute_request(req),
+            )
+            .await?;
 
         if status_code != StatusCode::OK
             && status_code != StatusCode::NOT_MODIFIED
@@ -276,7 +279,12 @@ where
         debug!("\nheaders == {:?}", headers);
         debug!("\nwhole body == {:#?}", whole_body);
 
-        let resp = GetDocumentResponse::try_from((status_code, &headers, &whole_body as &[u8]))?;
+        let resp = GetDocumentResponse::try_from((
+            status_code,
+            &headers,
+            &whole_body as &[u8],
+            diagnostics,
+        ))?;
         Ok(resp)
     }
 }
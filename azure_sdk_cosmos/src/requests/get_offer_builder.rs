@@ -0,0 +1,143 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::GetOfferResponse;
+use crate::ResourceType;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::{No, ToAssign, Yes};
+use hyper::StatusCode;
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct GetOfferBuilder<'a, 'b, OfferIdSet>
+where
+    OfferIdSet: ToAssign,
+{
+    cosmos_client: &'a dyn CosmosClient,
+    p_offer_id: PhantomData<OfferIdSet>,
+    offer_id: Option<&'b str>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+}
+
+impl<'a, 'b> GetOfferBuilder<'a, 'b, No> {
+    pub(crate) fn new(cosmos_client: &'a dyn CosmosClient) -> GetOfferBuilder<'a, 'b, No> {
+        GetOfferBuilder {
+            cosmos_client,
+            p_offer_id: PhantomData {},
+            offer_id: None,
+            user_agent: None,
+            activity_id: None,
+        }
+    }
+}
+
+impl<'a, 'b, OfferIdSet> CosmosClientRequired<'a> for GetOfferBuilder<'a, 'b, OfferIdSet>
+where
+    OfferIdSet: ToAssign,
+{
+    fn cosmos_client(&self) -> &'a dyn CosmosClient {
+        self.cosmos_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b> OfferIdRequired<'b> for GetOfferBuilder<'a, 'b, Yes> {
+    fn offer_id(&self) -> &'b str {
+        self.offer_id.unwrap()
+    }
+}
+
+impl<'a, 'b, OfferIdSet> UserAgentOption<'b> for GetOfferBuilder<'a, 'b, OfferIdSet>
+where
+    OfferIdSet: ToAssign,
+{
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, OfferIdSet> ActivityIdOption<'b> for GetOfferBuilder<'a, 'b, OfferIdSet>
+where
+    OfferIdSet: ToAssign,
+{
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b> OfferIdSupport<'b> for GetOfferBuilder<'a, 'b, No> {
+    type O = GetOfferBuilder<'a, 'b, Yes>;
+
+    fn with_offer_id(self, offer_id: &'b str) -> Self::O {
+        GetOfferBuilder {
+            cosmos_client: self.cosmos_client,
+            p_offer_id: PhantomData {},
+            offer_id: Some(offer_id),
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a, 'b, OfferIdSet> UserAgentSupport<'b> for GetOfferBuilder<'a, 'b, OfferIdSet>
+where
+    OfferIdSet: ToAssign,
+{
+    type O = GetOfferBuilder<'a, 'b, OfferIdSet>;
+
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        GetOfferBuilder {
+            cosmos_client: self.cosmos_client,
+            p_offer_id: PhantomData {},
+            offer_id: self.offer_id,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a, 'b, OfferIdSet> ActivityIdSupport<'b> for GetOfferBuilder<'a, 'b, OfferIdSet>
+where
+    OfferIdSet: ToAssign,
+{
+    type O = GetOfferBuilder<'a, 'b, OfferIdSet>;
+
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        GetOfferBuilder {
+            cosmos_client: self.cosmos_client,
+            p_offer_id: PhantomData {},
+            offer_id: self.offer_id,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b> GetOfferBuilder<'a, 'b, Yes> {
+    pub async fn execute(&self) -> Result<GetOfferResponse, AzureError> {
+        trace!("GetOfferBuilder::execute called");
+
+        let mut req = self.cosmos_client.prepare_request(
+            &format!("offers/{}", self.offer_id()),
+            hyper::Method::GET,
+            ResourceType::Offers,
+        );
+
+        req = UserAgentOption::add_header(self, req);
+        req = ActivityIdOption::add_header(self, req);
+
+        let req = req.body(hyper::Body::empty())?;
+
+        let future_response = self.cosmos_client.execute_request(req);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::OK)
+                .await?;
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+}
@@ -1,7 +1,8 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::GetPartitionKeyRangesResponse;
 use crate::ResourceType;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use chrono::{DateTime, Utc};
 use hyper::StatusCode;
@@ -238,10 +239,14 @@ where
 
         let request = request.body(hyper::Body::empty())?;
 
-        let future_response = self.collection_client().hyper_client().request(request);
-        let (headers, body) =
-            check_status_extract_headers_and_body(future_response, StatusCode::OK).await?;
+        let future_response = self
+            .collection_client()
+            .cosmos_client()
+            .execute_request(request);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::OK)
+                .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
@@ -1,6 +1,7 @@
+use crate::diagnostics::extract_status_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::GetPermissionResponse;
-use azure_sdk_core::errors::{extract_status_headers_and_body, AzureError, UnexpectedHTTPResult};
+use azure_sdk_core::errors::{AzureError, UnexpectedHTTPResult};
 use azure_sdk_core::prelude::*;
 use hyper::StatusCode;
 use std::convert::TryInto;
@@ -167,12 +168,15 @@ where
         let request = request.body(hyper::Body::empty())?;
         debug!("\nrequest == {:#?}", request);
 
-        let (status, headers, body) =
-            extract_status_headers_and_body(self.permission_client.hyper_client().request(request))
-                .await?;
+        let (status, headers, body, diagnostics) = extract_status_headers_body_and_diagnostics(
+            self.permission_client
+                .cosmos_client()
+                .execute_request(request),
+        )
+        .await?;
 
         match status {
-            StatusCode::OK => Ok(Some((&headers, &body as &[u8]).try_into()?)),
+            StatusCode::OK => Ok(Some((&headers, &body as &[u8], diagnostics).try_into()?)),
             StatusCode::NOT_FOUND => Ok(None),
             _ => Err(UnexpectedHTTPResult::new_multiple(
                 vec![StatusCode::OK, StatusCode::NOT_FOUND],
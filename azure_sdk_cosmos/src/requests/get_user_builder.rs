@@ -1,7 +1,8 @@
+use crate::diagnostics::extract_status_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateUserResponse;
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::errors::UnexpectedHTTPResult;
-use azure_sdk_core::errors::{extract_status_headers_and_body, AzureError};
 use azure_sdk_core::prelude::*;
 use hyper::StatusCode;
 use std::convert::TryInto;
@@ -155,12 +156,15 @@ where
         let req = req.body(hyper::Body::empty())?;
         debug!("\nreq == {:?}", req);
 
-        let (status_code, headers, body) =
-            extract_status_headers_and_body(self.user_client.hyper_client().request(req)).await?;
+        let (status_code, headers, body, diagnostics) =
+            extract_status_headers_body_and_diagnostics(
+                self.user_client.cosmos_client().execute_request(req),
+            )
+            .await?;
 
         match status_code {
             StatusCode::NOT_FOUND => Ok(None),
-            StatusCode::OK => Ok(Some((&headers, &body as &[u8]).try_into()?)),
+            StatusCode::OK => Ok(Some((&headers, &body as &[u8], diagnostics).try_into()?)),
             _ => Err(UnexpectedHTTPResult::new_multiple(
                 vec![StatusCode::OK, StatusCode::NOT_FOUND],
                 status_code,
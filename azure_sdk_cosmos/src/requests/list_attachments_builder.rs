@@ -1,8 +1,9 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::ListAttachmentsResponse;
 use crate::DocumentClientRequired;
 use crate::{DocumentClient, ResourceType};
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use futures::stream::{unfold, Stream};
 use hyper::StatusCode;
@@ -361,8 +362,8 @@ where
 
         let req = req.body(hyper::Body::empty())?;
 
-        let (headers, whole_body) = check_status_extract_headers_and_body(
-            self.document_client.hyper_client().request(req),
+        let (headers, whole_body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.document_client.cosmos_client().execute_request(req),
             StatusCode::OK,
         )
         .await?;
@@ -370,7 +371,7 @@ where
         debug!("\nheaders == {:?}", headers);
         debug!("\nwhole body == {:#?}", whole_body);
 
-        Ok((&headers, &whole_body as &[u8]).try_into()?)
+        Ok((&headers, &whole_body as &[u8], diagnostics).try_into()?)
     }
 
     pub fn stream(&self) -> impl Stream<Item = Result<ListAttachmentsResponse, AzureError>> + '_ {
@@ -1,7 +1,8 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::ListCollectionsResponse;
 use crate::{DatabaseClientRequired, ResourceType};
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use futures::stream::{unfold, Stream};
 use hyper::StatusCode;
@@ -222,10 +223,14 @@ where
 
         trace!("request prepared == {:?}", request);
 
-        let future_response = self.database_client.hyper_client().request(request);
-        let (headers, body) =
-            check_status_extract_headers_and_body(future_response, StatusCode::OK).await?;
-        Ok((&headers, &body as &[u8]).try_into()?)
+        let future_response = self
+            .database_client
+            .cosmos_client()
+            .execute_request(request);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::OK)
+                .await?;
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 
     pub fn stream(&self) -> impl Stream<Item = Result<ListCollectionsResponse, AzureError>> + '_ {
@@ -0,0 +1,351 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::ListConflictsResponse;
+use crate::ResourceType;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use futures::stream::{unfold, Stream};
+use hyper::StatusCode;
+use std::convert::TryInto;
+
+#[derive(Debug)]
+pub struct ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    collection_client: &'a dyn CollectionClient<C, D>,
+    if_match_condition: Option<IfMatchCondition<'b>>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+    consistency_level: Option<ConsistencyLevel<'b>>,
+    continuation: Option<&'b str>,
+    max_item_count: i32,
+}
+
+impl<'a, 'b, C, D> ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    pub(crate) fn new(
+        collection_client: &'a dyn CollectionClient<C, D>,
+    ) -> ListConflictsBuilder<'a, 'b, C, D> {
+        ListConflictsBuilder {
+            collection_client,
+            if_match_condition: None,
+            user_agent: None,
+            activity_id: None,
+            consistency_level: None,
+            continuation: None,
+            max_item_count: -1,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> Clone for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            collection_client: self.collection_client,
+            if_match_condition: self.if_match_condition,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level.clone(),
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> CollectionClientRequired<'a, C, D> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn collection_client(&self) -> &'a dyn CollectionClient<C, D> {
+        self.collection_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b, C, D> IfMatchConditionOption<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'b>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, 'b, C, D> UserAgentOption<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, C, D> ActivityIdOption<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b, C, D> ConsistencyLevelOption<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn consistency_level(&self) -> Option<ConsistencyLevel<'b>> {
+        self.consistency_level.clone()
+    }
+}
+
+impl<'a, 'b, C, D> ContinuationOption<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn continuation(&self) -> Option<&'b str> {
+        self.continuation
+    }
+}
+
+impl<'a, 'b, C, D> MaxItemCountOption for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn max_item_count(&self) -> i32 {
+        self.max_item_count
+    }
+}
+
+impl<'a, 'b, C, D> IfMatchConditionSupport<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ListConflictsBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'b>) -> Self::O {
+        ListConflictsBuilder {
+            collection_client: self.collection_client,
+            if_match_condition: Some(if_match_condition),
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> UserAgentSupport<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ListConflictsBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        ListConflictsBuilder {
+            collection_client: self.collection_client,
+            if_match_condition: self.if_match_condition,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> ActivityIdSupport<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ListConflictsBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        ListConflictsBuilder {
+            collection_client: self.collection_client,
+            if_match_condition: self.if_match_condition,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+            consistency_level: self.consistency_level,
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> ConsistencyLevelSupport<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ListConflictsBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_consistency_level(self, consistency_level: ConsistencyLevel<'b>) -> Self::O {
+        ListConflictsBuilder {
+            collection_client: self.collection_client,
+            if_match_condition: self.if_match_condition,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: Some(consistency_level),
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> ContinuationSupport<'b> for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ListConflictsBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_continuation(self, continuation: &'b str) -> Self::O {
+        ListConflictsBuilder {
+            collection_client: self.collection_client,
+            if_match_condition: self.if_match_condition,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            continuation: Some(continuation),
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> MaxItemCountSupport for ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ListConflictsBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_max_item_count(self, max_item_count: i32) -> Self::O {
+        ListConflictsBuilder {
+            collection_client: self.collection_client,
+            if_match_condition: self.if_match_condition,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            continuation: self.continuation,
+            max_item_count,
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b, C, D> ListConflictsBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    pub async fn execute(&self) -> Result<ListConflictsResponse, AzureError> {
+        trace!("ListConflictsBuilder::execute called");
+
+        let request = self.collection_client.cosmos_client().prepare_request(
+            &format!(
+                "dbs/{}/colls/{}/conflicts",
+                self.collection_client.database_client().database_name(),
+                self.collection_client.collection_name()
+            ),
+            hyper::Method::GET,
+            ResourceType::Conflicts,
+        );
+
+        // add trait headers
+        let request = IfMatchConditionOption::add_header(self, request);
+        let request = UserAgentOption::add_header(self, request);
+        let request = ActivityIdOption::add_header(self, request);
+        let request = ConsistencyLevelOption::add_header(self, request);
+        let request = ContinuationOption::add_header(self, request);
+        let request = MaxItemCountOption::add_header(self, request);
+
+        let request = request.body(hyper::Body::empty())?;
+
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.collection_client()
+                .cosmos_client()
+                .execute_request(request),
+            StatusCode::OK,
+        )
+        .await?;
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+
+    pub fn stream(&self) -> impl Stream<Item = Result<ListConflictsResponse, AzureError>> + '_ {
+        #[derive(Debug, Clone, PartialEq)]
+        enum States {
+            Init,
+            Continuation(String),
+        }
+
+        unfold(
+            Some(States::Init),
+            move |continuation_token: Option<States>| {
+                async move {
+                    debug!("continuation_token == {:?}", &continuation_token);
+                    let response = match continuation_token {
+                        Some(States::Init) => self.execute().await,
+                        Some(States::Continuation(continuation_token)) => {
+                            self.clone()
+                                .with_continuation(&continuation_token)
+                                .execute()
+                                .await
+                        }
+                        None => return None,
+                    };
+
+                    // the ? operator does not work in async move (yet?)
+                    // so we have to resort to this boilerplate
+                    let response = match response {
+                        Ok(response) => response,
+                        Err(err) => return Some((Err(err), None)),
+                    };
+
+                    let continuation_token = response
+                        .continuation_token
+                        .as_ref()
+                        .map(|ct| States::Continuation(ct.to_owned()));
+
+                    Some((Ok(response), continuation_token))
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,181 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::ListOffersResponse;
+use crate::ResourceType;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use futures::stream::{unfold, Stream};
+use hyper::StatusCode;
+use std::convert::TryInto;
+
+#[derive(Debug, Clone)]
+pub struct ListOffersBuilder<'a> {
+    cosmos_client: &'a dyn CosmosClient,
+    user_agent: Option<&'a str>,
+    activity_id: Option<&'a str>,
+    continuation: Option<&'a str>,
+    max_item_count: i32,
+}
+
+impl<'a> ListOffersBuilder<'a> {
+    pub(crate) fn new(cosmos_client: &'a dyn CosmosClient) -> ListOffersBuilder<'a> {
+        ListOffersBuilder {
+            cosmos_client,
+            user_agent: None,
+            activity_id: None,
+            continuation: None,
+            max_item_count: -1,
+        }
+    }
+}
+
+impl<'a> CosmosClientRequired<'a> for ListOffersBuilder<'a> {
+    fn cosmos_client(&self) -> &'a dyn CosmosClient {
+        self.cosmos_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a> UserAgentOption<'a> for ListOffersBuilder<'a> {
+    fn user_agent(&self) -> Option<&'a str> {
+        self.user_agent
+    }
+}
+
+impl<'a> ActivityIdOption<'a> for ListOffersBuilder<'a> {
+    fn activity_id(&self) -> Option<&'a str> {
+        self.activity_id
+    }
+}
+
+impl<'a> ContinuationOption<'a> for ListOffersBuilder<'a> {
+    fn continuation(&self) -> Option<&'a str> {
+        self.continuation
+    }
+}
+
+impl<'a> MaxItemCountOption for ListOffersBuilder<'a> {
+    fn max_item_count(&self) -> i32 {
+        self.max_item_count
+    }
+}
+
+impl<'a> UserAgentSupport<'a> for ListOffersBuilder<'a> {
+    type O = ListOffersBuilder<'a>;
+
+    fn with_user_agent(self, user_agent: &'a str) -> Self::O {
+        ListOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a> ActivityIdSupport<'a> for ListOffersBuilder<'a> {
+    type O = ListOffersBuilder<'a>;
+
+    fn with_activity_id(self, activity_id: &'a str) -> Self::O {
+        ListOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a> ContinuationSupport<'a> for ListOffersBuilder<'a> {
+    type O = ListOffersBuilder<'a>;
+
+    fn with_continuation(self, continuation: &'a str) -> Self::O {
+        ListOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            continuation: Some(continuation),
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a> MaxItemCountSupport for ListOffersBuilder<'a> {
+    type O = ListOffersBuilder<'a>;
+
+    fn with_max_item_count(self, max_item_count: i32) -> Self::O {
+        ListOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            continuation: self.continuation,
+            max_item_count,
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a> ListOffersBuilder<'a> {
+    pub async fn execute(&self) -> Result<ListOffersResponse, AzureError> {
+        trace!("ListOffersBuilder::execute called");
+
+        let mut req =
+            self.cosmos_client
+                .prepare_request("offers", hyper::Method::GET, ResourceType::Offers);
+
+        req = UserAgentOption::add_header(self, req);
+        req = ActivityIdOption::add_header(self, req);
+        req = ContinuationOption::add_header(self, req);
+        req = MaxItemCountOption::add_header(self, req);
+
+        let req = req.body(hyper::Body::empty())?;
+
+        let future_response = self.cosmos_client.execute_request(req);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::OK)
+                .await?;
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+
+    pub fn stream(&self) -> impl Stream<Item = Result<ListOffersResponse, AzureError>> + '_ {
+        #[derive(Debug, Clone, PartialEq)]
+        enum States {
+            Init,
+            Continuation(String),
+        }
+
+        unfold(
+            Some(States::Init),
+            move |continuation_token: Option<States>| async move {
+                debug!("continuation_token == {:?}", &continuation_token);
+                let response = match continuation_token {
+                    Some(States::Init) => self.execute().await,
+                    Some(States::Continuation(continuation_token)) => {
+                        self.clone()
+                            .with_continuation(&continuation_token)
+                            .execute()
+                            .await
+                    }
+                    None => return None,
+                };
+
+                let response = match response {
+                    Ok(response) => response,
+                    Err(err) => return Some((Err(err), None)),
+                };
+
+                let continuation_token = response
+                    .continuation_token
+                    .as_ref()
+                    .map(|ct| States::Continuation(ct.to_owned()));
+
+                Some((Ok(response), continuation_token))
+            },
+        )
+    }
+}
@@ -10,6 +10,7 @@ mod create_stored_procedure_builder;
 mod create_user_builder;
 mod delete_attachment_builder;
 mod delete_collection_builder;
+mod delete_conflict_builder;
 mod delete_database_builder;
 mod delete_document_builder;
 mod delete_permission_builder;
@@ -19,29 +20,41 @@ mod delete_user_builder;
 mod delete_user_defined_function_builder;
 mod execute_stored_procedure_builder;
 mod get_attachment_builder;
+mod get_attachment_media_builder;
 mod get_collection_builder;
+mod get_conflict_builder;
+mod get_database_account_builder;
 mod get_database_builder;
 mod get_document_builder;
+mod get_offer_builder;
 mod get_partition_key_ranges_builder;
 mod get_permission_builer;
 mod get_user_builder;
 mod list_attachments_builder;
 mod list_collections_builder;
+mod list_conflicts_builder;
 mod list_databases_builder;
 mod list_documents_builder;
+mod list_offers_builder;
 mod list_permissions_builder;
 mod list_stored_procedures_builder;
 mod list_triggers_builder;
 mod list_user_defined_functions_builder;
 mod list_users_builder;
+mod patch_document_builder;
 mod query_documents_builder;
+mod query_offers_builder;
+mod read_change_feed_builder;
+mod replace_attachment_media_builder;
 mod replace_collection_builder;
 mod replace_document_builder;
+mod replace_offer_builder;
 mod replace_permission_builder;
 mod replace_reference_attachment_builder;
 mod replace_slug_attachment_builder;
 mod replace_stored_procedure_builder;
 mod replace_user_builder;
+mod transactional_batch_builder;
 pub use self::create_collection_builder::CreateCollectionBuilder;
 pub use self::create_database_builder::CreateDatabaseBuilder;
 pub use self::create_document_builder::CreateDocumentBuilder;
@@ -54,6 +67,7 @@ pub use self::create_stored_procedure_builder::CreateStoredProcedureBuilder;
 pub use self::create_user_builder::CreateUserBuilder;
 pub use self::delete_attachment_builder::DeleteAttachmentBuilder;
 pub use self::delete_collection_builder::DeleteCollectionBuilder;
+pub use self::delete_conflict_builder::DeleteConflictBuilder;
 pub use self::delete_database_builder::DeleteDatabaseBuilder;
 pub use self::delete_document_builder::DeleteDocumentBuilder;
 pub use self::delete_permission_builder::DeletePermissionsBuilder;
@@ -63,26 +77,38 @@ pub use self::delete_user_builder::DeleteUserBuilder;
 pub use self::delete_user_defined_function_builder::DeleteUserDefinedFunctionBuilder;
 pub use self::execute_stored_procedure_builder::ExecuteStoredProcedureBuilder;
 pub use self::get_attachment_builder::GetAttachmentBuilder;
+pub use self::get_attachment_media_builder::GetAttachmentMediaBuilder;
 pub use self::get_collection_builder::GetCollectionBuilder;
+pub use self::get_conflict_builder::GetConflictBuilder;
+pub use self::get_database_account_builder::GetDatabaseAccountBuilder;
 pub use self::get_database_builder::GetDatabaseBuilder;
 pub use self::get_document_builder::GetDocumentBuilder;
+pub use self::get_offer_builder::GetOfferBuilder;
 pub use self::get_partition_key_ranges_builder::GetPartitionKeyRangesBuilder;
 pub use self::get_permission_builer::GetPermissionBuilder;
 pub use self::get_user_builder::GetUserBuilder;
 pub use self::list_attachments_builder::ListAttachmentsBuilder;
 pub use self::list_collections_builder::ListCollectionsBuilder;
+pub use self::list_conflicts_builder::ListConflictsBuilder;
 pub use self::list_databases_builder::ListDatabasesBuilder;
 pub use self::list_documents_builder::ListDocumentsBuilder;
+pub use self::list_offers_builder::ListOffersBuilder;
 pub use self::list_permissions_builder::ListPermissionsBuilder;
 pub use self::list_stored_procedures_builder::ListStoredProceduresBuilder;
 pub use self::list_triggers_builder::ListTriggersBuilder;
 pub use self::list_user_defined_functions_builder::ListUserDefinedFunctionsBuilder;
 pub use self::list_users_builder::ListUsersBuilder;
+pub use self::patch_document_builder::PatchDocumentBuilder;
 pub use self::query_documents_builder::QueryDocumentsBuilder;
+pub use self::query_offers_builder::QueryOffersBuilder;
+pub use self::read_change_feed_builder::ReadChangeFeedBuilder;
+pub use self::replace_attachment_media_builder::ReplaceAttachmentMediaBuilder;
 pub use self::replace_collection_builder::ReplaceCollectionBuilder;
 pub use self::replace_document_builder::ReplaceDocumentBuilder;
+pub use self::replace_offer_builder::ReplaceOfferBuilder;
 pub use self::replace_permission_builder::ReplacePermissionBuilder;
 pub use self::replace_reference_attachment_builder::ReplaceReferenceAttachmentBuilder;
 pub use self::replace_slug_attachment_builder::ReplaceSlugAttachmentBuilder;
 pub use self::replace_stored_procedure_builder::ReplaceStoredProcedureBuilder;
 pub use self::replace_user_builder::ReplaceUserBuilder;
+pub use self::transactional_batch_builder::TransactionalBatchBuilder;
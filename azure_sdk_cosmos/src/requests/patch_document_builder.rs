@@ -0,0 +1,522 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::PatchDocumentResponse;
+use crate::{PatchOperation, ResourceType};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::{No, ToAssign, Yes};
+use chrono::{DateTime, Utc};
+use hyper::StatusCode;
+use serde::Serialize;
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    collection_client: &'a dyn CollectionClient<C, D>,
+    p_partition_keys: PhantomData<PartitionKeysSet>,
+    p_document_id: PhantomData<DocumentIdSet>,
+    partition_keys: Option<&'b PartitionKeys>,
+    document_id: Option<&'b str>,
+    condition: Option<&'b str>,
+    if_match_condition: Option<IfMatchCondition<'b>>,
+    if_modified_since: Option<&'b DateTime<Utc>>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+    consistency_level: Option<ConsistencyLevel<'b>>,
+    allow_tentative_writes: bool,
+}
+
+impl<'a, 'b, C, D> PatchDocumentBuilder<'a, 'b, C, D, No, No>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    pub(crate) fn new(
+        collection_client: &'a dyn CollectionClient<C, D>,
+    ) -> PatchDocumentBuilder<'a, 'b, C, D, No, No> {
+        PatchDocumentBuilder {
+            collection_client,
+            p_partition_keys: PhantomData {},
+            partition_keys: None,
+            p_document_id: PhantomData {},
+            document_id: None,
+            condition: None,
+            if_match_condition: None,
+            if_modified_since: None,
+            user_agent: None,
+            activity_id: None,
+            consistency_level: None,
+            allow_tentative_writes: false,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> CollectionClientRequired<'a, C, D>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn collection_client(&self) -> &'a dyn CollectionClient<C, D> {
+        self.collection_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b, C, D, DocumentIdSet> PartitionKeysRequired<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, Yes, DocumentIdSet>
+where
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn partition_keys(&self) -> &'b PartitionKeys {
+        self.partition_keys.unwrap()
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet> DocumentIdRequired<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, Yes>
+where
+    PartitionKeysSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn document_id(&self) -> &'b str {
+        self.document_id.unwrap()
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> ConditionOption<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn condition(&self) -> Option<&'b str> {
+        self.condition
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> IfMatchConditionOption<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'b>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> IfModifiedSinceOption<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn if_modified_since(&self) -> Option<&'b DateTime<Utc>> {
+        self.if_modified_since
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> UserAgentOption<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> ActivityIdOption<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> ConsistencyLevelOption<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn consistency_level(&self) -> Option<ConsistencyLevel<'b>> {
+        self.consistency_level.clone()
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> AllowTentativeWritesOption
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn allow_tentative_writes(&self) -> bool {
+        self.allow_tentative_writes
+    }
+}
+
+impl<'a, 'b, C, D, DocumentIdSet> PartitionKeysSupport<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, No, DocumentIdSet>
+where
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, Yes, DocumentIdSet>;
+
+    #[inline]
+    fn with_partition_keys(self, partition_keys: &'b PartitionKeys) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: Some(partition_keys),
+            document_id: self.document_id,
+            condition: self.condition,
+            if_match_condition: self.if_match_condition,
+            if_modified_since: self.if_modified_since,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            allow_tentative_writes: self.allow_tentative_writes,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet> DocumentIdSupport<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, No>
+where
+    PartitionKeysSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, Yes>;
+
+    #[inline]
+    fn with_document_id(self, document_id: &'b str) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: self.partition_keys,
+            document_id: Some(document_id),
+            condition: self.condition,
+            if_match_condition: self.if_match_condition,
+            if_modified_since: self.if_modified_since,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            allow_tentative_writes: self.allow_tentative_writes,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> ConditionSupport<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>;
+
+    #[inline]
+    fn with_condition(self, condition: &'b str) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: self.partition_keys,
+            document_id: self.document_id,
+            condition: Some(condition),
+            if_match_condition: self.if_match_condition,
+            if_modified_since: self.if_modified_since,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            allow_tentative_writes: self.allow_tentative_writes,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> IfMatchConditionSupport<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'b>) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: self.partition_keys,
+            document_id: self.document_id,
+            condition: self.condition,
+            if_match_condition: Some(if_match_condition),
+            if_modified_since: self.if_modified_since,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            allow_tentative_writes: self.allow_tentative_writes,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> IfModifiedSinceSupport<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>;
+
+    #[inline]
+    fn with_if_modified_since(self, if_modified_since: &'b DateTime<Utc>) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: self.partition_keys,
+            document_id: self.document_id,
+            condition: self.condition,
+            if_match_condition: self.if_match_condition,
+            if_modified_since: Some(if_modified_since),
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            allow_tentative_writes: self.allow_tentative_writes,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> UserAgentSupport<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>;
+
+    #[inline]
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: self.partition_keys,
+            document_id: self.document_id,
+            condition: self.condition,
+            if_match_condition: self.if_match_condition,
+            if_modified_since: self.if_modified_since,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            allow_tentative_writes: self.allow_tentative_writes,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> ActivityIdSupport<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>;
+
+    #[inline]
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: self.partition_keys,
+            document_id: self.document_id,
+            condition: self.condition,
+            if_match_condition: self.if_match_condition,
+            if_modified_since: self.if_modified_since,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+            consistency_level: self.consistency_level,
+            allow_tentative_writes: self.allow_tentative_writes,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> ConsistencyLevelSupport<'b>
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>;
+
+    #[inline]
+    fn with_consistency_level(self, consistency_level: ConsistencyLevel<'b>) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: self.partition_keys,
+            document_id: self.document_id,
+            condition: self.condition,
+            if_match_condition: self.if_match_condition,
+            if_modified_since: self.if_modified_since,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: Some(consistency_level),
+            allow_tentative_writes: self.allow_tentative_writes,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet> AllowTentativeWritesSupport
+    for PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>
+where
+    PartitionKeysSet: ToAssign,
+    DocumentIdSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = PatchDocumentBuilder<'a, 'b, C, D, PartitionKeysSet, DocumentIdSet>;
+
+    #[inline]
+    fn with_allow_tentative_writes(self, allow_tentative_writes: bool) -> Self::O {
+        PatchDocumentBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_document_id: PhantomData {},
+            partition_keys: self.partition_keys,
+            document_id: self.document_id,
+            condition: self.condition,
+            if_match_condition: self.if_match_condition,
+            if_modified_since: self.if_modified_since,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            allow_tentative_writes,
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b, C, D> PatchDocumentBuilder<'a, 'b, C, D, Yes, Yes>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    /// Applies the operations to the document atomically. If a condition has been
+    /// specified and the document does not satisfy it the request fails with 412
+    /// (Precondition Failed) and the document is left untouched.
+    pub async fn execute_with_operations(
+        &self,
+        operations: &[PatchOperation],
+    ) -> Result<PatchDocumentResponse, AzureError> {
+        trace!("PatchDocumentBuilder::execute_with_operations() called");
+
+        let req = self.collection_client.cosmos_client().prepare_request(
+            &format!(
+                "dbs/{}/colls/{}/docs/{}",
+                self.collection_client.database_client().database_name(),
+                self.collection_client.collection_name(),
+                self.document_id()
+            ),
+            hyper::Method::PATCH,
+            ResourceType::Documents,
+        );
+
+        // add trait headers
+        let req = IfMatchConditionOption::add_header(self, req);
+        let req = IfModifiedSinceOption::add_header(self, req);
+        let req = UserAgentOption::add_header(self, req);
+        let req = ActivityIdOption::add_header(self, req);
+        let req = ConsistencyLevelOption::add_header(self, req);
+        let req = PartitionKeysRequired::add_header(self, req);
+        let req = AllowTentativeWritesOption::add_header(self, req);
+
+        let req = req.header(http::header::CONTENT_TYPE, "application/json_patch+json");
+
+        #[derive(Debug, Serialize)]
+        struct Request<'k> {
+            operations: &'k [PatchOperation],
+            #[serde(skip_serializing_if = "Option::is_none")]
+            condition: Option<&'k str>,
+        }
+
+        let serialized = serde_json::to_string(&Request {
+            operations,
+            condition: self.condition(),
+        })?;
+
+        let req = req.body(hyper::Body::from(serialized))?;
+        debug!("request == {:#?}", req);
+
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.collection_client.cosmos_client().execute_request(req),
+            StatusCode::OK,
+        )
+        .await?;
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+}
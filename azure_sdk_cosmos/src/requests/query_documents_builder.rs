@@ -1,10 +1,13 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
-use crate::responses::QueryDocumentsResponse;
-use crate::{Query, ResourceType};
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::query_pipeline::{is_partition_complete, merge_results, QueryInfo, QueryPlan};
+use crate::responses::{CrossPartitionQueryResponse, QueryDocumentsResponse};
+use crate::{Diagnostics, Query, ResourceType};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use chrono::{DateTime, Utc};
+use futures::future::try_join_all;
 use futures::stream::{unfold, Stream};
 use hyper::StatusCode;
 use serde::de::DeserializeOwned;
@@ -31,6 +34,7 @@ where
     partition_keys: Option<&'b PartitionKeys>,
     query_cross_partition: bool,
     parallelize_cross_partition_query: bool,
+    partition_range_id: Option<&'b str>,
 }
 
 impl<'a, 'b, C, D, QuerySet> Clone for QueryDocumentsBuilder<'a, 'b, C, D, QuerySet>
@@ -54,6 +58,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -81,6 +86,7 @@ where
             partition_keys: None,
             query_cross_partition: false,
             parallelize_cross_partition_query: false,
+            partition_range_id: None,
         }
     }
 }
@@ -239,6 +245,19 @@ where
     }
 }
 
+impl<'a, 'b, C, D, QuerySet> PartitionRangeIdOption<'b>
+    for QueryDocumentsBuilder<'a, 'b, C, D, QuerySet>
+where
+    QuerySet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn partition_range_id(&self) -> Option<&'b str> {
+        self.partition_range_id
+    }
+}
+
 impl<'a, 'b, C, D> QuerySupport<'b> for QueryDocumentsBuilder<'a, 'b, C, D, No>
 where
     C: CosmosClient,
@@ -262,6 +281,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -291,6 +311,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -320,6 +341,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -348,6 +370,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -376,6 +399,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -405,6 +429,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -434,6 +459,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -462,6 +488,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -491,6 +518,7 @@ where
             partition_keys: Some(partition_keys),
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -520,6 +548,7 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition,
             parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
         }
     }
 }
@@ -552,6 +581,37 @@ where
             partition_keys: self.partition_keys,
             query_cross_partition: self.query_cross_partition,
             parallelize_cross_partition_query,
+            partition_range_id: self.partition_range_id,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, QuerySet> PartitionRangeIdSupport<'b>
+    for QueryDocumentsBuilder<'a, 'b, C, D, QuerySet>
+where
+    QuerySet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = QueryDocumentsBuilder<'a, 'b, C, D, QuerySet>;
+
+    #[inline]
+    fn with_partition_range_id(self, partition_range_id: &'b str) -> Self::O {
+        QueryDocumentsBuilder {
+            collection_client: self.collection_client,
+            p_query: PhantomData {},
+            query: self.query,
+            if_match_condition: self.if_match_condition,
+            if_modified_since: self.if_modified_since,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+            partition_keys: self.partition_keys,
+            query_cross_partition: self.query_cross_partition,
+            parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+            partition_range_id: Some(partition_range_id),
         }
     }
 }
@@ -592,6 +652,7 @@ where
         let req = MaxItemCountOption::add_header(self, req);
         let req = PartitionKeysOption::add_header(self, req);
         let req = QueryCrossPartitionOption::add_header(self, req);
+        let req = PartitionRangeIdOption::add_header(self, req);
 
         let body = serde_json::to_string(self.query())?;
         debug!("body == {}", body);
@@ -599,8 +660,8 @@ where
         let req = req.body(hyper::Body::from(body))?;
         debug!("{:?}", req);
 
-        let (headers, body) = check_status_extract_headers_and_body(
-            self.collection_client.hyper_client().request(req),
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.collection_client.cosmos_client().execute_request(req),
             StatusCode::OK,
         )
         .await?;
@@ -608,7 +669,177 @@ where
         debug!("\nheaders == {:?}", headers);
         debug!("\nbody == {:#?}", body);
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
+    }
+
+    /// Asks the gateway how the query must be executed across the partitions.
+    pub async fn query_plan(&self) -> Result<QueryPlan, AzureError> {
+        trace!("QueryDocumentBuilder::query_plan called");
+
+        let req = self.collection_client.cosmos_client().prepare_request(
+            &format!(
+                "dbs/{}/colls/{}/docs",
+                self.collection_client.database_client().database_name(),
+                self.collection_client.collection_name()
+            ),
+            hyper::Method::POST,
+            ResourceType::Documents,
+        );
+
+        let req = req.header(crate::headers::HEADER_DOCUMENTDB_ISQUERY, true.to_string());
+        let req = req.header(http::header::CONTENT_TYPE, "application/query+json");
+        let req = req.header(
+            crate::headers::HEADER_COSMOS_IS_QUERY_PLAN_REQUEST,
+            true.to_string(),
+        );
+        let req = req.header(
+            crate::headers::HEADER_COSMOS_SUPPORTED_QUERY_FEATURES,
+            "Aggregate, Distinct, MultipleOrderBy, OffsetAndLimit, OrderBy, Top",
+        );
+        let req = req.header(crate::headers::HEADER_COSMOS_QUERY_VERSION, "1.4");
+        let req = req.header(
+            crate::headers::HEADER_DOCUMENTDB_QUERY_ENABLECROSSPARTITION,
+            true.to_string(),
+        );
+
+        let req = UserAgentOption::add_header(self, req);
+        let req = ActivityIdOption::add_header(self, req);
+        let req = PartitionKeysOption::add_header(self, req);
+
+        let body = serde_json::to_string(self.query())?;
+        debug!("body == {}", body);
+
+        let req = req.body(hyper::Body::from(body))?;
+
+        let (_headers, body, _diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.collection_client.cosmos_client().execute_request(req),
+            StatusCode::OK,
+        )
+        .await?;
+
+        debug!("query plan == {}", std::str::from_utf8(&body)?);
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Executes the query client side so that ORDER BY, TOP, OFFSET/LIMIT, DISTINCT and
+    /// aggregates give the correct results across partitions. The query plan is requested
+    /// first, then the rewritten query is sent to every partition key range it targets and
+    /// the partial results are merged. Every partition key range is read until it has
+    /// returned all the results the merge can use: with TOP or OFFSET/LIMIT, and without
+    /// DISTINCT or aggregates, no more than offset + limit of them, otherwise all of its
+    /// results. The results read are kept in memory until they are merged.
+    pub async fn execute_cross_partition<T>(
+        &self,
+    ) -> Result<CrossPartitionQueryResponse<T>, AzureError>
+    where
+        T: DeserializeOwned,
+    {
+        trace!("QueryDocumentBuilder::execute_cross_partition called");
+
+        let query_plan = self.query_plan().await?;
+        debug!("query_plan == {:?}", query_plan);
+
+        if !query_plan.query_info.group_by_expressions.is_empty() {
+            return Err(AzureError::GenericErrorWithText(
+                "GROUP BY queries are not supported by the query pipeline".to_owned(),
+            ));
+        }
+
+        let rewritten_query = query_plan.rewritten_query();
+        let query = Query::with_params(
+            rewritten_query
+                .as_deref()
+                .unwrap_or_else(|| self.query().query()),
+            self.query().params().to_vec(),
+        );
+
+        let partition_key_range_ids = self
+            .collection_client
+            .get_partition_key_ranges()
+            .execute()
+            .await?
+            .partition_key_ranges
+            .into_iter()
+            .filter(|partition_key_range| {
+                query_plan
+                    .query_ranges
+                    .iter()
+                    .any(|query_range| query_range.overlaps(partition_key_range))
+            })
+            .map(|partition_key_range| partition_key_range.id)
+            .collect::<Vec<_>>();
+
+        let partitions = try_join_all(partition_key_range_ids.iter().map(
+            |partition_key_range_id| {
+                self.read_partition(&query, partition_key_range_id, &query_plan.query_info)
+            },
+        ))
+        .await?;
+
+        let mut diagnostics = Vec::new();
+        let partitions = partitions
+            .into_iter()
+            .map(|(partition_diagnostics, results)| {
+                diagnostics.extend(partition_diagnostics);
+                results
+            })
+            .collect();
+
+        let results = merge_results(&query_plan.query_info, partitions)?
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<T>, _>>()?;
+
+        Ok(CrossPartitionQueryResponse {
+            results,
+            query_plan,
+            partition_key_range_ids,
+            diagnostics,
+        })
+    }
+
+    // Reads the query results of a single partition key range, page after page, until the
+    // last page or until the partition key range has returned all the results the merge
+    // can use.
+    async fn read_partition(
+        &self,
+        query: &Query<'_>,
+        partition_key_range_id: &str,
+        query_info: &QueryInfo,
+    ) -> Result<(Vec<Diagnostics>, Vec<serde_json::Value>), AzureError> {
+        let mut diagnostics = Vec::new();
+        let mut results = Vec::new();
+        let mut continuation: Option<String> = None;
+
+        loop {
+            let response = QueryDocumentsBuilder {
+                collection_client: self.collection_client,
+                p_query: PhantomData {},
+                query: Some(query),
+                if_match_condition: self.if_match_condition,
+                if_modified_since: self.if_modified_since,
+                user_agent: self.user_agent,
+                activity_id: self.activity_id,
+                consistency_level: self.consistency_level.clone(),
+                continuation: continuation.as_deref(),
+                max_item_count: self.max_item_count,
+                partition_keys: self.partition_keys,
+                query_cross_partition: true,
+                parallelize_cross_partition_query: self.parallelize_cross_partition_query,
+                partition_range_id: Some(partition_key_range_id),
+            }
+            .execute::<serde_json::Value>()
+            .await?;
+
+            continuation = response.continuation_token.clone();
+            diagnostics.push(response.diagnostics.clone());
+            results.extend(response.into_raw().results);
+
+            if continuation.is_none() || is_partition_complete(query_info, &results) {
+                return Ok((diagnostics, results));
+            }
+        }
     }
 
     pub fn stream<T>(
@@ -0,0 +1,254 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::QueryOffersResponse;
+use crate::{Query, ResourceType};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::{No, ToAssign, Yes};
+use futures::stream::{unfold, Stream};
+use hyper::StatusCode;
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    cosmos_client: &'a dyn CosmosClient,
+    p_query: PhantomData<QuerySet>,
+    query: Option<&'b Query<'b>>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+    continuation: Option<&'b str>,
+    max_item_count: i32,
+}
+
+impl<'a, 'b> QueryOffersBuilder<'a, 'b, No> {
+    pub(crate) fn new(cosmos_client: &'a dyn CosmosClient) -> QueryOffersBuilder<'a, 'b, No> {
+        QueryOffersBuilder {
+            cosmos_client,
+            p_query: PhantomData {},
+            query: None,
+            user_agent: None,
+            activity_id: None,
+            continuation: None,
+            max_item_count: -1,
+        }
+    }
+}
+
+impl<'a, 'b, QuerySet> CosmosClientRequired<'a> for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    fn cosmos_client(&self) -> &'a dyn CosmosClient {
+        self.cosmos_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b> QueryRequired<'b> for QueryOffersBuilder<'a, 'b, Yes> {
+    fn query(&self) -> &'b Query<'b> {
+        self.query.unwrap()
+    }
+}
+
+impl<'a, 'b, QuerySet> UserAgentOption<'b> for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, QuerySet> ActivityIdOption<'b> for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b, QuerySet> ContinuationOption<'b> for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    fn continuation(&self) -> Option<&'b str> {
+        self.continuation
+    }
+}
+
+impl<'a, 'b, QuerySet> MaxItemCountOption for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    fn max_item_count(&self) -> i32 {
+        self.max_item_count
+    }
+}
+
+impl<'a, 'b> QuerySupport<'b> for QueryOffersBuilder<'a, 'b, No> {
+    type O = QueryOffersBuilder<'a, 'b, Yes>;
+
+    fn with_query(self, query: &'b Query<'b>) -> Self::O {
+        QueryOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            p_query: PhantomData {},
+            query: Some(query),
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, QuerySet> UserAgentSupport<'b> for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    type O = QueryOffersBuilder<'a, 'b, QuerySet>;
+
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        QueryOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            p_query: PhantomData {},
+            query: self.query,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, QuerySet> ActivityIdSupport<'b> for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    type O = QueryOffersBuilder<'a, 'b, QuerySet>;
+
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        QueryOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            p_query: PhantomData {},
+            query: self.query,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+            continuation: self.continuation,
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, QuerySet> ContinuationSupport<'b> for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    type O = QueryOffersBuilder<'a, 'b, QuerySet>;
+
+    fn with_continuation(self, continuation: &'b str) -> Self::O {
+        QueryOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            p_query: PhantomData {},
+            query: self.query,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            continuation: Some(continuation),
+            max_item_count: self.max_item_count,
+        }
+    }
+}
+
+impl<'a, 'b, QuerySet> MaxItemCountSupport for QueryOffersBuilder<'a, 'b, QuerySet>
+where
+    QuerySet: ToAssign,
+{
+    type O = QueryOffersBuilder<'a, 'b, QuerySet>;
+
+    fn with_max_item_count(self, max_item_count: i32) -> Self::O {
+        QueryOffersBuilder {
+            cosmos_client: self.cosmos_client,
+            p_query: PhantomData {},
+            query: self.query,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            continuation: self.continuation,
+            max_item_count,
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b> QueryOffersBuilder<'a, 'b, Yes> {
+    pub async fn execute(&self) -> Result<QueryOffersResponse, AzureError> {
+        trace!("QueryOffersBuilder::execute called");
+
+        let mut req =
+            self.cosmos_client
+                .prepare_request("offers", hyper::Method::POST, ResourceType::Offers);
+
+        // signal that this is a query
+        req = req.header(crate::headers::HEADER_DOCUMENTDB_ISQUERY, true.to_string());
+        req = req.header(http::header::CONTENT_TYPE, "application/query+json");
+
+        req = UserAgentOption::add_header(self, req);
+        req = ActivityIdOption::add_header(self, req);
+        req = ContinuationOption::add_header(self, req);
+        req = MaxItemCountOption::add_header(self, req);
+
+        let body = serde_json::to_string(self.query())?;
+        debug!("body == {}", body);
+
+        let req = req.body(hyper::Body::from(body))?;
+
+        let future_response = self.cosmos_client.execute_request(req);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::OK)
+                .await?;
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+
+    pub fn stream(&self) -> impl Stream<Item = Result<QueryOffersResponse, AzureError>> + '_ {
+        #[derive(Debug, Clone, PartialEq)]
+        enum States {
+            Init,
+            Continuation(String),
+        }
+
+        unfold(
+            Some(States::Init),
+            move |continuation_token: Option<States>| async move {
+                debug!("continuation_token == {:?}", &continuation_token);
+                let response = match continuation_token {
+                    Some(States::Init) => self.execute().await,
+                    Some(States::Continuation(continuation_token)) => {
+                        self.clone()
+                            .with_continuation(&continuation_token)
+                            .execute()
+                            .await
+                    }
+                    None => return None,
+                };
+
+                let response = match response {
+                    Ok(response) => response,
+                    Err(err) => return Some((Err(err), None)),
+                };
+
+                let continuation_token = response
+                    .continuation_token
+                    .as_ref()
+                    .map(|ct| States::Continuation(ct.to_owned()));
+
+                Some((Ok(response), continuation_token))
+            },
+        )
+    }
+}
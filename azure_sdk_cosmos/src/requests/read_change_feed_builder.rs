@@ -0,0 +1,419 @@
+use crate::diagnostics::extract_status_headers_body_and_diagnostics;
+use crate::headers::*;
+use crate::prelude::*;
+use crate::responses::ReadChangeFeedResponse;
+use crate::ResourceType;
+use azure_sdk_core::errors::{AzureError, UnexpectedHTTPResult};
+use azure_sdk_core::prelude::*;
+use futures::stream::{unfold, Stream};
+use hyper::StatusCode;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone)]
+pub struct ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    collection_client: &'a dyn CollectionClient<C, D>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+    consistency_level: Option<ConsistencyLevel<'b>>,
+    max_item_count: i32,
+    partition_range_id: Option<&'b str>,
+    change_feed_continuation: Option<&'b ChangeFeedContinuation>,
+}
+
+impl<'a, 'b, C, D> ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    pub(crate) fn new(
+        collection_client: &'a dyn CollectionClient<C, D>,
+    ) -> ReadChangeFeedBuilder<'a, 'b, C, D> {
+        ReadChangeFeedBuilder {
+            collection_client,
+            user_agent: None,
+            activity_id: None,
+            consistency_level: None,
+            max_item_count: -1,
+            partition_range_id: None,
+            change_feed_continuation: None,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> CollectionClientRequired<'a, C, D> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn collection_client(&self) -> &'a dyn CollectionClient<C, D> {
+        self.collection_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b, C, D> UserAgentOption<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, C, D> ActivityIdOption<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b, C, D> ConsistencyLevelOption<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn consistency_level(&self) -> Option<ConsistencyLevel<'b>> {
+        self.consistency_level.clone()
+    }
+}
+
+impl<'a, 'b, C, D> MaxItemCountOption for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn max_item_count(&self) -> i32 {
+        self.max_item_count
+    }
+}
+
+impl<'a, 'b, C, D> PartitionRangeIdOption<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn partition_range_id(&self) -> Option<&'b str> {
+        self.partition_range_id
+    }
+}
+
+impl<'a, 'b, C, D> ChangeFeedContinuationOption<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn change_feed_continuation(&self) -> Option<&'b ChangeFeedContinuation> {
+        self.change_feed_continuation
+    }
+}
+
+impl<'a, 'b, C, D> UserAgentSupport<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReadChangeFeedBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        ReadChangeFeedBuilder {
+            collection_client: self.collection_client,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            max_item_count: self.max_item_count,
+            partition_range_id: self.partition_range_id,
+            change_feed_continuation: self.change_feed_continuation,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> ActivityIdSupport<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReadChangeFeedBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        ReadChangeFeedBuilder {
+            collection_client: self.collection_client,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+            consistency_level: self.consistency_level,
+            max_item_count: self.max_item_count,
+            partition_range_id: self.partition_range_id,
+            change_feed_continuation: self.change_feed_continuation,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> ConsistencyLevelSupport<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReadChangeFeedBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_consistency_level(self, consistency_level: ConsistencyLevel<'b>) -> Self::O {
+        ReadChangeFeedBuilder {
+            collection_client: self.collection_client,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: Some(consistency_level),
+            max_item_count: self.max_item_count,
+            partition_range_id: self.partition_range_id,
+            change_feed_continuation: self.change_feed_continuation,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> MaxItemCountSupport for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReadChangeFeedBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_max_item_count(self, max_item_count: i32) -> Self::O {
+        ReadChangeFeedBuilder {
+            collection_client: self.collection_client,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            max_item_count,
+            partition_range_id: self.partition_range_id,
+            change_feed_continuation: self.change_feed_continuation,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> PartitionRangeIdSupport<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReadChangeFeedBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_partition_range_id(self, partition_range_id: &'b str) -> Self::O {
+        ReadChangeFeedBuilder {
+            collection_client: self.collection_client,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            max_item_count: self.max_item_count,
+            partition_range_id: Some(partition_range_id),
+            change_feed_continuation: self.change_feed_continuation,
+        }
+    }
+}
+
+impl<'a, 'b, C, D> ChangeFeedContinuationSupport<'b> for ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReadChangeFeedBuilder<'a, 'b, C, D>;
+
+    #[inline]
+    fn with_change_feed_continuation(
+        self,
+        change_feed_continuation: &'b ChangeFeedContinuation,
+    ) -> Self::O {
+        ReadChangeFeedBuilder {
+            collection_client: self.collection_client,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+            max_item_count: self.max_item_count,
+            partition_range_id: self.partition_range_id,
+            change_feed_continuation: Some(change_feed_continuation),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b, C, D> ReadChangeFeedBuilder<'a, 'b, C, D>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    /// Reads a single page of the change feed. The partition key range must be specified
+    /// with `with_partition_range_id` for the returned continuation to be updated.
+    pub async fn execute<T>(&self) -> Result<ReadChangeFeedResponse<T>, AzureError>
+    where
+        T: DeserializeOwned,
+    {
+        let continuation = self.change_feed_continuation.cloned().unwrap_or_default();
+        self.execute_with_continuation(self.partition_range_id, continuation)
+            .await
+    }
+
+    /// Reads the changes of every partition key range (or just the one specified with
+    /// `with_partition_range_id`) starting from the checkpoint passed with
+    /// `with_change_feed_continuation`. Each item is a non empty batch of documents along
+    /// with the updated continuation: persist it once the batch has been processed.
+    /// The stream ends when every partition key range has been read to its current end;
+    /// start a new stream from the last continuation to get the subsequent changes.
+    pub fn stream<T>(
+        &self,
+    ) -> impl Stream<Item = Result<ReadChangeFeedResponse<T>, AzureError>> + '_
+    where
+        T: DeserializeOwned,
+    {
+        #[derive(Debug, Clone, PartialEq)]
+        enum States {
+            Init,
+            Draining(VecDeque<String>, ChangeFeedContinuation),
+        }
+
+        unfold(Some(States::Init), move |state: Option<States>| {
+            async move {
+                let (mut pending, mut continuation) = match state {
+                    Some(States::Init) => {
+                        let pending = match self.partition_range_id {
+                            Some(partition_range_id) => {
+                                vec![partition_range_id.to_owned()].into_iter().collect()
+                            }
+                            None => match self
+                                .collection_client
+                                .get_partition_key_ranges()
+                                .execute()
+                                .await
+                            {
+                                Ok(response) => response
+                                    .partition_key_ranges
+                                    .into_iter()
+                                    .map(|partition_key_range| partition_key_range.id)
+                                    .collect(),
+                                Err(err) => return Some((Err(err), None)),
+                            },
+                        };
+                        let continuation =
+                            self.change_feed_continuation.cloned().unwrap_or_default();
+                        (pending, continuation)
+                    }
+                    Some(States::Draining(pending, continuation)) => (pending, continuation),
+                    None => return None,
+                };
+
+                while let Some(partition_range_id) = pending.pop_front() {
+                    let response = match self
+                        .execute_with_continuation(Some(&partition_range_id), continuation)
+                        .await
+                    {
+                        Ok(response) => response,
+                        Err(err) => return Some((Err(err), None)),
+                    };
+
+                    continuation = response.continuation.clone();
+
+                    // an empty batch means the partition key range has been fully read
+                    if !response.documents.is_empty() {
+                        pending.push_front(partition_range_id);
+                        return Some((Ok(response), Some(States::Draining(pending, continuation))));
+                    }
+                }
+
+                None
+            }
+        })
+    }
+
+    async fn execute_with_continuation<T>(
+        &self,
+        partition_range_id: Option<&str>,
+        mut continuation: ChangeFeedContinuation,
+    ) -> Result<ReadChangeFeedResponse<T>, AzureError>
+    where
+        T: DeserializeOwned,
+    {
+        trace!(
+            "ReadChangeFeedBuilder::execute_with_continuation(partition_range_id == {:?}) called",
+            partition_range_id
+        );
+
+        let req = self.collection_client.cosmos_client().prepare_request(
+            &format!(
+                "dbs/{}/colls/{}/docs",
+                self.collection_client.database_client().database_name(),
+                self.collection_client.collection_name()
+            ),
+            hyper::Method::GET,
+            ResourceType::Documents,
+        );
+
+        // add trait headers
+        let req = UserAgentOption::add_header(self, req);
+        let req = ActivityIdOption::add_header(self, req);
+        let req = ConsistencyLevelOption::add_header(self, req);
+        let req = MaxItemCountOption::add_header(self, req);
+
+        let mut req = req.header(HEADER_A_IM, "Incremental feed");
+        if let Some(partition_range_id) = partition_range_id {
+            req = req.header(HEADER_DOCUMENTDB_PARTITIONRANGEID, partition_range_id);
+            if let Some(etag) = continuation.if_none_match(partition_range_id) {
+                req = req.header(hyper::header::IF_NONE_MATCH, etag);
+            }
+        }
+
+        let req = req.body(hyper::Body::empty())?;
+
+        let (status_code, headers, whole_body, diagnostics) =
+            extract_status_headers_body_and_diagnostics(
+                self.collection_client.cosmos_client().execute_request(req),
+            )
+            .await?;
+
+        if status_code != StatusCode::OK && status_code != StatusCode::NOT_MODIFIED {
+            return Err(UnexpectedHTTPResult::new_multiple(
+                vec![StatusCode::OK, StatusCode::NOT_MODIFIED],
+                status_code,
+                std::str::from_utf8(&whole_body)?,
+            )
+            .into());
+        }
+
+        debug!("\nheaders == {:?}", headers);
+        debug!("\nwhole body == {:#?}", whole_body);
+
+        let mut response = ReadChangeFeedResponse::try_from((
+            status_code,
+            &headers,
+            &whole_body as &[u8],
+            diagnostics,
+        ))?;
+
+        if let Some(partition_range_id) = partition_range_id {
+            continuation.set_etag(partition_range_id, response.etag.as_str());
+            response.partition_key_range_id = Some(partition_range_id.to_owned());
+        }
+        response.continuation = continuation;
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,289 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::ReplaceAttachmentMediaResponse;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::{No, ToAssign, Yes};
+use futures::io::{AsyncRead, AsyncReadExt};
+use futures::stream::{try_unfold, Stream};
+use hyper::StatusCode;
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>
+where
+    ContentTypeSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    attachment_client: &'a dyn AttachmentClient<C, D, COLL, DOC>,
+    p_content_type: PhantomData<ContentTypeSet>,
+    content_type: Option<&'b str>,
+    content_length: Option<u64>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+}
+
+impl<'a, 'b, C, D, COLL, DOC> ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, No>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    pub(crate) fn new(
+        attachment_client: &'a dyn AttachmentClient<C, D, COLL, DOC>,
+    ) -> ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, No> {
+        ReplaceAttachmentMediaBuilder {
+            attachment_client,
+            p_content_type: PhantomData {},
+            content_type: None,
+            content_length: None,
+            user_agent: None,
+            activity_id: None,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC, ContentTypeSet> AttachmentClientRequired<'a, C, D, COLL, DOC>
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>
+where
+    ContentTypeSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    fn attachment_client(&self) -> &'a dyn AttachmentClient<C, D, COLL, DOC> {
+        self.attachment_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b, C, D, COLL, DOC> ContentTypeRequired<'b>
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, Yes>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    fn content_type(&self) -> &'b str {
+        self.content_type.unwrap()
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC, ContentTypeSet> ContentLengthOption
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>
+where
+    ContentTypeSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC, ContentTypeSet> UserAgentOption<'b>
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>
+where
+    ContentTypeSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC, ContentTypeSet> ActivityIdOption<'b>
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>
+where
+    ContentTypeSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    #[inline]
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC> ContentTypeSupport<'b>
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, No>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    type O = ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, Yes>;
+
+    #[inline]
+    fn with_content_type(self, content_type: &'b str) -> Self::O {
+        ReplaceAttachmentMediaBuilder {
+            attachment_client: self.attachment_client,
+            p_content_type: PhantomData {},
+            content_type: Some(content_type),
+            content_length: self.content_length,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC, ContentTypeSet> ContentLengthSupport
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>
+where
+    ContentTypeSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    type O = ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>;
+
+    #[inline]
+    fn with_content_length(self, content_length: u64) -> Self::O {
+        ReplaceAttachmentMediaBuilder {
+            attachment_client: self.attachment_client,
+            p_content_type: PhantomData {},
+            content_type: self.content_type,
+            content_length: Some(content_length),
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC, ContentTypeSet> UserAgentSupport<'b>
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>
+where
+    ContentTypeSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    type O = ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>;
+
+    #[inline]
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        ReplaceAttachmentMediaBuilder {
+            attachment_client: self.attachment_client,
+            p_content_type: PhantomData {},
+            content_type: self.content_type,
+            content_length: self.content_length,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, COLL, DOC, ContentTypeSet> ActivityIdSupport<'b>
+    for ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>
+where
+    ContentTypeSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    type O = ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, ContentTypeSet>;
+
+    #[inline]
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        ReplaceAttachmentMediaBuilder {
+            attachment_client: self.attachment_client,
+            p_content_type: PhantomData {},
+            content_type: self.content_type,
+            content_length: self.content_length,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b, C, D, COLL, DOC> ReplaceAttachmentMediaBuilder<'a, 'b, C, D, COLL, DOC, Yes>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    DOC: DocumentClient<C, D, COLL>,
+{
+    /// Replaces the content of the attachment with the bytes read from `reader`, sent as
+    /// they are read. Without a content length the body is sent with chunked transfer
+    /// encoding. As a streamed body cannot be sent twice, the request is neither retried
+    /// when throttled nor sent to another region.
+    pub async fn execute_with_reader<R>(
+        &self,
+        reader: R,
+    ) -> Result<ReplaceAttachmentMediaResponse, AzureError>
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let attachment = self.attachment_client.get().execute().await?.attachment;
+
+        let mut req = self
+            .attachment_client
+            .prepare_media_request(&attachment.media, hyper::Method::PUT)?;
+
+        // add trait headers
+        req = UserAgentOption::add_header(self, req);
+        req = ActivityIdOption::add_header(self, req);
+        req = ContentTypeRequired::add_header(self, req);
+        req = ContentLengthOption::add_header(self, req);
+
+        req = req.header("Slug", self.attachment_client.attachment_name());
+
+        let req = req.body(hyper::Body::wrap_stream(read_chunks(reader)))?;
+
+        debug!("req == {:#?}", req);
+
+        let (headers, whole_body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.attachment_client.cosmos_client().execute_request(req),
+            StatusCode::OK,
+        )
+        .await?;
+
+        debug!("\nheaders == {:?}", headers);
+        debug!("\nwhole body == {:#?}", whole_body);
+
+        (&headers, &whole_body as &[u8], diagnostics).try_into()
+    }
+}
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+fn read_chunks<R>(reader: R) -> impl Stream<Item = Result<Vec<u8>, std::io::Error>>
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    try_unfold(reader, |mut reader| async move {
+        let mut chunk = vec![0; CHUNK_SIZE];
+        let read = reader.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        chunk.truncate(read);
+        Ok(Some((chunk, reader)))
+    })
+}
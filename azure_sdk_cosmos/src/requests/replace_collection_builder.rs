@@ -1,8 +1,10 @@
-use crate::collection::IndexingPolicy;
 use crate::collection::PartitionKey;
+use crate::collection::{ConflictResolutionPolicy, IndexingPolicy, UniqueKeyPolicy};
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateCollectionResponse;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use crate::TimeToLive;
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use hyper::StatusCode;
@@ -22,6 +24,9 @@ where
     p_indexing_policy: PhantomData<IndexingPolicySet>,
     partition_key: Option<&'a PartitionKey>,
     indexing_policy: Option<&'a IndexingPolicy>,
+    conflict_resolution_policy: Option<&'a ConflictResolutionPolicy>,
+    default_ttl: Option<TimeToLive>,
+    unique_key_policy: Option<&'a UniqueKeyPolicy>,
     user_agent: Option<&'b str>,
     activity_id: Option<&'b str>,
     consistency_level: Option<ConsistencyLevel<'b>>,
@@ -41,6 +46,9 @@ where
             partition_key: None,
             p_indexing_policy: PhantomData {},
             indexing_policy: None,
+            conflict_resolution_policy: None,
+            default_ttl: None,
+            unique_key_policy: None,
             user_agent: None,
             activity_id: None,
             consistency_level: None,
@@ -88,6 +96,45 @@ where
     }
 }
 
+impl<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet> ConflictResolutionPolicyOption<'a>
+    for ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>
+where
+    PartitionKeysSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    fn conflict_resolution_policy(&self) -> Option<&'a ConflictResolutionPolicy> {
+        self.conflict_resolution_policy
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet> DefaultTtlOption
+    for ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>
+where
+    PartitionKeysSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    fn default_ttl(&self) -> Option<TimeToLive> {
+        self.default_ttl
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet> UniqueKeyPolicyOption<'a>
+    for ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>
+where
+    PartitionKeysSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    fn unique_key_policy(&self) -> Option<&'a UniqueKeyPolicy> {
+        self.unique_key_policy
+    }
+}
+
 impl<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet> UserAgentOption<'b>
     for ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>
 where
@@ -143,6 +190,9 @@ where
             p_indexing_policy: PhantomData {},
             partition_key: Some(partition_key),
             indexing_policy: self.indexing_policy,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: self.user_agent,
             activity_id: self.activity_id,
             consistency_level: self.consistency_level,
@@ -166,6 +216,93 @@ where
             p_indexing_policy: PhantomData {},
             partition_key: self.partition_key,
             indexing_policy: Some(indexing_policy),
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet> ConflictResolutionPolicySupport<'a>
+    for ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>
+where
+    PartitionKeysSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>;
+
+    fn with_conflict_resolution_policy(
+        self,
+        conflict_resolution_policy: &'a ConflictResolutionPolicy,
+    ) -> Self::O {
+        ReplaceCollectionBuilder {
+            collection_client: self.collection_client,
+            p_partition_key: PhantomData {},
+            p_indexing_policy: PhantomData {},
+            partition_key: self.partition_key,
+            indexing_policy: self.indexing_policy,
+            conflict_resolution_policy: Some(conflict_resolution_policy),
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet> DefaultTtlSupport
+    for ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>
+where
+    PartitionKeysSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>;
+
+    fn with_default_ttl(self, default_ttl: TimeToLive) -> Self::O {
+        ReplaceCollectionBuilder {
+            collection_client: self.collection_client,
+            p_partition_key: PhantomData {},
+            p_indexing_policy: PhantomData {},
+            partition_key: self.partition_key,
+            indexing_policy: self.indexing_policy,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: Some(default_ttl),
+            unique_key_policy: self.unique_key_policy,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet> UniqueKeyPolicySupport<'a>
+    for ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>
+where
+    PartitionKeysSet: ToAssign,
+    IndexingPolicySet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = ReplaceCollectionBuilder<'a, 'b, C, D, PartitionKeysSet, IndexingPolicySet>;
+
+    fn with_unique_key_policy(self, unique_key_policy: &'a UniqueKeyPolicy) -> Self::O {
+        ReplaceCollectionBuilder {
+            collection_client: self.collection_client,
+            p_partition_key: PhantomData {},
+            p_indexing_policy: PhantomData {},
+            partition_key: self.partition_key,
+            indexing_policy: self.indexing_policy,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: Some(unique_key_policy),
             user_agent: self.user_agent,
             activity_id: self.activity_id,
             consistency_level: self.consistency_level,
@@ -190,6 +327,9 @@ where
             p_indexing_policy: PhantomData {},
             partition_key: self.partition_key,
             indexing_policy: self.indexing_policy,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: Some(user_agent),
             activity_id: self.activity_id,
             consistency_level: self.consistency_level,
@@ -214,6 +354,9 @@ where
             p_indexing_policy: PhantomData {},
             partition_key: self.partition_key,
             indexing_policy: self.indexing_policy,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: self.user_agent,
             activity_id: Some(activity_id),
             consistency_level: self.consistency_level,
@@ -238,6 +381,9 @@ where
             p_indexing_policy: PhantomData {},
             partition_key: self.partition_key,
             indexing_policy: self.indexing_policy,
+            conflict_resolution_policy: self.conflict_resolution_policy,
+            default_ttl: self.default_ttl,
+            unique_key_policy: self.unique_key_policy,
             user_agent: self.user_agent,
             activity_id: self.activity_id,
             consistency_level: Some(consistency_level),
@@ -271,12 +417,24 @@ where
             indexing_policy: &'k IndexingPolicy,
             #[serde(rename = "partitionKey")]
             partition_key: &'k crate::collection::PartitionKey,
+            #[serde(rename = "conflictResolutionPolicy")]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            conflict_resolution_policy: Option<&'k ConflictResolutionPolicy>,
+            #[serde(rename = "defaultTtl")]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            default_ttl: Option<TimeToLive>,
+            #[serde(rename = "uniqueKeyPolicy")]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            unique_key_policy: Option<&'k UniqueKeyPolicy>,
         };
 
         let request = Request {
             id: self.collection_client().collection_name(),
             indexing_policy: self.indexing_policy(),
             partition_key: self.partition_key(),
+            conflict_resolution_policy: self.conflict_resolution_policy(),
+            default_ttl: self.default_ttl(),
+            unique_key_policy: self.unique_key_policy(),
         };
 
         let body = serde_json::to_string(&request)?;
@@ -289,12 +447,12 @@ where
         // [https://docs.microsoft.com/en-us/rest/api/cosmos-db/replace-a-collection](https://docs.microsoft.com/en-us/rest/api/cosmos-db/replace-a-collection).
         // They say you should receive 201 instead azure returns 200 upon success. I've filed a PR
         // to correct it.
-        let (headers, body) = check_status_extract_headers_and_body(
-            self.collection_client.hyper_client().request(req),
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.collection_client.cosmos_client().execute_request(req),
             StatusCode::OK,
         )
         .await?;
 
-        Ok((&headers, &body as &[u8]).try_into()?)
+        Ok((&headers, &body as &[u8], diagnostics).try_into()?)
     }
 }
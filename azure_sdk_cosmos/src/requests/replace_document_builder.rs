@@ -1,7 +1,8 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::ReplaceDocumentResponse;
 use crate::ResourceType;
-use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use chrono::{DateTime, Utc};
@@ -499,12 +500,12 @@ where
         let req = req.body(hyper::Body::from(serialized))?;
         debug!("request == {:#?}", req);
 
-        let (headers, body) = check_status_extract_headers_and_body(
-            self.collection_client.hyper_client().request(req),
+        let (headers, body, diagnostics) = check_status_extract_headers_body_and_diagnostics(
+            self.collection_client.cosmos_client().execute_request(req),
             StatusCode::OK,
         )
         .await?;
 
-        (&headers, &body as &[u8]).try_into()
+        (&headers, &body as &[u8], diagnostics).try_into()
     }
 }
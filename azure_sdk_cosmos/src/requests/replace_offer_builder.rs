@@ -0,0 +1,151 @@
+use crate::diagnostics::check_status_extract_headers_body_and_diagnostics;
+use crate::prelude::*;
+use crate::responses::ReplaceOfferResponse;
+use crate::{ResourceType, ThroughputOffer};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::{No, ToAssign, Yes};
+use hyper::StatusCode;
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct ReplaceOfferBuilder<'a, 'b, ThroughputOfferSet>
+where
+    ThroughputOfferSet: ToAssign,
+{
+    cosmos_client: &'a dyn CosmosClient,
+    p_throughput_offer: PhantomData<ThroughputOfferSet>,
+    throughput_offer: Option<&'b ThroughputOffer>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+}
+
+impl<'a, 'b> ReplaceOfferBuilder<'a, 'b, No> {
+    pub(crate) fn new(cosmos_client: &'a dyn CosmosClient) -> ReplaceOfferBuilder<'a, 'b, No> {
+        ReplaceOfferBuilder {
+            cosmos_client,
+            p_throughput_offer: PhantomData {},
+            throughput_offer: None,
+            user_agent: None,
+            activity_id: None,
+        }
+    }
+}
+
+impl<'a, 'b, ThroughputOfferSet> CosmosClientRequired<'a>
+    for ReplaceOfferBuilder<'a, 'b, ThroughputOfferSet>
+where
+    ThroughputOfferSet: ToAssign,
+{
+    fn cosmos_client(&self) -> &'a dyn CosmosClient {
+        self.cosmos_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b> ThroughputOfferRequired<'b> for ReplaceOfferBuilder<'a, 'b, Yes> {
+    fn throughput_offer(&self) -> &'b ThroughputOffer {
+        self.throughput_offer.unwrap()
+    }
+}
+
+impl<'a, 'b, ThroughputOfferSet> UserAgentOption<'b>
+    for ReplaceOfferBuilder<'a, 'b, ThroughputOfferSet>
+where
+    ThroughputOfferSet: ToAssign,
+{
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, ThroughputOfferSet> ActivityIdOption<'b>
+    for ReplaceOfferBuilder<'a, 'b, ThroughputOfferSet>
+where
+    ThroughputOfferSet: ToAssign,
+{
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b> ThroughputOfferSupport<'b> for ReplaceOfferBuilder<'a, 'b, No> {
+    type O = ReplaceOfferBuilder<'a, 'b, Yes>;
+
+    fn with_throughput_offer(self, throughput_offer: &'b ThroughputOffer) -> Self::O {
+        ReplaceOfferBuilder {
+            cosmos_client: self.cosmos_client,
+            p_throughput_offer: PhantomData {},
+            throughput_offer: Some(throughput_offer),
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a, 'b, ThroughputOfferSet> UserAgentSupport<'b>
+    for ReplaceOfferBuilder<'a, 'b, ThroughputOfferSet>
+where
+    ThroughputOfferSet: ToAssign,
+{
+    type O = ReplaceOfferBuilder<'a, 'b, ThroughputOfferSet>;
+
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        ReplaceOfferBuilder {
+            cosmos_client: self.cosmos_client,
+            p_throughput_offer: PhantomData {},
+            throughput_offer: self.throughput_offer,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+        }
+    }
+}
+
+impl<'a, 'b, ThroughputOfferSet> ActivityIdSupport<'b>
+    for ReplaceOfferBuilder<'a, 'b, ThroughputOfferSet>
+where
+    ThroughputOfferSet: ToAssign,
+{
+    type O = ReplaceOfferBuilder<'a, 'b, ThroughputOfferSet>;
+
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        ReplaceOfferBuilder {
+            cosmos_client: self.cosmos_client,
+            p_throughput_offer: PhantomData {},
+            throughput_offer: self.throughput_offer,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b> ReplaceOfferBuilder<'a, 'b, Yes> {
+    pub async fn execute(&self) -> Result<ReplaceOfferResponse, AzureError> {
+        trace!("ReplaceOfferBuilder::execute called");
+
+        let mut req = self.cosmos_client.prepare_request(
+            &format!("offers/{}", self.throughput_offer().rid),
+            hyper::Method::PUT,
+            ResourceType::Offers,
+        );
+
+        req = UserAgentOption::add_header(self, req);
+        req = ActivityIdOption::add_header(self, req);
+
+        let body = serde_json::to_string(self.throughput_offer())?;
+        debug!("body == {}", body);
+
+        let req = req.body(hyper::Body::from(body))?;
+
+        let future_response = self.cosmos_client.execute_request(req);
+        let (headers, body, diagnostics) =
+            check_status_extract_headers_body_and_diagnostics(future_response, StatusCode::OK)
+                .await?;
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+}
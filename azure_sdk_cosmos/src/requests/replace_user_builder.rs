@@ -1,6 +1,7 @@
+use crate::diagnostics::extract_status_headers_body_and_diagnostics;
 use crate::prelude::*;
 use crate::responses::CreateUserResponse;
-use azure_sdk_core::errors::{extract_status_headers_and_body, AzureError, UnexpectedHTTPResult};
+use azure_sdk_core::errors::{AzureError, UnexpectedHTTPResult};
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::{No, ToAssign, Yes};
 use hyper::StatusCode;
@@ -223,12 +224,15 @@ where
         let req = req.body(hyper::Body::from(request_body))?;
         debug!("\nreq == {:?}", req);
 
-        let (status_code, headers, body) =
-            extract_status_headers_and_body(self.user_client.hyper_client().request(req)).await?;
+        let (status_code, headers, body, diagnostics) =
+            extract_status_headers_body_and_diagnostics(
+                self.user_client.cosmos_client().execute_request(req),
+            )
+            .await?;
 
         match status_code {
             StatusCode::NOT_FOUND => Ok(None),
-            StatusCode::OK => Ok(Some((&headers, &body as &[u8]).try_into()?)),
+            StatusCode::OK => Ok(Some((&headers, &body as &[u8], diagnostics).try_into()?)),
             _ => Err(UnexpectedHTTPResult::new_multiple(
                 vec![StatusCode::OK, StatusCode::NOT_FOUND],
                 status_code,
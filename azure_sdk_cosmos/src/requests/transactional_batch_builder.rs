@@ -0,0 +1,328 @@
+use crate::diagnostics::extract_status_headers_body_and_diagnostics;
+use crate::headers::*;
+use crate::prelude::*;
+use crate::responses::TransactionalBatchResponse;
+use crate::{ResourceType, TRANSACTIONAL_BATCH_MAX_OPERATIONS};
+use azure_sdk_core::errors::{AzureError, UnexpectedHTTPResult};
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::{No, ToAssign, Yes};
+use hyper::StatusCode;
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>
+where
+    PartitionKeysSet: ToAssign,
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    collection_client: &'a dyn CollectionClient<C, D>,
+    p_partition_keys: PhantomData<PartitionKeysSet>,
+    p_transactional_batch: PhantomData<TransactionalBatchSet>,
+    partition_keys: Option<&'b PartitionKeys>,
+    transactional_batch: Option<&'b TransactionalBatch>,
+    user_agent: Option<&'b str>,
+    activity_id: Option<&'b str>,
+    consistency_level: Option<ConsistencyLevel<'b>>,
+}
+
+impl<'a, 'b, C, D> TransactionalBatchBuilder<'a, 'b, C, D, No, No>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    pub(crate) fn new(
+        collection_client: &'a dyn CollectionClient<C, D>,
+    ) -> TransactionalBatchBuilder<'a, 'b, C, D, No, No> {
+        TransactionalBatchBuilder {
+            collection_client,
+            p_partition_keys: PhantomData {},
+            partition_keys: None,
+            p_transactional_batch: PhantomData {},
+            transactional_batch: None,
+            user_agent: None,
+            activity_id: None,
+            consistency_level: None,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet> CollectionClientRequired<'a, C, D>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>
+where
+    PartitionKeysSet: ToAssign,
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn collection_client(&self) -> &'a dyn CollectionClient<C, D> {
+        self.collection_client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, 'b, C, D, TransactionalBatchSet> PartitionKeysRequired<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, Yes, TransactionalBatchSet>
+where
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn partition_keys(&self) -> &'b PartitionKeys {
+        self.partition_keys.unwrap()
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet> TransactionalBatchRequired<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, Yes>
+where
+    PartitionKeysSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn transactional_batch(&self) -> &'b TransactionalBatch {
+        self.transactional_batch.unwrap()
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet> UserAgentOption<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>
+where
+    PartitionKeysSet: ToAssign,
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn user_agent(&self) -> Option<&'b str> {
+        self.user_agent
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet> ActivityIdOption<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>
+where
+    PartitionKeysSet: ToAssign,
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn activity_id(&self) -> Option<&'b str> {
+        self.activity_id
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet> ConsistencyLevelOption<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>
+where
+    PartitionKeysSet: ToAssign,
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    #[inline]
+    fn consistency_level(&self) -> Option<ConsistencyLevel<'b>> {
+        self.consistency_level.clone()
+    }
+}
+
+impl<'a, 'b, C, D, TransactionalBatchSet> PartitionKeysSupport<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, No, TransactionalBatchSet>
+where
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = TransactionalBatchBuilder<'a, 'b, C, D, Yes, TransactionalBatchSet>;
+
+    #[inline]
+    fn with_partition_keys(self, partition_keys: &'b PartitionKeys) -> Self::O {
+        TransactionalBatchBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_transactional_batch: PhantomData {},
+            partition_keys: Some(partition_keys),
+            transactional_batch: self.transactional_batch,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet> TransactionalBatchSupport<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, No>
+where
+    PartitionKeysSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, Yes>;
+
+    #[inline]
+    fn with_transactional_batch(self, transactional_batch: &'b TransactionalBatch) -> Self::O {
+        TransactionalBatchBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_transactional_batch: PhantomData {},
+            partition_keys: self.partition_keys,
+            transactional_batch: Some(transactional_batch),
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet> UserAgentSupport<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>
+where
+    PartitionKeysSet: ToAssign,
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>;
+
+    #[inline]
+    fn with_user_agent(self, user_agent: &'b str) -> Self::O {
+        TransactionalBatchBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_transactional_batch: PhantomData {},
+            partition_keys: self.partition_keys,
+            transactional_batch: self.transactional_batch,
+            user_agent: Some(user_agent),
+            activity_id: self.activity_id,
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet> ActivityIdSupport<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>
+where
+    PartitionKeysSet: ToAssign,
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>;
+
+    #[inline]
+    fn with_activity_id(self, activity_id: &'b str) -> Self::O {
+        TransactionalBatchBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_transactional_batch: PhantomData {},
+            partition_keys: self.partition_keys,
+            transactional_batch: self.transactional_batch,
+            user_agent: self.user_agent,
+            activity_id: Some(activity_id),
+            consistency_level: self.consistency_level,
+        }
+    }
+}
+
+impl<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet> ConsistencyLevelSupport<'b>
+    for TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>
+where
+    PartitionKeysSet: ToAssign,
+    TransactionalBatchSet: ToAssign,
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    type O = TransactionalBatchBuilder<'a, 'b, C, D, PartitionKeysSet, TransactionalBatchSet>;
+
+    #[inline]
+    fn with_consistency_level(self, consistency_level: ConsistencyLevel<'b>) -> Self::O {
+        TransactionalBatchBuilder {
+            collection_client: self.collection_client,
+            p_partition_keys: PhantomData {},
+            p_transactional_batch: PhantomData {},
+            partition_keys: self.partition_keys,
+            transactional_batch: self.transactional_batch,
+            user_agent: self.user_agent,
+            activity_id: self.activity_id,
+            consistency_level: Some(consistency_level),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, 'b, C, D> TransactionalBatchBuilder<'a, 'b, C, D, Yes, Yes>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    /// Executes the batch. The request fails only if the batch itself is invalid: the
+    /// outcome of each operation is in the `results` of the response. If any operation
+    /// fails none is applied and `is_successful` returns false.
+    pub async fn execute(&self) -> Result<TransactionalBatchResponse, AzureError> {
+        trace!("TransactionalBatchBuilder::execute called");
+
+        if self.transactional_batch().is_empty()
+            || self.transactional_batch().len() > TRANSACTIONAL_BATCH_MAX_OPERATIONS
+        {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "a transactional batch must have between 1 and {} operations, {} specified",
+                TRANSACTIONAL_BATCH_MAX_OPERATIONS,
+                self.transactional_batch().len()
+            )));
+        }
+
+        let req = self.collection_client.cosmos_client().prepare_request(
+            &format!(
+                "dbs/{}/colls/{}/docs",
+                self.collection_client.database_client().database_name(),
+                self.collection_client.collection_name()
+            ),
+            hyper::Method::POST,
+            ResourceType::Documents,
+        );
+
+        // add trait headers
+        let req = UserAgentOption::add_header(self, req);
+        let req = ActivityIdOption::add_header(self, req);
+        let req = ConsistencyLevelOption::add_header(self, req);
+        let req = PartitionKeysRequired::add_header(self, req);
+
+        let req = req
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(HEADER_COSMOS_IS_BATCH_REQUEST, "True")
+            .header(HEADER_COSMOS_BATCH_ATOMIC, "True")
+            .header(HEADER_COSMOS_BATCH_ORDERED, "True");
+
+        let body = serde_json::to_string(self.transactional_batch())?;
+        debug!("body == {}", body);
+        let req = req.body(hyper::Body::from(body))?;
+
+        let (status_code, headers, body, diagnostics) =
+            extract_status_headers_body_and_diagnostics(
+                self.collection_client.cosmos_client().execute_request(req),
+            )
+            .await?;
+
+        // 207 (Multi-Status) means the batch has been rolled back because of a failed
+        // operation. The body tells which one.
+        if status_code != StatusCode::OK && status_code != StatusCode::MULTI_STATUS {
+            return Err(UnexpectedHTTPResult::new_multiple(
+                vec![StatusCode::OK, StatusCode::MULTI_STATUS],
+                status_code,
+                std::str::from_utf8(&body)?,
+            )
+            .into());
+        }
+
+        (&headers, &body as &[u8], diagnostics).try_into()
+    }
+}
@@ -0,0 +1,431 @@
+use crate::clients::{
+    resource_token_authorization, CosmosStruct, CosmosUriBuilder, DatabaseStruct, PermissionStruct,
+    UserStruct,
+};
+use crate::diagnostics::execute_with_diagnostics;
+use crate::prelude::*;
+use crate::retry_policy::{copy_request, is_replayable};
+use crate::{AuthorizationToken, PermissionMode};
+use azure_sdk_core::errors::AzureError;
+use futures::future::BoxFuture;
+use hyper::header::{self, HeaderValue};
+use hyper::{Body, Request, Response, StatusCode};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Access to a resource granted to a user through the permission named
+/// `permission_name`, for example `PermissionMode::Read("dbs/db/colls/coll".to_owned())`.
+/// The tokens of the permission are valid for `expiry` (at most 5 hours).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceGrant {
+    pub permission_name: String,
+    pub permission_mode: PermissionMode<String>,
+    pub expiry: Duration,
+}
+
+impl ResourceGrant {
+    pub fn new<S>(
+        permission_name: S,
+        permission_mode: PermissionMode<String>,
+        expiry: Duration,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            permission_name: permission_name.into(),
+            permission_mode,
+            expiry,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedToken {
+    permission_mode: PermissionMode<String>,
+    auth_token: AuthorizationToken,
+    expires_at: Instant,
+}
+
+/// Hands out resource tokens scoped to the permissions of the users of a database, for
+/// example to give clients that cannot hold the master key access to their own documents.
+///
+/// The broker needs a client authorized with the master key. The users and permissions
+/// are created (or updated, if the grant changed) on demand and the tokens are cached
+/// until `refresh_margin` (5 minutes by default) before they expire. Clones share the
+/// cache.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), azure_sdk_core::errors::AzureError> {
+/// use azure_sdk_cosmos::prelude::*;
+/// use azure_sdk_cosmos::{PermissionMode, ResourceGrant, ResourceTokenBroker};
+/// use std::time::Duration;
+///
+/// let master_token = AuthorizationToken::new_master("bWFzdGVyIGtleQ==").unwrap();
+/// let client = ClientBuilder::new("account", master_token)?;
+/// let broker = ResourceTokenBroker::new(client.clone(), "db");
+///
+/// let grant = ResourceGrant::new(
+///     "read-sessions",
+///     PermissionMode::Read("dbs/db/colls/sessions".to_owned()),
+///     Duration::from_secs(3600),
+/// );
+/// // a token to hand out...
+/// let auth_token = broker.auth_token("alice", &grant).await?;
+/// // ...or a client that refreshes it when it expires
+/// let alice_client = broker.client_for(&client, "alice", &grant).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResourceTokenBroker<C>
+where
+    C: CosmosClient + Clone,
+{
+    cosmos_client: C,
+    database_name: String,
+    refresh_margin: Duration,
+    // (user name, permission name) -> token
+    tokens: Arc<Mutex<HashMap<(String, String), CachedToken>>>,
+}
+
+impl<C> ResourceTokenBroker<C>
+where
+    C: CosmosClient + Clone,
+{
+    pub fn new<S>(cosmos_client: C, database_name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            cosmos_client,
+            database_name: database_name.into(),
+            refresh_margin: Duration::from_secs(5 * 60),
+            tokens: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// How long before their expiry the cached tokens are replaced.
+    pub fn with_refresh_margin(self, refresh_margin: Duration) -> Self {
+        Self {
+            refresh_margin,
+            ..self
+        }
+    }
+
+    /// The resource token of the grant, from the cache if it is not about to expire.
+    pub async fn auth_token(
+        &self,
+        user_name: &str,
+        grant: &ResourceGrant,
+    ) -> Result<AuthorizationToken, AzureError> {
+        if let Some(auth_token) = self.cached_token(user_name, grant, None) {
+            return Ok(auth_token);
+        }
+        self.refresh_token(user_name, grant).await
+    }
+
+    /// The resource tokens of the grants, in the same order.
+    pub async fn auth_tokens(
+        &self,
+        user_name: &str,
+        grants: &[ResourceGrant],
+    ) -> Result<Vec<AuthorizationToken>, AzureError> {
+        let mut auth_tokens = Vec::with_capacity(grants.len());
+        for grant in grants {
+            auth_tokens.push(self.auth_token(user_name, grant).await?);
+        }
+        Ok(auth_tokens)
+    }
+
+    /// Removes the cached tokens of the user, for example after revoking its permissions.
+    pub fn invalidate(&self, user_name: &str) {
+        self.tokens
+            .lock()
+            .unwrap()
+            .retain(|(cached_user_name, _), _| cached_user_name != user_name);
+    }
+
+    /// Refreshes the token of the grant when the server rejects it. See
+    /// `CosmosStruct::with_resource_token_refresher`.
+    pub fn refresher(&self, user_name: &str, grant: &ResourceGrant) -> ResourceTokenRefresher
+    where
+        C: 'static,
+    {
+        let broker = self.clone();
+        let user_name = user_name.to_owned();
+        let grant = grant.clone();
+        ResourceTokenRefresher::new(move |rejected_token: AuthorizationToken| {
+            let broker = broker.clone();
+            let user_name = user_name.clone();
+            let grant = grant.clone();
+            Box::pin(async move {
+                // another request may have already replaced the rejected token
+                match broker.cached_token(&user_name, &grant, Some(&rejected_token)) {
+                    Some(auth_token) => Ok(auth_token),
+                    None => broker.refresh_token(&user_name, &grant).await,
+                }
+            })
+        })
+    }
+
+    /// A copy of `cosmos_client` authorized with the token of the grant, refreshed when
+    /// the server rejects it.
+    pub async fn client_for<'b, CUB>(
+        &self,
+        cosmos_client: &CosmosStruct<'b, CUB>,
+        user_name: &str,
+        grant: &ResourceGrant,
+    ) -> Result<CosmosStruct<'b, CUB>, AzureError>
+    where
+        C: 'static,
+        CUB: CosmosUriBuilder + Clone,
+    {
+        let auth_token = self.auth_token(user_name, grant).await?;
+        Ok(cosmos_client
+            .with_auth_token(auth_token)
+            .with_resource_token_refresher(self.refresher(user_name, grant)))
+    }
+
+    fn cached_token(
+        &self,
+        user_name: &str,
+        grant: &ResourceGrant,
+        rejected_token: Option<&AuthorizationToken>,
+    ) -> Option<AuthorizationToken> {
+        let tokens = self.tokens.lock().unwrap();
+        tokens
+            .get(&(user_name.to_owned(), grant.permission_name.clone()))
+            .filter(|cached| cached.permission_mode == grant.permission_mode)
+            .filter(|cached| Some(&cached.auth_token) != rejected_token)
+            .filter(|cached| Instant::now() + self.refresh_margin < cached.expires_at)
+            .map(|cached| cached.auth_token.clone())
+    }
+
+    // Replaces the permission to get a new token, creating the user and the permission if
+    // they do not exist yet.
+    async fn refresh_token(
+        &self,
+        user_name: &str,
+        grant: &ResourceGrant,
+    ) -> Result<AuthorizationToken, AzureError> {
+        let database_client = DatabaseStruct::new(
+            Cow::Borrowed(&self.cosmos_client),
+            Cow::Borrowed(self.database_name.as_str()),
+        );
+        let user_client =
+            UserStruct::new(Cow::Borrowed(&database_client), Cow::Borrowed(user_name));
+        let permission_client = PermissionStruct::new(
+            Cow::Borrowed(&user_client),
+            Cow::Borrowed(grant.permission_name.as_str()),
+        );
+        let expiry_seconds = grant.expiry.as_secs();
+
+        let requested_at = Instant::now();
+        let permission = match permission_client
+            .replace_permission()
+            .with_expiry_seconds(expiry_seconds)
+            .execute_with_permission(&grant.permission_mode)
+            .await
+        {
+            Ok(response) => response.permission,
+            Err(error) if is_not_found(&error) => {
+                match permission_client
+                    .create_permission()
+                    .with_expiry_seconds(expiry_seconds)
+                    .execute_with_permission(&grant.permission_mode)
+                    .await
+                {
+                    Ok(response) => response.permission,
+                    Err(error) if is_not_found(&error) => {
+                        debug!("creating user {} to grant {:?}", user_name, grant);
+                        match user_client.create_user().execute().await {
+                            Ok(_) => {}
+                            Err(AzureError::UnexpectedHTTPResult(error))
+                                if error.status_code() == StatusCode::CONFLICT => {}
+                            Err(error) => return Err(error),
+                        }
+                        permission_client
+                            .create_permission()
+                            .with_expiry_seconds(expiry_seconds)
+                            .execute_with_permission(&grant.permission_mode)
+                            .await?
+                            .permission
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+            Err(error) => return Err(error),
+        };
+
+        let auth_token: AuthorizationToken = permission.permission_token.into();
+        self.tokens.lock().unwrap().insert(
+            (user_name.to_owned(), grant.permission_name.clone()),
+            CachedToken {
+                permission_mode: grant.permission_mode.clone(),
+                auth_token: auth_token.clone(),
+                expires_at: requested_at + grant.expiry,
+            },
+        );
+        Ok(auth_token)
+    }
+}
+
+fn is_not_found(error: &AzureError) -> bool {
+    match error {
+        AzureError::UnexpectedHTTPResult(error) => error.status_code() == StatusCode::NOT_FOUND,
+        _ => false,
+    }
+}
+
+type RefreshFn = dyn Fn(AuthorizationToken) -> BoxFuture<'static, Result<AuthorizationToken, AzureError>>
+    + Send
+    + Sync;
+
+/// Gives a client a new resource token when the server rejects the current one (401 -
+/// Unauthorized), usually because it expired. The request is then sent again with the
+/// new token, which is used by the following requests too. Clones share the token.
+/// Obtained with `ResourceTokenBroker::refresher` and set with
+/// `CosmosStruct::with_resource_token_refresher`.
+#[derive(Clone)]
+pub struct ResourceTokenRefresher {
+    refresh: Arc<RefreshFn>,
+    auth_token: Arc<RwLock<Option<AuthorizationToken>>>,
+}
+
+impl ResourceTokenRefresher {
+    /// `refresh` is called with the rejected token and returns the new one.
+    pub fn new<F>(refresh: F) -> Self
+    where
+        F: Fn(AuthorizationToken) -> BoxFuture<'static, Result<AuthorizationToken, AzureError>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            refresh: Arc::new(refresh),
+            auth_token: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    // A refresher calling the same function that does not share the token obtained so
+    // far, for a client given a token of its own.
+    pub(crate) fn without_auth_token(&self) -> Self {
+        Self {
+            refresh: self.refresh.clone(),
+            auth_token: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// The last token obtained, `None` until the first refresh.
+    pub(crate) fn auth_token(&self) -> Option<AuthorizationToken> {
+        self.auth_token.read().unwrap().clone()
+    }
+
+    async fn refresh(&self, rejected_token: AuthorizationToken) -> Option<AuthorizationToken> {
+        match (self.refresh)(rejected_token).await {
+            Ok(auth_token) => {
+                *self.auth_token.write().unwrap() = Some(auth_token.clone());
+                Some(auth_token)
+            }
+            Err(error) => {
+                warn!("cannot refresh the resource token: {}", error);
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ResourceTokenRefresher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ResourceTokenRefresher")
+    }
+}
+
+// Sends the request again with a new resource token if the server rejects the one of the
+// request. If the token cannot be refreshed the 401 response is returned.
+pub(crate) async fn execute_with_token_refresh<C>(
+    cosmos_client: &C,
+    request: Request<Body>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    C: CosmosClient + ?Sized,
+{
+    let refresher = match cosmos_client.resource_token_refresher() {
+        Some(refresher) => refresher,
+        None => return execute_with_diagnostics(cosmos_client, request).await,
+    };
+
+    let rejected_token = match request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|authorization| authorization.to_str().ok())
+        .and_then(resource_token_from_authorization)
+    {
+        Some(rejected_token) => rejected_token,
+        None => return execute_with_diagnostics(cosmos_client, request).await,
+    };
+    // a streamed body can be sent only once: the token is not refreshed
+    if !is_replayable(request.body()) {
+        return execute_with_diagnostics(cosmos_client, request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    // the body must be buffered as it has to be sent again with the new token
+    let body = hyper::body::to_bytes(body).await?;
+    let request = copy_request(&parts, &body);
+
+    let response = execute_with_diagnostics(cosmos_client, request).await?;
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+
+    debug!("resource token rejected, refreshing it");
+    let authorization = match refresher.refresh(rejected_token).await {
+        Some(AuthorizationToken::Resource(signature)) => resource_token_authorization(&signature),
+        _ => return Ok(response),
+    };
+    let mut request = copy_request(&parts, &body);
+    request.headers_mut().insert(
+        header::AUTHORIZATION,
+        HeaderValue::from_str(&authorization).unwrap(),
+    );
+    execute_with_diagnostics(cosmos_client, request).await
+}
+
+fn resource_token_from_authorization(authorization: &str) -> Option<AuthorizationToken> {
+    // the header is form urlencoded as a whole
+    let (authorization, _) = url::form_urlencoded::parse(authorization.as_bytes()).next()?;
+    let mut signature = None;
+    for part in authorization.split('&') {
+        if part == "type=master" {
+            return None;
+        } else if let Some(value) = part.strip_prefix("sig=") {
+            signature = Some(value.to_owned());
+        }
+    }
+    signature.map(AuthorizationToken::Resource)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_resource_token_from_authorization() {
+        let authorization = resource_token_authorization("ocPyc9QQ==;w+WR1a/b==;");
+        assert_eq!(
+            resource_token_from_authorization(&authorization),
+            Some(AuthorizationToken::Resource(
+                "ocPyc9QQ==;w+WR1a/b==;".to_owned()
+            ))
+        );
+        assert_eq!(
+            resource_token_from_authorization("type%3Dmaster%26ver%3D1.0%26sig%3Dabc%3D"),
+            None
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::collection::Collection;
 use crate::from_headers::*;
+use crate::Diagnostics;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::etag_from_headers;
 use azure_sdk_core::session_token_from_headers;
@@ -11,6 +12,7 @@ pub struct CreateCollectionResponse {
     pub collection: Collection,
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub etag: String,
     pub session_token: String,
     pub last_state_change: DateTime<Utc>,
@@ -23,16 +25,20 @@ pub struct CreateCollectionResponse {
     pub current_replica_set_size: u64,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for CreateCollectionResponse {
+diagnostics_accessors!(CreateCollectionResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for CreateCollectionResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         Ok(Self {
             collection: serde_json::from_slice(body)?,
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             etag: etag_from_headers(headers)?,
             session_token: session_token_from_headers(headers)?,
             last_state_change: last_state_change_from_headers(headers)?,
@@ -1,6 +1,6 @@
 use crate::database::Database;
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::etag_from_headers;
 use azure_sdk_core::session_token_from_headers;
@@ -22,14 +22,18 @@ pub struct CreateDatabaseResponse {
     pub schema_version: String,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for CreateDatabaseResponse {
+diagnostics_accessors!(CreateDatabaseResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for CreateDatabaseResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         Ok(CreateDatabaseResponse {
             database: serde_json::from_slice(&body)?,
@@ -45,6 +49,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for CreateDatabaseResponse {
             schema_version: schema_version_from_headers(headers)?.to_owned(),
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
         })
     }
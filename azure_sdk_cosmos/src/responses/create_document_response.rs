@@ -1,6 +1,6 @@
 use crate::document_attributes::DocumentAttributes;
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{etag_from_headers, session_token_from_headers};
 use chrono::{DateTime, Utc};
@@ -32,16 +32,22 @@ pub struct CreateDocumentResponse {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
 }
 
-impl std::convert::TryFrom<(StatusCode, &HeaderMap, &[u8])> for CreateDocumentResponse {
+diagnostics_accessors!(CreateDocumentResponse);
+
+impl std::convert::TryFrom<(StatusCode, &HeaderMap, &[u8], Diagnostics)>
+    for CreateDocumentResponse
+{
     type Error = AzureError;
-    fn try_from(value: (StatusCode, &HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (StatusCode, &HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let status_code = value.0;
         let headers = value.1;
         let body = value.2;
+        let diagnostics = value.3;
 
         debug!("headers == {:#?}", headers);
 
@@ -70,6 +76,7 @@ impl std::convert::TryFrom<(StatusCode, &HeaderMap, &[u8])> for CreateDocumentRe
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
         })
@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use crate::Permission;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{etag_from_headers, session_token_from_headers};
@@ -11,17 +12,21 @@ pub struct CreatePermissionResponse<'a> {
     pub permission: Permission<'a, Cow<'a, str>>,
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub etag: String,
     pub session_token: String,
     pub content_path: String,
     pub alt_content_path: String,
 }
 
-impl<'a> std::convert::TryFrom<(&HeaderMap, &[u8])> for CreatePermissionResponse<'a> {
+diagnostics_accessors!(CreatePermissionResponse<'a>);
+
+impl<'a> std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for CreatePermissionResponse<'a> {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("headers == {:#?}", headers);
         debug!("body == {:#?}", std::str::from_utf8(body)?);
@@ -30,6 +35,7 @@ impl<'a> std::convert::TryFrom<(&HeaderMap, &[u8])> for CreatePermissionResponse
             permission: body.try_into()?,
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             etag: etag_from_headers(headers)?,
             session_token: session_token_from_headers(headers)?,
             content_path: content_path_from_headers(headers)?.to_owned(),
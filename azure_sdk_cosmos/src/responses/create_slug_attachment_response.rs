@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use crate::{Attachment, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{etag_from_headers, session_token_from_headers, SessionToken};
@@ -28,14 +29,18 @@ pub struct CreateSlugAttachmentResponse {
     pub request_charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for CreateSlugAttachmentResponse {
+diagnostics_accessors!(CreateSlugAttachmentResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for CreateSlugAttachmentResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
+        let diagnostics = value.2;
         let body = std::str::from_utf8(value.1)?;
 
         debug!("headers == {:#?}", headers);
@@ -65,6 +70,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for CreateSlugAttachmentResponse
             request_charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
         })
@@ -1,6 +1,6 @@
 use crate::from_headers::*;
 use crate::user_defined_function::UserDefinedFunction;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::etag_from_headers;
 use azure_sdk_core::session_token_from_headers;
@@ -32,15 +32,19 @@ pub struct CreateUserDefinedFunctionResponse {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for CreateUserDefinedFunctionResponse {
+diagnostics_accessors!(CreateUserDefinedFunctionResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for CreateUserDefinedFunctionResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("{:#?}", headers);
         debug!("{:#?}", std::str::from_utf8(body));
@@ -69,6 +73,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for CreateUserDefinedFunctionRes
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
         })
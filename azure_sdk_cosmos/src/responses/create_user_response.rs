@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use crate::User;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{etag_from_headers, session_token_from_headers};
@@ -10,20 +11,25 @@ pub struct CreateUserResponse {
     pub user: User,
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub etag: String,
     pub session_token: String,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for CreateUserResponse {
+diagnostics_accessors!(CreateUserResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for CreateUserResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         Ok(Self {
             user: body.try_into()?,
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             etag: etag_from_headers(headers)?,
             session_token: session_token_from_headers(headers)?,
         })
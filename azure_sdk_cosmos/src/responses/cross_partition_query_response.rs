@@ -0,0 +1,38 @@
+use crate::query_pipeline::QueryPlan;
+use crate::Diagnostics;
+
+/// Merged results of a query executed by the client side query pipeline, with the
+/// diagnostics of every request made.
+#[derive(Debug, Clone)]
+pub struct CrossPartitionQueryResponse<T> {
+    pub results: Vec<T>,
+    pub query_plan: QueryPlan,
+    pub partition_key_range_ids: Vec<String>,
+    pub diagnostics: Vec<Diagnostics>,
+}
+
+impl<T> CrossPartitionQueryResponse<T> {
+    /// Request units consumed by every request made.
+    pub fn charge(&self) -> f64 {
+        self.diagnostics
+            .iter()
+            .filter_map(|diagnostics| diagnostics.charge)
+            .sum()
+    }
+
+    /// Retries made because the requests were throttled.
+    pub fn retry_count(&self) -> u32 {
+        self.diagnostics
+            .iter()
+            .map(|diagnostics| diagnostics.retry_count)
+            .sum()
+    }
+
+    /// Time spent waiting before the retries.
+    pub fn throttled_time(&self) -> std::time::Duration {
+        self.diagnostics
+            .iter()
+            .map(|diagnostics| diagnostics.throttled_time)
+            .sum()
+    }
+}
@@ -1,5 +1,5 @@
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{session_token_from_headers, SessionToken};
 use chrono::{DateTime, Utc};
@@ -27,14 +27,18 @@ pub struct DeleteAttachmentResponse {
     pub request_charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteAttachmentResponse {
+diagnostics_accessors!(DeleteAttachmentResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for DeleteAttachmentResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
+        let diagnostics = value.2;
         let body = std::str::from_utf8(value.1)?;
 
         debug!("headers == {:#?}", headers);
@@ -61,6 +65,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteAttachmentResponse {
             request_charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
         })
@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use azure_sdk_core::errors::AzureError;
 use hyper::header::HeaderMap;
 
@@ -6,13 +7,17 @@ use hyper::header::HeaderMap;
 pub struct DeleteCollectionResponse {
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteCollectionResponse {
+diagnostics_accessors!(DeleteCollectionResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for DeleteCollectionResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let _body = value.1;
+        let diagnostics = value.2;
 
         let charge = request_charge_from_headers(headers)?;
         let activity_id = activity_id_from_headers(headers)?;
@@ -20,6 +25,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteCollectionResponse {
         Ok(Self {
             charge,
             activity_id,
+            diagnostics,
         })
     }
 }
@@ -1,5 +1,5 @@
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::session_token_from_headers;
 use hyper::header::HeaderMap;
@@ -8,23 +8,27 @@ use hyper::header::HeaderMap;
 pub struct DeleteDatabaseResponse {
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
     pub resource_quota: Vec<ResourceQuota>,
     pub resource_usage: Vec<ResourceQuota>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteDatabaseResponse {
+diagnostics_accessors!(DeleteDatabaseResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for DeleteDatabaseResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let _body = value.1;
+        let diagnostics = value.2;
 
         let charge = request_charge_from_headers(headers)?;
         let activity_id = activity_id_from_headers(headers)?;
-
         Ok(Self {
             charge,
             activity_id,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             resource_quota: resource_quota_from_headers(headers)?,
             resource_usage: resource_usage_from_headers(headers)?,
@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::session_token_from_headers;
 use hyper::header::HeaderMap;
@@ -7,15 +8,18 @@ use hyper::header::HeaderMap;
 pub struct DeleteDocumentResponse {
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteDocumentResponse {
+diagnostics_accessors!(DeleteDocumentResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for DeleteDocumentResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let _body = value.1;
-
+        let diagnostics = value.2;
         let charge = request_charge_from_headers(headers)?;
         let activity_id = activity_id_from_headers(headers)?;
         let session_token = session_token_from_headers(headers)?;
@@ -23,6 +27,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteDocumentResponse {
         Ok(Self {
             charge,
             activity_id,
+            diagnostics,
             session_token,
         })
     }
@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::session_token_from_headers;
 use http::HeaderMap;
@@ -7,16 +8,20 @@ use http::HeaderMap;
 pub struct DeletePermissionResponse {
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
     pub content_path: String,
     pub alt_content_path: String,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeletePermissionResponse {
+diagnostics_accessors!(DeletePermissionResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for DeletePermissionResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let _body = value.1;
+        let diagnostics = value.2;
 
         debug!("headers == {:#?}", headers);
         debug!("_body == {:#?}", std::str::from_utf8(_body)?);
@@ -24,6 +29,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeletePermissionResponse {
         Ok(Self {
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             content_path: content_path_from_headers(headers)?.to_owned(),
             alt_content_path: alt_content_path_from_headers(headers)?.to_owned(),
@@ -1,5 +1,5 @@
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::session_token_from_headers;
 use chrono::{DateTime, Utc};
@@ -9,21 +9,26 @@ use http::HeaderMap;
 pub struct DeleteStoredProcedureResponse {
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
     pub last_change: DateTime<Utc>,
     pub resource_quota: Vec<ResourceQuota>,
     pub resource_usage: Vec<ResourceQuota>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteStoredProcedureResponse {
+diagnostics_accessors!(DeleteStoredProcedureResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for DeleteStoredProcedureResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let _body = value.1;
+        let diagnostics = value.2;
 
         Ok(Self {
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             last_change: last_state_change_from_headers(headers)?,
             resource_quota: resource_quota_from_headers(headers)?,
@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use azure_sdk_core::errors::AzureError;
 use http::HeaderMap;
 
@@ -6,17 +7,22 @@ use http::HeaderMap;
 pub struct DeleteUserResponse {
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for DeleteUserResponse {
+diagnostics_accessors!(DeleteUserResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for DeleteUserResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let _body = value.1;
+        let diagnostics = value.2;
 
         Ok(Self {
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
         })
     }
 }
@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{session_token_from_headers, SessionToken};
 use chrono::{DateTime, Utc};
@@ -29,18 +30,23 @@ where
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
 }
 
-impl<T> std::convert::TryFrom<(&HeaderMap, &[u8])> for ExecuteStoredProcedureResponse<T>
+diagnostics_accessors!(ExecuteStoredProcedureResponse<T> where T: DeserializeOwned);
+
+impl<T> std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)>
+    for ExecuteStoredProcedureResponse<T>
 where
     T: DeserializeOwned,
 {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("headers == {:#?}", headers);
         debug!("body == {:#?}", body);
@@ -65,6 +71,7 @@ where
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
         })
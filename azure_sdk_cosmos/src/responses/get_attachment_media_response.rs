@@ -0,0 +1,69 @@
+use crate::from_headers::*;
+use crate::Diagnostics;
+use azure_sdk_core::errors::AzureError;
+use chrono::{DateTime, Utc};
+use futures::stream::Stream;
+use http::HeaderMap;
+use hyper::body::Bytes;
+use std::fmt;
+use std::pin::Pin;
+
+/// The content of an attachment, read from the connection as it is consumed.
+pub type MediaStream = Pin<Box<dyn Stream<Item = Result<Bytes, AzureError>> + Send>>;
+
+pub struct GetAttachmentMediaResponse {
+    pub content_type: String,
+    pub content_length: Option<u64>,
+    pub max_media_storage_usage_mb: u64,
+    pub media_storage_usage_mb: u64,
+    pub request_charge: f64,
+    pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
+    pub date: DateTime<Utc>,
+    pub media: MediaStream,
+}
+
+diagnostics_accessors!(GetAttachmentMediaResponse);
+
+impl fmt::Debug for GetAttachmentMediaResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetAttachmentMediaResponse")
+            .field("content_type", &self.content_type)
+            .field("content_length", &self.content_length)
+            .field(
+                "max_media_storage_usage_mb",
+                &self.max_media_storage_usage_mb,
+            )
+            .field("media_storage_usage_mb", &self.media_storage_usage_mb)
+            .field("request_charge", &self.request_charge)
+            .field("activity_id", &self.activity_id)
+            .field("diagnostics", &self.diagnostics)
+            .field("date", &self.date)
+            .finish()
+    }
+}
+
+impl std::convert::TryFrom<(&HeaderMap, MediaStream, Diagnostics)> for GetAttachmentMediaResponse {
+    type Error = AzureError;
+    fn try_from(value: (&HeaderMap, MediaStream, Diagnostics)) -> Result<Self, Self::Error> {
+        let headers = value.0;
+        let diagnostics = value.2;
+
+        debug!("headers == {:#?}", headers);
+
+        Ok(Self {
+            content_type: content_type_from_headers(headers)?.to_owned(),
+            content_length: headers
+                .get(http::header::CONTENT_LENGTH)
+                .and_then(|content_length| content_length.to_str().ok())
+                .and_then(|content_length| content_length.parse().ok()),
+            max_media_storage_usage_mb: max_media_storage_usage_mb_from_headers(headers)?,
+            media_storage_usage_mb: media_storage_usage_mb_from_headers(headers)?,
+            request_charge: request_charge_from_headers(headers)?,
+            activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
+            date: date_from_headers(headers)?,
+            media: value.1,
+        })
+    }
+}
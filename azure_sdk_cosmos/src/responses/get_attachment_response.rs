@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use crate::{Attachment, IndexingDirective, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{etag_from_headers, session_token_from_headers, SessionToken};
@@ -29,14 +30,18 @@ pub struct GetAttachmentResponse {
     pub indexing_directive: Option<IndexingDirective>,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for GetAttachmentResponse {
+diagnostics_accessors!(GetAttachmentResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for GetAttachmentResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
+        let diagnostics = value.2;
         let body = std::str::from_utf8(value.1)?;
 
         debug!("headers == {:#?}", headers);
@@ -64,6 +69,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for GetAttachmentResponse {
             indexing_directive: indexing_directive_from_headers_optional(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
         })
@@ -1,5 +1,6 @@
 use crate::collection::Collection;
 use crate::from_headers::*;
+use crate::Diagnostics;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{etag_from_headers, session_token_from_headers};
 use chrono::{DateTime, Utc};
@@ -25,15 +26,19 @@ pub struct GetCollectionResponse {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
     pub gateway_version: String,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for GetCollectionResponse {
+diagnostics_accessors!(GetCollectionResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for GetCollectionResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("body == {}", std::str::from_utf8(body)?);
 
@@ -58,6 +63,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for GetCollectionResponse {
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
         })
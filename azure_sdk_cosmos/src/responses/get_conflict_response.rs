@@ -0,0 +1,39 @@
+use crate::from_headers::*;
+use crate::Conflict;
+use crate::Diagnostics;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::{etag_from_headers, session_token_from_headers};
+use hyper::header::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetConflictResponse {
+    pub conflict: Conflict,
+    pub etag: String,
+    pub session_token: String,
+    pub charge: f64,
+    pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
+}
+
+diagnostics_accessors!(GetConflictResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for GetConflictResponse {
+    type Error = AzureError;
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
+        let headers = value.0;
+        let body = value.1;
+        let diagnostics = value.2;
+
+        debug!("headers == {:#?}", headers);
+        debug!("body == {}", std::str::from_utf8(body)?);
+
+        Ok(Self {
+            conflict: serde_json::from_slice(body)?,
+            etag: etag_from_headers(headers)?,
+            session_token: session_token_from_headers(headers)?,
+            charge: request_charge_from_headers(headers)?,
+            activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
+        })
+    }
+}
@@ -1,6 +1,6 @@
 use crate::database::Database;
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{etag_from_headers, session_token_from_headers};
 use chrono::{DateTime, Utc};
@@ -11,6 +11,7 @@ pub struct GetDatabaseResponse {
     pub database: Database,
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
     pub etag: String,
     pub last_state_change: DateTime<Utc>,
@@ -21,11 +22,14 @@ pub struct GetDatabaseResponse {
     pub gateway_version: String,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for GetDatabaseResponse {
+diagnostics_accessors!(GetDatabaseResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for GetDatabaseResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("get database response == {}", std::str::from_utf8(body)?);
 
@@ -33,6 +37,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for GetDatabaseResponse {
             database: serde_json::from_slice(body)?,
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             etag: etag_from_headers(headers)?,
             last_state_change: last_state_change_from_headers(headers)?,
@@ -1,6 +1,6 @@
 use crate::from_headers::*;
 use crate::Document;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{etag_from_headers, session_token_from_headers, SessionToken};
 use chrono::{DateTime, Utc};
@@ -14,15 +14,19 @@ pub enum GetDocumentResponse<T> {
     NotFound(Box<NotFoundDocumentResponse>),
 }
 
-impl<T> std::convert::TryFrom<(StatusCode, &HeaderMap, &[u8])> for GetDocumentResponse<T>
+impl<T> std::convert::TryFrom<(StatusCode, &HeaderMap, &[u8], Diagnostics)>
+    for GetDocumentResponse<T>
 where
     T: DeserializeOwned,
 {
     type Error = AzureError;
-    fn try_from(response: (StatusCode, &HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(
+        response: (StatusCode, &HeaderMap, &[u8], Diagnostics),
+    ) -> Result<Self, Self::Error> {
         let status_code = response.0;
         let headers = response.1;
         let body = response.2;
+        let diagnostics = response.3;
 
         debug!("status_code == {:?}", status_code);
         debug!("headers == {:#?}", headers);
@@ -33,11 +37,11 @@ where
 
         if has_been_found {
             Ok(GetDocumentResponse::Found(Box::new(
-                FoundDocumentResponse::try_from((headers, body))?,
+                FoundDocumentResponse::try_from((headers, body, diagnostics))?,
             )))
         } else {
             Ok(GetDocumentResponse::NotFound(Box::new(
-                NotFoundDocumentResponse::try_from((headers, body))?,
+                NotFoundDocumentResponse::try_from((headers, body, diagnostics))?,
             )))
         }
     }
@@ -66,18 +70,22 @@ pub struct FoundDocumentResponse<T> {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
 }
 
-impl<T> std::convert::TryFrom<(&HeaderMap, &[u8])> for FoundDocumentResponse<T>
+diagnostics_accessors!(FoundDocumentResponse<T>);
+
+impl<T> std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for FoundDocumentResponse<T>
 where
     T: DeserializeOwned,
 {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         Ok(Self {
             document: Document::try_from((headers, body))?,
@@ -102,6 +110,7 @@ where
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
         })
@@ -126,14 +135,18 @@ pub struct NotFoundDocumentResponse {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for NotFoundDocumentResponse {
+diagnostics_accessors!(NotFoundDocumentResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for NotFoundDocumentResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
+        let diagnostics = value.2;
 
         Ok(Self {
             content_location: content_location_from_headers(headers)?.to_owned(),
@@ -152,6 +165,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for NotFoundDocumentResponse {
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
         })
@@ -0,0 +1,33 @@
+use crate::from_headers::*;
+use crate::Diagnostics;
+use crate::ThroughputOffer;
+use azure_sdk_core::errors::AzureError;
+use hyper::header::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetOfferResponse {
+    pub offer: ThroughputOffer,
+    pub charge: f64,
+    pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
+}
+
+diagnostics_accessors!(GetOfferResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for GetOfferResponse {
+    type Error = AzureError;
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
+        let headers = value.0;
+        let body = value.1;
+        let diagnostics = value.2;
+
+        debug!("offer response == {}", std::str::from_utf8(body)?);
+
+        Ok(Self {
+            offer: serde_json::from_slice(body)?,
+            charge: request_charge_from_headers(headers)?,
+            activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use crate::PartitionKeyRange;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::session_token_from_headers;
@@ -24,16 +25,20 @@ pub struct GetPartitionKeyRangesResponse {
     pub session_token: String,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
     pub partition_key_ranges: Vec<PartitionKeyRange>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for GetPartitionKeyRangesResponse {
+diagnostics_accessors!(GetPartitionKeyRangesResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for GetPartitionKeyRangesResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("body == {}", std::str::from_utf8(body)?);
 
@@ -66,6 +71,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for GetPartitionKeyRangesRespons
             cosmos_llsn: cosmos_llsn_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
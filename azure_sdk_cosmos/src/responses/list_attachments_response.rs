@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use crate::{Attachment, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{
@@ -40,15 +41,19 @@ pub struct ListAttachmentsResponse {
     pub request_charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
     pub continuation_token: Option<String>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListAttachmentsResponse {
+diagnostics_accessors!(ListAttachmentsResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ListAttachmentsResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
+        let diagnostics = value.2;
         let body = std::str::from_utf8(value.1)?;
 
         debug!("headers == {:#?}", headers);
@@ -78,6 +83,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListAttachmentsResponse {
             request_charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             date: date_from_headers(headers)?,
             continuation_token: continuation_token_from_headers_optional(headers)?,
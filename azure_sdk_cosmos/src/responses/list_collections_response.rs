@@ -1,6 +1,6 @@
 use crate::collection::Collection;
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{continuation_token_from_headers_optional, session_token_from_headers};
 use chrono::{DateTime, Utc};
@@ -20,16 +20,20 @@ pub struct ListCollectionsResponse {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
     pub gateway_version: String,
     pub continuation_token: Option<String>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListCollectionsResponse {
+diagnostics_accessors!(ListCollectionsResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ListCollectionsResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         #[derive(Deserialize, Debug)]
         pub struct Response {
@@ -55,6 +59,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListCollectionsResponse {
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             continuation_token: continuation_token_from_headers_optional(headers)?,
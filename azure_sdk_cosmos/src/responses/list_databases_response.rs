@@ -1,6 +1,6 @@
 use crate::database::Database;
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{continuation_token_from_headers_optional, session_token_from_headers};
 use chrono::{DateTime, Utc};
@@ -12,6 +12,7 @@ pub struct ListDatabasesResponse {
     pub databases: Vec<Database>,
     pub count: u32,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub charge: f64,
     pub session_token: String,
     pub last_state_change: DateTime<Utc>,
@@ -23,11 +24,14 @@ pub struct ListDatabasesResponse {
     pub gateway_version: String,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListDatabasesResponse {
+diagnostics_accessors!(ListDatabasesResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ListDatabasesResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("headers == {:#?}", headers);
 
@@ -49,6 +53,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListDatabasesResponse {
             count: response.count,
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             last_state_change: last_state_change_from_headers(headers)?,
             resource_quota: resource_quota_from_headers(headers)?,
@@ -1,5 +1,5 @@
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use crate::{Document, DocumentAttributes};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{
@@ -44,11 +44,14 @@ pub struct ListDocumentsResponse<T> {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
     pub continuation_token: Option<String>,
 }
 
+diagnostics_accessors!(ListDocumentsResponse<T>);
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ListDocumentsResponseEntities<T> {
     #[serde(rename = "_rid")]
@@ -74,14 +77,15 @@ where
     }
 }
 
-impl<T> std::convert::TryFrom<(&HeaderMap, &[u8])> for ListDocumentsResponse<T>
+impl<T> std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ListDocumentsResponse<T>
 where
     T: DeserializeOwned,
 {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("headers == {:#?}", headers);
 
@@ -129,6 +133,7 @@ where
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             continuation_token: continuation_token_from_headers_optional(headers)?,
             date: date_from_headers(headers)?,
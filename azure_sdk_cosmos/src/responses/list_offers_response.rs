@@ -0,0 +1,52 @@
+use crate::from_headers::*;
+use crate::Diagnostics;
+use crate::ThroughputOffer;
+use azure_sdk_core::continuation_token_from_headers_optional;
+use azure_sdk_core::errors::AzureError;
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListOffersResponse {
+    pub rid: String,
+    pub offers: Vec<ThroughputOffer>,
+    pub count: u32,
+    pub charge: f64,
+    pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
+    pub continuation_token: Option<String>,
+}
+
+diagnostics_accessors!(ListOffersResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ListOffersResponse {
+    type Error = AzureError;
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
+        let headers = value.0;
+        let body = value.1;
+        let diagnostics = value.2;
+
+        debug!("headers == {:#?}", headers);
+
+        #[derive(Deserialize, Debug)]
+        struct Response {
+            #[serde(rename = "_rid")]
+            rid: String,
+            #[serde(rename = "Offers")]
+            offers: Vec<ThroughputOffer>,
+            #[serde(rename = "_count")]
+            count: u32,
+        }
+
+        let response: Response = serde_json::from_slice(body)?;
+
+        Ok(Self {
+            rid: response.rid,
+            offers: response.offers,
+            count: response.count,
+            charge: request_charge_from_headers(headers)?,
+            activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
+            continuation_token: continuation_token_from_headers_optional(headers)?,
+        })
+    }
+}
@@ -1,5 +1,6 @@
 use crate::from_headers::*;
 use crate::permission::CosmosPermission;
+use crate::Diagnostics;
 use crate::Permission;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{continuation_token_from_headers_optional, session_token_from_headers};
@@ -11,17 +12,21 @@ pub struct ListPermissionsResponse<'a> {
     pub permissions: Vec<Permission<'a, Cow<'a, str>>>,
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
     pub content_path: String,
     pub alt_content_path: String,
     pub continuation_token: Option<String>,
 }
 
-impl<'a> std::convert::TryFrom<(&HeaderMap, &[u8])> for ListPermissionsResponse<'a> {
+diagnostics_accessors!(ListPermissionsResponse<'a>);
+
+impl<'a> std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ListPermissionsResponse<'a> {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("headers == {:#?}", headers);
         debug!("body == {:#?}", std::str::from_utf8(body)?);
@@ -50,6 +55,7 @@ impl<'a> std::convert::TryFrom<(&HeaderMap, &[u8])> for ListPermissionsResponse<
             permissions,
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             content_path: content_path_from_headers(headers)?.to_owned(),
             alt_content_path: alt_content_path_from_headers(headers)?.to_owned(),
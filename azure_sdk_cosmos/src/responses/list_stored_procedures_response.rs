@@ -1,6 +1,6 @@
 use crate::from_headers::*;
 use crate::stored_procedure::StoredProcedure;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{continuation_token_from_headers_optional, session_token_from_headers};
 use chrono::{DateTime, Utc};
@@ -11,6 +11,7 @@ pub struct ListStoredProceduresResponse {
     pub stored_procedures: Vec<StoredProcedure>,
     pub charge: f64,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub session_token: String,
     pub last_change: DateTime<Utc>,
     pub resource_quota: Vec<ResourceQuota>,
@@ -19,11 +20,14 @@ pub struct ListStoredProceduresResponse {
     pub continuation_token: Option<String>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListStoredProceduresResponse {
+diagnostics_accessors!(ListStoredProceduresResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ListStoredProceduresResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
         struct Response {
@@ -37,6 +41,7 @@ impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListStoredProceduresResponse
             stored_procedures: serde_json::from_slice::<Response>(body)?.stored_procedures,
             charge: request_charge_from_headers(headers)?,
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             session_token: session_token_from_headers(headers)?,
             last_change: last_state_change_from_headers(headers)?,
             resource_quota: resource_quota_from_headers(headers)?,
@@ -1,4 +1,5 @@
 use crate::from_headers::*;
+use crate::Diagnostics;
 use crate::User;
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{
@@ -21,20 +22,26 @@ pub struct ListUsersResponse {
     #[serde(skip_deserializing)]
     pub activity_id: uuid::Uuid,
     #[serde(skip_deserializing)]
+    pub diagnostics: Diagnostics,
+    #[serde(skip_deserializing)]
     pub session_token: SessionToken,
     #[serde(skip_deserializing)]
     pub continuation_token: Option<String>,
 }
 
-impl std::convert::TryFrom<(&HeaderMap, &[u8])> for ListUsersResponse {
+diagnostics_accessors!(ListUsersResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ListUsersResponse {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         let mut list_users_response: ListUsersResponse = serde_json::from_slice(body)?;
         list_users_response.charge = request_charge_from_headers(headers)?;
         list_users_response.activity_id = activity_id_from_headers(headers)?;
+        list_users_response.diagnostics = diagnostics;
         list_users_response.continuation_token = continuation_token_from_headers_optional(headers)?;
         list_users_response.session_token = session_token_from_headers(headers)?;
 
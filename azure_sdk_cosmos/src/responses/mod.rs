@@ -1,3 +1,20 @@
+// Accessors for the retries recorded in the diagnostics of a response.
+macro_rules! diagnostics_accessors {
+    ($response:ident $(<$($param:tt),*>)? $(where $($bound:tt)*)?) => {
+        impl$(<$($param),*>)? $response$(<$($param),*>)? $(where $($bound)*)? {
+            /// Retries made because the request was throttled.
+            pub fn retry_count(&self) -> u32 {
+                self.diagnostics.retry_count
+            }
+
+            /// Time spent waiting before the retries.
+            pub fn throttled_time(&self) -> std::time::Duration {
+                self.diagnostics.throttled_time
+            }
+        }
+    };
+}
+
 mod create_collection_response;
 mod create_database_response;
 mod create_document_response;
@@ -8,8 +25,10 @@ mod create_stored_procedure_response;
 mod create_trigger_response;
 mod create_user_defined_function_response;
 mod create_user_response;
+mod cross_partition_query_response;
 mod delete_attachment_response;
 mod delete_collection_response;
+mod delete_conflict_response;
 mod delete_database_response;
 mod delete_document_response;
 mod delete_permission_response;
@@ -18,26 +37,38 @@ mod delete_trigger_response;
 mod delete_user_defined_function_response;
 mod delete_user_response;
 mod execute_stored_procedure_response;
+mod get_attachment_media_response;
 mod get_attachment_response;
 mod get_collection_response;
+mod get_conflict_response;
+mod get_database_account_response;
 mod get_database_response;
 mod get_document_response;
+mod get_offer_response;
 mod get_partition_key_ranges_response;
 mod get_permission_response;
 mod list_attachments_response;
 mod list_collections_response;
+mod list_conflicts_response;
 mod list_databases_response;
 mod list_documents_response;
+mod list_offers_response;
 mod list_permissions_response;
 mod list_stored_procedures_response;
 mod list_triggers_response;
 mod list_user_defined_functions_response;
 mod list_users_response;
+mod patch_document_response;
 mod query_documents_response;
+mod query_offers_response;
+mod read_change_feed_response;
+mod replace_attachment_media_response;
 mod replace_document_response;
+mod replace_offer_response;
 mod replace_permission_response;
 mod replace_reference_attachment_response;
 mod replace_stored_procedure_response;
+mod transactional_batch_response;
 pub use self::create_collection_response::CreateCollectionResponse;
 pub use self::create_database_response::CreateDatabaseResponse;
 pub use self::create_document_response::CreateDocumentResponse;
@@ -48,8 +79,10 @@ pub use self::create_stored_procedure_response::CreateStoredProcedureResponse;
 pub use self::create_trigger_response::CreateTriggerResponse;
 pub use self::create_user_defined_function_response::CreateUserDefinedFunctionResponse;
 pub use self::create_user_response::CreateUserResponse;
+pub use self::cross_partition_query_response::CrossPartitionQueryResponse;
 pub use self::delete_attachment_response::DeleteAttachmentResponse;
 pub use self::delete_collection_response::DeleteCollectionResponse;
+pub use self::delete_conflict_response::DeleteConflictResponse;
 pub use self::delete_database_response::DeleteDatabaseResponse;
 pub use self::delete_document_response::DeleteDocumentResponse;
 pub use self::delete_permission_response::DeletePermissionResponse;
@@ -58,28 +91,42 @@ pub use self::delete_trigger_response::DeleteTriggerResponse;
 pub use self::delete_user_defined_function_response::DeleteUserDefinedFunctionResponse;
 pub use self::delete_user_response::DeleteUserResponse;
 pub use self::execute_stored_procedure_response::ExecuteStoredProcedureResponse;
+pub use self::get_attachment_media_response::{GetAttachmentMediaResponse, MediaStream};
 pub use self::get_attachment_response::GetAttachmentResponse;
 pub use self::get_collection_response::GetCollectionResponse;
+pub use self::get_conflict_response::GetConflictResponse;
+pub use self::get_database_account_response::GetDatabaseAccountResponse;
 pub use self::get_database_response::GetDatabaseResponse;
 pub use self::get_document_response::GetDocumentResponse;
+pub use self::get_offer_response::GetOfferResponse;
 pub use self::get_partition_key_ranges_response::GetPartitionKeyRangesResponse;
 pub use self::get_permission_response::GetPermissionResponse;
 pub use self::list_attachments_response::ListAttachmentsResponse;
 pub use self::list_collections_response::ListCollectionsResponse;
+pub use self::list_conflicts_response::ListConflictsResponse;
 pub use self::list_databases_response::ListDatabasesResponse;
 pub use self::list_documents_response::{
     ListDocumentsResponse, ListDocumentsResponseAttributes, ListDocumentsResponseEntities,
 };
+pub use self::list_offers_response::ListOffersResponse;
 pub use self::list_permissions_response::ListPermissionsResponse;
 pub use self::list_stored_procedures_response::ListStoredProceduresResponse;
 pub use self::list_triggers_response::ListTriggersResponse;
 pub use self::list_user_defined_functions_response::ListUserDefinedFunctionsResponse;
 pub use self::list_users_response::ListUsersResponse;
+pub use self::patch_document_response::PatchDocumentResponse;
 pub use self::query_documents_response::{
     QueryDocumentsResponse, QueryDocumentsResponseDocuments, QueryDocumentsResponseRaw,
     QueryResponseMeta, QueryResult,
 };
+pub use self::query_offers_response::QueryOffersResponse;
+pub use self::read_change_feed_response::ReadChangeFeedResponse;
+pub use self::replace_attachment_media_response::ReplaceAttachmentMediaResponse;
 pub use self::replace_document_response::ReplaceDocumentResponse;
+pub use self::replace_offer_response::ReplaceOfferResponse;
 pub use self::replace_permission_response::ReplacePermissionResponse;
 pub use self::replace_reference_attachment_response::ReplaceReferenceAttachmentResponse;
 pub use self::replace_stored_procedure_response::ReplaceStoredProcedureResponse;
+pub use self::transactional_batch_response::{
+    TransactionalBatchOperationResult, TransactionalBatchResponse,
+};
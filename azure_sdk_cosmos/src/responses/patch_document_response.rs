@@ -0,0 +1 @@
+pub type PatchDocumentResponse = crate::responses::ReplaceDocumentResponse;
@@ -1,7 +1,7 @@
 use crate::document_attributes::DocumentAttributes;
 use crate::errors::ConversionToDocumentError;
 use crate::from_headers::*;
-use crate::ResourceQuota;
+use crate::{Diagnostics, ResourceQuota};
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::{
     continuation_token_from_headers_optional, session_token_from_headers, SessionToken,
@@ -83,11 +83,14 @@ pub struct QueryDocumentsResponse<T> {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
     pub continuation_token: Option<String>,
 }
 
+diagnostics_accessors!(QueryDocumentsResponse<T>);
+
 impl<T> QueryDocumentsResponse<T> {
     pub fn into_raw(self) -> QueryDocumentsResponseRaw<T> {
         self.into()
@@ -100,14 +103,15 @@ impl<T> QueryDocumentsResponse<T> {
     }
 }
 
-impl<T> std::convert::TryFrom<(&HeaderMap, &[u8])> for QueryDocumentsResponse<T>
+impl<T> std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for QueryDocumentsResponse<T>
 where
     T: DeserializeOwned,
 {
     type Error = AzureError;
-    fn try_from(value: (&HeaderMap, &[u8])) -> Result<Self, Self::Error> {
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
         let headers = value.0;
         let body = value.1;
+        let diagnostics = value.2;
 
         debug!("headers == {:#?}", headers);
         debug!("body == {}", std::str::from_utf8(body)?);
@@ -140,7 +144,7 @@ where
         }
 
         Ok(QueryDocumentsResponse {
-            query_response_meta: value.try_into()?,
+            query_response_meta: (headers, body).try_into()?,
             results,
             last_state_change: last_state_change_from_headers(headers)?,
             resource_quota: resource_quota_from_headers(headers)?,
@@ -163,6 +167,7 @@ where
             charge: request_charge_from_headers(headers)?,
             service_version: service_version_from_headers(headers)?.to_owned(),
             activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
             gateway_version: gateway_version_from_headers(headers)?.to_owned(),
             continuation_token: continuation_token_from_headers_optional(headers)?,
             date: date_from_headers(headers)?,
@@ -196,11 +201,14 @@ pub struct QueryDocumentsResponseRaw<T> {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
     pub continuation_token: Option<String>,
 }
 
+diagnostics_accessors!(QueryDocumentsResponseRaw<T>);
+
 impl<T> std::convert::From<QueryDocumentsResponse<T>> for QueryDocumentsResponseRaw<T> {
     #[inline]
     fn from(q: QueryDocumentsResponse<T>) -> Self {
@@ -235,6 +243,7 @@ impl<T> std::convert::From<QueryDocumentsResponse<T>> for QueryDocumentsResponse
             charge: q.charge,
             service_version: q.service_version,
             activity_id: q.activity_id,
+            diagnostics: q.diagnostics,
             gateway_version: q.gateway_version,
             continuation_token: q.continuation_token,
             date: q.date,
@@ -268,11 +277,14 @@ pub struct QueryDocumentsResponseDocuments<T> {
     pub charge: f64,
     pub service_version: String,
     pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
     pub gateway_version: String,
     pub date: DateTime<Utc>,
     pub continuation_token: Option<String>,
 }
 
+diagnostics_accessors!(QueryDocumentsResponseDocuments<T>);
+
 impl<T> std::convert::TryFrom<QueryDocumentsResponse<T>> for QueryDocumentsResponseDocuments<T> {
     type Error = ConversionToDocumentError;
 
@@ -319,6 +331,7 @@ impl<T> std::convert::TryFrom<QueryDocumentsResponse<T>> for QueryDocumentsRespo
             charge: q.charge,
             service_version: q.service_version,
             activity_id: q.activity_id,
+            diagnostics: q.diagnostics,
             gateway_version: q.gateway_version,
             continuation_token: q.continuation_token,
             date: q.date,
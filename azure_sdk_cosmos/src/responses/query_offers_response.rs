@@ -0,0 +1 @@
+pub type QueryOffersResponse = crate::responses::ListOffersResponse;
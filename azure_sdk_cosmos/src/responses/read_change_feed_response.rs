@@ -0,0 +1,74 @@
+use crate::from_headers::*;
+use crate::responses::{ListDocumentsResponseAttributes, ListDocumentsResponseEntities};
+use crate::Diagnostics;
+use crate::{ChangeFeedContinuation, Document};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::{etag_from_headers, session_token_from_headers, SessionToken};
+use chrono::{DateTime, Utc};
+use http::StatusCode;
+use hyper::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+#[derive(Debug, Clone)]
+pub struct ReadChangeFeedResponse<T> {
+    pub documents: Vec<Document<T>>,
+    pub partition_key_range_id: Option<String>,
+    pub etag: String,
+    pub continuation: ChangeFeedContinuation,
+    pub session_token: SessionToken,
+    pub charge: f64,
+    pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
+    pub date: DateTime<Utc>,
+}
+
+diagnostics_accessors!(ReadChangeFeedResponse<T>);
+
+impl<T> std::convert::TryFrom<(StatusCode, &HeaderMap, &[u8], Diagnostics)>
+    for ReadChangeFeedResponse<T>
+where
+    T: DeserializeOwned,
+{
+    type Error = AzureError;
+    fn try_from(
+        response: (StatusCode, &HeaderMap, &[u8], Diagnostics),
+    ) -> Result<Self, Self::Error> {
+        let status_code = response.0;
+        let headers = response.1;
+        let body = response.2;
+        let diagnostics = response.3;
+
+        debug!("status_code == {:?}", status_code);
+        debug!("headers == {:#?}", headers);
+
+        // a 304 (Not Modified) means there are no new changes: the body is empty.
+        let documents = if status_code == StatusCode::NOT_MODIFIED {
+            Vec::new()
+        } else {
+            let document_attributes = ListDocumentsResponseAttributes::try_from(body)?;
+            let entries = ListDocumentsResponseEntities::try_from(body)?;
+
+            document_attributes
+                .documents
+                .into_iter()
+                .zip(entries.entities)
+                .map(|(da, e)| Document {
+                    document_attributes: da,
+                    document: e,
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            documents,
+            partition_key_range_id: None,
+            etag: etag_from_headers(headers)?,
+            continuation: ChangeFeedContinuation::default(),
+            session_token: session_token_from_headers(headers)?,
+            charge: request_charge_from_headers(headers)?,
+            activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
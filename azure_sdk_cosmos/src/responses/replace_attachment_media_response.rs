@@ -0,0 +1,36 @@
+use crate::from_headers::*;
+use crate::Diagnostics;
+use azure_sdk_core::errors::AzureError;
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplaceAttachmentMediaResponse {
+    pub max_media_storage_usage_mb: u64,
+    pub media_storage_usage_mb: u64,
+    pub request_charge: f64,
+    pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
+    pub date: DateTime<Utc>,
+}
+
+diagnostics_accessors!(ReplaceAttachmentMediaResponse);
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for ReplaceAttachmentMediaResponse {
+    type Error = AzureError;
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
+        let headers = value.0;
+        let diagnostics = value.2;
+
+        debug!("headers == {:#?}", headers);
+
+        Ok(Self {
+            max_media_storage_usage_mb: max_media_storage_usage_mb_from_headers(headers)?,
+            media_storage_usage_mb: media_storage_usage_mb_from_headers(headers)?,
+            request_charge: request_charge_from_headers(headers)?,
+            activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
+            date: date_from_headers(headers)?,
+        })
+    }
+}
@@ -0,0 +1 @@
+pub type ReplaceOfferResponse = crate::responses::GetOfferResponse;
@@ -0,0 +1,108 @@
+use crate::from_headers::*;
+use crate::Diagnostics;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::session_token_from_headers;
+use http::{HeaderMap, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionalBatchResponse {
+    /// One result per operation, in the same order of the operations of the batch.
+    pub results: Vec<TransactionalBatchOperationResult>,
+    pub session_token: String,
+    pub charge: f64,
+    pub activity_id: uuid::Uuid,
+    pub diagnostics: Diagnostics,
+}
+
+diagnostics_accessors!(TransactionalBatchResponse);
+
+impl TransactionalBatchResponse {
+    /// True if every operation succeeded and the batch has been committed.
+    pub fn is_successful(&self) -> bool {
+        self.results
+            .iter()
+            .all(|result| result.status_code.is_success())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TransactionalBatchOperationResult {
+    /// Status code of the operation. When the batch fails the offending operation has its
+    /// own status code while the others get 424 (Failed Dependency).
+    #[serde(rename = "statusCode", deserialize_with = "deserialize_status_code")]
+    pub status_code: StatusCode,
+    #[serde(rename = "subStatusCode", default)]
+    pub sub_status_code: Option<u32>,
+    #[serde(rename = "requestCharge", default)]
+    pub charge: f64,
+    #[serde(rename = "eTag", default)]
+    pub etag: Option<String>,
+    #[serde(rename = "resourceBody", default)]
+    pub resource_body: Option<serde_json::Value>,
+}
+
+impl TransactionalBatchOperationResult {
+    /// Deserializes the document returned by the operation, if any. Delete operations and
+    /// failed operations do not return a document.
+    pub fn document<T>(&self) -> Result<Option<T>, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.resource_body
+            .as_ref()
+            .map(|body| T::deserialize(body))
+            .transpose()
+    }
+}
+
+fn deserialize_status_code<'de, D>(deserializer: D) -> Result<StatusCode, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let status_code = u16::deserialize(deserializer)?;
+    StatusCode::from_u16(status_code).map_err(serde::de::Error::custom)
+}
+
+impl std::convert::TryFrom<(&HeaderMap, &[u8], Diagnostics)> for TransactionalBatchResponse {
+    type Error = AzureError;
+    fn try_from(value: (&HeaderMap, &[u8], Diagnostics)) -> Result<Self, Self::Error> {
+        let headers = value.0;
+        let body = value.1;
+        let diagnostics = value.2;
+
+        debug!("headers == {:#?}", headers);
+        debug!("body == {}", std::str::from_utf8(body)?);
+
+        Ok(Self {
+            results: serde_json::from_slice(body)?,
+            session_token: session_token_from_headers(headers)?,
+            charge: request_charge_from_headers(headers)?,
+            activity_id: activity_id_from_headers(headers)?,
+            diagnostics,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_operation_results() {
+        let body = r#"[
+            {"statusCode": 409, "requestCharge": 1.24},
+            {"statusCode": 424, "requestCharge": 0}
+        ]"#;
+        let results: Vec<TransactionalBatchOperationResult> = serde_json::from_str(body).unwrap();
+        assert_eq!(results[0].status_code, StatusCode::CONFLICT);
+        assert_eq!(results[1].status_code, StatusCode::FAILED_DEPENDENCY);
+        assert_eq!(results[1].document::<serde_json::Value>().unwrap(), None);
+
+        let body = r#"[{"statusCode": 201, "requestCharge": 6.48, "eTag": "\"00000000\"", "resourceBody": {"id": "a", "_ts": 1}}]"#;
+        let results: Vec<TransactionalBatchOperationResult> = serde_json::from_str(body).unwrap();
+        let document: serde_json::Value = results[0].document().unwrap().unwrap();
+        assert_eq!(document["id"], "a");
+    }
+}
@@ -0,0 +1,238 @@
+use crate::diagnostics::diagnostics_mut;
+use crate::headers::*;
+use async_timer::new_timer;
+use http::request::Parts;
+use hyper::body::Bytes;
+use hyper::body::HttpBody;
+use hyper::{Body, Request, Response, StatusCode};
+use hyper_rustls::HttpsConnector;
+use std::time::Duration;
+
+/// Governs how requests throttled by Cosmos DB (429 - Too Many Requests) are retried.
+///
+/// Before each retry the client waits for the time suggested by the service in the
+/// `x-ms-retry-after-ms` header or, if missing, for an exponentially growing delay starting
+/// from `base_wait`. Every wait is capped at `max_wait`. The 429 is returned to the caller
+/// once `max_retries` retries have been made or when waiting again would exceed
+/// `max_total_wait`. The defaults mirror the ones of the .NET SDK (9 retries, 30 seconds).
+///
+/// Requests whose body is streamed (created with `Body::wrap_stream`) are never retried.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_wait: Duration,
+    max_wait: Duration,
+    max_total_wait: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 9,
+            base_wait: Duration::from_millis(100),
+            max_wait: Duration::from_secs(5),
+            max_total_wait: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Throttled requests fail straight away with the 429 status code.
+    pub fn no_retries() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_retries(self, max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..self
+        }
+    }
+
+    /// Wait before the first retry when the service does not specify one.
+    pub fn with_base_wait(self, base_wait: Duration) -> Self {
+        Self { base_wait, ..self }
+    }
+
+    pub fn with_max_wait(self, max_wait: Duration) -> Self {
+        Self { max_wait, ..self }
+    }
+
+    pub fn with_max_total_wait(self, max_total_wait: Duration) -> Self {
+        Self {
+            max_total_wait,
+            ..self
+        }
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub fn base_wait(&self) -> Duration {
+        self.base_wait
+    }
+
+    pub fn max_wait(&self) -> Duration {
+        self.max_wait
+    }
+
+    pub fn max_total_wait(&self) -> Duration {
+        self.max_total_wait
+    }
+
+    // Returns how long to wait before retrying a throttled request or None if the
+    // request must not be retried anymore.
+    pub(crate) fn wait_time(
+        &self,
+        retry_count: u32,
+        throttled_time: Duration,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if retry_count >= self.max_retries {
+            return None;
+        }
+
+        let wait = retry_after
+            .unwrap_or_else(|| {
+                self.base_wait
+                    .checked_mul(2u32.saturating_pow(retry_count))
+                    .unwrap_or(self.max_wait)
+            })
+            .min(self.max_wait);
+
+        if throttled_time + wait > self.max_total_wait {
+            None
+        } else {
+            Some(wait)
+        }
+    }
+}
+
+fn retry_after_from_headers(headers: &hyper::HeaderMap) -> Option<Duration> {
+    headers
+        .get(HEADER_RETRY_AFTER_MS)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<f64>().ok())
+        .map(|millis| Duration::from_micros((millis * 1000.0) as u64))
+}
+
+// Sends the request retrying it as long as it gets throttled and the retry policy allows it.
+// The number of retries and the time spent waiting are recorded in the diagnostics kept in
+// the response extensions so every response can report them.
+pub(crate) async fn execute_with_retry_policy(
+    hyper_client: &hyper::Client<HttpsConnector<hyper::client::HttpConnector>>,
+    retry_policy: &RetryPolicy,
+    request: Request<Body>,
+) -> Result<Response<Body>, hyper::Error> {
+    if !is_replayable(request.body()) {
+        // a streamed body can be sent only once: the request is not retried
+        return hyper_client.request(request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    // the body must be buffered as it has to be sent again at every retry
+    let body = hyper::body::to_bytes(body).await?;
+
+    let mut retry_count = 0;
+    let mut throttled_time = Duration::default();
+
+    loop {
+        let request = copy_request(&parts, &body);
+
+        let mut response = hyper_client.request(request).await?;
+
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = retry_after_from_headers(response.headers());
+            if let Some(wait) = retry_policy.wait_time(retry_count, throttled_time, retry_after) {
+                debug!(
+                    "request throttled, retrying in {:?} (retry {})",
+                    wait,
+                    retry_count + 1
+                );
+                new_timer(wait).await;
+                retry_count += 1;
+                throttled_time += wait;
+                continue;
+            }
+        }
+
+        let diagnostics = diagnostics_mut(&mut response);
+        diagnostics.retry_count = retry_count;
+        diagnostics.throttled_time = throttled_time;
+        return Ok(response);
+    }
+}
+
+// Whether the body can be buffered and sent more than once: bodies streamed with
+// `Body::wrap_stream` have no known length and are sent as they are read.
+pub(crate) fn is_replayable(body: &Body) -> bool {
+    body.size_hint().exact().is_some()
+}
+
+// A new request with the parts and the buffered body of a request, to send it again.
+pub(crate) fn copy_request(parts: &Parts, body: &Bytes) -> Request<Body> {
+    let mut request = Request::new(Body::from(body.clone()));
+    *request.method_mut() = parts.method.clone();
+    *request.uri_mut() = parts.uri.clone();
+    *request.version_mut() = parts.version;
+    *request.headers_mut() = parts.headers.clone();
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_time_honors_retry_after() {
+        let retry_policy = RetryPolicy::default();
+        assert_eq!(
+            retry_policy.wait_time(0, Duration::default(), Some(Duration::from_millis(250))),
+            Some(Duration::from_millis(250))
+        );
+        // capped at max_wait
+        assert_eq!(
+            retry_policy.wait_time(0, Duration::default(), Some(Duration::from_secs(60))),
+            Some(retry_policy.max_wait())
+        );
+    }
+
+    #[test]
+    fn wait_time_backs_off_exponentially() {
+        let retry_policy = RetryPolicy::default();
+        assert_eq!(
+            retry_policy.wait_time(0, Duration::default(), None),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            retry_policy.wait_time(3, Duration::default(), None),
+            Some(Duration::from_millis(800))
+        );
+        assert_eq!(
+            retry_policy.wait_time(8, Duration::default(), None),
+            Some(retry_policy.max_wait())
+        );
+    }
+
+    #[test]
+    fn wait_time_gives_up() {
+        let retry_policy = RetryPolicy::default().with_max_retries(2);
+        assert_eq!(retry_policy.wait_time(2, Duration::default(), None), None);
+        assert_eq!(
+            retry_policy.wait_time(1, Duration::from_secs(29), Some(Duration::from_secs(2))),
+            None
+        );
+        assert_eq!(
+            RetryPolicy::no_retries().wait_time(0, Duration::default(), None),
+            None
+        );
+    }
+}
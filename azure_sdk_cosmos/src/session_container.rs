@@ -0,0 +1,290 @@
+use crate::headers::*;
+use crate::location_cache::execute_with_failover;
+use crate::CosmosClient;
+use hyper::header::HeaderValue;
+use hyper::{Body, HeaderMap, Request, Response};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+/// Session tokens of the collections used by a client, kept per partition key range.
+///
+/// Every request on the documents of a collection is sent with the session tokens the
+/// client received for that collection (unless the request already carries one, for
+/// example because it was built with `ConsistencyLevel::Session`) and every response
+/// updates them. This way requests made with session consistency always
+/// read the writes made by the same client. Clones share the session tokens.
+#[derive(Debug, Clone, Default)]
+pub struct SessionContainer {
+    // collection link (dbs/{db}/colls/{coll}) -> partition key range id -> token
+    tokens: Arc<RwLock<HashMap<String, BTreeMap<String, String>>>>,
+}
+
+impl SessionContainer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The session token to send to the collection, in the `{range id}:{token}` form
+    /// (comma separated when the collection has more than one partition key range).
+    pub fn session_token(&self, collection_link: &str) -> Option<String> {
+        self.tokens
+            .read()
+            .unwrap()
+            .get(collection_link)
+            .filter(|tokens| !tokens.is_empty())
+            .map(|tokens| join_tokens(tokens.iter()))
+    }
+
+    /// The session token to send to a single partition key range of the collection.
+    pub fn partition_key_range_session_token(
+        &self,
+        collection_link: &str,
+        partition_key_range_id: &str,
+    ) -> Option<String> {
+        self.tokens
+            .read()
+            .unwrap()
+            .get(collection_link)
+            .and_then(|tokens| tokens.get_key_value(partition_key_range_id))
+            .map(|token| join_tokens(std::iter::once(token)))
+    }
+
+    /// Merges the session token returned by the collection with the known ones, keeping
+    /// the most recent token of every partition key range.
+    pub fn update(&self, collection_link: &str, session_token: &str) {
+        let mut tokens = self.tokens.write().unwrap();
+        let collection_tokens = tokens.entry(collection_link.to_owned()).or_default();
+
+        for range_token in session_token.split(',') {
+            let mut parts = range_token.trim().splitn(2, ':');
+            let (range_id, token) = match (parts.next(), parts.next()) {
+                (Some(range_id), Some(token)) if !range_id.is_empty() => (range_id, token),
+                _ => {
+                    warn!("ignoring malformed session token {:?}", range_token);
+                    continue;
+                }
+            };
+
+            match collection_tokens.get_mut(range_id) {
+                Some(known) if token_version(known) >= token_version(token) => {}
+                Some(known) => *known = token.to_owned(),
+                None => {
+                    collection_tokens.insert(range_id.to_owned(), token.to_owned());
+                }
+            }
+        }
+    }
+
+    /// Forgets the session tokens of the collection, for example because it was deleted.
+    pub fn remove(&self, collection_link: &str) {
+        self.tokens.write().unwrap().remove(collection_link);
+    }
+
+    pub fn clear(&self) {
+        self.tokens.write().unwrap().clear();
+    }
+
+    fn set_session_token(&self, collection_link: &str, headers: &mut HeaderMap) {
+        if headers.contains_key(HEADER_SESSION_TOKEN) {
+            return;
+        }
+
+        let session_token = match headers
+            .get(HEADER_DOCUMENTDB_PARTITIONRANGEID)
+            .and_then(|range_id| range_id.to_str().ok())
+        {
+            Some(range_id) => self.partition_key_range_session_token(collection_link, range_id),
+            None => self.session_token(collection_link),
+        };
+
+        if let Some(session_token) = session_token {
+            match HeaderValue::from_str(&session_token) {
+                Ok(session_token) => {
+                    headers.insert(HEADER_SESSION_TOKEN, session_token);
+                }
+                Err(error) => warn!("invalid session token {:?}: {}", session_token, error),
+            }
+        }
+    }
+}
+
+fn join_tokens<'a, I>(tokens: I) -> String
+where
+    I: Iterator<Item = (&'a String, &'a String)>,
+{
+    tokens
+        .map(|(range_id, token)| format!("{}:{}", range_id, token))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Tokens are either a bare LSN (`12`) or `{version}#{global LSN}#{region id}={LSN}...`.
+fn token_version(token: &str) -> (i64, i64) {
+    let mut parts = token.split('#');
+    match (parts.next(), parts.next()) {
+        (Some(version), Some(global_lsn)) => (
+            version.parse().unwrap_or(-1),
+            global_lsn.parse().unwrap_or(-1),
+        ),
+        (Some(lsn), None) => (-1, lsn.parse().unwrap_or(-1)),
+        _ => (-1, -1),
+    }
+}
+
+// Link of the collection addressed by the request, if any: dbs/{db}/colls/{coll}.
+fn collection_link(path: &str) -> Option<&str> {
+    let path = path.trim_start_matches('/');
+    let segments = path.split('/').take(4).collect::<Vec<_>>();
+    match segments.as_slice() {
+        ["dbs", database, "colls", collection]
+            if !database.is_empty() && !collection.is_empty() =>
+        {
+            let length = segments.iter().map(|segment| segment.len()).sum::<usize>() + 3;
+            Some(&path[..length])
+        }
+        _ => None,
+    }
+}
+
+// Only the requests on the data of the collection (documents, their attachments, conflicts
+// and stored procedure executions) use its session tokens: the other resources (the
+// collection itself, its stored procedures, triggers, ...) are kept in the master partition
+// whose session tokens have nothing to do with the ones of the partition key ranges.
+fn is_data_request(method: &hyper::Method, path: &str) -> bool {
+    let segments = path.trim_start_matches('/').split('/').collect::<Vec<_>>();
+    match segments.get(4) {
+        Some(&"docs") | Some(&"conflicts") => true,
+        Some(&"sprocs") => *method == hyper::Method::POST && segments.len() == 6,
+        _ => false,
+    }
+}
+
+// Sends the request with the session token of its collection and stores the session
+// token returned with the response.
+pub(crate) async fn execute_with_session_token<C>(
+    cosmos_client: &C,
+    mut request: Request<Body>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    C: CosmosClient + ?Sized,
+{
+    let session_container = cosmos_client.session_container();
+    let path = request.uri().path().trim_start_matches('/').to_owned();
+    let collection_link = collection_link(&path);
+    let is_data_request = is_data_request(request.method(), &path);
+    let is_collection_delete =
+        request.method() == hyper::Method::DELETE && collection_link == Some(path.as_str());
+
+    if let (Some(collection_link), true) = (collection_link, is_data_request) {
+        session_container.set_session_token(collection_link, request.headers_mut());
+    }
+
+    let response = execute_with_failover(cosmos_client, request).await?;
+
+    if let Some(collection_link) = collection_link {
+        if is_collection_delete && response.status().is_success() {
+            session_container.remove(collection_link);
+        } else if let (Some(session_token), true) = (
+            response
+                .headers()
+                .get(HEADER_SESSION_TOKEN)
+                .and_then(|session_token| session_token.to_str().ok()),
+            is_data_request,
+        ) {
+            session_container.update(collection_link, session_token);
+        }
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_keeps_latest_tokens() {
+        let session_container = SessionContainer::new();
+        assert_eq!(session_container.session_token("dbs/db/colls/coll"), None);
+
+        session_container.update("dbs/db/colls/coll", "0:1#10#3=8");
+        session_container.update("dbs/db/colls/coll", "1:1#5#3=4,0:1#9#3=7");
+        session_container.update("dbs/db/colls/other", "0:42");
+
+        assert_eq!(
+            session_container.session_token("dbs/db/colls/coll"),
+            Some("0:1#10#3=8,1:1#5#3=4".to_owned())
+        );
+        assert_eq!(
+            session_container.partition_key_range_session_token("dbs/db/colls/coll", "1"),
+            Some("1:1#5#3=4".to_owned())
+        );
+
+        session_container.update("dbs/db/colls/coll", "0:2#1#3=1");
+        assert_eq!(
+            session_container.partition_key_range_session_token("dbs/db/colls/coll", "0"),
+            Some("0:2#1#3=1".to_owned())
+        );
+
+        session_container.remove("dbs/db/colls/coll");
+        assert_eq!(session_container.session_token("dbs/db/colls/coll"), None);
+        assert_eq!(
+            session_container.session_token("dbs/db/colls/other"),
+            Some("0:42".to_owned())
+        );
+    }
+
+    #[test]
+    fn set_session_token_keeps_explicit_token() {
+        let session_container = SessionContainer::new();
+        session_container.update("dbs/db/colls/coll", "0:1#10,1:1#12");
+
+        let mut headers = HeaderMap::new();
+        session_container.set_session_token("dbs/db/colls/coll", &mut headers);
+        assert_eq!(headers[HEADER_SESSION_TOKEN], "0:1#10,1:1#12");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HEADER_DOCUMENTDB_PARTITIONRANGEID,
+            HeaderValue::from_static("1"),
+        );
+        session_container.set_session_token("dbs/db/colls/coll", &mut headers);
+        assert_eq!(headers[HEADER_SESSION_TOKEN], "1:1#12");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(HEADER_SESSION_TOKEN, HeaderValue::from_static("0:1#3"));
+        session_container.set_session_token("dbs/db/colls/coll", &mut headers);
+        assert_eq!(headers[HEADER_SESSION_TOKEN], "0:1#3");
+    }
+
+    #[test]
+    fn collection_link_from_path() {
+        assert_eq!(
+            collection_link("/dbs/db/colls/coll/docs/doc"),
+            Some("dbs/db/colls/coll")
+        );
+        assert_eq!(
+            collection_link("/dbs/db/colls/coll"),
+            Some("dbs/db/colls/coll")
+        );
+        assert_eq!(collection_link("/dbs/db/colls"), None);
+        assert_eq!(collection_link("/dbs/db/users/user"), None);
+        assert_eq!(collection_link("/offers"), None);
+    }
+
+    #[test]
+    fn data_requests() {
+        let get = hyper::Method::GET;
+        let post = hyper::Method::POST;
+        assert!(is_data_request(&get, "/dbs/db/colls/coll/docs/doc"));
+        assert!(is_data_request(&post, "/dbs/db/colls/coll/docs"));
+        assert!(is_data_request(
+            &get,
+            "/dbs/db/colls/coll/docs/doc/attachments/a"
+        ));
+        assert!(is_data_request(&post, "/dbs/db/colls/coll/sprocs/sproc"));
+        assert!(!is_data_request(&post, "/dbs/db/colls/coll/sprocs"));
+        assert!(!is_data_request(&get, "/dbs/db/colls/coll"));
+        assert!(!is_data_request(&get, "/dbs/db/colls/coll/pkranges"));
+    }
+}
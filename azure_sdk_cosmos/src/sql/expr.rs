@@ -0,0 +1,350 @@
+use super::schema::Schema;
+use azure_sdk_core::errors::AzureError;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Path to a property of a document, rooted at an alias of the query (the collection alias
+/// or the alias of a JOIN).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    alias: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Property(String),
+    Index(usize),
+}
+
+impl Path {
+    pub fn new<A: Into<String>>(alias: A) -> Self {
+        Self {
+            alias: alias.into(),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Builds a path from its dot separated representation, for example `c.address.city`.
+    pub fn parse(path: &str) -> Self {
+        let mut properties = path.split('.');
+        // split always returns at least one item
+        let mut result = Self::new(properties.next().unwrap_or_default());
+        for property in properties {
+            result = result.property(property);
+        }
+        result
+    }
+
+    /// Builds the path `alias.path` after checking that `path` (dot separated, without the
+    /// alias) is a property of `T` as serialized by serde, honoring renames. The shape of
+    /// `T` is taken from `T::default()`, so properties below an `Option` that defaults to
+    /// `None`, a map or a sequence cannot be checked and are accepted.
+    pub fn checked<T, A>(alias: A, path: &str) -> Result<Self, AzureError>
+    where
+        T: Serialize + Default,
+        A: Into<String>,
+    {
+        let schema = Schema::of(&T::default())?;
+        if !schema.contains(path.split('.')) {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "{} is not a property of {}",
+                path,
+                std::any::type_name::<T>()
+            )));
+        }
+
+        Ok(path.split('.').fold(Self::new(alias), |result, property| {
+            result.property(property)
+        }))
+    }
+
+    pub fn property<P: Into<String>>(mut self, property: P) -> Self {
+        self.segments.push(Segment::Property(property.into()));
+        self
+    }
+
+    pub fn index(mut self, index: usize) -> Self {
+        self.segments.push(Segment::Index(index));
+        self
+    }
+
+    fn render(&self, sql: &mut String) {
+        sql.push_str(&self.alias);
+        for segment in &self.segments {
+            match segment {
+                Segment::Property(property) if is_identifier(property) => {
+                    sql.push('.');
+                    sql.push_str(property);
+                }
+                // serde_json escapes the quotes and the backslashes in the property name
+                Segment::Property(property) => {
+                    sql.push('[');
+                    sql.push_str(&Value::from(property.as_str()).to_string());
+                    sql.push(']');
+                }
+                Segment::Index(index) => {
+                    sql.push_str(&format!("[{}]", index));
+                }
+            }
+        }
+    }
+}
+
+// Keywords cannot be used with the dot notation (c.value is a syntax error).
+const RESERVED_WORDS: &str = "and array as asc between by case cast convert cross desc distinct \
+    else end escape exists false for from group having in inner insert into is join left like \
+    limit not null offset on or order outer over right select set then top true udf undefined \
+    update value when where with";
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    let starts_well = match chars.next() {
+        Some(c) => c.is_ascii_alphabetic() || c == '_',
+        None => false,
+    };
+    starts_well
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !RESERVED_WORDS
+            .split_whitespace()
+            .any(|word| word.eq_ignore_ascii_case(s))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOperator {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    And,
+    Or,
+}
+
+impl BinaryOperator {
+    fn as_str(self) -> &'static str {
+        match self {
+            BinaryOperator::Equal => "=",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanOrEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanOrEqual => ">=",
+            BinaryOperator::And => "AND",
+            BinaryOperator::Or => "OR",
+        }
+    }
+}
+
+/// A SQL expression. Values are never written in the query text: they are always sent as
+/// parameters so they need no escaping.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Path(Path),
+    Value(Value),
+    Function(&'static str, Vec<Expr>),
+    Binary(Box<Expr>, BinaryOperator, Box<Expr>),
+    Not(Box<Expr>),
+    In(Box<Expr>, Vec<Expr>),
+}
+
+impl Expr {
+    pub fn value<V: Into<Value>>(value: V) -> Self {
+        Expr::Value(value.into())
+    }
+
+    pub(crate) fn render(&self, sql: &mut String, parameters: &mut Vec<(String, Value)>) {
+        match self {
+            Expr::Path(path) => path.render(sql),
+            Expr::Value(value) => {
+                let name = format!("@p{}", parameters.len());
+                sql.push_str(&name);
+                parameters.push((name, value.clone()));
+            }
+            Expr::Function(name, arguments) => {
+                sql.push_str(name);
+                render_list(arguments, sql, parameters);
+            }
+            Expr::Binary(left, operator, right) => {
+                sql.push('(');
+                left.render(sql, parameters);
+                sql.push(' ');
+                sql.push_str(operator.as_str());
+                sql.push(' ');
+                right.render(sql, parameters);
+                sql.push(')');
+            }
+            Expr::Not(expr) => {
+                sql.push_str("NOT ");
+                expr.render(sql, parameters);
+            }
+            Expr::In(expr, values) => {
+                expr.render(sql, parameters);
+                sql.push_str(" IN ");
+                render_list(values, sql, parameters);
+            }
+        }
+    }
+}
+
+fn render_list(exprs: &[Expr], sql: &mut String, parameters: &mut Vec<(String, Value)>) {
+    sql.push('(');
+    for (i, expr) in exprs.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        expr.render(sql, parameters);
+    }
+    sql.push(')');
+}
+
+/// Operators shared by every expression. Values are converted to parameters.
+pub trait Operand: Into<Expr> {
+    fn equal<E: Into<Expr>>(self, other: E) -> Expr {
+        binary(self, BinaryOperator::Equal, other)
+    }
+
+    fn not_equal<E: Into<Expr>>(self, other: E) -> Expr {
+        binary(self, BinaryOperator::NotEqual, other)
+    }
+
+    fn less_than<E: Into<Expr>>(self, other: E) -> Expr {
+        binary(self, BinaryOperator::LessThan, other)
+    }
+
+    fn less_or_equal<E: Into<Expr>>(self, other: E) -> Expr {
+        binary(self, BinaryOperator::LessThanOrEqual, other)
+    }
+
+    fn greater_than<E: Into<Expr>>(self, other: E) -> Expr {
+        binary(self, BinaryOperator::GreaterThan, other)
+    }
+
+    fn greater_or_equal<E: Into<Expr>>(self, other: E) -> Expr {
+        binary(self, BinaryOperator::GreaterThanOrEqual, other)
+    }
+
+    fn and<E: Into<Expr>>(self, other: E) -> Expr {
+        binary(self, BinaryOperator::And, other)
+    }
+
+    fn or<E: Into<Expr>>(self, other: E) -> Expr {
+        binary(self, BinaryOperator::Or, other)
+    }
+
+    fn not(self) -> Expr {
+        Expr::Not(Box::new(self.into()))
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    fn is_in<I, E>(self, values: I) -> Expr
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Expr>,
+    {
+        Expr::In(
+            Box::new(self.into()),
+            values.into_iter().map(|value| value.into()).collect(),
+        )
+    }
+}
+
+fn binary<L: Into<Expr>, R: Into<Expr>>(left: L, operator: BinaryOperator, right: R) -> Expr {
+    Expr::Binary(Box::new(left.into()), operator, Box::new(right.into()))
+}
+
+impl Operand for Expr {}
+
+impl Operand for Path {}
+
+impl From<Path> for Expr {
+    fn from(path: Path) -> Self {
+        Expr::Path(path)
+    }
+}
+
+impl From<&Path> for Expr {
+    fn from(path: &Path) -> Self {
+        Expr::Path(path.clone())
+    }
+}
+
+macro_rules! impl_from_value {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Expr {
+                fn from(value: $t) -> Self {
+                    Expr::Value(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value!(bool, i32, i64, u32, u64, f64, &str, String, Value);
+
+/// `ARRAY_CONTAINS(array, value)`: true if the array contains the value.
+pub fn array_contains<A: Into<Expr>, V: Into<Expr>>(array: A, value: V) -> Expr {
+    Expr::Function("ARRAY_CONTAINS", vec![array.into(), value.into()])
+}
+
+/// `ARRAY_LENGTH(array)`.
+pub fn array_length<A: Into<Expr>>(array: A) -> Expr {
+    Expr::Function("ARRAY_LENGTH", vec![array.into()])
+}
+
+/// `IS_DEFINED(expr)`: true if the property exists in the document.
+pub fn is_defined<E: Into<Expr>>(expr: E) -> Expr {
+    Expr::Function("IS_DEFINED", vec![expr.into()])
+}
+
+/// `IS_NULL(expr)`.
+pub fn is_null<E: Into<Expr>>(expr: E) -> Expr {
+    Expr::Function("IS_NULL", vec![expr.into()])
+}
+
+/// `CONTAINS(string, substring)`.
+pub fn contains<S: Into<Expr>, T: Into<Expr>>(string: S, substring: T) -> Expr {
+    Expr::Function("CONTAINS", vec![string.into(), substring.into()])
+}
+
+/// `STARTSWITH(string, prefix)`.
+pub fn starts_with<S: Into<Expr>, P: Into<Expr>>(string: S, prefix: P) -> Expr {
+    Expr::Function("STARTSWITH", vec![string.into(), prefix.into()])
+}
+
+/// `LOWER(string)`.
+pub fn lower<S: Into<Expr>>(string: S) -> Expr {
+    Expr::Function("LOWER", vec![string.into()])
+}
+
+/// `UPPER(string)`.
+pub fn upper<S: Into<Expr>>(string: S) -> Expr {
+    Expr::Function("UPPER", vec![string.into()])
+}
+
+/// `COUNT(expr)` aggregate.
+pub fn count<E: Into<Expr>>(expr: E) -> Expr {
+    Expr::Function("COUNT", vec![expr.into()])
+}
+
+/// `SUM(expr)` aggregate.
+pub fn sum<E: Into<Expr>>(expr: E) -> Expr {
+    Expr::Function("SUM", vec![expr.into()])
+}
+
+/// `MIN(expr)` aggregate.
+pub fn min<E: Into<Expr>>(expr: E) -> Expr {
+    Expr::Function("MIN", vec![expr.into()])
+}
+
+/// `MAX(expr)` aggregate.
+pub fn max<E: Into<Expr>>(expr: E) -> Expr {
+    Expr::Function("MAX", vec![expr.into()])
+}
+
+/// `AVG(expr)` aggregate.
+pub fn avg<E: Into<Expr>>(expr: E) -> Expr {
+    Expr::Function("AVG", vec![expr.into()])
+}
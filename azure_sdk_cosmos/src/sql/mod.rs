@@ -0,0 +1,295 @@
+//! Typed builder of Cosmos DB SQL queries.
+//!
+//! Values are never interpolated in the query text: every value becomes a parameter
+//! (`@p0`, `@p1`, ...) so the names in the text always match the `Param`s of the
+//! resulting [`Query`](crate::Query).
+//!
+//! ```
+//! use azure_sdk_cosmos::sql::{array_contains, Operand, Path, Select};
+//!
+//! let c = Path::new("c");
+//! let query = Select::from("c")
+//!     .filter(c.clone().property("age").greater_or_equal(18))
+//!     .filter(array_contains(c.clone().property("tags"), "admin"))
+//!     .order_by(c.property("name"))
+//!     .build();
+//!
+//! assert_eq!(
+//!     query.text(),
+//!     "SELECT * FROM c WHERE ((c.age >= @p0) AND ARRAY_CONTAINS(c.tags, @p1)) ORDER BY c.name ASC"
+//! );
+//! ```
+mod expr;
+mod schema;
+pub use self::expr::{
+    array_contains, array_length, avg, contains, count, is_defined, is_null, lower, max, min,
+    starts_with, sum, upper, BinaryOperator, Expr, Operand, Path,
+};
+use crate::{Param, Query};
+use serde_json::Value;
+
+/// Builder of a SELECT query. Multiple filters are combined with AND.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Select {
+    alias: String,
+    top: Option<u64>,
+    distinct: bool,
+    projection: Projection,
+    joins: Vec<(String, Path)>,
+    filter: Option<Expr>,
+    order_by: Vec<(Expr, bool)>,
+    offset_limit: Option<(u64, u64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Projection {
+    All,
+    Value(Expr),
+    Columns(Vec<(Expr, Option<String>)>),
+}
+
+impl Select {
+    /// Selects from the collection using `alias` to refer to its documents.
+    pub fn from<A: Into<String>>(alias: A) -> Self {
+        Self {
+            alias: alias.into(),
+            top: None,
+            distinct: false,
+            projection: Projection::All,
+            joins: Vec::new(),
+            filter: None,
+            order_by: Vec::new(),
+            offset_limit: None,
+        }
+    }
+
+    pub fn top(self, top: u64) -> Self {
+        Self {
+            top: Some(top),
+            ..self
+        }
+    }
+
+    pub fn distinct(self) -> Self {
+        Self {
+            distinct: true,
+            ..self
+        }
+    }
+
+    /// Adds `expr` to the projected properties.
+    pub fn column<E: Into<Expr>>(self, expr: E) -> Self {
+        self.push_column(expr.into(), None)
+    }
+
+    /// Adds `expr AS name` to the projected properties.
+    pub fn column_as<E: Into<Expr>, N: Into<String>>(self, expr: E, name: N) -> Self {
+        self.push_column(expr.into(), Some(name.into()))
+    }
+
+    /// `SELECT VALUE expr`: returns the bare values instead of objects.
+    pub fn value<E: Into<Expr>>(self, expr: E) -> Self {
+        Self {
+            projection: Projection::Value(expr.into()),
+            ..self
+        }
+    }
+
+    /// `JOIN alias IN path`: iterates over the items of the array at `path`.
+    pub fn join<A: Into<String>>(mut self, alias: A, path: Path) -> Self {
+        self.joins.push((alias.into(), path));
+        self
+    }
+
+    pub fn filter<E: Into<Expr>>(self, expr: E) -> Self {
+        let filter = match self.filter {
+            Some(filter) => filter.and(expr),
+            None => expr.into(),
+        };
+        Self {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
+    pub fn order_by<E: Into<Expr>>(mut self, expr: E) -> Self {
+        self.order_by.push((expr.into(), true));
+        self
+    }
+
+    pub fn order_by_desc<E: Into<Expr>>(mut self, expr: E) -> Self {
+        self.order_by.push((expr.into(), false));
+        self
+    }
+
+    pub fn offset_limit(self, offset: u64, limit: u64) -> Self {
+        Self {
+            offset_limit: Some((offset, limit)),
+            ..self
+        }
+    }
+
+    fn push_column(mut self, expr: Expr, name: Option<String>) -> Self {
+        match &mut self.projection {
+            Projection::Columns(columns) => columns.push((expr, name)),
+            _ => self.projection = Projection::Columns(vec![(expr, name)]),
+        }
+        self
+    }
+
+    pub fn build(&self) -> SqlQuery {
+        let mut sql = String::from("SELECT ");
+        let mut parameters = Vec::new();
+
+        if self.distinct {
+            sql.push_str("DISTINCT ");
+        }
+        if let Some(top) = self.top {
+            sql.push_str(&format!("TOP {} ", top));
+        }
+
+        match &self.projection {
+            Projection::All => sql.push('*'),
+            Projection::Value(expr) => {
+                sql.push_str("VALUE ");
+                expr.render(&mut sql, &mut parameters);
+            }
+            Projection::Columns(columns) => {
+                for (i, (expr, name)) in columns.iter().enumerate() {
+                    if i > 0 {
+                        sql.push_str(", ");
+                    }
+                    expr.render(&mut sql, &mut parameters);
+                    if let Some(name) = name {
+                        sql.push_str(" AS ");
+                        sql.push_str(name);
+                    }
+                }
+            }
+        }
+
+        sql.push_str(" FROM ");
+        sql.push_str(&self.alias);
+
+        for (alias, path) in &self.joins {
+            sql.push_str(&format!(" JOIN {} IN ", alias));
+            Expr::from(path).render(&mut sql, &mut parameters);
+        }
+
+        if let Some(filter) = &self.filter {
+            sql.push_str(" WHERE ");
+            filter.render(&mut sql, &mut parameters);
+        }
+
+        for (i, (expr, ascending)) in self.order_by.iter().enumerate() {
+            sql.push_str(if i == 0 { " ORDER BY " } else { ", " });
+            expr.render(&mut sql, &mut parameters);
+            sql.push_str(if *ascending { " ASC" } else { " DESC" });
+        }
+
+        if let Some((offset, limit)) = self.offset_limit {
+            sql.push_str(&format!(" OFFSET {} LIMIT {}", offset, limit));
+        }
+
+        SqlQuery { sql, parameters }
+    }
+}
+
+/// The text of a query built with [`Select`](Select) and its parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlQuery {
+    sql: String,
+    parameters: Vec<(String, Value)>,
+}
+
+impl SqlQuery {
+    pub fn text(&self) -> &str {
+        &self.sql
+    }
+
+    pub fn parameters(&self) -> &[(String, Value)] {
+        &self.parameters
+    }
+
+    /// The query to pass to `QueryDocumentsBuilder::with_query`.
+    pub fn query(&self) -> Query<'_> {
+        Query::with_params(
+            self.sql.as_str(),
+            self.parameters
+                .iter()
+                .map(|(name, value)| Param::new(name.as_str(), value.clone()))
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default, Serialize)]
+    struct Order {
+        id: String,
+        #[serde(rename = "customerId")]
+        customer_id: String,
+        total: f64,
+        items: Vec<String>,
+    }
+
+    #[test]
+    fn build_select() {
+        let o = Path::new("o");
+        let i = Path::new("i");
+        let query = Select::from("o")
+            .top(10)
+            .column(o.clone().property("id"))
+            .column_as(i.clone(), "item")
+            .join("i", o.clone().property("items"))
+            .filter(o.clone().property("total").greater_than(100.5))
+            .filter(
+                o.clone()
+                    .property("status")
+                    .is_in(vec!["open", "paid"])
+                    .or(is_defined(o.clone().property("value")).not()),
+            )
+            .order_by_desc(o.property("total"))
+            .offset_limit(5, 10)
+            .build();
+
+        assert_eq!(
+            query.text(),
+            "SELECT TOP 10 o.id, i AS item FROM o JOIN i IN o.items \
+             WHERE ((o.total > @p0) AND (o.status IN (@p1, @p2) OR NOT IS_DEFINED(o[\"value\"]))) \
+             ORDER BY o.total DESC OFFSET 5 LIMIT 10"
+        );
+
+        let serialized = serde_json::to_string(&query.query()).unwrap();
+        assert_eq!(
+            serialized,
+            format!(
+                r#"{{"query":{},"parameters":[{{"name":"@p0","value":100.5}},{{"name":"@p1","value":"open"}},{{"name":"@p2","value":"paid"}}]}}"#,
+                Value::from(query.text())
+            )
+        );
+    }
+
+    #[test]
+    fn build_select_value() {
+        let query = Select::from("c")
+            .value(count(Path::new("c").property("id")))
+            .filter(Path::parse("c.address.city").equal("Milan"))
+            .build();
+        assert_eq!(
+            query.text(),
+            "SELECT VALUE COUNT(c.id) FROM c WHERE (c.address.city = @p0)"
+        );
+    }
+
+    #[test]
+    fn checked_path() {
+        let path = Path::checked::<Order, _>("o", "customerId").unwrap();
+        assert_eq!(path, Path::new("o").property("customerId"));
+        assert!(Path::checked::<Order, _>("o", "customer_id").is_err());
+        assert!(Path::checked::<Order, _>("o", "total.amount").is_err());
+    }
+}
@@ -0,0 +1,309 @@
+use azure_sdk_core::errors::AzureError;
+use serde::ser::{self, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Shape of a serialized value: the properties serde writes for it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Schema {
+    /// A number, string or boolean.
+    Scalar,
+    /// A sequence, a map, a `None` or an enum: their properties cannot be known from a
+    /// single value so every path below them is accepted.
+    Opaque,
+    Object(BTreeMap<&'static str, Schema>),
+}
+
+impl Schema {
+    pub(crate) fn of<T: Serialize>(value: &T) -> Result<Self, AzureError> {
+        value
+            .serialize(SchemaSerializer)
+            .map_err(|error| AzureError::GenericErrorWithText(error.0))
+    }
+
+    pub(crate) fn contains<'p, I>(&self, mut path: I) -> bool
+    where
+        I: Iterator<Item = &'p str>,
+    {
+        match path.next() {
+            None => true,
+            Some(property) => match self {
+                Schema::Scalar => false,
+                Schema::Opaque => true,
+                Schema::Object(properties) => properties
+                    .get(property)
+                    .map(|schema| schema.contains(path))
+                    .unwrap_or(false),
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SchemaError(String);
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl ser::Error for SchemaError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SchemaError(msg.to_string())
+    }
+}
+
+struct SchemaSerializer;
+
+struct ObjectSerializer(BTreeMap<&'static str, Schema>);
+
+struct OpaqueSerializer;
+
+impl ser::Serializer for SchemaSerializer {
+    type Ok = Schema;
+    type Error = SchemaError;
+    type SerializeSeq = OpaqueSerializer;
+    type SerializeTuple = OpaqueSerializer;
+    type SerializeTupleStruct = OpaqueSerializer;
+    type SerializeTupleVariant = OpaqueSerializer;
+    type SerializeMap = OpaqueSerializer;
+    type SerializeStruct = ObjectSerializer;
+    type SerializeStructVariant = OpaqueSerializer;
+
+    fn serialize_bool(self, _: bool) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_i8(self, _: i8) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_i16(self, _: i16) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_i32(self, _: i32) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_i64(self, _: i64) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_u8(self, _: u8) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_u16(self, _: u16) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_u32(self, _: u32) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_u64(self, _: u64) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_f32(self, _: f32) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_f64(self, _: f64) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_char(self, _: char) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_str(self, _: &str) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_bytes(self, _: &[u8]) -> Result<Schema, SchemaError> {
+        Ok(Schema::Opaque)
+    }
+    fn serialize_none(self) -> Result<Schema, SchemaError> {
+        Ok(Schema::Opaque)
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Schema, SchemaError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Schema, SchemaError> {
+        Ok(Schema::Opaque)
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Schema, SchemaError> {
+        Ok(Schema::Opaque)
+    }
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<Schema, SchemaError> {
+        Ok(Schema::Scalar)
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Schema, SchemaError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Schema, SchemaError> {
+        Ok(Schema::Opaque)
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<OpaqueSerializer, SchemaError> {
+        Ok(OpaqueSerializer)
+    }
+    fn serialize_tuple(self, _: usize) -> Result<OpaqueSerializer, SchemaError> {
+        Ok(OpaqueSerializer)
+    }
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<OpaqueSerializer, SchemaError> {
+        Ok(OpaqueSerializer)
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<OpaqueSerializer, SchemaError> {
+        Ok(OpaqueSerializer)
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<OpaqueSerializer, SchemaError> {
+        Ok(OpaqueSerializer)
+    }
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<ObjectSerializer, SchemaError> {
+        Ok(ObjectSerializer(BTreeMap::new()))
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<OpaqueSerializer, SchemaError> {
+        Ok(OpaqueSerializer)
+    }
+}
+
+impl ser::SerializeStruct for ObjectSerializer {
+    type Ok = Schema;
+    type Error = SchemaError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SchemaError> {
+        self.0.insert(key, value.serialize(SchemaSerializer)?);
+        Ok(())
+    }
+
+    // A field skipped in this value (with skip_serializing_if) can be present in others.
+    fn skip_field(&mut self, key: &'static str) -> Result<(), SchemaError> {
+        self.0.insert(key, Schema::Opaque);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Schema, SchemaError> {
+        Ok(Schema::Object(self.0))
+    }
+}
+
+// The items of sequences, maps and enum variants are not inspected.
+macro_rules! impl_opaque {
+    ($trait:ident, $method:ident) => {
+        impl ser::$trait for OpaqueSerializer {
+            type Ok = Schema;
+            type Error = SchemaError;
+
+            fn $method<T: ?Sized + Serialize>(&mut self, _: &T) -> Result<(), SchemaError> {
+                Ok(())
+            }
+
+            fn end(self) -> Result<Schema, SchemaError> {
+                Ok(Schema::Opaque)
+            }
+        }
+    };
+}
+
+impl_opaque!(SerializeSeq, serialize_element);
+impl_opaque!(SerializeTuple, serialize_element);
+impl_opaque!(SerializeTupleStruct, serialize_field);
+impl_opaque!(SerializeTupleVariant, serialize_field);
+
+impl ser::SerializeStructVariant for OpaqueSerializer {
+    type Ok = Schema;
+    type Error = SchemaError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), SchemaError> {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Schema, SchemaError> {
+        Ok(Schema::Opaque)
+    }
+}
+
+impl ser::SerializeMap for OpaqueSerializer {
+    type Ok = Schema;
+    type Error = SchemaError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, _: &T) -> Result<(), SchemaError> {
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, _: &T) -> Result<(), SchemaError> {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Schema, SchemaError> {
+        Ok(Schema::Opaque)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default, Serialize)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(Default, Serialize)]
+    struct Customer {
+        #[serde(rename = "customerName")]
+        name: String,
+        address: Address,
+        tags: Vec<String>,
+        manager: Option<Address>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        referrer: Option<Address>,
+    }
+
+    #[test]
+    fn schema_contains() {
+        let schema = Schema::of(&Customer::default()).unwrap();
+        assert!(schema.contains("customerName".split('.')));
+        assert!(!schema.contains("name".split('.')));
+        assert!(schema.contains("address.city".split('.')));
+        assert!(!schema.contains("address.city.zip".split('.')));
+        assert!(!schema.contains("address.town".split('.')));
+        assert!(schema.contains("tags".split('.')));
+        // None cannot be inspected
+        assert!(schema.contains("manager.anything".split('.')));
+        // skipped fields cannot be inspected either
+        assert!(schema.contains("referrer.city".split('.')));
+        assert!(!schema.contains("referer".split('.')));
+    }
+}
@@ -0,0 +1,1604 @@
+use super::query::SelectQuery;
+use crate::bulk::partition_key_component;
+use crate::clients::{ClientBuilder, CosmosStruct, CustomCosmosUri};
+use crate::headers::*;
+use crate::AuthorizationToken;
+use azure_sdk_core::errors::AzureError;
+use futures::channel::oneshot;
+use http::request::Parts;
+use hyper::header::{self, HeaderMap, HeaderValue};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// The key of the Cosmos DB emulator: the in-memory emulator does not check the signatures.
+const MASTER_KEY: &str =
+    "C2y6yDjf5/R+ob0N8A7Cgv30VRDJIWEHLM+4QDU5DE2nQ9nDuVTqobD4b8mGGyPMbIZnqyMsEcaGQy67XIw/Jw==";
+// Page size used when the request does not specify x-ms-max-item-count.
+const DEFAULT_MAX_ITEM_COUNT: usize = 100;
+const TIME_FORMAT: &str = "%a, %d %b %Y %H:%M:%S%.3f GMT";
+// Throughput of the collections created without x-ms-offer-throughput.
+const DEFAULT_THROUGHPUT: u64 = 400;
+// The scripts of a collection: the segment of their uri and the name of their list.
+const SCRIPTS: &[(&str, &str)] = &[
+    ("sprocs", "StoredProcedures"),
+    ("triggers", "Triggers"),
+    ("udfs", "UserDefinedFunctions"),
+];
+
+/// A Cosmos DB account kept in memory and served over plain HTTP on a random local port,
+/// to unit test code using the clients of this crate without the Cosmos DB emulator.
+/// Available with the `test_utils` feature.
+///
+/// It supports databases, collections (with a single partition key range), documents
+/// (with etags, partition keys and time to live), the SQL queries with simple filters,
+/// projections, ordering and aggregates, paging through continuation tokens, attachments
+/// (with their media), stored procedures, triggers and user defined functions (stored but
+/// never run), the offers of the collections, and users with permissions whose resource
+/// tokens expire and are scoped to their resource.
+/// Requests on other resources fail with 501 - Not Implemented. Master key signatures
+/// are not checked and throughput is never exhausted.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), azure_sdk_core::errors::AzureError> {
+/// use azure_sdk_cosmos::prelude::*;
+/// use azure_sdk_cosmos::test_utils::InMemoryEmulator;
+///
+/// let emulator = InMemoryEmulator::start().await?;
+/// let client = emulator.client();
+/// client.create_database().with_database_name(&"db").execute().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The server stops when the emulator is dropped.
+#[derive(Debug)]
+pub struct InMemoryEmulator {
+    address: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl InMemoryEmulator {
+    /// Starts the emulator. Must be called within a Tokio runtime.
+    pub async fn start() -> Result<Self, AzureError> {
+        let store = Arc::new(Mutex::new(Store::default()));
+        let make_service = make_service_fn(move |_| {
+            let store = store.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    handle_request(store.clone(), request)
+                }))
+            }
+        });
+
+        let server = Server::try_bind(&([127, 0, 0, 1], 0).into())?.serve(make_service);
+        let address = server.local_addr();
+        let (shutdown, shutdown_received) = oneshot::channel::<()>();
+        let server = server.with_graceful_shutdown(async {
+            shutdown_received.await.ok();
+        });
+
+        tokio::spawn(async move {
+            if let Err(error) = server.await {
+                warn!("in-memory emulator failed: {}", error);
+            }
+        });
+
+        Ok(Self {
+            address,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// The uri of the emulator, for example `http://127.0.0.1:49152`.
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// A client connected to the emulator, as returned by `ClientBuilder::new_custom`.
+    pub fn client(&self) -> CosmosStruct<'static, CustomCosmosUri> {
+        ClientBuilder::new_custom(
+            self.address.to_string(),
+            AuthorizationToken::new_master(MASTER_KEY).unwrap(),
+            self.uri(),
+        )
+        .unwrap()
+    }
+}
+
+impl Drop for InMemoryEmulator {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Store {
+    databases: Vec<DatabaseEntry>,
+    // signature -> resource token
+    resource_tokens: HashMap<String, ResourceToken>,
+    // media id -> content of a slug attachment
+    media: HashMap<String, Media>,
+    // the throughput offers of the collections
+    offers: Vec<Value>,
+    lsn: u64,
+    last_rid: u64,
+}
+
+// A token issued for a permission: it grants access to `resource` (a link such as
+// dbs/db/colls/coll) and to its children until it expires.
+#[derive(Debug)]
+struct ResourceToken {
+    resource: String,
+    read_only: bool,
+    expires_at: Instant,
+}
+
+#[derive(Debug)]
+struct DatabaseEntry {
+    resource: Value,
+    collections: Vec<CollectionEntry>,
+    users: Vec<UserEntry>,
+}
+
+#[derive(Debug)]
+struct UserEntry {
+    resource: Value,
+    permissions: Vec<Value>,
+}
+
+#[derive(Debug)]
+struct CollectionEntry {
+    resource: Value,
+    partition_key_paths: Vec<String>,
+    documents: Vec<DocumentEntry>,
+    // segment of the uri of the scripts (sprocs, triggers or udfs) -> scripts
+    scripts: HashMap<String, Vec<Value>>,
+}
+
+impl CollectionEntry {
+    // Deletes the documents whose time to live elapsed, as the service does in background.
+    // The ttl of the documents is ignored if the collection has no defaultTtl.
+    fn remove_expired_documents(&mut self, now: i64) {
+        let default_ttl = match self.resource["defaultTtl"].as_i64() {
+            Some(default_ttl) => default_ttl,
+            None => return,
+        };
+        self.documents.retain(|entry| {
+            let ttl = entry.document["ttl"].as_i64().unwrap_or(default_ttl);
+            ttl < 0 || entry.document["_ts"].as_i64().unwrap_or_default() + ttl > now
+        });
+    }
+}
+
+#[derive(Debug)]
+struct DocumentEntry {
+    partition_key: Value,
+    document: Value,
+    attachments: Vec<Value>,
+}
+
+// The content of an attachment created with a Slug header, stored by the account apart
+// from the attachment.
+#[derive(Debug, Clone)]
+struct Media {
+    content_type: String,
+    content: Vec<u8>,
+}
+
+// What the emulator answers: the status code, the body and the headers depending on the
+// request. The headers shared by every response are added by `into_response`.
+#[derive(Debug)]
+struct Reply {
+    status: StatusCode,
+    body: Option<Value>,
+    charge: f64,
+    item_count: Option<usize>,
+    continuation: Option<String>,
+    // replaces the JSON body
+    media: Option<Media>,
+}
+
+impl Reply {
+    fn new(status: StatusCode, body: Value, charge: f64) -> Self {
+        Self {
+            status,
+            body: Some(body),
+            charge,
+            item_count: None,
+            continuation: None,
+            media: None,
+        }
+    }
+
+    fn empty(status: StatusCode) -> Self {
+        Self {
+            status,
+            body: None,
+            charge: 1.0,
+            item_count: None,
+            continuation: None,
+            media: None,
+        }
+    }
+
+    fn media(media: &Media) -> Self {
+        Self {
+            media: Some(media.clone()),
+            ..Self::empty(StatusCode::OK)
+        }
+    }
+
+    // Boxed since errors are returned as the `Err` of the handlers.
+    fn error<M: Into<String>>(status: StatusCode, message: M) -> Box<Self> {
+        let code = status
+            .canonical_reason()
+            .unwrap_or_default()
+            .replace(' ', "");
+        Box::new(Self::new(
+            status,
+            json!({ "code": code, "message": message.into() }),
+            1.0,
+        ))
+    }
+
+    fn into_response(self, request: &Parts, lsn: u64) -> Response<Body> {
+        let now = chrono::Utc::now().format(TIME_FORMAT).to_string();
+        let activity_id = request
+            .headers
+            .get(HEADER_ACTIVITY_ID)
+            .and_then(|activity_id| activity_id.to_str().ok())
+            .and_then(|activity_id| uuid::Uuid::parse_str(activity_id).ok())
+            .unwrap_or_else(uuid::Uuid::new_v4);
+        let etag = self
+            .body
+            .as_ref()
+            .and_then(|body| body.get("_etag"))
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+            .unwrap_or_else(new_etag);
+        let content_path = request.uri.path().trim_start_matches('/');
+
+        let mut headers = HeaderMap::new();
+        let mut insert = |name: &'static str, value: String| {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(name, value);
+            }
+        };
+        let content_type = match &self.media {
+            Some(media) => media.content_type.clone(),
+            None => "application/json".to_owned(),
+        };
+        insert(header::CONTENT_TYPE.as_str(), content_type);
+        insert(header::CONTENT_LOCATION.as_str(), request.uri.to_string());
+        insert(header::SERVER.as_str(), "Microsoft-HTTPAPI/2.0".to_owned());
+        insert(header::DATE.as_str(), now.clone());
+        insert(header::ETAG.as_str(), etag);
+        insert(HEADER_ACTIVITY_ID, activity_id.to_string());
+        insert(HEADER_REQUEST_CHARGE, self.charge.to_string());
+        insert(HEADER_SESSION_TOKEN, format!("0:-1#{}", lsn));
+        insert(HEADER_LAST_STATE_CHANGE_UTC, now);
+        insert(
+            HEADER_RESOURCE_QUOTA,
+            "documentSize=10240;documentsSize=10485760;documentsCount=-1;collectionSize=10485760;"
+                .to_owned(),
+        );
+        insert(
+            HEADER_RESOURCE_USAGE,
+            "documentSize=0;documentsSize=0;documentsCount=0;collectionSize=0;".to_owned(),
+        );
+        insert(HEADER_SCHEMA_VERSION, "1.10".to_owned());
+        insert(HEADER_SERVICE_VERSION, "version=2.11.0.0".to_owned());
+        insert(HEADER_GATEWAY_VERSION, "version=2.11.0".to_owned());
+        insert(HEADER_CONTENT_PATH, content_path.to_owned());
+        insert(HEADER_ALT_CONTENT_PATH, content_path.to_owned());
+        for name in &[
+            HEADER_LSN,
+            HEADER_ITEM_LSN,
+            HEADER_GLOBAL_COMMITTED_LSN,
+            HEADER_QUORUM_ACKED_LSN,
+            HEADER_COSMOS_LLSN,
+            HEADER_COSMOS_ITEM_LLSN,
+            HEADER_COSMOS_QUORUM_ACKED_LLSN,
+            HEADER_TRANSPORT_REQUEST_ID,
+        ] {
+            insert(name, lsn.to_string());
+        }
+        for (name, value) in &[
+            (HEADER_CURRENT_WRITE_QUORUM, 1),
+            (HEADER_CURRENT_REPLICA_SET_SIZE, 1),
+            (HEADER_ROLE, 0),
+            (HEADER_NUMBER_OF_READ_REGIONS, 0),
+            (HEADER_COLLECTION_PARTITION_INDEX, 0),
+            (HEADER_COLLECTION_SERVICE_INDEX, 0),
+            (HEADER_MAX_MEDIA_STORAGE_USAGE_MB, 10240),
+            (HEADER_MEDIA_STORAGE_USAGE_MB, 0),
+        ] {
+            insert(name, value.to_string());
+        }
+        if let Some(item_count) = self.item_count {
+            insert(HEADER_ITEM_COUNT, item_count.to_string());
+        }
+        if let Some(continuation) = self.continuation {
+            insert(HEADER_CONTINUATION, continuation);
+        }
+
+        let body = match (self.media, self.body) {
+            (Some(media), _) => Body::from(media.content),
+            (None, Some(body)) => Body::from(body.to_string()),
+            (None, None) => Body::empty(),
+        };
+        let mut response = Response::new(body);
+        *response.status_mut() = self.status;
+        *response.headers_mut() = headers;
+        response
+    }
+}
+
+async fn handle_request(
+    store: Arc<Mutex<Store>>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+    debug!("in-memory emulator: {} {}", parts.method, parts.uri);
+
+    let mut store = store.lock().unwrap();
+    let reply = store.handle(&parts, &body).unwrap_or_else(|error| *error);
+    Ok(reply.into_response(&parts, store.lsn))
+}
+
+impl Store {
+    fn handle(&mut self, request: &Parts, body: &[u8]) -> Result<Reply, Box<Reply>> {
+        let segments = request
+            .uri
+            .path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect::<Vec<_>>();
+        let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+        self.check_resource_token(request, &segments)?;
+
+        match (&request.method, segments.as_slice()) {
+            (&Method::GET, []) => Ok(Reply::new(StatusCode::OK, database_account(), 1.0)),
+            (&Method::GET, ["dbs"]) => {
+                let databases = self
+                    .databases
+                    .iter()
+                    .map(|database| database.resource.clone())
+                    .collect();
+                list(request, "", "Databases", databases)
+            }
+            (&Method::POST, ["dbs"]) => self.create_database(body),
+            (&Method::GET, ["dbs", database]) => Ok(Reply::new(
+                StatusCode::OK,
+                self.database(database)?.resource.clone(),
+                1.0,
+            )),
+            (&Method::DELETE, ["dbs", database]) => {
+                let self_link = self.database(database)?.resource["_self"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_owned();
+                self.databases
+                    .retain(|entry| entry.resource["id"] != *database);
+                self.offers.retain(|offer| {
+                    !offer["resource"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .starts_with(&self_link)
+                });
+                self.lsn += 1;
+                Ok(Reply::empty(StatusCode::NO_CONTENT))
+            }
+            (&Method::GET, ["dbs", database, "colls"]) => {
+                let database = self.database(database)?;
+                let collections = database
+                    .collections
+                    .iter()
+                    .map(|collection| collection.resource.clone())
+                    .collect();
+                list(
+                    request,
+                    database.resource["_rid"].as_str().unwrap_or_default(),
+                    "DocumentCollections",
+                    collections,
+                )
+            }
+            (&Method::POST, ["dbs", database, "colls"]) => {
+                self.create_collection(request, database, body)
+            }
+            (&Method::GET, ["dbs", database, "colls", collection]) => Ok(Reply::new(
+                StatusCode::OK,
+                self.collection(database, collection)?.resource.clone(),
+                1.0,
+            )),
+            (&Method::PUT, ["dbs", database, "colls", collection]) => {
+                self.replace_collection(database, collection, body)
+            }
+            (&Method::DELETE, ["dbs", database, "colls", collection]) => {
+                let rid = self.collection(database, collection)?.resource["_rid"].clone();
+                self.database(database)?
+                    .collections
+                    .retain(|entry| entry.resource["id"] != *collection);
+                self.offers.retain(|offer| offer["offerResourceId"] != rid);
+                self.lsn += 1;
+                Ok(Reply::empty(StatusCode::NO_CONTENT))
+            }
+            (&Method::GET, ["dbs", database, "colls", collection, "pkranges"]) => {
+                let collection = self.collection(database, collection)?;
+                let rid = collection.resource["_rid"].as_str().unwrap_or_default();
+                let partition_key_range = json!({
+                    "_rid": format!("{}AgAAAAAAAAA=", rid),
+                    "id": "0",
+                    "_etag": collection.resource["_etag"],
+                    "minInclusive": "",
+                    "maxExclusive": "FF",
+                    "ridPrefix": 0,
+                    "_self": format!("{}pkranges/0/", collection.resource["_self"].as_str().unwrap_or_default()),
+                    "throughputFraction": 1,
+                    "status": "online",
+                    "parents": [],
+                    "_ts": collection.resource["_ts"],
+                });
+                list(
+                    request,
+                    rid,
+                    "PartitionKeyRanges",
+                    vec![partition_key_range],
+                )
+            }
+            (&Method::GET, ["dbs", database, "colls", collection, "docs"]) => {
+                let collection = self.collection(database, collection)?;
+                let documents = collection
+                    .documents
+                    .iter()
+                    .map(|entry| entry.document.clone())
+                    .collect();
+                list(
+                    request,
+                    collection.resource["_rid"].as_str().unwrap_or_default(),
+                    "Documents",
+                    documents,
+                )
+            }
+            (&Method::POST, ["dbs", database, "colls", collection, "docs"])
+                if is_query(request) =>
+            {
+                let collection = self.collection(database, collection)?;
+                query_documents(request, collection, body)
+            }
+            (&Method::POST, ["dbs", database, "colls", collection, "docs"]) => {
+                self.upsert_document(request, database, collection, None, body)
+            }
+            (&Method::PUT, ["dbs", database, "colls", collection, "docs", id]) => {
+                self.upsert_document(request, database, collection, Some(id), body)
+            }
+            (&Method::GET, ["dbs", database, "colls", collection, "docs", id]) => {
+                let collection = self.collection(database, collection)?;
+                let partition_key = request_partition_key(request, collection)?;
+                let entry = find_document(collection, &partition_key, id)?;
+                if if_none_match(request, &entry.document) {
+                    Ok(Reply::empty(StatusCode::NOT_MODIFIED))
+                } else {
+                    Ok(Reply::new(StatusCode::OK, entry.document.clone(), 1.0))
+                }
+            }
+            (&Method::DELETE, ["dbs", database, "colls", collection, "docs", id]) => {
+                let collection = self.collection(database, collection)?;
+                let partition_key = request_partition_key(request, collection)?;
+                let entry = find_document(collection, &partition_key, id)?;
+                check_if_match(request, &entry.document)?;
+                collection.documents.retain(|entry| {
+                    entry.partition_key != partition_key || entry.document["id"] != *id
+                });
+                self.lsn += 1;
+                Ok(Reply::empty(StatusCode::NO_CONTENT))
+            }
+            (&Method::GET, ["dbs", database, "colls", collection, kind]) if is_script(kind) => {
+                let collection = self.collection(database, collection)?;
+                let scripts = collection.scripts.get(*kind).cloned().unwrap_or_default();
+                list(
+                    request,
+                    collection.resource["_rid"].as_str().unwrap_or_default(),
+                    script_list_name(kind),
+                    scripts,
+                )
+            }
+            (&Method::POST, ["dbs", database, "colls", collection, kind]) if is_script(kind) => {
+                self.upsert_script(database, collection, kind, None, body)
+            }
+            (&Method::PUT, ["dbs", database, "colls", collection, kind, id]) if is_script(kind) => {
+                self.upsert_script(database, collection, kind, Some(id), body)
+            }
+            (&Method::DELETE, ["dbs", database, "colls", collection, kind, id])
+                if is_script(kind) =>
+            {
+                let scripts = self
+                    .collection(database, collection)?
+                    .scripts
+                    .entry((*kind).to_owned())
+                    .or_default();
+                let count = scripts.len();
+                scripts.retain(|existing| existing["id"] != *id);
+                if scripts.len() == count {
+                    return Err(Reply::error(
+                        StatusCode::NOT_FOUND,
+                        format!("{} {} does not exist", kind, id),
+                    ));
+                }
+                self.lsn += 1;
+                Ok(Reply::empty(StatusCode::NO_CONTENT))
+            }
+            (&Method::POST, ["dbs", database, "colls", collection, "docs", id, "attachments"]) => {
+                self.create_attachment(request, database, collection, id, body)
+            }
+            (
+                &Method::GET,
+                ["dbs", database, "colls", collection, "docs", id, "attachments", attachment],
+            ) => {
+                let document = self.document(request, database, collection, id)?;
+                let attachment = find_attachment(document, attachment)?;
+                Ok(Reply::new(StatusCode::OK, attachment.clone(), 1.0))
+            }
+            (
+                &Method::DELETE,
+                ["dbs", database, "colls", collection, "docs", id, "attachments", attachment],
+            ) => {
+                let document = self.document(request, database, collection, id)?;
+                let media = find_attachment(document, attachment)?["media"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_owned();
+                document
+                    .attachments
+                    .retain(|existing| existing["id"] != *attachment);
+                if let Some(media) = media.strip_prefix("/media/") {
+                    self.media.remove(media);
+                }
+                self.lsn += 1;
+                Ok(Reply::empty(StatusCode::NO_CONTENT))
+            }
+            (&Method::GET, ["media", media]) => match self.media.get(*media) {
+                Some(media) => Ok(Reply::media(media)),
+                None => Err(Reply::error(
+                    StatusCode::NOT_FOUND,
+                    format!("media {} does not exist", media),
+                )),
+            },
+            (&Method::PUT, ["media", media]) => self.replace_media(request, media, body),
+            (&Method::GET, ["offers"]) => list(request, "", "Offers", self.offers.clone()),
+            (&Method::GET, ["offers", offer]) => {
+                Ok(Reply::new(StatusCode::OK, self.offer(offer)?.clone(), 1.0))
+            }
+            (&Method::PUT, ["offers", offer]) => self.replace_offer(offer, body),
+            (&Method::POST, ["dbs", database, "users"]) => self.create_user(database, body),
+            (&Method::GET, ["dbs", database, "users", user]) => Ok(Reply::new(
+                StatusCode::OK,
+                self.user(database, user)?.resource.clone(),
+                1.0,
+            )),
+            (&Method::POST, ["dbs", database, "users", user, "permissions"]) => {
+                self.upsert_permission(request, database, user, None, body)
+            }
+            (&Method::PUT, ["dbs", database, "users", user, "permissions", permission]) => {
+                self.upsert_permission(request, database, user, Some(permission), body)
+            }
+            (&Method::DELETE, ["dbs", database, "users", user, "permissions", permission]) => {
+                let user = self.user(database, user)?;
+                let count = user.permissions.len();
+                user.permissions
+                    .retain(|existing| existing["id"] != *permission);
+                if user.permissions.len() == count {
+                    return Err(Reply::error(
+                        StatusCode::NOT_FOUND,
+                        format!("permission {} does not exist", permission),
+                    ));
+                }
+                self.lsn += 1;
+                Ok(Reply::empty(StatusCode::NO_CONTENT))
+            }
+            _ => Err(Reply::error(
+                StatusCode::NOT_IMPLEMENTED,
+                format!(
+                    "{} {} is not supported by the in-memory emulator",
+                    request.method,
+                    request.uri.path()
+                ),
+            )),
+        }
+    }
+
+    fn database(&mut self, id: &str) -> Result<&mut DatabaseEntry, Box<Reply>> {
+        self.databases
+            .iter_mut()
+            .find(|database| database.resource["id"] == id)
+            .ok_or_else(|| {
+                Reply::error(
+                    StatusCode::NOT_FOUND,
+                    format!("database {} does not exist", id),
+                )
+            })
+    }
+
+    fn collection(&mut self, database: &str, id: &str) -> Result<&mut CollectionEntry, Box<Reply>> {
+        let collection = self
+            .database(database)?
+            .collections
+            .iter_mut()
+            .find(|collection| collection.resource["id"] == id)
+            .ok_or_else(|| {
+                Reply::error(
+                    StatusCode::NOT_FOUND,
+                    format!("collection {} does not exist", id),
+                )
+            })?;
+        collection.remove_expired_documents(chrono::Utc::now().timestamp());
+        Ok(collection)
+    }
+
+    fn document(
+        &mut self,
+        request: &Parts,
+        database: &str,
+        collection: &str,
+        id: &str,
+    ) -> Result<&mut DocumentEntry, Box<Reply>> {
+        let collection = self.collection(database, collection)?;
+        let partition_key = request_partition_key(request, collection)?;
+        collection
+            .documents
+            .iter_mut()
+            .find(|entry| entry.partition_key == partition_key && entry.document["id"] == id)
+            .ok_or_else(|| {
+                Reply::error(
+                    StatusCode::NOT_FOUND,
+                    format!("document {} does not exist", id),
+                )
+            })
+    }
+
+    fn offer(&mut self, id: &str) -> Result<&mut Value, Box<Reply>> {
+        self.offers
+            .iter_mut()
+            .find(|offer| offer["id"] == id)
+            .ok_or_else(|| {
+                Reply::error(
+                    StatusCode::NOT_FOUND,
+                    format!("offer {} does not exist", id),
+                )
+            })
+    }
+
+    fn user(&mut self, database: &str, id: &str) -> Result<&mut UserEntry, Box<Reply>> {
+        self.database(database)?
+            .users
+            .iter_mut()
+            .find(|user| user.resource["id"] == id)
+            .ok_or_else(|| {
+                Reply::error(StatusCode::NOT_FOUND, format!("user {} does not exist", id))
+            })
+    }
+
+    // Requests signed with a resource token can only reach the resource of the token (and
+    // its children) and, if the permission mode is Read, only read it. Signatures made
+    // with the master key are not checked.
+    fn check_resource_token(&self, request: &Parts, segments: &[&str]) -> Result<(), Box<Reply>> {
+        let authorization = request
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|authorization| authorization.to_str().ok())
+            .map(percent_decode)
+            .unwrap_or_default();
+        if !authorization.starts_with("type=resource") {
+            return Ok(());
+        }
+
+        let signature = authorization
+            .split('&')
+            .find_map(|part| part.strip_prefix("sig="))
+            .unwrap_or_default();
+        let resource_token = match self.resource_tokens.get(signature) {
+            Some(resource_token) if resource_token.expires_at > Instant::now() => resource_token,
+            Some(_) => {
+                return Err(Reply::error(
+                    StatusCode::UNAUTHORIZED,
+                    "the resource token has expired",
+                ))
+            }
+            None => {
+                return Err(Reply::error(
+                    StatusCode::UNAUTHORIZED,
+                    "the resource token is not valid",
+                ))
+            }
+        };
+
+        let resource = resource_token
+            .resource
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        let is_read = request.method == Method::GET || is_query(request);
+        if !segments.starts_with(&resource) || (resource_token.read_only && !is_read) {
+            return Err(Reply::error(
+                StatusCode::FORBIDDEN,
+                "the resource token does not grant access to the resource",
+            ));
+        }
+        Ok(())
+    }
+
+    fn next_rid(&mut self) -> String {
+        self.last_rid += 1;
+        base64::encode(&self.last_rid.to_be_bytes()[4..])
+    }
+
+    fn create_database(&mut self, body: &[u8]) -> Result<Reply, Box<Reply>> {
+        let mut resource = parse_resource(body)?;
+        let id = resource_id(&resource)?;
+        if self
+            .databases
+            .iter()
+            .any(|database| database.resource["id"] == id)
+        {
+            return Err(Reply::error(
+                StatusCode::CONFLICT,
+                format!("database {} already exists", id),
+            ));
+        }
+
+        let rid = self.next_rid();
+        set_system_properties(&mut resource, &rid, format!("dbs/{}/", rid));
+        resource["_colls"] = json!("colls/");
+        resource["_users"] = json!("users/");
+
+        self.lsn += 1;
+        self.databases.push(DatabaseEntry {
+            resource: resource.clone(),
+            collections: Vec::new(),
+            users: Vec::new(),
+        });
+        Ok(Reply::new(StatusCode::CREATED, resource, 4.95))
+    }
+
+    fn create_collection(
+        &mut self,
+        request: &Parts,
+        database: &str,
+        body: &[u8],
+    ) -> Result<Reply, Box<Reply>> {
+        let mut resource = parse_resource(body)?;
+        let id = resource_id(&resource)?;
+        let throughput = match request.headers.get(HEADER_OFFER_THROUGHPUT) {
+            Some(throughput) => throughput
+                .to_str()
+                .ok()
+                .and_then(|throughput| throughput.parse::<u64>().ok())
+                .ok_or_else(|| {
+                    Reply::error(StatusCode::BAD_REQUEST, "invalid x-ms-offer-throughput")
+                })?,
+            None => DEFAULT_THROUGHPUT,
+        };
+        let rid = self.next_rid();
+        let offer_rid = self.next_rid();
+        let database = self.database(database)?;
+        if database
+            .collections
+            .iter()
+            .any(|collection| collection.resource["id"] == id)
+        {
+            return Err(Reply::error(
+                StatusCode::CONFLICT,
+                format!("collection {} already exists", id),
+            ));
+        }
+
+        let self_link = format!(
+            "{}colls/{}/",
+            database.resource["_self"].as_str().unwrap_or_default(),
+            rid
+        );
+        set_system_properties(&mut resource, &rid, self_link);
+        if resource.get("indexingPolicy").is_none() {
+            resource["indexingPolicy"] = json!({
+                "automatic": true,
+                "indexingMode": "consistent",
+                "includedPaths": [{ "path": "/*" }],
+                "excludedPaths": [],
+            });
+        }
+        if resource.get("partitionKey").is_none() {
+            resource["partitionKey"] = json!({ "paths": [], "kind": "Hash" });
+        }
+        if resource.get("uniqueKeyPolicy").is_none() {
+            resource["uniqueKeyPolicy"] = json!({ "uniqueKeys": [] });
+        }
+        for (property, value) in &[
+            ("_docs", "docs/"),
+            ("_sprocs", "sprocs/"),
+            ("_triggers", "triggers/"),
+            ("_udfs", "udfs/"),
+            ("_conflicts", "conflicts/"),
+        ] {
+            resource[*property] = json!(value);
+        }
+
+        let partition_key_paths = resource["partitionKey"]["paths"]
+            .as_array()
+            .map(|paths| {
+                paths
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        database.collections.push(CollectionEntry {
+            resource: resource.clone(),
+            partition_key_paths,
+            documents: Vec::new(),
+            scripts: HashMap::new(),
+        });
+
+        let mut offer = json!({
+            "id": offer_rid,
+            "offerVersion": "V2",
+            "offerType": "Invalid",
+            "content": { "offerThroughput": throughput },
+            "resource": resource["_self"],
+            "offerResourceId": rid,
+        });
+        set_system_properties(&mut offer, &offer_rid, format!("offers/{}/", offer_rid));
+        self.offers.push(offer);
+        self.lsn += 1;
+        Ok(Reply::new(StatusCode::CREATED, resource, 4.95))
+    }
+
+    fn replace_collection(
+        &mut self,
+        database: &str,
+        id: &str,
+        body: &[u8],
+    ) -> Result<Reply, Box<Reply>> {
+        let mut resource = parse_resource(body)?;
+        if resource_id(&resource)? != id {
+            return Err(Reply::error(
+                StatusCode::BAD_REQUEST,
+                "the id of the collection does not match the one of the uri",
+            ));
+        }
+        let collection = self.collection(database, id)?;
+        if let Some(partition_key) = resource.get("partitionKey") {
+            if partition_key["paths"] != collection.resource["partitionKey"]["paths"] {
+                return Err(Reply::error(
+                    StatusCode::BAD_REQUEST,
+                    "the partition key of a collection cannot be changed",
+                ));
+            }
+        }
+        if let Some(unique_key_policy) = resource.get("uniqueKeyPolicy") {
+            if *unique_key_policy != collection.resource["uniqueKeyPolicy"] {
+                return Err(Reply::error(
+                    StatusCode::BAD_REQUEST,
+                    "the unique key policy of a collection cannot be changed",
+                ));
+            }
+        }
+
+        for property in &[
+            "partitionKey",
+            "uniqueKeyPolicy",
+            "_rid",
+            "_self",
+            "_docs",
+            "_sprocs",
+            "_triggers",
+            "_udfs",
+            "_conflicts",
+        ] {
+            resource[*property] = collection.resource[*property].clone();
+        }
+        if resource.get("indexingPolicy").is_none() {
+            resource["indexingPolicy"] = collection.resource["indexingPolicy"].clone();
+        }
+        touch(&mut resource);
+        collection.resource = resource.clone();
+        self.lsn += 1;
+        Ok(Reply::new(StatusCode::OK, resource, 9.52))
+    }
+
+    // Only the throughput of an offer can be changed.
+    fn replace_offer(&mut self, id: &str, body: &[u8]) -> Result<Reply, Box<Reply>> {
+        let resource = parse_resource(body)?;
+        let throughput = resource["content"]["offerThroughput"]
+            .as_u64()
+            .filter(|throughput| *throughput >= DEFAULT_THROUGHPUT)
+            .ok_or_else(|| {
+                Reply::error(
+                    StatusCode::BAD_REQUEST,
+                    format!("the throughput must be at least {}", DEFAULT_THROUGHPUT),
+                )
+            })?;
+        let offer = self.offer(id)?;
+        offer["content"]["offerThroughput"] = json!(throughput);
+        touch(offer);
+        let offer = offer.clone();
+        self.lsn += 1;
+        Ok(Reply::new(StatusCode::OK, offer, 1.0))
+    }
+
+    // Creates the script if `replaced_id` is None, otherwise replaces it.
+    fn upsert_script(
+        &mut self,
+        database: &str,
+        collection: &str,
+        kind: &str,
+        replaced_id: Option<&str>,
+        body: &[u8],
+    ) -> Result<Reply, Box<Reply>> {
+        let mut resource = parse_resource(body)?;
+        let id = resource_id(&resource)?;
+        let rid = self.next_rid();
+        let collection = self.collection(database, collection)?;
+        let self_link = format!(
+            "{}{}/{}/",
+            collection.resource["_self"].as_str().unwrap_or_default(),
+            kind,
+            rid
+        );
+        let scripts = collection.scripts.entry(kind.to_owned()).or_default();
+        let existing = scripts.iter().position(|script| script["id"] == id);
+
+        let status = match (existing, replaced_id) {
+            (Some(index), Some(replaced_id)) if replaced_id == id => {
+                resource["_rid"] = scripts[index]["_rid"].clone();
+                resource["_self"] = scripts[index]["_self"].clone();
+                touch(&mut resource);
+                scripts[index] = resource.clone();
+                StatusCode::OK
+            }
+            (_, Some(_)) if replaced_id != Some(id.as_str()) => {
+                return Err(Reply::error(
+                    StatusCode::BAD_REQUEST,
+                    "the id of the script does not match the one of the uri",
+                ))
+            }
+            (Some(_), _) => {
+                return Err(Reply::error(
+                    StatusCode::CONFLICT,
+                    format!("{} {} already exists", kind, id),
+                ))
+            }
+            (None, Some(_)) => {
+                return Err(Reply::error(
+                    StatusCode::NOT_FOUND,
+                    format!("{} {} does not exist", kind, id),
+                ))
+            }
+            (None, None) => {
+                set_system_properties(&mut resource, &rid, self_link);
+                scripts.push(resource.clone());
+                StatusCode::CREATED
+            }
+        };
+        self.lsn += 1;
+        Ok(Reply::new(status, resource, 9.52))
+    }
+
+    // With a Slug header the body is the content of the attachment, stored as media of the
+    // account. Otherwise the body is the attachment itself, referencing external media.
+    fn create_attachment(
+        &mut self,
+        request: &Parts,
+        database: &str,
+        collection: &str,
+        id: &str,
+        body: &[u8],
+    ) -> Result<Reply, Box<Reply>> {
+        let rid = self.next_rid();
+        let slug = request
+            .headers
+            .get("Slug")
+            .and_then(|slug| slug.to_str().ok());
+        let (mut resource, media) = match slug {
+            Some(slug) => {
+                // media ids end up in uris
+                let media_id = rid.replace('/', "-");
+                let content_type = request_content_type(request);
+                let resource = json!({
+                    "id": slug,
+                    "contentType": content_type,
+                    "media": format!("/media/{}", media_id),
+                });
+                let media = Media {
+                    content_type,
+                    content: body.to_vec(),
+                };
+                (resource, Some((media_id, media)))
+            }
+            None => (parse_resource(body)?, None),
+        };
+        let attachment_id = resource_id(&resource)?;
+
+        let document = self.document(request, database, collection, id)?;
+        if document
+            .attachments
+            .iter()
+            .any(|attachment| attachment["id"] == attachment_id)
+        {
+            return Err(Reply::error(
+                StatusCode::CONFLICT,
+                format!("attachment {} already exists", attachment_id),
+            ));
+        }
+        let self_link = format!(
+            "{}attachments/{}/",
+            document.document["_self"].as_str().unwrap_or_default(),
+            rid
+        );
+        set_system_properties(&mut resource, &rid, self_link);
+        document.attachments.push(resource.clone());
+
+        if let Some((media_id, media)) = media {
+            self.media.insert(media_id, media);
+        }
+        self.lsn += 1;
+        Ok(Reply::new(StatusCode::CREATED, resource, 5.71))
+    }
+
+    fn replace_media(
+        &mut self,
+        request: &Parts,
+        id: &str,
+        body: &[u8],
+    ) -> Result<Reply, Box<Reply>> {
+        let media = self.media.get_mut(id).ok_or_else(|| {
+            Reply::error(
+                StatusCode::NOT_FOUND,
+                format!("media {} does not exist", id),
+            )
+        })?;
+        media.content_type = request_content_type(request);
+        media.content = body.to_vec();
+        self.lsn += 1;
+        Ok(Reply::empty(StatusCode::OK))
+    }
+
+    fn create_user(&mut self, database: &str, body: &[u8]) -> Result<Reply, Box<Reply>> {
+        let mut resource = parse_resource(body)?;
+        let id = resource_id(&resource)?;
+        let rid = self.next_rid();
+        let database = self.database(database)?;
+        if database.users.iter().any(|user| user.resource["id"] == id) {
+            return Err(Reply::error(
+                StatusCode::CONFLICT,
+                format!("user {} already exists", id),
+            ));
+        }
+
+        let self_link = format!(
+            "{}users/{}/",
+            database.resource["_self"].as_str().unwrap_or_default(),
+            rid
+        );
+        set_system_properties(&mut resource, &rid, self_link);
+        resource["_permissions"] = json!("permissions/");
+        database.users.push(UserEntry {
+            resource: resource.clone(),
+            permissions: Vec::new(),
+        });
+        self.lsn += 1;
+        Ok(Reply::new(StatusCode::CREATED, resource, 4.95))
+    }
+
+    // Creates (POST) or replaces (PUT) a permission, issuing a new resource token valid
+    // for x-ms-documentdb-expiry-seconds (one hour by default).
+    fn upsert_permission(
+        &mut self,
+        request: &Parts,
+        database: &str,
+        user: &str,
+        replaced_id: Option<&str>,
+        body: &[u8],
+    ) -> Result<Reply, Box<Reply>> {
+        let mut permission = parse_resource(body)?;
+        let id = resource_id(&permission)?;
+        let (resource, read_only) = match (
+            permission["resource"].as_str(),
+            permission["permissionMode"].as_str(),
+        ) {
+            (Some(resource), Some("All")) => (resource.to_owned(), false),
+            (Some(resource), Some("Read")) => (resource.to_owned(), true),
+            _ => {
+                return Err(Reply::error(
+                    StatusCode::BAD_REQUEST,
+                    "the permission must have a resource and a mode (All or Read)",
+                ))
+            }
+        };
+        let expiry_seconds = match request.headers.get(HEADER_DOCUMENTDB_EXPIRY_SECONDS) {
+            Some(expiry_seconds) => expiry_seconds
+                .to_str()
+                .ok()
+                .and_then(|expiry_seconds| expiry_seconds.parse().ok())
+                .ok_or_else(|| Reply::error(StatusCode::BAD_REQUEST, "invalid expiry seconds"))?,
+            None => 3600,
+        };
+
+        let rid = self.next_rid();
+        let signature = format!(
+            "{};{};",
+            base64::encode(uuid::Uuid::new_v4().as_bytes()),
+            base64::encode(uuid::Uuid::new_v4().as_bytes())
+        );
+        let user = self.user(database, user)?;
+        let existing = user
+            .permissions
+            .iter()
+            .position(|existing| existing["id"] == id);
+        let status = match (existing, replaced_id) {
+            (Some(index), Some(_)) => {
+                for property in &["_rid", "_self"] {
+                    permission[*property] = user.permissions[index][*property].clone();
+                }
+                user.permissions.remove(index);
+                StatusCode::OK
+            }
+            (Some(_), None) => {
+                return Err(Reply::error(
+                    StatusCode::CONFLICT,
+                    format!("permission {} already exists", id),
+                ))
+            }
+            (None, Some(_)) => {
+                return Err(Reply::error(
+                    StatusCode::NOT_FOUND,
+                    format!("permission {} does not exist", id),
+                ))
+            }
+            (None, None) => {
+                permission["_rid"] = json!(rid);
+                permission["_self"] = json!(format!(
+                    "{}permissions/{}/",
+                    user.resource["_self"].as_str().unwrap_or_default(),
+                    rid
+                ));
+                StatusCode::CREATED
+            }
+        };
+        touch(&mut permission);
+        permission["_token"] = json!(format!("type=resource&ver=1&sig={}", signature));
+        user.permissions.push(permission.clone());
+
+        self.resource_tokens.insert(
+            signature,
+            ResourceToken {
+                resource,
+                read_only,
+                expires_at: Instant::now() + Duration::from_secs(expiry_seconds),
+            },
+        );
+        self.lsn += 1;
+        Ok(Reply::new(status, permission, 1.0))
+    }
+
+    // Creates (POST) or replaces (PUT) a document. A POST with
+    // x-ms-documentdb-is-upsert replaces the document if it exists.
+    fn upsert_document(
+        &mut self,
+        request: &Parts,
+        database: &str,
+        collection: &str,
+        replaced_id: Option<&str>,
+        body: &[u8],
+    ) -> Result<Reply, Box<Reply>> {
+        let mut document = parse_resource(body)?;
+        let id = resource_id(&document)?;
+        if replaced_id.map(|replaced_id| replaced_id != id) == Some(true) {
+            return Err(Reply::error(
+                StatusCode::BAD_REQUEST,
+                "the id of the document does not match the one of the uri",
+            ));
+        }
+        let is_upsert = request
+            .headers
+            .get(HEADER_DOCUMENTDB_IS_UPSERT)
+            .map(|is_upsert| is_upsert == "true" || is_upsert == "True")
+            .unwrap_or(false);
+
+        let rid = self.next_rid();
+        let collection = self.collection(database, collection)?;
+        let partition_key = document_partition_key(collection, &document);
+        if let Some(requested) = request_partition_key_header(request)? {
+            if requested != partition_key {
+                return Err(Reply::error(
+                    StatusCode::BAD_REQUEST,
+                    "the partition key extracted from the document does not match the one \
+                     specified in the header",
+                ));
+            }
+        }
+
+        let existing = collection
+            .documents
+            .iter_mut()
+            .find(|entry| entry.partition_key == partition_key && entry.document["id"] == id);
+
+        let status = match (existing, replaced_id, is_upsert) {
+            (Some(existing), _, true) | (Some(existing), Some(_), _) => {
+                check_if_match(request, &existing.document)?;
+                for property in &["_rid", "_self", "_attachments"] {
+                    document[*property] = existing.document[*property].clone();
+                }
+                touch(&mut document);
+                existing.document = document.clone();
+                StatusCode::OK
+            }
+            (Some(_), None, false) => {
+                return Err(Reply::error(
+                    StatusCode::CONFLICT,
+                    format!("document {} already exists", id),
+                ))
+            }
+            (None, Some(_), _) => {
+                return Err(Reply::error(
+                    StatusCode::NOT_FOUND,
+                    format!("document {} does not exist", id),
+                ))
+            }
+            (None, None, _) => {
+                let self_link = format!(
+                    "{}docs/{}/",
+                    collection.resource["_self"].as_str().unwrap_or_default(),
+                    rid
+                );
+                set_system_properties(&mut document, &rid, self_link);
+                document["_attachments"] = json!("attachments/");
+                collection.documents.push(DocumentEntry {
+                    partition_key,
+                    document: document.clone(),
+                    attachments: Vec::new(),
+                });
+                StatusCode::CREATED
+            }
+        };
+
+        self.lsn += 1;
+        Ok(Reply::new(status, document, 5.71))
+    }
+}
+
+fn database_account() -> Value {
+    json!({
+        "_self": "",
+        "id": "localhost",
+        "_rid": "localhost",
+        "media": "//media/",
+        "addresses": "//addresses/",
+        "_dbs": "//dbs/",
+        "writableLocations": [],
+        "readableLocations": [],
+        "enableMultipleWriteLocations": false,
+        "userConsistencyPolicy": { "defaultConsistencyLevel": "Session" },
+    })
+}
+
+fn is_script(segment: &str) -> bool {
+    SCRIPTS.iter().any(|(kind, _)| *kind == segment)
+}
+
+fn script_list_name(kind: &str) -> &'static str {
+    SCRIPTS
+        .iter()
+        .find(|(segment, _)| *segment == kind)
+        .map(|(_, name)| *name)
+        .unwrap_or_default()
+}
+
+fn parse_resource(body: &[u8]) -> Result<Value, Box<Reply>> {
+    match serde_json::from_slice(body) {
+        Ok(Value::Object(resource)) => Ok(Value::Object(resource)),
+        _ => Err(Reply::error(
+            StatusCode::BAD_REQUEST,
+            "the body must be a JSON object",
+        )),
+    }
+}
+
+fn resource_id(resource: &Value) -> Result<String, Box<Reply>> {
+    match resource.get("id").and_then(Value::as_str) {
+        Some(id) if !id.is_empty() => Ok(id.to_owned()),
+        _ => Err(Reply::error(
+            StatusCode::BAD_REQUEST,
+            "the resource must have an id",
+        )),
+    }
+}
+
+fn set_system_properties(resource: &mut Value, rid: &str, self_link: String) {
+    resource["_rid"] = json!(rid);
+    resource["_self"] = json!(self_link);
+    touch(resource);
+}
+
+// A new etag and timestamp, for every change of the resource.
+fn touch(resource: &mut Value) {
+    resource["_etag"] = json!(new_etag());
+    resource["_ts"] = json!(chrono::Utc::now().timestamp());
+}
+
+fn new_etag() -> String {
+    format!("\"{}\"", uuid::Uuid::new_v4())
+}
+
+// Answers with a page of the items, starting from the continuation token of the request
+// (the index of the first item).
+fn list(request: &Parts, rid: &str, name: &str, items: Vec<Value>) -> Result<Reply, Box<Reply>> {
+    let (page, continuation) = page(request, items)?;
+    let mut body = Map::new();
+    body.insert("_rid".to_owned(), json!(rid));
+    body.insert("_count".to_owned(), json!(page.len()));
+    let item_count = page.len();
+    body.insert(name.to_owned(), Value::Array(page));
+
+    Ok(Reply {
+        item_count: Some(item_count),
+        continuation,
+        ..Reply::new(
+            StatusCode::OK,
+            Value::Object(body),
+            1.0 + item_count as f64 * 0.1,
+        )
+    })
+}
+
+fn page(request: &Parts, items: Vec<Value>) -> Result<(Vec<Value>, Option<String>), Box<Reply>> {
+    let header = |name: &str| {
+        request
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+
+    let max_item_count = match header(HEADER_MAX_ITEM_COUNT).map(str::parse::<i64>) {
+        Some(Ok(max_item_count)) if max_item_count > 0 => max_item_count as usize,
+        Some(Ok(_)) | None => DEFAULT_MAX_ITEM_COUNT,
+        Some(Err(_)) => {
+            return Err(Reply::error(
+                StatusCode::BAD_REQUEST,
+                "invalid x-ms-max-item-count",
+            ))
+        }
+    };
+    let start = match header(HEADER_CONTINUATION).map(str::parse::<usize>) {
+        Some(Ok(start)) => start,
+        None => 0,
+        Some(Err(_)) => {
+            return Err(Reply::error(
+                StatusCode::BAD_REQUEST,
+                "invalid continuation token",
+            ))
+        }
+    };
+
+    let end = start.saturating_add(max_item_count);
+    let continuation = if end < items.len() {
+        Some(end.to_string())
+    } else {
+        None
+    };
+    let page = items.into_iter().skip(start).take(max_item_count).collect();
+    Ok((page, continuation))
+}
+
+fn is_query(request: &Parts) -> bool {
+    request
+        .headers
+        .get(header::CONTENT_TYPE)
+        .map(|content_type| content_type == "application/query+json")
+        .unwrap_or(false)
+}
+
+fn query_documents(
+    request: &Parts,
+    collection: &CollectionEntry,
+    body: &[u8],
+) -> Result<Reply, Box<Reply>> {
+    if request
+        .headers
+        .contains_key(HEADER_COSMOS_IS_QUERY_PLAN_REQUEST)
+    {
+        return Err(Reply::error(
+            StatusCode::BAD_REQUEST,
+            "query plans are not supported by the in-memory emulator",
+        ));
+    }
+
+    #[derive(Deserialize)]
+    struct QueryBody {
+        query: String,
+        #[serde(default)]
+        parameters: Vec<ParameterBody>,
+    }
+    #[derive(Deserialize)]
+    struct ParameterBody {
+        name: String,
+        value: Value,
+    }
+
+    let query: QueryBody = serde_json::from_slice(body)
+        .map_err(|error| Reply::error(StatusCode::BAD_REQUEST, error.to_string()))?;
+    let parameters = query
+        .parameters
+        .into_iter()
+        .map(|parameter| (parameter.name, parameter.value))
+        .collect::<HashMap<_, _>>();
+    let select = SelectQuery::parse(&query.query)
+        .map_err(|error| Reply::error(StatusCode::BAD_REQUEST, error))?;
+
+    let partition_key = request_partition_key_header(request)?;
+    let is_cross_partition = request
+        .headers
+        .get(HEADER_DOCUMENTDB_QUERY_ENABLECROSSPARTITION)
+        .map(|value| value == "true" || value == "True")
+        .unwrap_or(false);
+    if partition_key.is_none() && !is_cross_partition && !collection.partition_key_paths.is_empty()
+    {
+        return Err(Reply::error(
+            StatusCode::BAD_REQUEST,
+            "cross partition query is required but disabled: set the partition key or \
+             enable cross partition queries",
+        ));
+    }
+
+    let documents = collection
+        .documents
+        .iter()
+        .filter(|entry| {
+            partition_key
+                .as_ref()
+                .map(|partition_key| entry.partition_key == *partition_key)
+                .unwrap_or(true)
+        })
+        .map(|entry| &entry.document);
+    let results = select
+        .execute(documents, &parameters)
+        .map_err(|error| Reply::error(StatusCode::BAD_REQUEST, error))?;
+
+    let mut reply = list(
+        request,
+        collection.resource["_rid"].as_str().unwrap_or_default(),
+        "Documents",
+        results,
+    )?;
+    reply.charge += 1.8;
+    Ok(reply)
+}
+
+// The partition key of the document, as a JSON array like the one sent in the
+// x-ms-documentdb-partitionkey header.
+fn document_partition_key(collection: &CollectionEntry, document: &Value) -> Value {
+    Value::Array(
+        collection
+            .partition_key_paths
+            .iter()
+            .map(|path| partition_key_component(document, path))
+            .collect(),
+    )
+}
+
+fn request_partition_key_header(request: &Parts) -> Result<Option<Value>, Box<Reply>> {
+    match request.headers.get(HEADER_DOCUMENTDB_PARTITIONKEY) {
+        Some(partition_key) => partition_key
+            .to_str()
+            .ok()
+            .and_then(|partition_key| serde_json::from_str(partition_key).ok())
+            .map(Some)
+            .ok_or_else(|| Reply::error(StatusCode::BAD_REQUEST, "invalid partition key")),
+        None => Ok(None),
+    }
+}
+
+fn request_partition_key(
+    request: &Parts,
+    collection: &CollectionEntry,
+) -> Result<Value, Box<Reply>> {
+    match request_partition_key_header(request)? {
+        Some(partition_key) => Ok(partition_key),
+        None if collection.partition_key_paths.is_empty() => Ok(json!([])),
+        None => Err(Reply::error(
+            StatusCode::BAD_REQUEST,
+            "the partition key must be specified",
+        )),
+    }
+}
+
+fn find_document<'a>(
+    collection: &'a CollectionEntry,
+    partition_key: &Value,
+    id: &str,
+) -> Result<&'a DocumentEntry, Box<Reply>> {
+    collection
+        .documents
+        .iter()
+        .find(|entry| entry.partition_key == *partition_key && entry.document["id"] == id)
+        .ok_or_else(|| {
+            Reply::error(
+                StatusCode::NOT_FOUND,
+                format!("document {} does not exist", id),
+            )
+        })
+}
+
+fn find_attachment<'a>(document: &'a DocumentEntry, id: &str) -> Result<&'a Value, Box<Reply>> {
+    document
+        .attachments
+        .iter()
+        .find(|attachment| attachment["id"] == id)
+        .ok_or_else(|| {
+            Reply::error(
+                StatusCode::NOT_FOUND,
+                format!("attachment {} does not exist", id),
+            )
+        })
+}
+
+fn request_content_type(request: &Parts) -> String {
+    request
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_owned()
+}
+
+fn check_if_match(request: &Parts, document: &Value) -> Result<(), Box<Reply>> {
+    match request.headers.get(header::IF_MATCH) {
+        Some(etag) if document["_etag"] != etag.to_str().unwrap_or_default() => Err(Reply::error(
+            StatusCode::PRECONDITION_FAILED,
+            "the etag does not match",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn if_none_match(request: &Parts, document: &Value) -> bool {
+    request
+        .headers
+        .get(header::IF_NONE_MATCH)
+        .map(|etag| document["_etag"] == etag.to_str().unwrap_or_default())
+        .unwrap_or(false)
+}
+
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
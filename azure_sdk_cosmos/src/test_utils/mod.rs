@@ -0,0 +1,6 @@
+//! Helpers to unit test the code using the Cosmos DB clients, available with the
+//! `test_utils` feature.
+mod emulator;
+mod query;
+
+pub use self::emulator::InMemoryEmulator;
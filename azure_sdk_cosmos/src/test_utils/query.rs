@@ -0,0 +1,816 @@
+// A small interpreter of the Cosmos DB SQL dialect, enough for the queries made by unit
+// tests:
+//
+// SELECT [DISTINCT] [TOP n] * | VALUE expr | expr [AS name], ...
+// FROM alias [WHERE expr] [ORDER BY expr [ASC|DESC], ...] [OFFSET n LIMIT n]
+//
+// Expressions are property paths, literals, parameters, comparisons, AND, OR, NOT, IN,
+// BETWEEN and the most common functions. Aggregates (COUNT, SUM, MIN, MAX, AVG) are
+// supported after VALUE only.
+use serde_json::{Map, Number, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Parameter(String),
+    Number(f64),
+    String(String),
+    Symbol(&'static str),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    const SYMBOLS: [&str; 16] = [
+        "!=", "<>", "<=", ">=", "=", "<", ">", "(", ")", "[", "]", ",", ".", "*", "-", "+",
+    ];
+
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_alphabetic() || c == '_' || c == '@' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word = chars[start..i].iter().collect::<String>();
+            tokens.push(if c == '@' {
+                Token::Parameter(word)
+            } else {
+                Token::Identifier(word)
+            });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let number = chars[start..i].iter().collect::<String>();
+            tokens.push(Token::Number(
+                number
+                    .parse()
+                    .map_err(|_| format!("invalid number {}", number))?,
+            ));
+        } else if c == '"' || c == '\'' {
+            let mut string = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    Some('\\') => {
+                        match chars.get(i + 1) {
+                            Some('n') => string.push('\n'),
+                            Some('t') => string.push('\t'),
+                            Some(escaped) => string.push(*escaped),
+                            None => return Err("unterminated string".to_owned()),
+                        }
+                        i += 2;
+                    }
+                    Some(quote) if *quote == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(other) => {
+                        string.push(*other);
+                        i += 1;
+                    }
+                    None => return Err("unterminated string".to_owned()),
+                }
+            }
+            tokens.push(Token::String(string));
+        } else {
+            let rest = chars[i..].iter().take(2).collect::<String>();
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(*symbol))
+                .ok_or_else(|| format!("unexpected character {:?}", c))?;
+            i += symbol.len();
+            tokens.push(Token::Symbol(symbol));
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Parameter(String),
+    // the root (the alias of FROM) followed by the property names and the indexes
+    Path(Vec<Value>),
+    Array(Vec<Expr>),
+    Function(String, Vec<Expr>),
+    Compare(Box<Expr>, &'static str, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    In(Box<Expr>, Vec<Expr>),
+    Between(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Projection {
+    All,
+    Value(Expr),
+    Columns(Vec<(Expr, Option<String>)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SelectQuery {
+    distinct: bool,
+    top: Option<usize>,
+    projection: Projection,
+    alias: String,
+    filter: Option<Expr>,
+    order_by: Vec<(Expr, bool)>,
+    offset_limit: Option<(usize, usize)>,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Identifier(word)) => word.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn accept_keyword(&mut self, keyword: &str) -> bool {
+        let is_keyword = self.is_keyword(keyword);
+        if is_keyword {
+            self.position += 1;
+        }
+        is_keyword
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), String> {
+        if self.accept_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(format!("expected {}, found {:?}", keyword, self.peek()))
+        }
+    }
+
+    fn accept_symbol(&mut self, symbol: &str) -> bool {
+        let is_symbol = match self.peek() {
+            Some(Token::Symbol(found)) => *found == symbol,
+            _ => false,
+        };
+        if is_symbol {
+            self.position += 1;
+        }
+        is_symbol
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), String> {
+        if self.accept_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(format!("expected {}, found {:?}", symbol, self.peek()))
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Identifier(identifier)) => Ok(identifier),
+            other => Err(format!("expected an identifier, found {:?}", other)),
+        }
+    }
+
+    fn integer(&mut self) -> Result<usize, String> {
+        match self.next() {
+            Some(Token::Number(number)) if number >= 0.0 && number.fract() == 0.0 => {
+                Ok(number as usize)
+            }
+            other => Err(format!("expected an integer, found {:?}", other)),
+        }
+    }
+
+    fn select(&mut self) -> Result<SelectQuery, String> {
+        self.expect_keyword("SELECT")?;
+        let distinct = self.accept_keyword("DISTINCT");
+        let top = if self.accept_keyword("TOP") {
+            Some(self.integer()?)
+        } else {
+            None
+        };
+
+        let projection = if self.accept_symbol("*") {
+            Projection::All
+        } else if self.accept_keyword("VALUE") {
+            Projection::Value(self.expr()?)
+        } else {
+            let mut columns = Vec::new();
+            loop {
+                let expr = self.expr()?;
+                let name = if self.accept_keyword("AS") {
+                    Some(self.identifier()?)
+                } else {
+                    None
+                };
+                columns.push((expr, name));
+                if !self.accept_symbol(",") {
+                    break;
+                }
+            }
+            Projection::Columns(columns)
+        };
+
+        self.expect_keyword("FROM")?;
+        let mut alias = self.identifier()?;
+        if self.accept_keyword("AS") {
+            alias = self.identifier()?;
+        } else if let Some(Token::Identifier(word)) = self.peek() {
+            if !is_clause(word) {
+                alias = self.identifier()?;
+            }
+        }
+
+        if self.is_keyword("JOIN") {
+            return Err("JOIN is not supported by the emulator".to_owned());
+        }
+
+        let filter = if self.accept_keyword("WHERE") {
+            Some(self.expr()?)
+        } else {
+            None
+        };
+
+        let mut order_by = Vec::new();
+        if self.accept_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let expr = self.expr()?;
+                let ascending = !self.accept_keyword("DESC");
+                if ascending {
+                    self.accept_keyword("ASC");
+                }
+                order_by.push((expr, ascending));
+                if !self.accept_symbol(",") {
+                    break;
+                }
+            }
+        }
+
+        let offset_limit = if self.accept_keyword("OFFSET") {
+            let offset = self.integer()?;
+            self.expect_keyword("LIMIT")?;
+            Some((offset, self.integer()?))
+        } else {
+            None
+        };
+
+        match self.peek() {
+            None => Ok(SelectQuery {
+                distinct,
+                top,
+                projection,
+                alias,
+                filter,
+                order_by,
+                offset_limit,
+            }),
+            Some(token) => Err(format!("unexpected {:?}", token)),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.accept_keyword("OR") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.not()?;
+        while self.accept_keyword("AND") {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.accept_keyword("NOT") {
+            Ok(Expr::Not(Box::new(self.not()?)))
+        } else {
+            self.comparison()
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let left = self.primary()?;
+
+        for operator in &["=", "!=", "<>", "<=", ">=", "<", ">"] {
+            if self.accept_symbol(operator) {
+                let operator = if *operator == "<>" { "!=" } else { *operator };
+                return Ok(Expr::Compare(
+                    Box::new(left),
+                    operator,
+                    Box::new(self.primary()?),
+                ));
+            }
+        }
+
+        let negated = self.accept_keyword("NOT");
+        let expr = if self.accept_keyword("IN") {
+            self.expect_symbol("(")?;
+            Expr::In(Box::new(left), self.list(")")?)
+        } else if self.accept_keyword("BETWEEN") {
+            let low = self.primary()?;
+            self.expect_keyword("AND")?;
+            Expr::Between(Box::new(left), Box::new(low), Box::new(self.primary()?))
+        } else if negated {
+            return Err("expected IN or BETWEEN after NOT".to_owned());
+        } else {
+            return Ok(left);
+        };
+
+        Ok(if negated {
+            Expr::Not(Box::new(expr))
+        } else {
+            expr
+        })
+    }
+
+    // Comma separated expressions up to the closing symbol.
+    fn list(&mut self, close: &str) -> Result<Vec<Expr>, String> {
+        let mut exprs = Vec::new();
+        if self.accept_symbol(close) {
+            return Ok(exprs);
+        }
+        loop {
+            exprs.push(self.expr()?);
+            if self.accept_symbol(close) {
+                return Ok(exprs);
+            }
+            self.expect_symbol(",")?;
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expr::Literal(number_value(number))),
+            Some(Token::String(string)) => Ok(Expr::Literal(Value::String(string))),
+            Some(Token::Parameter(name)) => Ok(Expr::Parameter(name)),
+            Some(Token::Symbol("-")) => match self.next() {
+                Some(Token::Number(number)) => Ok(Expr::Literal(number_value(-number))),
+                other => Err(format!("expected a number, found {:?}", other)),
+            },
+            Some(Token::Symbol("(")) => {
+                let expr = self.expr()?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            Some(Token::Symbol("[")) => Ok(Expr::Array(self.list("]")?)),
+            Some(Token::Identifier(word)) => {
+                if word.eq_ignore_ascii_case("true") {
+                    Ok(Expr::Literal(Value::Bool(true)))
+                } else if word.eq_ignore_ascii_case("false") {
+                    Ok(Expr::Literal(Value::Bool(false)))
+                } else if word.eq_ignore_ascii_case("null") {
+                    Ok(Expr::Literal(Value::Null))
+                } else if self.accept_symbol("(") {
+                    Ok(Expr::Function(word.to_uppercase(), self.list(")")?))
+                } else {
+                    let mut path = vec![Value::String(word)];
+                    loop {
+                        if self.accept_symbol(".") {
+                            path.push(Value::String(self.identifier()?));
+                        } else if self.accept_symbol("[") {
+                            match self.next() {
+                                Some(Token::String(property)) => path.push(Value::String(property)),
+                                Some(Token::Number(index)) => path.push(number_value(index)),
+                                other => {
+                                    return Err(format!(
+                                        "expected a property or an index, found {:?}",
+                                        other
+                                    ))
+                                }
+                            }
+                            self.expect_symbol("]")?;
+                        } else {
+                            return Ok(Expr::Path(path));
+                        }
+                    }
+                }
+            }
+            other => Err(format!("unexpected {:?}", other)),
+        }
+    }
+}
+
+fn is_clause(word: &str) -> bool {
+    ["WHERE", "ORDER", "OFFSET", "JOIN"]
+        .iter()
+        .any(|clause| clause.eq_ignore_ascii_case(word))
+}
+
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < 9_007_199_254_740_992.0 {
+        Value::from(number as i64)
+    } else {
+        Number::from_f64(number)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+impl SelectQuery {
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+        }
+        .select()
+    }
+
+    /// Runs the query on the documents, returning the results in order.
+    pub(crate) fn execute<'a, I>(
+        &self,
+        documents: I,
+        parameters: &HashMap<String, Value>,
+    ) -> Result<Vec<Value>, String>
+    where
+        I: IntoIterator<Item = &'a Value>,
+    {
+        let context = Context {
+            alias: &self.alias,
+            parameters,
+        };
+
+        let mut documents = documents
+            .into_iter()
+            .filter(|document| match &self.filter {
+                Some(filter) => context.eval(filter, document) == Some(Value::Bool(true)),
+                None => true,
+            })
+            .collect::<Vec<_>>();
+
+        if !self.order_by.is_empty() {
+            documents.sort_by(|a, b| {
+                self.order_by
+                    .iter()
+                    .map(|(expr, ascending)| {
+                        let ordering =
+                            compare_values(&context.eval(expr, a), &context.eval(expr, b));
+                        if *ascending {
+                            ordering
+                        } else {
+                            ordering.reverse()
+                        }
+                    })
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+
+        let mut results: Vec<Value> = match &self.projection {
+            Projection::Value(Expr::Function(name, arguments)) if is_aggregate(name) => {
+                let values = documents
+                    .iter()
+                    .filter_map(|document| match arguments.first() {
+                        Some(argument) => context.eval(argument, document),
+                        None => Some(Value::Null),
+                    })
+                    .collect::<Vec<_>>();
+                aggregate(name, &values).into_iter().collect()
+            }
+            Projection::All => documents.into_iter().cloned().collect(),
+            Projection::Value(expr) => documents
+                .iter()
+                .filter_map(|document| context.eval(expr, document))
+                .collect(),
+            Projection::Columns(columns) => documents
+                .iter()
+                .map(|document| {
+                    let mut object = Map::new();
+                    for (index, (expr, name)) in columns.iter().enumerate() {
+                        let name = match (name, expr) {
+                            (Some(name), _) => name.clone(),
+                            (None, Expr::Path(path)) if path.len() > 1 => match path.last() {
+                                Some(Value::String(property)) => property.clone(),
+                                _ => format!("${}", index + 1),
+                            },
+                            _ => format!("${}", index + 1),
+                        };
+                        if let Some(value) = context.eval(expr, document) {
+                            object.insert(name, value);
+                        }
+                    }
+                    Value::Object(object)
+                })
+                .collect(),
+        };
+
+        if self.distinct {
+            let mut distinct: Vec<Value> = Vec::with_capacity(results.len());
+            for result in results {
+                if !distinct.contains(&result) {
+                    distinct.push(result);
+                }
+            }
+            results = distinct;
+        }
+        if let Some((offset, limit)) = self.offset_limit {
+            results = results.into_iter().skip(offset).take(limit).collect();
+        }
+        if let Some(top) = self.top {
+            results.truncate(top);
+        }
+
+        Ok(results)
+    }
+}
+
+struct Context<'a> {
+    alias: &'a str,
+    parameters: &'a HashMap<String, Value>,
+}
+
+impl<'a> Context<'a> {
+    // None stands for undefined.
+    fn eval(&self, expr: &Expr, document: &Value) -> Option<Value> {
+        match expr {
+            Expr::Literal(value) => Some(value.clone()),
+            Expr::Parameter(name) => self.parameters.get(name).cloned(),
+            Expr::Path(path) => {
+                let mut segments = path.iter();
+                match segments.next() {
+                    Some(Value::String(root)) if root == self.alias => {}
+                    _ => return None,
+                }
+                segments
+                    .try_fold(document, |value, segment| match segment {
+                        Value::String(property) => value.get(property),
+                        Value::Number(index) => value.get(index.as_u64()? as usize),
+                        _ => None,
+                    })
+                    .cloned()
+            }
+            Expr::Array(items) => Some(Value::Array(
+                items
+                    .iter()
+                    .filter_map(|item| self.eval(item, document))
+                    .collect(),
+            )),
+            Expr::Compare(left, operator, right) => {
+                let left = self.eval(left, document)?;
+                let right = self.eval(right, document)?;
+                let ordering = match (&left, &right) {
+                    (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+                    (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+                    (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+                    _ if *operator == "=" => return Some(Value::Bool(left == right)),
+                    _ if *operator == "!=" => return Some(Value::Bool(left != right)),
+                    _ => None,
+                }?;
+                Some(Value::Bool(match *operator {
+                    "=" => ordering == Ordering::Equal,
+                    "!=" => ordering != Ordering::Equal,
+                    "<" => ordering == Ordering::Less,
+                    "<=" => ordering != Ordering::Greater,
+                    ">" => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less,
+                }))
+            }
+            Expr::And(left, right) => {
+                match (self.eval(left, document), self.eval(right, document)) {
+                    (Some(Value::Bool(false)), _) | (_, Some(Value::Bool(false))) => {
+                        Some(Value::Bool(false))
+                    }
+                    (Some(Value::Bool(true)), Some(Value::Bool(true))) => Some(Value::Bool(true)),
+                    _ => None,
+                }
+            }
+            Expr::Or(left, right) => {
+                match (self.eval(left, document), self.eval(right, document)) {
+                    (Some(Value::Bool(true)), _) | (_, Some(Value::Bool(true))) => {
+                        Some(Value::Bool(true))
+                    }
+                    (Some(Value::Bool(false)), Some(Value::Bool(false))) => {
+                        Some(Value::Bool(false))
+                    }
+                    _ => None,
+                }
+            }
+            Expr::Not(expr) => match self.eval(expr, document)? {
+                Value::Bool(value) => Some(Value::Bool(!value)),
+                _ => None,
+            },
+            Expr::In(expr, items) => {
+                let value = self.eval(expr, document)?;
+                Some(Value::Bool(items.iter().any(|item| {
+                    self.eval(
+                        &Expr::Compare(
+                            Box::new(Expr::Literal(value.clone())),
+                            "=",
+                            Box::new(item.clone()),
+                        ),
+                        document,
+                    ) == Some(Value::Bool(true))
+                })))
+            }
+            Expr::Between(expr, low, high) => self.eval(
+                &Expr::And(
+                    Box::new(Expr::Compare(expr.clone(), ">=", low.clone())),
+                    Box::new(Expr::Compare(expr.clone(), "<=", high.clone())),
+                ),
+                document,
+            ),
+            Expr::Function(name, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.eval(argument, document))
+                    .collect::<Vec<_>>();
+                function(name, &arguments)
+            }
+        }
+    }
+}
+
+fn function(name: &str, arguments: &[Option<Value>]) -> Option<Value> {
+    let argument = |index: usize| arguments.get(index).cloned().flatten();
+    let string = |index: usize| match argument(index) {
+        Some(Value::String(string)) => Some(string),
+        _ => None,
+    };
+
+    let value = match name {
+        "IS_DEFINED" => Value::Bool(argument(0).is_some()),
+        "IS_NULL" => Value::Bool(argument(0) == Some(Value::Null)),
+        "IS_BOOL" => Value::Bool(matches!(argument(0), Some(Value::Bool(_)))),
+        "IS_NUMBER" => Value::Bool(matches!(argument(0), Some(Value::Number(_)))),
+        "IS_STRING" => Value::Bool(matches!(argument(0), Some(Value::String(_)))),
+        "IS_ARRAY" => Value::Bool(matches!(argument(0), Some(Value::Array(_)))),
+        "IS_OBJECT" => Value::Bool(matches!(argument(0), Some(Value::Object(_)))),
+        "CONTAINS" => Value::Bool(string(0)?.contains(&string(1)?)),
+        "STARTSWITH" => Value::Bool(string(0)?.starts_with(&string(1)?)),
+        "ENDSWITH" => Value::Bool(string(0)?.ends_with(&string(1)?)),
+        "LOWER" => Value::String(string(0)?.to_lowercase()),
+        "UPPER" => Value::String(string(0)?.to_uppercase()),
+        "LENGTH" => Value::from(string(0)?.chars().count()),
+        "ARRAY_LENGTH" => match argument(0)? {
+            Value::Array(items) => Value::from(items.len()),
+            _ => return None,
+        },
+        "ARRAY_CONTAINS" => match argument(0)? {
+            Value::Array(items) => Value::Bool(items.contains(&argument(1)?)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn is_aggregate(name: &str) -> bool {
+    ["COUNT", "SUM", "MIN", "MAX", "AVG"].contains(&name)
+}
+
+fn aggregate(name: &str, values: &[Value]) -> Option<Value> {
+    let numbers = values.iter().filter_map(Value::as_f64).collect::<Vec<_>>();
+    match name {
+        "COUNT" => Some(Value::from(values.len())),
+        "SUM" => Some(number_value(numbers.iter().sum())),
+        "AVG" if !numbers.is_empty() => Some(number_value(
+            numbers.iter().sum::<f64>() / numbers.len() as f64,
+        )),
+        "MIN" => values
+            .iter()
+            .min_by(|a, b| compare_values(&Some((*a).clone()), &Some((*b).clone())))
+            .cloned(),
+        "MAX" => values
+            .iter()
+            .max_by(|a, b| compare_values(&Some((*a).clone()), &Some((*b).clone())))
+            .cloned(),
+        _ => None,
+    }
+}
+
+// Order of ORDER BY: undefined, null, booleans, numbers, strings, arrays and objects.
+fn compare_values(a: &Option<Value>, b: &Option<Value>) -> Ordering {
+    fn rank(value: &Option<Value>) -> u8 {
+        match value {
+            None => 0,
+            Some(Value::Null) => 1,
+            Some(Value::Bool(_)) => 2,
+            Some(Value::Number(_)) => 3,
+            Some(Value::String(_)) => 4,
+            Some(Value::Array(_)) => 5,
+            Some(Value::Object(_)) => 6,
+        }
+    }
+
+    match (a, b) {
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn documents() -> Vec<Value> {
+        vec![
+            json!({ "id": "1", "name": "Milan", "population": 1352, "tags": ["north"] }),
+            json!({ "id": "2", "name": "Rome", "population": 2873, "tags": ["capital"] }),
+            json!({ "id": "3", "name": "Naples", "population": 967 }),
+            json!({ "id": "4", "name": "Turin", "population": 870, "tags": ["north"] }),
+        ]
+    }
+
+    fn run(query: &str, parameters: &[(&str, Value)]) -> Vec<Value> {
+        let parameters = parameters
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+        SelectQuery::parse(query)
+            .unwrap()
+            .execute(&documents(), &parameters)
+            .unwrap()
+    }
+
+    #[test]
+    fn filter_and_order() {
+        assert_eq!(
+            run(
+                "SELECT VALUE c.id FROM c WHERE c.population > @min ORDER BY c.population DESC",
+                &[("@min", json!(900))]
+            ),
+            vec![json!("2"), json!("1"), json!("3")]
+        );
+        assert_eq!(
+            run(
+                "SELECT c.name, c.population AS p FROM c WHERE ARRAY_CONTAINS(c.tags, 'north') \
+                 AND NOT (c.id IN ('4', '5'))",
+                &[]
+            ),
+            vec![json!({ "name": "Milan", "p": 1352 })]
+        );
+        assert_eq!(
+            run(
+                "SELECT TOP 2 * FROM root r WHERE IS_DEFINED(r.tags) ORDER BY r.name",
+                &[]
+            )
+            .iter()
+            .map(|document| document["id"].clone())
+            .collect::<Vec<_>>(),
+            vec![json!("1"), json!("2")]
+        );
+        assert_eq!(
+            run(
+                "SELECT VALUE c.id FROM c ORDER BY c.id OFFSET 1 LIMIT 2",
+                &[]
+            ),
+            vec![json!("2"), json!("3")]
+        );
+    }
+
+    #[test]
+    fn aggregates() {
+        assert_eq!(run("SELECT VALUE COUNT(1) FROM c", &[]), vec![json!(4)]);
+        assert_eq!(
+            run(
+                "SELECT VALUE SUM(c.population) FROM c WHERE c.population BETWEEN 800 AND 1000",
+                &[]
+            ),
+            vec![json!(1837)]
+        );
+        assert_eq!(
+            run("SELECT VALUE MAX(c.name) FROM c", &[]),
+            vec![json!("Turin")]
+        );
+    }
+
+    #[test]
+    fn unsupported_queries() {
+        assert!(SelectQuery::parse("SELECT * FROM c JOIN t IN c.tags").is_err());
+        assert!(SelectQuery::parse("SELECT * FROM c WHERE").is_err());
+        assert!(SelectQuery::parse("DELETE FROM c").is_err());
+    }
+}
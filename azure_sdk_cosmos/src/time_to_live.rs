@@ -0,0 +1,124 @@
+use azure_sdk_core::errors::AzureError;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// Time to live of the documents of a collection (`defaultTtl`) or of a single document
+/// (`ttl`). The service deletes the expired documents on its own.
+///
+/// On a collection, `Never` enables the expiration without a default: only the documents
+/// with their own `ttl` expire. If the collection has no `defaultTtl` the `ttl` of its
+/// documents is ignored. On a document, `Never` keeps the document forever whatever the
+/// default of its collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "i64", into = "i64")]
+pub enum TimeToLive {
+    Never,
+    /// Seconds since the last write of the document.
+    Seconds(u32),
+}
+
+impl TimeToLive {
+    /// Duration after which a document expires, `None` if it never does.
+    pub fn duration(self) -> Option<Duration> {
+        match self {
+            TimeToLive::Never => None,
+            TimeToLive::Seconds(seconds) => Some(Duration::from_secs(u64::from(seconds))),
+        }
+    }
+}
+
+// `Never` is the longest time to live.
+impl Ord for TimeToLive {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (TimeToLive::Never, TimeToLive::Never) => Ordering::Equal,
+            (TimeToLive::Never, TimeToLive::Seconds(_)) => Ordering::Greater,
+            (TimeToLive::Seconds(_), TimeToLive::Never) => Ordering::Less,
+            (TimeToLive::Seconds(a), TimeToLive::Seconds(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for TimeToLive {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<TimeToLive> for i64 {
+    fn from(time_to_live: TimeToLive) -> Self {
+        match time_to_live {
+            TimeToLive::Never => -1,
+            TimeToLive::Seconds(seconds) => i64::from(seconds),
+        }
+    }
+}
+
+impl TryFrom<i64> for TimeToLive {
+    type Error = AzureError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            -1 => Ok(TimeToLive::Never),
+            value if value > 0 && value <= i64::from(u32::MAX) => {
+                Ok(TimeToLive::Seconds(value as u32))
+            }
+            value => Err(AzureError::GenericErrorWithText(format!(
+                "invalid time to live {}: it must be -1 or a positive number of seconds",
+                value
+            ))),
+        }
+    }
+}
+
+impl From<Duration> for TimeToLive {
+    /// Rounds up to the next second, saturating at `u32::MAX` seconds.
+    fn from(duration: Duration) -> Self {
+        let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+        TimeToLive::Seconds(u32::try_from(seconds.max(1)).unwrap_or(u32::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_time_to_live() {
+        assert_eq!(serde_json::to_string(&TimeToLive::Never).unwrap(), "-1");
+        assert_eq!(
+            serde_json::to_string(&TimeToLive::Seconds(3600)).unwrap(),
+            "3600"
+        );
+        assert_eq!(
+            serde_json::from_str::<TimeToLive>("-1").unwrap(),
+            TimeToLive::Never
+        );
+        assert_eq!(
+            serde_json::from_str::<TimeToLive>("60").unwrap(),
+            TimeToLive::Seconds(60)
+        );
+        assert!(serde_json::from_str::<TimeToLive>("0").is_err());
+        assert!(serde_json::from_str::<TimeToLive>("-2").is_err());
+
+        assert_eq!(
+            TimeToLive::from(Duration::from_millis(1500)),
+            TimeToLive::Seconds(2)
+        );
+        assert_eq!(
+            TimeToLive::Seconds(90).duration(),
+            Some(Duration::from_secs(90))
+        );
+    }
+
+    #[test]
+    fn never_is_the_longest_time_to_live() {
+        assert!(TimeToLive::Never > TimeToLive::Seconds(u32::MAX));
+        assert!(TimeToLive::Seconds(1) < TimeToLive::Seconds(2));
+        assert_eq!(
+            TimeToLive::Seconds(60).max(TimeToLive::Never),
+            TimeToLive::Never
+        );
+    }
+}
@@ -1,10 +1,16 @@
 use crate::requests;
-use crate::{PartitionKeys, ResourceType};
+use crate::{
+    DiagnosticsHandler, LocationCache, PartitionKeys, ResourceTokenRefresher, ResourceType,
+    RetryPolicy, SessionContainer,
+};
+use azure_sdk_core::errors::AzureError;
 use azure_sdk_core::No;
 use http::request::Builder;
 use hyper_rustls::HttpsConnector;
 use std::borrow::Cow;
 use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
 
 pub trait HasHyperClient: Debug + Send + Sync {
     fn hyper_client(&self) -> &hyper::Client<HttpsConnector<hyper::client::HttpConnector>>;
@@ -13,6 +19,11 @@ pub trait HasHyperClient: Debug + Send + Sync {
 pub trait CosmosClient: HasHyperClient + Send + Sync {
     fn create_database(&self) -> requests::CreateDatabaseBuilder<'_, No>;
     fn list_databases(&self) -> requests::ListDatabasesBuilder<'_>;
+    fn list_offers(&self) -> requests::ListOffersBuilder<'_>;
+    fn get_offer(&self) -> requests::GetOfferBuilder<'_, '_, No>;
+    fn query_offers(&self) -> requests::QueryOffersBuilder<'_, '_, No>;
+    fn replace_offer(&self) -> requests::ReplaceOfferBuilder<'_, '_, No>;
+    fn get_database_account(&self) -> requests::GetDatabaseAccountBuilder<'_>;
 
     fn prepare_request(
         &self,
@@ -20,6 +31,35 @@ pub trait CosmosClient: HasHyperClient + Send + Sync {
         http_method: hyper::Method,
         resource_type: ResourceType,
     ) -> Builder;
+
+    fn retry_policy(&self) -> &RetryPolicy;
+
+    fn location_cache(&self) -> &LocationCache;
+
+    fn session_container(&self) -> &SessionContainer;
+
+    fn diagnostics_handler(&self) -> Option<&DiagnosticsHandler>;
+
+    fn resource_token_refresher(&self) -> Option<&ResourceTokenRefresher>;
+
+    /// Sends the request retrying it, according to the retry policy, as long as it
+    /// gets throttled. If preferred locations are set the request is sent to the
+    /// preferred region, failing over to the other ones when it is unavailable. The
+    /// session token of the collection is added to the request and updated from the
+    /// response. The diagnostics of the request are stored in the extensions of the
+    /// response, where `Diagnostics::from_response` reads them, and passed to the
+    /// diagnostics handler, if any. If the server rejects the resource token
+    /// of the request and the client has a resource token refresher, the request is sent
+    /// again with a new token.
+    fn execute_request(
+        &self,
+        request: hyper::Request<hyper::Body>,
+    ) -> Pin<Box<dyn Future<Output = Result<hyper::Response<hyper::Body>, hyper::Error>> + Send + '_>>
+    {
+        Box::pin(crate::resource_token_broker::execute_with_token_refresh(
+            self, request,
+        ))
+    }
 }
 
 pub trait HasCosmosClient<C>: HasHyperClient
@@ -238,14 +278,20 @@ where
     fn list_user_defined_functions(
         &self,
     ) -> requests::ListUserDefinedFunctionsBuilder<'_, '_, C, D>;
+    fn list_conflicts(&self) -> requests::ListConflictsBuilder<'_, '_, C, D>;
 
     fn create_document(&self) -> requests::CreateDocumentBuilder<'_, '_, C, D, No>;
     fn replace_document(&self) -> requests::ReplaceDocumentBuilder<'_, '_, C, D, No, No>;
+    fn patch_document(&self) -> requests::PatchDocumentBuilder<'_, '_, C, D, No, No>;
     fn list_documents(&self) -> requests::ListDocumentsBuilder<'_, '_, C, D>;
     fn query_documents(&self) -> requests::QueryDocumentsBuilder<'_, '_, C, D, No>;
+    fn read_change_feed(&self) -> requests::ReadChangeFeedBuilder<'_, '_, C, D>;
+    fn transactional_batch(&self) -> requests::TransactionalBatchBuilder<'_, '_, C, D, No, No>;
 
     fn get_partition_key_ranges(&self) -> requests::GetPartitionKeyRangesBuilder<'_, '_, C, D>;
 
+    fn bulk_executor(&self) -> crate::bulk::BulkExecutor<'_, C, D>;
+
     fn prepare_request(&self, method: hyper::Method) -> http::request::Builder {
         self.cosmos_client().prepare_request(
             &format!("dbs/{}/colls", self.database_client().database_name()),
@@ -386,6 +432,76 @@ where
         IntoCowStr: Into<Cow<'a, str>>;
 }
 
+pub trait ConflictClient<C, D, COLL>: HasCollectionClient<C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+{
+    fn conflict_name(&self) -> &str;
+
+    fn get_conflict(&self) -> requests::GetConflictBuilder<'_, '_, C, D, COLL>;
+    fn delete_conflict(&self) -> requests::DeleteConflictBuilder<'_, '_, C, D, COLL>;
+
+    fn prepare_request(&self, method: hyper::Method) -> http::request::Builder {
+        self.cosmos_client().prepare_request(
+            &format!(
+                "dbs/{}/colls/{}/conflicts",
+                self.database_client().database_name(),
+                self.collection_client().collection_name(),
+            ),
+            method,
+            ResourceType::Conflicts,
+        )
+    }
+    fn prepare_request_with_conflict_name(&self, method: hyper::Method) -> http::request::Builder {
+        self.cosmos_client().prepare_request(
+            &format!(
+                "dbs/{}/colls/{}/conflicts/{}",
+                self.database_client().database_name(),
+                self.collection_client().collection_name(),
+                self.conflict_name()
+            ),
+            method,
+            ResourceType::Conflicts,
+        )
+    }
+}
+
+pub trait HasConflictClient<C, D, COLL, CONFLICT>: HasCollectionClient<C, D, COLL>
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    CONFLICT: ConflictClient<C, D, COLL>,
+{
+    fn conflict_client(&self) -> &CONFLICT;
+}
+
+pub trait WithConflictClient<'a, C, D, COLL, CONFLICT>: Debug + Send + Sync
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    CONFLICT: ConflictClient<C, D, COLL>,
+{
+    fn with_conflict_client<IntoCowStr>(&'a self, conflict_name: IntoCowStr) -> CONFLICT
+    where
+        IntoCowStr: Into<Cow<'a, str>>;
+}
+
+pub trait IntoConflictClient<'a, C, D, COLL, CONFLICT>: Debug + Send + Sync
+where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+    COLL: CollectionClient<C, D>,
+    CONFLICT: ConflictClient<C, D, COLL>,
+{
+    fn into_conflict_client<IntoCowStr>(self, conflict_name: IntoCowStr) -> CONFLICT
+    where
+        IntoCowStr: Into<Cow<'a, str>>;
+}
+
 pub trait StoredProcedureClient<C, D, COLL>: HasCollectionClient<C, D, COLL>
 where
     C: CosmosClient,
@@ -644,6 +760,10 @@ where
     ) -> requests::ReplaceReferenceAttachmentBuilder<'_, '_, C, D, COLL, DOC, No, No>;
     fn delete(&self) -> requests::DeleteAttachmentBuilder<'_, '_, C, D, COLL, DOC>;
     fn get(&self) -> requests::GetAttachmentBuilder<'_, '_, C, D, COLL, DOC>;
+    fn get_attachment_media(&self) -> requests::GetAttachmentMediaBuilder<'_, '_, C, D, COLL, DOC>;
+    fn replace_attachment_media(
+        &self,
+    ) -> requests::ReplaceAttachmentMediaBuilder<'_, '_, C, D, COLL, DOC, No>;
 
     fn prepare_request(&self, method: hyper::Method) -> http::request::Builder {
         self.cosmos_client().prepare_request(
@@ -673,6 +793,26 @@ where
             ResourceType::Attachments,
         )
     }
+    /// Prepares a request on the content of the attachment, given the `media` link of the
+    /// attachment (such as `/media/{id}`). Fails if the attachment references external
+    /// media.
+    fn prepare_media_request(
+        &self,
+        media: &str,
+        method: hyper::Method,
+    ) -> Result<http::request::Builder, AzureError> {
+        let media = media.trim_start_matches('/');
+        if !media.starts_with("media/") {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "attachment {} references the external media {}",
+                self.attachment_name(),
+                media
+            )));
+        }
+        Ok(self
+            .cosmos_client()
+            .prepare_request(media, method, ResourceType::Media))
+    }
 }
 
 pub trait HasAttachmentClient<C, D, COLL, DOC, ATT>: HasDocumentClient<C, D, COLL, DOC>
@@ -0,0 +1,121 @@
+use crate::PatchOperation;
+use azure_sdk_core::errors::AzureError;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Maximum number of operations Cosmos DB accepts in a single transactional batch.
+pub const TRANSACTIONAL_BATCH_MAX_OPERATIONS: usize = 100;
+
+/// Operations to be executed atomically against the documents of a single logical
+/// partition. Either every operation succeeds or none is applied. The operations are
+/// executed in the order they were added.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(transparent)]
+pub struct TransactionalBatch {
+    operations: Vec<BatchOperation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct BatchOperation {
+    #[serde(rename = "operationType")]
+    operation_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(rename = "resourceBody")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource_body: Option<Value>,
+}
+
+impl TransactionalBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn create<T>(&mut self, document: &T) -> Result<&mut Self, AzureError>
+    where
+        T: Serialize,
+    {
+        self.push("Create", None, Some(serde_json::to_value(document)?));
+        Ok(self)
+    }
+
+    pub fn upsert<T>(&mut self, document: &T) -> Result<&mut Self, AzureError>
+    where
+        T: Serialize,
+    {
+        self.push("Upsert", None, Some(serde_json::to_value(document)?));
+        Ok(self)
+    }
+
+    pub fn replace<T>(&mut self, id: &str, document: &T) -> Result<&mut Self, AzureError>
+    where
+        T: Serialize,
+    {
+        self.push("Replace", Some(id), Some(serde_json::to_value(document)?));
+        Ok(self)
+    }
+
+    pub fn read(&mut self, id: &str) -> &mut Self {
+        self.push("Read", Some(id), None);
+        self
+    }
+
+    pub fn delete(&mut self, id: &str) -> &mut Self {
+        self.push("Delete", Some(id), None);
+        self
+    }
+
+    pub fn patch(&mut self, id: &str, operations: &[PatchOperation]) -> &mut Self {
+        self.push("Patch", Some(id), Some(json!({ "operations": operations })));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    fn push(
+        &mut self,
+        operation_type: &'static str,
+        id: Option<&str>,
+        resource_body: Option<Value>,
+    ) {
+        self.operations.push(BatchOperation {
+            operation_type,
+            id: id.map(|id| id.to_owned()),
+            resource_body,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_transactional_batch() {
+        let mut batch = TransactionalBatch::new();
+        batch
+            .create(&json!({ "id": "a", "pk": "p" }))
+            .unwrap()
+            .read("b")
+            .delete("c")
+            .patch("d", &[PatchOperation::increment("/count", 1)]);
+
+        assert_eq!(batch.len(), 4);
+        assert_eq!(
+            serde_json::to_string(&batch).unwrap(),
+            concat!(
+                r#"[{"operationType":"Create","resourceBody":{"id":"a","pk":"p"}},"#,
+                r#"{"operationType":"Read","id":"b"},"#,
+                r#"{"operationType":"Delete","id":"c"},"#,
+                r#"{"operationType":"Patch","id":"d","resourceBody":"#,
+                r#"{"operations":[{"op":"incr","path":"/count","value":1}]}}]"#
+            )
+        );
+    }
+}
@@ -57,6 +57,7 @@ async fn attachment() -> Result<(), Box<dyn Error>> {
             indexing_mode: IndexingMode::Consistent,
             included_paths: vec![ip],
             excluded_paths: vec![],
+            ..Default::default()
         };
 
         database_client
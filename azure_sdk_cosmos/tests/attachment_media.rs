@@ -0,0 +1,99 @@
+#![cfg(all(test, feature = "test_utils"))]
+use azure_sdk_core::prelude::*;
+use azure_sdk_cosmos::prelude::*;
+use azure_sdk_cosmos::responses::GetAttachmentMediaResponse;
+use azure_sdk_cosmos::test_utils::InMemoryEmulator;
+use futures::stream::TryStreamExt;
+mod setup;
+
+async fn read_media(response: GetAttachmentMediaResponse) -> Vec<u8> {
+    response
+        .media
+        .try_fold(Vec::new(), |mut content, chunk| async move {
+            content.extend_from_slice(&chunk);
+            Ok(content)
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn attachment_media() {
+    let emulator = InMemoryEmulator::start().await.unwrap();
+    let client = emulator.client();
+
+    setup::create_database(&client, "db").await;
+    let database_client = client.with_database_client("db");
+    setup::create_collection(&database_client, "coll", "/id").await;
+    let collection_client = database_client.with_collection_client("coll");
+    collection_client
+        .create_document()
+        .with_partition_keys(&"doc".into())
+        .execute_with_document(&Document::new(serde_json::json!({ "id": "doc" })))
+        .await
+        .unwrap();
+    let document_client = collection_client.with_document_client("doc", "doc".into());
+
+    let attachment_client = document_client.with_attachment_client("slug");
+    attachment_client
+        .create_slug()
+        .with_content_type("text/plain")
+        .with_body(b"hello")
+        .execute()
+        .await
+        .unwrap();
+
+    let response = attachment_client
+        .get_attachment_media()
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(response.content_type, "text/plain");
+    assert_eq!(response.content_length, Some(5));
+    assert_eq!(read_media(response).await, b"hello");
+
+    // larger than a chunk, streamed with and without a known length
+    let content = (0..200 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+    attachment_client
+        .replace_attachment_media()
+        .with_content_type("application/octet-stream")
+        .execute_with_reader(futures::io::Cursor::new(content.clone()))
+        .await
+        .unwrap();
+    let response = attachment_client
+        .get_attachment_media()
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(response.content_type, "application/octet-stream");
+    assert_eq!(read_media(response).await, content);
+
+    attachment_client
+        .replace_attachment_media()
+        .with_content_type("text/plain")
+        .with_content_length(3)
+        .execute_with_reader(futures::io::Cursor::new(b"bye".to_vec()))
+        .await
+        .unwrap();
+    let response = attachment_client
+        .get_attachment_media()
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(read_media(response).await, b"bye");
+
+    // the content of external media is not stored by the account
+    let attachment_client = document_client.with_attachment_client("reference");
+    attachment_client
+        .create_reference()
+        .with_content_type("image/jpeg")
+        .with_media("https://example.com/image.jpg")
+        .execute()
+        .await
+        .unwrap();
+    assert!(attachment_client
+        .get_attachment_media()
+        .execute()
+        .await
+        .is_err());
+}
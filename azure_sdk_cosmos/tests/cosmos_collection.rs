@@ -26,6 +26,7 @@ async fn create_and_delete_collection() {
         indexing_mode: IndexingMode::Consistent,
         included_paths: vec![],
         excluded_paths: vec![],
+        ..Default::default()
     };
     let collection = database_client
         .create_collection()
@@ -85,6 +86,7 @@ async fn replace_collection() {
         indexing_mode: IndexingMode::Consistent,
         included_paths: vec![],
         excluded_paths: vec![],
+        ..Default::default()
     };
     let collection = database_client
         .create_collection()
@@ -122,6 +124,7 @@ async fn replace_collection() {
         indexing_mode: IndexingMode::Consistent,
         included_paths: vec![ip],
         excluded_paths: vec![],
+        ..Default::default()
     };
 
     new_ip
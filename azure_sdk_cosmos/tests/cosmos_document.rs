@@ -38,6 +38,7 @@ async fn create_and_delete_document() {
         indexing_mode: IndexingMode::Consistent,
         included_paths: vec![],
         excluded_paths: vec![],
+        ..Default::default()
     };
 
     database_client
@@ -124,6 +125,7 @@ async fn query_documents() {
         indexing_mode: IndexingMode::Consistent,
         included_paths: vec![],
         excluded_paths: vec![],
+        ..Default::default()
     };
 
     database_client
@@ -199,6 +201,7 @@ async fn replace_document() {
         indexing_mode: IndexingMode::Consistent,
         included_paths: vec![],
         excluded_paths: vec![],
+        ..Default::default()
     };
 
     database_client
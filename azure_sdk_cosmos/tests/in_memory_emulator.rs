@@ -0,0 +1,335 @@
+#![cfg(all(test, feature = "test_utils"))]
+#[macro_use]
+extern crate serde_derive;
+use azure_sdk_core::prelude::*;
+use azure_sdk_cosmos::collection::*;
+use azure_sdk_cosmos::prelude::*;
+use azure_sdk_cosmos::responses::GetDocumentResponse;
+use azure_sdk_cosmos::test_utils::InMemoryEmulator;
+use azure_sdk_cosmos::{Param, Query};
+use futures::stream::StreamExt;
+use hyper::StatusCode;
+mod setup;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct City {
+    id: String,
+    country: String,
+    population: u64,
+}
+
+fn city(id: &str, country: &str, population: u64) -> Document<City> {
+    Document::new(City {
+        id: id.to_owned(),
+        country: country.to_owned(),
+        population,
+    })
+}
+
+#[tokio::test]
+async fn documents() {
+    let emulator = InMemoryEmulator::start().await.unwrap();
+    let client = emulator.client();
+
+    setup::create_database(&client, "db").await;
+    let database_client = client.with_database_client("db");
+
+    setup::create_collection(&database_client, "cities", "/country").await;
+    let collection_client = database_client.with_collection_client("cities");
+
+    for (id, country, population) in &[
+        ("milan", "Italy", 1352),
+        ("rome", "Italy", 2873),
+        ("paris", "France", 2161),
+        ("lyon", "France", 516),
+    ] {
+        collection_client
+            .create_document()
+            .with_partition_keys(&(*country).into())
+            .execute_with_document(&city(id, country, *population))
+            .await
+            .unwrap();
+    }
+
+    // documents are identified by id and partition key
+    let error = collection_client
+        .create_document()
+        .with_partition_keys(&"Italy".into())
+        .execute_with_document(&city("milan", "Italy", 0))
+        .await
+        .unwrap_err();
+    assert_eq!(setup::status_code(error), StatusCode::CONFLICT);
+    collection_client
+        .create_document()
+        .with_partition_keys(&"France".into())
+        .execute_with_document(&city("milan", "France", 0))
+        .await
+        .unwrap();
+
+    let partition_keys: PartitionKeys = "Italy".into();
+    let document_client = collection_client.with_document_client("rome", partition_keys.clone());
+    let etag = match document_client
+        .get_document()
+        .execute::<City>()
+        .await
+        .unwrap()
+    {
+        GetDocumentResponse::Found(response) => {
+            assert_eq!(response.document.document.population, 2873);
+            response.etag
+        }
+        GetDocumentResponse::NotFound(_) => panic!("document not found"),
+    };
+
+    // replacing with an old etag fails
+    let response = collection_client
+        .replace_document()
+        .with_document_id("rome")
+        .with_partition_keys(&partition_keys)
+        .with_if_match_condition(IfMatchCondition::Match(&etag))
+        .execute_with_document(&city("rome", "Italy", 2874))
+        .await
+        .unwrap();
+    assert_ne!(response.document_attributes.etag, etag);
+    let error = collection_client
+        .replace_document()
+        .with_document_id("rome")
+        .with_partition_keys(&partition_keys)
+        .with_if_match_condition(IfMatchCondition::Match(&etag))
+        .execute_with_document(&city("rome", "Italy", 2875))
+        .await
+        .unwrap_err();
+    assert_eq!(setup::status_code(error), StatusCode::PRECONDITION_FAILED);
+
+    // a query on a single partition
+    let query = Query::with_params(
+        "SELECT * FROM c WHERE c.population > @population ORDER BY c.population DESC",
+        vec![Param::new("@population", 1000)],
+    );
+    let response = collection_client
+        .query_documents()
+        .with_query(&query)
+        .with_partition_keys(&partition_keys)
+        .execute::<City>()
+        .await
+        .unwrap();
+    let ids = response
+        .into_documents()
+        .unwrap()
+        .results
+        .into_iter()
+        .map(|result| result.result.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["rome", "milan"]);
+
+    // a cross partition query read in pages
+    let query = Query::new("SELECT VALUE c.id FROM c ORDER BY c.id");
+    let query_documents = collection_client
+        .query_documents()
+        .with_query(&query)
+        .with_query_cross_partition(true)
+        .with_max_item_count(2);
+    let pages = query_documents
+        .stream::<String>()
+        .map(|page| page.unwrap().into_raw().results)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        pages,
+        vec![
+            vec!["lyon".to_owned(), "milan".to_owned()],
+            vec!["milan".to_owned(), "paris".to_owned()],
+            vec!["rome".to_owned()],
+        ]
+    );
+
+    document_client.delete_document().execute().await.unwrap();
+    match document_client
+        .get_document()
+        .execute::<City>()
+        .await
+        .unwrap()
+    {
+        GetDocumentResponse::Found(_) => panic!("document not deleted"),
+        GetDocumentResponse::NotFound(_) => {}
+    }
+
+    let documents = collection_client
+        .list_documents()
+        .execute::<City>()
+        .await
+        .unwrap()
+        .documents;
+    assert_eq!(documents.len(), 4);
+
+    database_client.delete_database().execute().await.unwrap();
+    let databases = client.list_databases().execute().await.unwrap().databases;
+    assert!(databases.is_empty());
+}
+
+#[tokio::test]
+async fn time_to_live() {
+    let emulator = InMemoryEmulator::start().await.unwrap();
+    let client = emulator.client();
+
+    setup::create_database(&client, "db").await;
+    let database_client = client.with_database_client("db");
+
+    // documents expire only if they set their own ttl
+    let indexing_policy = setup::indexing_policy();
+    let partition_key = "/country".into();
+    let collection = database_client
+        .create_collection()
+        .with_collection_name(&"sessions")
+        .with_offer(Offer::Throughput(400))
+        .with_partition_key(&partition_key)
+        .with_indexing_policy(&indexing_policy)
+        .with_default_ttl(TimeToLive::Never)
+        .execute()
+        .await
+        .unwrap()
+        .collection;
+    assert_eq!(collection.default_ttl, Some(TimeToLive::Never));
+    let collection_client = database_client.with_collection_client("sessions");
+
+    let partition_keys: PartitionKeys = "Italy".into();
+    collection_client
+        .create_document()
+        .with_partition_keys(&partition_keys)
+        .execute_with_document(&city("milan", "Italy", 1352).with_ttl(TimeToLive::Seconds(1)))
+        .await
+        .unwrap();
+    collection_client
+        .create_document()
+        .with_partition_keys(&partition_keys)
+        .execute_with_document(&city("rome", "Italy", 2873))
+        .await
+        .unwrap();
+
+    let document_client = collection_client.with_document_client("milan", partition_keys.clone());
+    match document_client
+        .get_document()
+        .execute::<City>()
+        .await
+        .unwrap()
+    {
+        GetDocumentResponse::Found(response) => assert_eq!(
+            response.document.document_attributes.ttl(),
+            Some(TimeToLive::Seconds(1))
+        ),
+        GetDocumentResponse::NotFound(_) => panic!("document not found"),
+    }
+
+    tokio::time::delay_for(std::time::Duration::from_millis(2100)).await;
+
+    match document_client
+        .get_document()
+        .execute::<City>()
+        .await
+        .unwrap()
+    {
+        GetDocumentResponse::Found(_) => panic!("document not expired"),
+        GetDocumentResponse::NotFound(_) => {}
+    }
+    let documents = collection_client
+        .list_documents()
+        .execute::<City>()
+        .await
+        .unwrap()
+        .documents;
+    assert_eq!(documents.len(), 1);
+
+    let collection = collection_client
+        .replace_collection()
+        .with_partition_key(&partition_key)
+        .with_indexing_policy(&indexing_policy)
+        .with_default_ttl(TimeToLive::Seconds(3600))
+        .execute()
+        .await
+        .unwrap()
+        .collection;
+    assert_eq!(collection.default_ttl, Some(TimeToLive::Seconds(3600)));
+}
+
+#[tokio::test]
+async fn indexing_and_unique_key_policies() {
+    let emulator = InMemoryEmulator::start().await.unwrap();
+    let client = emulator.client();
+
+    setup::create_database(&client, "db").await;
+    let database_client = client.with_database_client("db");
+
+    let mut indexing_policy = IndexingPolicy {
+        included_paths: vec![IncludedPath {
+            path: "/*".to_owned(),
+            indexes: None,
+        }],
+        ..Default::default()
+    }
+    .with_composite_indexes(vec![CompositeIndex {
+        paths: vec![
+            "/country".into(),
+            CompositePath {
+                path: "/population".to_owned(),
+                order: Some(CompositePathOrder::Descending),
+            },
+        ],
+    }]);
+    let unique_key_policy = UniqueKeyPolicy {
+        unique_keys: vec![UniqueKey {
+            paths: vec!["/name".to_owned()],
+        }],
+    };
+    let partition_key = "/country".into();
+    database_client
+        .create_collection()
+        .with_collection_name(&"cities")
+        .with_offer(Offer::Throughput(400))
+        .with_partition_key(&partition_key)
+        .with_indexing_policy(&indexing_policy)
+        .with_unique_key_policy(&unique_key_policy)
+        .execute()
+        .await
+        .unwrap();
+    let collection_client = database_client.with_collection_client("cities");
+
+    let collection = collection_client
+        .get_collection()
+        .execute()
+        .await
+        .unwrap()
+        .collection;
+    assert_eq!(collection.indexing_policy, indexing_policy);
+    assert_eq!(collection.unique_key_policy, Some(unique_key_policy));
+
+    indexing_policy.spatial_indexes.push(SpatialIndex {
+        path: "/location/*".to_owned(),
+        types: vec![SpatialType::Point, SpatialType::Polygon],
+    });
+    collection_client
+        .replace_collection()
+        .with_partition_key(&partition_key)
+        .with_indexing_policy(&indexing_policy)
+        .execute()
+        .await
+        .unwrap();
+    let collection = collection_client
+        .get_collection()
+        .execute()
+        .await
+        .unwrap()
+        .collection;
+    assert_eq!(collection.indexing_policy, indexing_policy);
+    assert_eq!(collection.unique_key_policy.unwrap().unique_keys.len(), 1);
+
+    // the unique keys are fixed when the collection is created
+    let error = collection_client
+        .replace_collection()
+        .with_partition_key(&partition_key)
+        .with_indexing_policy(&indexing_policy)
+        .with_unique_key_policy(&UniqueKeyPolicy::default())
+        .execute()
+        .await
+        .unwrap_err();
+    assert_eq!(setup::status_code(error), StatusCode::BAD_REQUEST);
+}
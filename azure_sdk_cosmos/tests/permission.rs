@@ -49,6 +49,7 @@ async fn permissions() {
             indexing_mode: IndexingMode::Consistent,
             included_paths: vec![ip],
             excluded_paths: vec![],
+            ..Default::default()
         };
 
         database_client
@@ -29,6 +29,7 @@ async fn permissions() {
         indexing_mode: IndexingMode::Consistent,
         included_paths: vec![],
         excluded_paths: vec![],
+        ..Default::default()
     };
 
     let create_collection_response = database_client
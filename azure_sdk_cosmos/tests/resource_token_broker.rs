@@ -0,0 +1,149 @@
+#![cfg(all(test, feature = "test_utils"))]
+use azure_sdk_cosmos::prelude::*;
+use azure_sdk_cosmos::test_utils::InMemoryEmulator;
+use azure_sdk_cosmos::{PermissionMode, ResourceGrant, ResourceTokenBroker};
+use hyper::StatusCode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+mod setup;
+
+#[tokio::test]
+async fn resource_tokens() {
+    let emulator = InMemoryEmulator::start().await.unwrap();
+    let client = emulator.client();
+
+    setup::create_database(&client, "db").await;
+    let database_client = client.with_database_client("db");
+    for collection_name in &["sessions", "secrets"] {
+        setup::create_collection(&database_client, collection_name, "/id").await;
+    }
+
+    let broker =
+        ResourceTokenBroker::new(client.clone(), "db").with_refresh_margin(Duration::default());
+    let grant = ResourceGrant::new(
+        "read-sessions",
+        PermissionMode::Read("dbs/db/colls/sessions".to_owned()),
+        Duration::from_secs(1),
+    );
+
+    // the user and the permission are created on demand and the token is cached
+    let auth_token = broker.auth_token("alice", &grant).await.unwrap();
+    assert!(matches!(auth_token, AuthorizationToken::Resource(_)));
+    assert_eq!(
+        broker
+            .auth_tokens("alice", std::slice::from_ref(&grant))
+            .await
+            .unwrap(),
+        vec![auth_token.clone()]
+    );
+
+    let status_codes = Arc::new(Mutex::new(Vec::new()));
+    let alice_client = broker
+        .client_for(
+            &client.with_diagnostics_handler({
+                let status_codes = status_codes.clone();
+                move |request_diagnostics| {
+                    status_codes
+                        .lock()
+                        .unwrap()
+                        .push(request_diagnostics.status_code)
+                }
+            }),
+            "alice",
+            &grant,
+        )
+        .await
+        .unwrap();
+    alice_client
+        .with_database_client("db")
+        .with_collection_client("sessions")
+        .list_documents()
+        .execute::<serde_json::Value>()
+        .await
+        .unwrap();
+
+    // the token is scoped to the resource of the permission
+    let error = alice_client
+        .with_database_client("db")
+        .with_collection_client("secrets")
+        .list_documents()
+        .execute::<serde_json::Value>()
+        .await
+        .unwrap_err();
+    assert_eq!(setup::status_code(error), StatusCode::FORBIDDEN);
+    let error = alice_client
+        .with_database_client("db")
+        .with_collection_client("sessions")
+        .create_document()
+        .with_partition_keys(&"session".into())
+        .execute_with_document(&Document::new(serde_json::json!({ "id": "session" })))
+        .await
+        .unwrap_err();
+    assert_eq!(setup::status_code(error), StatusCode::FORBIDDEN);
+
+    tokio::time::delay_for(Duration::from_millis(1200)).await;
+
+    // a client with a plain token fails once the token expires...
+    let error = client
+        .with_auth_token(auth_token.clone())
+        .with_database_client("db")
+        .with_collection_client("sessions")
+        .list_documents()
+        .execute::<serde_json::Value>()
+        .await
+        .unwrap_err();
+    assert_eq!(setup::status_code(error), StatusCode::UNAUTHORIZED);
+
+    // ...while a client with the refresher of the broker refreshes it
+    client
+        .with_resource_token_refresher(broker.refresher("alice", &grant))
+        .with_auth_token(auth_token)
+        .with_database_client("db")
+        .with_collection_client("sessions")
+        .list_documents()
+        .execute::<serde_json::Value>()
+        .await
+        .unwrap();
+
+    // as does the client of the broker
+    status_codes.lock().unwrap().clear();
+    alice_client
+        .with_database_client("db")
+        .with_collection_client("sessions")
+        .list_documents()
+        .execute::<serde_json::Value>()
+        .await
+        .unwrap();
+    assert_eq!(
+        *status_codes.lock().unwrap(),
+        vec![StatusCode::UNAUTHORIZED, StatusCode::OK]
+    );
+    // and keeps using the new token
+    status_codes.lock().unwrap().clear();
+    alice_client
+        .with_database_client("db")
+        .with_collection_client("sessions")
+        .list_documents()
+        .execute::<serde_json::Value>()
+        .await
+        .unwrap();
+    assert_eq!(*status_codes.lock().unwrap(), vec![StatusCode::OK]);
+
+    // changing the grant updates the permission
+    let grant = ResourceGrant::new(
+        "read-sessions",
+        PermissionMode::All("dbs/db/colls/sessions".to_owned()),
+        Duration::from_secs(3600),
+    );
+    broker
+        .client_for(&client, "alice", &grant)
+        .await
+        .unwrap()
+        .with_database_client("db")
+        .with_collection_client("sessions")
+        .create_document()
+        .with_partition_keys(&"session".into())
+        .execute_with_document(&Document::new(serde_json::json!({ "id": "session" })))
+        .await
+        .unwrap();
+}
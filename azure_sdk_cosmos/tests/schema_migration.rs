@@ -0,0 +1,124 @@
+#![cfg(all(test, feature = "test_utils"))]
+use azure_sdk_cosmos::migration::{
+    CollectionSpec, DatabaseSpec, MigrationStep, SchemaMigration, SchemaSpec, TriggerSpec,
+};
+use azure_sdk_cosmos::prelude::*;
+use azure_sdk_cosmos::test_utils::InMemoryEmulator;
+use azure_sdk_cosmos::trigger::{TriggerOperation, TriggerType};
+use azure_sdk_cosmos::TimeToLive;
+
+fn shop(throughput: u64, archive: &str) -> SchemaSpec {
+    SchemaSpec::default().with_database(
+        DatabaseSpec::new("shop")
+            .with_collection(
+                CollectionSpec::new("orders", "/customerId")
+                    .with_throughput(throughput)
+                    .with_default_ttl(TimeToLive::Seconds(3600))
+                    .with_stored_procedure("archive", archive)
+                    .with_trigger(TriggerSpec::new(
+                        "validate",
+                        "function () {}",
+                        TriggerType::Pre,
+                        TriggerOperation::Create,
+                    ))
+                    .with_user_defined_function("tax", "function (amount) { return amount; }"),
+            )
+            .with_collection(CollectionSpec::new("carts", "/customerId")),
+    )
+}
+
+#[tokio::test]
+async fn schema_migration() {
+    let emulator = InMemoryEmulator::start().await.unwrap();
+    let client = emulator.client();
+
+    // a dry run changes nothing
+    let spec = shop(400, "function () {}");
+    let plan = SchemaMigration::new(&client, &spec)
+        .with_dry_run(true)
+        .migrate()
+        .await
+        .unwrap();
+    assert_eq!(plan.steps.len(), 6);
+    let databases = client.list_databases().execute().await.unwrap();
+    assert!(databases.databases.is_empty());
+
+    let plan = SchemaMigration::new(&client, &spec)
+        .migrate()
+        .await
+        .unwrap();
+    assert_eq!(plan.steps.len(), 6);
+    assert!(SchemaMigration::new(&client, &spec)
+        .plan()
+        .await
+        .unwrap()
+        .is_empty());
+
+    // only the changes are applied
+    let spec = shop(1000, "function () { return; }");
+    let plan = SchemaMigration::new(&client, &spec)
+        .migrate()
+        .await
+        .unwrap();
+    assert_eq!(plan.steps.len(), 2);
+    assert!(matches!(
+        plan.steps[0],
+        MigrationStep::ReplaceThroughput {
+            throughput: 1000,
+            ..
+        }
+    ));
+    assert!(matches!(
+        plan.steps[1],
+        MigrationStep::ReplaceStoredProcedure { .. }
+    ));
+    let offers = client.list_offers().execute().await.unwrap();
+    let mut throughputs = offers
+        .offers
+        .iter()
+        .filter_map(|offer| offer.throughput())
+        .collect::<Vec<_>>();
+    throughputs.sort_unstable();
+    assert_eq!(throughputs, vec![400, 1000]);
+    assert!(SchemaMigration::new(&client, &spec)
+        .plan()
+        .await
+        .unwrap()
+        .is_empty());
+
+    // what is not in the spec is deleted only when pruning
+    let spec = SchemaSpec::default().with_database(
+        DatabaseSpec::new("shop").with_collection(CollectionSpec::new("orders", "/customerId")),
+    );
+    let plan = SchemaMigration::new(&client, &spec).plan().await.unwrap();
+    assert_eq!(plan.steps.len(), 1, "{}", plan);
+    let plan = SchemaMigration::new(&client, &spec)
+        .with_prune(true)
+        .migrate()
+        .await
+        .unwrap();
+    assert_eq!(
+        plan.to_string(),
+        "replace collection shop/orders: default time to live\n\
+         delete stored procedure shop/orders/archive\n\
+         delete trigger shop/orders/validate\n\
+         delete user defined function shop/orders/tax\n\
+         delete collection shop/carts\n"
+    );
+    let collections = client
+        .with_database_client("shop")
+        .list_collections()
+        .execute()
+        .await
+        .unwrap();
+    assert_eq!(collections.collections.len(), 1);
+
+    // the partition key of a collection cannot be changed
+    let spec = SchemaSpec::default().with_database(
+        DatabaseSpec::new("shop").with_collection(CollectionSpec::new("orders", "/id")),
+    );
+    assert!(SchemaMigration::new(&client, &spec)
+        .migrate()
+        .await
+        .is_err());
+}
@@ -1,7 +1,9 @@
+#![allow(dead_code)]
 use azure_sdk_core::errors::AzureError;
 use azure_sdk_cosmos::clients::DefaultCosmosUri;
 use azure_sdk_cosmos::prelude::*;
 use azure_sdk_cosmos::AuthorizationToken;
+use hyper::StatusCode;
 
 pub fn initialize() -> Result<CosmosStruct<'static, DefaultCosmosUri>, AzureError> {
     let account = std::env::var("COSMOS_ACCOUNT").expect("Set env variable COSMOS_ACCOUNT first!");
@@ -13,3 +15,53 @@ pub fn initialize() -> Result<CosmosStruct<'static, DefaultCosmosUri>, AzureErro
 
     Ok(client)
 }
+
+pub fn status_code(error: AzureError) -> StatusCode {
+    match error {
+        AzureError::UnexpectedHTTPResult(error) => error.status_code(),
+        error => panic!("unexpected error {:?}", error),
+    }
+}
+
+pub fn indexing_policy() -> IndexingPolicy {
+    IndexingPolicy {
+        automatic: true,
+        indexing_mode: IndexingMode::Consistent,
+        included_paths: vec![],
+        excluded_paths: vec![],
+        composite_indexes: vec![],
+        spatial_indexes: vec![],
+    }
+}
+
+pub async fn create_database<C>(client: &C, database_name: &str)
+where
+    C: CosmosClient,
+{
+    client
+        .create_database()
+        .with_database_name(&database_name)
+        .execute()
+        .await
+        .unwrap();
+}
+
+// Creates a collection with 400 RU/s and the indexing policy above.
+pub async fn create_collection<C, D>(
+    database_client: &D,
+    collection_name: &str,
+    partition_key: &str,
+) where
+    C: CosmosClient,
+    D: DatabaseClient<C>,
+{
+    database_client
+        .create_collection()
+        .with_collection_name(&collection_name)
+        .with_offer(Offer::Throughput(400))
+        .with_partition_key(&partition_key.into())
+        .with_indexing_policy(&indexing_policy())
+        .execute()
+        .await
+        .unwrap();
+}
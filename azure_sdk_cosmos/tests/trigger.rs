@@ -72,6 +72,7 @@ async fn trigger() -> Result<(), Box<dyn Error>> {
             indexing_mode: IndexingMode::Consistent,
             included_paths: vec![ip],
             excluded_paths: vec![],
+            ..Default::default()
         };
 
         database_client
@@ -53,6 +53,7 @@ async fn user_defined_function00() -> Result<(), Box<dyn Error>> {
             indexing_mode: IndexingMode::Consistent,
             included_paths: vec![ip],
             excluded_paths: vec![],
+            ..Default::default()
         };
 
         database_client
@@ -0,0 +1,61 @@
+#[macro_use]
+extern crate log;
+
+use azure_sdk_core::prelude::*;
+use azure_sdk_storage_blob::prelude::*;
+use azure_sdk_storage_core::prelude::*;
+use std::error::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+    debug!("log initialized");
+    // First we retrieve the account name and master key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let master_key =
+        std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
+
+    let container = std::env::args()
+        .nth(1)
+        .expect("please specify container name as command line parameter");
+    let blob_name = std::env::args()
+        .nth(2)
+        .expect("please specify blob name as command line parameter");
+    let file_name = std::env::args()
+        .nth(3)
+        .expect("please specify the file to download to as command line parameter");
+    // the ETag printed by an interrupted run, to resume it
+    let etag = std::env::args().nth(4);
+
+    let client = client::with_access_key(&account, &master_key);
+
+    // The blob is fetched in ranges of 8 MiB, 8 ranges at a time. When the ETag
+    // is given only the missing part of the file is downloaded, provided the blob
+    // has not changed since.
+    let download = client
+        .download_blob()
+        .with_container_name(&container)
+        .with_blob_name(&blob_name)
+        .with_chunk_size(8 * 1024 * 1024)
+        .with_max_concurrency(8);
+    let res = match etag {
+        Some(ref etag) => {
+            download
+                .with_resume(true)
+                .with_if_match_condition(IfMatchCondition::Match(etag))
+                .finalize_to_file(&file_name)
+                .await?
+        }
+        None => download.finalize_to_file(&file_name).await?,
+    };
+    println!(
+        "downloaded {} bytes ({} already there), MD5 checked: {}, ETag: {}",
+        res.content_length - res.resumed_length,
+        res.resumed_length,
+        res.content_md5_checked,
+        res.etag
+    );
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+#[macro_use]
+extern crate log;
+
+use azure_sdk_core::prelude::*;
+use azure_sdk_storage_blob::prelude::*;
+use azure_sdk_storage_core::prelude::*;
+use std::error::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+    debug!("log initialized");
+    // First we retrieve the account name and master key from environment variables.
+    let account =
+        std::env::var("STORAGE_ACCOUNT").expect("Set env variable STORAGE_ACCOUNT first!");
+    let master_key =
+        std::env::var("STORAGE_MASTER_KEY").expect("Set env variable STORAGE_MASTER_KEY first!");
+
+    let container = std::env::args()
+        .nth(1)
+        .expect("please specify container name as command line parameter");
+    let blob_name = std::env::args()
+        .nth(2)
+        .expect("please specify blob name as command line parameter");
+    let file_name = std::env::args()
+        .nth(3)
+        .expect("please specify the file to upload as command line parameter");
+
+    let client = client::with_access_key(&account, &master_key);
+
+    // The file is sent in blocks of 8 MiB, 8 blocks at a time. Every block
+    // is retried up to 5 times before giving up.
+    let res = client
+        .upload_blob_from_reader()
+        .with_container_name(&container)
+        .with_blob_name(&blob_name)
+        .with_block_size(8 * 1024 * 1024)
+        .with_max_concurrency(8)
+        .with_max_retries(5)
+        .with_content_type("application/octet-stream")
+        .finalize_with_file(&file_name)
+        .await?;
+    println!(
+        "uploaded {} bytes in {} blocks: {:?}",
+        res.content_length, res.block_count, res
+    );
+
+    Ok(())
+}
@@ -0,0 +1,394 @@
+use crate::blob::requests::GetBlobBuilder;
+use crate::blob::retry::with_retries;
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::lease::LeaseId;
+use azure_sdk_core::modify_conditions::IfMatchCondition;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::range::Range;
+use azure_sdk_core::Yes;
+use azure_sdk_storage_core::prelude::*;
+use chrono::{DateTime, Utc};
+use futures::future::{BoxFuture, FutureExt, MaybeDone};
+use futures::io::{AsyncRead, AsyncSeek, SeekFrom};
+use futures::task::{Context, Poll};
+use hyper::StatusCode;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+
+/// Reads a blob as an `AsyncRead + AsyncSeek`, for the libraries that need random access
+/// to a file such as the zip or parquet readers.
+///
+/// The blob is read with ranged GETs of `read_ahead` bytes. While the caller consumes a
+/// range the next one is already requested, and a seek outside of the buffered range
+/// makes the next read request the range starting at the new position. Every GET carries
+/// the ETag the blob had when the reader was created in an If-Match header, so the reads
+/// fail if the blob is replaced instead of returning a mix of two versions.
+///
+/// A `BlobReader` is created with `Blob::blob_reader`.
+pub struct BlobReader<'a, C>
+where
+    C: Client,
+{
+    client: &'a C,
+    container_name: String,
+    blob_name: String,
+    snapshot: Option<DateTime<Utc>>,
+    timeout: Option<u64>,
+    lease_id: Option<LeaseId>,
+    client_request_id: Option<String>,
+    max_retries: u32,
+    read_ahead: u64,
+    etag: String,
+    content_length: u64,
+    position: u64,
+    buffer: Vec<u8>,
+    buffer_start: u64,
+    fetch: Option<(Range, MaybeDone<Fetch<'a>>)>,
+}
+
+type Fetch<'a> = BoxFuture<'a, Result<Vec<u8>, AzureError>>;
+
+impl<'a, C> std::fmt::Debug for BlobReader<'a, C>
+where
+    C: Client,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobReader")
+            .field("container_name", &self.container_name)
+            .field("blob_name", &self.blob_name)
+            .field("etag", &self.etag)
+            .field("content_length", &self.content_length)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+impl<'a, C> BlobReader<'a, C>
+where
+    C: Client,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn new(
+        client: &'a C,
+        container_name: &str,
+        blob_name: &str,
+        snapshot: Option<DateTime<Utc>>,
+        timeout: Option<u64>,
+        lease_id: Option<LeaseId>,
+        client_request_id: Option<&str>,
+        max_retries: u32,
+        read_ahead: u64,
+    ) -> Result<BlobReader<'a, C>, AzureError> {
+        if read_ahead == 0 {
+            return Err(AzureError::InputParametersError(
+                "the read ahead must be at least 1 byte".to_owned(),
+            ));
+        }
+
+        let mut reader = BlobReader {
+            client,
+            container_name: container_name.to_owned(),
+            blob_name: blob_name.to_owned(),
+            snapshot,
+            timeout,
+            lease_id,
+            client_request_id: client_request_id.map(|id| id.to_owned()),
+            max_retries,
+            read_ahead,
+            etag: String::new(),
+            content_length: 0,
+            position: 0,
+            buffer: Vec::new(),
+            buffer_start: 0,
+            fetch: None,
+        };
+
+        // The first range gives the ETag and the length of the blob. A range cannot be
+        // requested from an empty blob, which is then read as a whole.
+        let first_range = Range::new(0, read_ahead);
+        let response = match with_retries(max_retries, || {
+            reader.get_blob(Some(&first_range), None).finalize()
+        })
+        .await
+        {
+            Err(AzureError::UnexpectedHTTPResult(ref result))
+                if result.status_code() == StatusCode::RANGE_NOT_SATISFIABLE =>
+            {
+                with_retries(max_retries, || reader.get_blob(None, None).finalize()).await?
+            }
+            response => response?,
+        };
+
+        reader.etag = response
+            .blob
+            .etag
+            .clone()
+            .ok_or_else(|| AzureError::MissingHeaderError("ETag".to_owned()))?;
+        reader.content_length = response.blob_content_length;
+        reader.buffer = response.data;
+        Ok(reader)
+    }
+
+    /// The ETag of the blob being read.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// The length of the blob, in bytes.
+    pub fn content_length(&self) -> u64 {
+        self.content_length
+    }
+
+    /// The position of the next byte to read.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    fn get_blob<'b>(
+        &'b self,
+        range: Option<&'b Range>,
+        etag: Option<&'b str>,
+    ) -> GetBlobBuilder<'b, C, Yes, Yes> {
+        get_blob(
+            self.client,
+            &self.container_name,
+            &self.blob_name,
+            self.snapshot,
+            self.timeout,
+            self.lease_id.as_ref(),
+            self.client_request_id.as_deref(),
+            range,
+            etag,
+        )
+    }
+
+    // Copies the buffered bytes at the current position, if any.
+    fn read_buffered(&mut self, buf: &mut [u8]) -> Option<usize> {
+        let buffer_end = self.buffer_start + self.buffer.len() as u64;
+        if self.position < self.buffer_start || self.position >= buffer_end {
+            return None;
+        }
+        let start = (self.position - self.buffer_start) as usize;
+        let len = std::cmp::min(buf.len(), self.buffer.len() - start);
+        buf[..len].copy_from_slice(&self.buffer[start..start + len]);
+        self.position += len as u64;
+        Some(len)
+    }
+
+    // Starts fetching the range at `start`, replacing the fetch in progress.
+    fn start_fetch(&mut self, start: u64) {
+        let range = Range::new(
+            start,
+            std::cmp::min(start + self.read_ahead, self.content_length),
+        );
+
+        let client = self.client;
+        let container_name = self.container_name.clone();
+        let blob_name = self.blob_name.clone();
+        let snapshot = self.snapshot;
+        let timeout = self.timeout;
+        let lease_id = self.lease_id;
+        let client_request_id = self.client_request_id.clone();
+        let max_retries = self.max_retries;
+        let etag = self.etag.clone();
+
+        let future = async move {
+            let response = with_retries(max_retries, || {
+                get_blob(
+                    client,
+                    &container_name,
+                    &blob_name,
+                    snapshot,
+                    timeout,
+                    lease_id.as_ref(),
+                    client_request_id.as_deref(),
+                    Some(&range),
+                    Some(&etag),
+                )
+                .finalize()
+            })
+            .await?;
+            if response.data.len() as u64 != range.len() {
+                return Err(AzureError::GenericErrorWithText(format!(
+                    "received {} bytes for the range {}-{} of {} bytes",
+                    response.data.len(),
+                    range.start,
+                    range.end,
+                    range.len()
+                )));
+            }
+            Ok(response.data)
+        };
+
+        self.fetch = Some((range, MaybeDone::Future(future.boxed())));
+    }
+
+    // Starts fetching the range after the buffered one, if not done yet, and gives it a
+    // chance to progress while the buffered bytes are read.
+    fn read_ahead(&mut self, cx: &mut Context<'_>) {
+        let buffer_end = self.buffer_start + self.buffer.len() as u64;
+        if self.fetch.is_none() && buffer_end < self.content_length {
+            self.start_fetch(buffer_end);
+        }
+        if let Some((_, future)) = &mut self.fetch {
+            let _ = Pin::new(future).poll(cx);
+        }
+    }
+
+    fn to_io_error(&self, error: AzureError) -> io::Error {
+        match error {
+            AzureError::UnexpectedHTTPResult(ref result)
+                if result.status_code() == StatusCode::PRECONDITION_FAILED =>
+            {
+                io::Error::other(format!(
+                    "blob {}/{} changed while being read, its ETag is no longer {}",
+                    self.container_name, self.blob_name, self.etag
+                ))
+            }
+            error => io::Error::other(error.to_string()),
+        }
+    }
+}
+
+impl<'a, C> AsyncRead for BlobReader<'a, C>
+where
+    C: Client,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            if this.position >= this.content_length || buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+            if let Some(len) = this.read_buffered(buf) {
+                this.read_ahead(cx);
+                return Poll::Ready(Ok(len));
+            }
+
+            let position = this.position;
+            match &this.fetch {
+                Some((range, _)) if range.start <= position && position < range.end => {}
+                _ => this.start_fetch(position),
+            }
+
+            let (range, future) = this.fetch.as_mut().unwrap();
+            let range = *range;
+            if Pin::new(&mut *future).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            let result = Pin::new(future).take_output().unwrap();
+            this.fetch = None;
+            match result {
+                Ok(data) => {
+                    this.buffer = data;
+                    this.buffer_start = range.start;
+                }
+                Err(error) => return Poll::Ready(Err(this.to_io_error(error))),
+            }
+        }
+    }
+}
+
+impl<'a, C> AsyncSeek for BlobReader<'a, C>
+where
+    C: Client,
+{
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        Poll::Ready(
+            seek_position(this.position, this.content_length, pos)
+                .inspect(|&position| this.position = position),
+        )
+    }
+}
+
+// The position a seek leads to. Seeking past the end is allowed, reads then return 0
+// bytes, but seeking before the start is an error.
+fn seek_position(position: u64, content_length: u64, pos: SeekFrom) -> io::Result<u64> {
+    let (base, offset) = match pos {
+        SeekFrom::Start(offset) => return Ok(offset),
+        SeekFrom::End(offset) => (content_length, offset),
+        SeekFrom::Current(offset) => (position, offset),
+    };
+    let position = if offset >= 0 {
+        base.checked_add(offset as u64)
+    } else {
+        base.checked_sub(offset.unsigned_abs())
+    };
+    position.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_blob<'b, C>(
+    client: &'b C,
+    container_name: &'b str,
+    blob_name: &'b str,
+    snapshot: Option<DateTime<Utc>>,
+    timeout: Option<u64>,
+    lease_id: Option<&'b LeaseId>,
+    client_request_id: Option<&'b str>,
+    range: Option<&'b Range>,
+    etag: Option<&'b str>,
+) -> GetBlobBuilder<'b, C, Yes, Yes>
+where
+    C: Client,
+{
+    let mut request = GetBlobBuilder::new(client)
+        .with_container_name(container_name)
+        .with_blob_name(blob_name);
+    if let Some(range) = range {
+        request = request.with_range(range);
+    }
+    if let Some(etag) = etag {
+        request = request.with_if_match_condition(IfMatchCondition::Match(etag));
+    }
+    if let Some(snapshot) = snapshot {
+        request = request.with_snapshot(snapshot);
+    }
+    if let Some(timeout) = timeout {
+        request = request.with_timeout(timeout);
+    }
+    if let Some(lease_id) = lease_id {
+        request = request.with_lease_id(lease_id);
+    }
+    if let Some(client_request_id) = client_request_id {
+        request = request.with_client_request_id(client_request_id);
+    }
+    request
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seek() {
+        assert_eq!(seek_position(10, 100, SeekFrom::Start(20)).unwrap(), 20);
+        assert_eq!(seek_position(10, 100, SeekFrom::Start(200)).unwrap(), 200);
+        assert_eq!(seek_position(10, 100, SeekFrom::Current(5)).unwrap(), 15);
+        assert_eq!(seek_position(10, 100, SeekFrom::Current(-10)).unwrap(), 0);
+        assert_eq!(seek_position(10, 100, SeekFrom::End(-1)).unwrap(), 99);
+        assert!(seek_position(10, 100, SeekFrom::Current(-11)).is_err());
+        assert!(seek_position(10, 100, SeekFrom::End(-101)).is_err());
+    }
+
+    #[test]
+    fn reader_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<BlobReader<'static, azure_sdk_storage_core::key_client::KeyClient>>();
+    }
+}
@@ -0,0 +1,514 @@
+use crate::blob::block_id;
+use crate::blob::requests::{
+    GetBlobBuilder, PutAppendBlockBuilder, PutBlockBuilder, PutBlockListBuilder,
+};
+use crate::blob::retry::with_retries;
+use crate::blob::{BlobBlockType, BlobContentMD5Support, BlockList, BlockListSupport};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::lease::LeaseId;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::range::Range;
+use azure_sdk_storage_core::prelude::*;
+use futures::future::{BoxFuture, FutureExt};
+use futures::io::AsyncWrite;
+use futures::ready;
+use futures::task::{Context, Poll};
+use hyper::StatusCode;
+use std::collections::HashMap;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// Limit of the service for the version in use (2019-07-07).
+const MAX_BLOCK_COUNT: usize = 50_000;
+
+// The options of the requests sent by a `BlobWriter`, borrowed from its builder.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BlobWriterOptions<'a> {
+    pub(crate) container_name: &'a str,
+    pub(crate) blob_name: &'a str,
+    pub(crate) max_retries: u32,
+    pub(crate) max_size: Option<u64>,
+    pub(crate) timeout: Option<u64>,
+    pub(crate) content_type: Option<&'a str>,
+    pub(crate) content_encoding: Option<&'a str>,
+    pub(crate) content_language: Option<&'a str>,
+    pub(crate) cache_control: Option<&'a str>,
+    pub(crate) content_disposition: Option<&'a str>,
+    pub(crate) metadata: Option<&'a HashMap<&'a str, &'a str>>,
+    pub(crate) lease_id: Option<&'a LeaseId>,
+    pub(crate) client_request_id: Option<&'a str>,
+}
+
+enum Kind {
+    // The ids of the blocks sent so far and the MD5 of their content.
+    BlockBlob {
+        block_list: BlockList<Vec<u8>>,
+        content_md5: md5::Context,
+        commit_started: bool,
+    },
+    AppendBlob,
+}
+
+/// Writes a blob as an `AsyncWrite`, to pipe encoders such as gzip or csv writers
+/// straight into storage.
+///
+/// The bytes written are buffered until a block of `block_size` bytes is full, then the
+/// block is sent while the next one is filled. A block blob is made of the blocks sent
+/// with `put_block` and committed with `put_block_list` when the writer is closed: nothing
+/// is visible before `close` succeeds. An append blob gets every block with
+/// `put_append_block` at the position it expects, so a block appended after a concurrent
+/// writer fails instead of corrupting the blob. When a retry fails because the block has
+/// already been appended, by the attempt whose response was lost, the blob is read back to
+/// check it ends with the block.
+/// Flushing an append blob writer appends the buffered bytes at once.
+///
+/// A `BlobWriter` is created with `Blob::blob_writer`.
+pub struct BlobWriter<'a, C>
+where
+    C: Client,
+{
+    client: &'a C,
+    options: BlobWriterOptions<'a>,
+    kind: Kind,
+    block_size: usize,
+    position: u64,
+    buffer: Vec<u8>,
+    upload: Option<BoxFuture<'a, Result<(), AzureError>>>,
+    closed: bool,
+    failed: bool,
+}
+
+impl<'a, C> std::fmt::Debug for BlobWriter<'a, C>
+where
+    C: Client,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobWriter")
+            .field("options", &self.options)
+            .field("block_size", &self.block_size)
+            .field("position", &self.position)
+            .field("buffered", &self.buffer.len())
+            .field("closed", &self.closed)
+            .field("failed", &self.failed)
+            .finish()
+    }
+}
+
+impl<'a, C> BlobWriter<'a, C>
+where
+    C: Client,
+{
+    pub(crate) fn block_blob(
+        client: &'a C,
+        options: BlobWriterOptions<'a>,
+        block_size: u64,
+    ) -> BlobWriter<'a, C> {
+        let kind = Kind::BlockBlob {
+            block_list: BlockList { blocks: Vec::new() },
+            content_md5: md5::Context::new(),
+            commit_started: false,
+        };
+        BlobWriter::new(client, options, kind, block_size, 0)
+    }
+
+    pub(crate) fn append_blob(
+        client: &'a C,
+        options: BlobWriterOptions<'a>,
+        block_size: u64,
+        position: u64,
+    ) -> BlobWriter<'a, C> {
+        BlobWriter::new(client, options, Kind::AppendBlob, block_size, position)
+    }
+
+    fn new(
+        client: &'a C,
+        options: BlobWriterOptions<'a>,
+        kind: Kind,
+        block_size: u64,
+        position: u64,
+    ) -> BlobWriter<'a, C> {
+        BlobWriter {
+            client,
+            options,
+            kind,
+            block_size: block_size as usize,
+            position,
+            buffer: Vec::new(),
+            upload: None,
+            closed: false,
+            failed: false,
+        }
+    }
+
+    /// The number of bytes sent to the service so far, not counting the buffered ones.
+    /// For an append blob this includes the bytes the blob had before.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    // Sends the buffered bytes as the next block. The previous upload must be over.
+    fn start_upload(&mut self) -> io::Result<()> {
+        if let Kind::BlockBlob { block_list, .. } = &self.kind {
+            if block_list.blocks.len() == MAX_BLOCK_COUNT {
+                // the buffered bytes cannot be written, so the writer cannot be used anymore
+                self.failed = true;
+                return Err(io::Error::other(format!(
+                    "the blob does not fit in {} blocks of {} bytes",
+                    MAX_BLOCK_COUNT, self.block_size
+                )));
+            }
+        }
+
+        let client = self.client;
+        let options = self.options;
+        let offset = self.position;
+        let block = std::mem::take(&mut self.buffer);
+        let block_md5 = md5::compute(&block);
+        self.position += block.len() as u64;
+
+        let upload = match &mut self.kind {
+            Kind::BlockBlob {
+                block_list,
+                content_md5,
+                ..
+            } => {
+                let block_id = block_id(offset, &block_md5);
+                block_list
+                    .blocks
+                    .push(BlobBlockType::Uncommitted(block_id.clone()));
+                content_md5.consume(&block);
+                put_block(client, options, block_id, block, block_md5).boxed()
+            }
+            Kind::AppendBlob => put_append_block(client, options, offset, block, block_md5).boxed(),
+        };
+        self.upload = Some(upload);
+        Ok(())
+    }
+
+    // Waits for the upload in progress, if any. Once an upload has failed the bytes it
+    // carried are lost, so the writer cannot be used anymore.
+    fn poll_upload(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.failed {
+            return Poll::Ready(Err(io::Error::other("a previous write to the blob failed")));
+        }
+        if let Some(upload) = &mut self.upload {
+            let result = ready!(upload.as_mut().poll(cx));
+            self.upload = None;
+            if let Err(error) = result {
+                self.failed = true;
+                return Poll::Ready(Err(self.to_io_error(error)));
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn to_io_error(&self, error: AzureError) -> io::Error {
+        match (&self.kind, error) {
+            (Kind::AppendBlob, AzureError::UnexpectedHTTPResult(ref result))
+                if result.status_code() == StatusCode::PRECONDITION_FAILED =>
+            {
+                io::Error::other(format!(
+                    "cannot append to blob {}/{}: it has been written by someone else or it \
+                     would exceed its maximum size",
+                    self.options.container_name, self.options.blob_name
+                ))
+            }
+            (_, error) => io::Error::other(error.to_string()),
+        }
+    }
+}
+
+impl<'a, C> AsyncWrite for BlobWriter<'a, C>
+where
+    C: Client,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.closed {
+            return Poll::Ready(Err(io::Error::other("the blob writer is closed")));
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        if this.buffer.len() == this.block_size {
+            ready!(this.poll_upload(cx))?;
+            this.start_upload()?;
+        }
+        // gives the upload in progress a chance to progress
+        if let Poll::Ready(Err(error)) = this.poll_upload(cx) {
+            return Poll::Ready(Err(error));
+        }
+
+        let len = std::cmp::min(buf.len(), this.block_size - this.buffer.len());
+        if this.buffer.capacity() == 0 {
+            this.buffer.reserve_exact(this.block_size);
+        }
+        this.buffer.extend_from_slice(&buf[..len]);
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_upload(cx))?;
+        if let Kind::AppendBlob = this.kind {
+            if !this.buffer.is_empty() {
+                this.start_upload()?;
+                ready!(this.poll_upload(cx))?;
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_upload(cx))?;
+        if this.closed {
+            return Poll::Ready(Ok(()));
+        }
+
+        if !this.buffer.is_empty() {
+            this.start_upload()?;
+            ready!(this.poll_upload(cx))?;
+        }
+
+        let client = this.client;
+        let options = this.options;
+        if let Kind::BlockBlob {
+            block_list,
+            content_md5,
+            commit_started,
+        } = &mut this.kind
+        {
+            if !*commit_started {
+                *commit_started = true;
+                let block_list = std::mem::replace(block_list, BlockList { blocks: Vec::new() });
+                let content_md5 = std::mem::replace(content_md5, md5::Context::new()).compute();
+                this.upload =
+                    Some(put_block_list(client, options, block_list, content_md5).boxed());
+                ready!(this.poll_upload(cx))?;
+            }
+        }
+
+        this.closed = true;
+        Poll::Ready(Ok(()))
+    }
+}
+
+async fn put_block<C>(
+    client: &C,
+    options: BlobWriterOptions<'_>,
+    block_id: Vec<u8>,
+    block: Vec<u8>,
+    block_md5: md5::Digest,
+) -> Result<(), AzureError>
+where
+    C: Client,
+{
+    with_retries(options.max_retries, || {
+        let mut request = PutBlockBuilder::new(client)
+            .with_container_name(options.container_name)
+            .with_blob_name(options.blob_name)
+            .with_body(&block)
+            .with_block_id(&block_id)
+            .with_content_md5(&block_md5[..]);
+        if let Some(timeout) = options.timeout {
+            request = request.with_timeout(timeout);
+        }
+        if let Some(lease_id) = options.lease_id {
+            request = request.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = options.client_request_id {
+            request = request.with_client_request_id(client_request_id);
+        }
+        request.finalize()
+    })
+    .await?;
+    Ok(())
+}
+
+async fn put_append_block<C>(
+    client: &C,
+    options: BlobWriterOptions<'_>,
+    position: u64,
+    block: Vec<u8>,
+    block_md5: md5::Digest,
+) -> Result<(), AzureError>
+where
+    C: Client,
+{
+    let attempts = AtomicU32::new(0);
+    let result = with_retries(options.max_retries, || {
+        attempts.fetch_add(1, Ordering::Relaxed);
+        let mut request = PutAppendBlockBuilder::new(client)
+            .with_container_name(options.container_name)
+            .with_blob_name(options.blob_name)
+            .with_body(&block)
+            .with_content_md5(&block_md5[..])
+            .with_append_position(position);
+        if let Some(max_size) = options.max_size {
+            request = request.with_max_size(max_size);
+        }
+        if let Some(timeout) = options.timeout {
+            request = request.with_timeout(timeout);
+        }
+        if let Some(lease_id) = options.lease_id {
+            request = request.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = options.client_request_id {
+            request = request.with_client_request_id(client_request_id);
+        }
+        request.finalize()
+    })
+    .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        // The block may have been appended by an attempt whose response was lost, in which
+        // case the blob ends with it.
+        Err(AzureError::UnexpectedHTTPResult(ref error))
+            if error.status_code() == StatusCode::PRECONDITION_FAILED
+                && attempts.load(Ordering::Relaxed) > 1
+                && ends_with_block(client, options, position, &block).await? =>
+        {
+            Ok(())
+        }
+        Err(error) => Err(error),
+    }
+}
+
+async fn ends_with_block<C>(
+    client: &C,
+    options: BlobWriterOptions<'_>,
+    position: u64,
+    block: &[u8],
+) -> Result<bool, AzureError>
+where
+    C: Client,
+{
+    let range = Range::new(position, position + block.len() as u64);
+    let response = match with_retries(options.max_retries, || {
+        let mut request = GetBlobBuilder::new(client)
+            .with_container_name(options.container_name)
+            .with_blob_name(options.blob_name)
+            .with_range(&range);
+        if let Some(timeout) = options.timeout {
+            request = request.with_timeout(timeout);
+        }
+        if let Some(lease_id) = options.lease_id {
+            request = request.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = options.client_request_id {
+            request = request.with_client_request_id(client_request_id);
+        }
+        request.finalize()
+    })
+    .await
+    {
+        Ok(response) => response,
+        Err(AzureError::UnexpectedHTTPResult(ref error))
+            if error.status_code() == StatusCode::RANGE_NOT_SATISFIABLE =>
+        {
+            return Ok(false)
+        }
+        Err(error) => return Err(error),
+    };
+    Ok(response.blob_content_length == range.end && response.data == block)
+}
+
+async fn put_block_list<C>(
+    client: &C,
+    options: BlobWriterOptions<'_>,
+    block_list: BlockList<Vec<u8>>,
+    content_md5: md5::Digest,
+) -> Result<(), AzureError>
+where
+    C: Client,
+{
+    with_retries(options.max_retries, || {
+        let mut request = PutBlockListBuilder::new(client)
+            .with_container_name(options.container_name)
+            .with_blob_name(options.blob_name)
+            .with_block_list(&block_list)
+            .with_blob_content_md5(&content_md5[..]);
+        if let Some(timeout) = options.timeout {
+            request = request.with_timeout(timeout);
+        }
+        if let Some(content_type) = options.content_type {
+            request = request.with_content_type(content_type);
+        }
+        if let Some(content_encoding) = options.content_encoding {
+            request = request.with_content_encoding(content_encoding);
+        }
+        if let Some(content_language) = options.content_language {
+            request = request.with_content_language(content_language);
+        }
+        if let Some(cache_control) = options.cache_control {
+            request = request.with_cache_control(cache_control);
+        }
+        if let Some(content_disposition) = options.content_disposition {
+            request = request.with_content_disposition(content_disposition);
+        }
+        if let Some(metadata) = options.metadata {
+            request = request.with_metadata(metadata);
+        }
+        if let Some(lease_id) = options.lease_id {
+            request = request.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = options.client_request_id {
+            request = request.with_client_request_id(client_request_id);
+        }
+        request.finalize()
+    })
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn writer_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<BlobWriter<'static, azure_sdk_storage_core::key_client::KeyClient>>();
+    }
+
+    #[test]
+    fn too_many_blocks_fail_the_writer() {
+        let client = azure_sdk_storage_core::client::with_access_key("account", "key");
+        let options = BlobWriterOptions {
+            container_name: "container",
+            blob_name: "blob",
+            max_retries: 0,
+            max_size: None,
+            timeout: None,
+            content_type: None,
+            content_encoding: None,
+            content_language: None,
+            cache_control: None,
+            content_disposition: None,
+            metadata: None,
+            lease_id: None,
+            client_request_id: None,
+        };
+        let mut writer = BlobWriter::block_blob(&client, options, 4);
+        if let Kind::BlockBlob { block_list, .. } = &mut writer.kind {
+            block_list.blocks = (0..MAX_BLOCK_COUNT)
+                .map(|index| BlobBlockType::Committed(index.to_string().into_bytes()))
+                .collect();
+        }
+        writer.buffer.extend_from_slice(b"data");
+
+        assert!(writer.start_upload().is_err());
+        assert!(writer.failed);
+        assert!(writer.upload.is_none());
+        assert_eq!(writer.position(), 0);
+        assert_eq!(writer.buffer, b"data");
+    }
+}
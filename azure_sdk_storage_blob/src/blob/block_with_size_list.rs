@@ -45,6 +45,23 @@ where
     pub blocks: Vec<BlobBlockWithSize<T>>,
 }
 
+impl<T> BlockWithSizeList<T>
+where
+    T: Borrow<[u8]>,
+{
+    /// True if the list has an uncommitted block with this id and size.
+    pub fn contains_uncommitted(&self, block_id: &[u8], size_in_bytes: u64) -> bool {
+        self.blocks
+            .iter()
+            .any(|block| match &block.block_list_type {
+                BlobBlockType::Uncommitted(id) => {
+                    id.borrow() == block_id && block.size_in_bytes == size_in_bytes
+                }
+                _ => false,
+            })
+    }
+}
+
 impl BlockWithSizeList<Vec<u8>> {
     pub fn try_from(xml: &str) -> Result<BlockWithSizeList<Vec<u8>>, AzureError> {
         let bl: BlockList = serde_xml_rs::de::from_reader(xml.as_bytes())?;
@@ -128,4 +145,15 @@ mod test {
         assert!(bl.blocks[1].size_in_bytes == 62);
         assert!(bl.blocks[2].size_in_bytes == 62);
     }
+
+    #[test]
+    fn contains_uncommitted() {
+        let range = "<?xml version=\"1.0\" encoding=\"utf-8\"?><BlockList><CommittedBlocks><Block><Name>YmxvY2sx</Name><Size>62</Size></Block></CommittedBlocks><UncommittedBlocks><Block><Name>YmxvY2sy</Name><Size>62</Size></Block></UncommittedBlocks></BlockList>";
+
+        let bl = BlockWithSizeList::try_from(range).unwrap();
+        assert!(bl.contains_uncommitted(b"block2", 62));
+        assert!(!bl.contains_uncommitted(b"block2", 61));
+        assert!(!bl.contains_uncommitted(b"block1", 62));
+        assert!(!bl.contains_uncommitted(b"block3", 62));
+    }
 }
@@ -14,12 +14,18 @@ mod block_with_size_list;
 pub use self::block_with_size_list::BlockWithSizeList;
 mod block_list;
 pub use self::block_list::BlockList;
+mod blob_reader;
+pub use self::blob_reader::BlobReader;
+mod blob_writer;
+pub use self::blob_writer::BlobWriter;
+pub(crate) use self::blob_writer::BlobWriterOptions;
 pub mod requests;
 pub mod responses;
+mod retry;
 use azure_sdk_core::headers::{
-    BLOB_SEQUENCE_NUMBER, BLOB_TYPE, CONTENT_MD5, COPY_COMPLETION_TIME, COPY_ID, COPY_PROGRESS,
-    COPY_SOURCE, COPY_STATUS, COPY_STATUS_DESCRIPTION, CREATION_TIME, LEASE_DURATION, LEASE_STATE,
-    LEASE_STATUS, SERVER_ENCRYPTED,
+    BLOB_CONTENT_MD5, BLOB_SEQUENCE_NUMBER, BLOB_TYPE, CONTENT_MD5, COPY_COMPLETION_TIME, COPY_ID,
+    COPY_PROGRESS, COPY_SOURCE, COPY_STATUS, COPY_STATUS_DESCRIPTION, CREATION_TIME,
+    LEASE_DURATION, LEASE_STATE, LEASE_STATUS, SERVER_ENCRYPTED,
 };
 use azure_sdk_core::{
     errors::{AzureError, TraversingError},
@@ -31,6 +37,7 @@ use azure_sdk_core::{
 };
 use azure_sdk_storage_core::Client;
 use chrono::{DateTime, Utc};
+use http::request::Builder;
 use hyper::header;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use std::borrow::Borrow;
@@ -85,6 +92,61 @@ where
     }
 }
 
+pub trait BlobContentMD5Support<'a> {
+    type O;
+    fn with_blob_content_md5(self, _: &'a [u8]) -> Self::O;
+}
+
+/// The MD5 of the whole blob, stored with its properties (unlike the MD5 of the body
+/// of a request, only checked by the service on receipt).
+pub trait BlobContentMD5Option<'a> {
+    fn blob_content_md5(&self) -> Option<&'a [u8]>;
+
+    #[must_use]
+    fn add_header(&self, mut builder: Builder) -> Builder {
+        if let Some(blob_content_md5) = self.blob_content_md5() {
+            builder = builder.header(BLOB_CONTENT_MD5, base64::encode(blob_content_md5).as_str());
+        }
+        builder
+    }
+}
+
+pub trait BlockSizeSupport {
+    type O;
+    fn with_block_size(self, block_size: u64) -> Self::O;
+}
+
+pub trait BlockSizeOption {
+    fn block_size(&self) -> u64;
+}
+
+pub trait MaxConcurrencySupport {
+    type O;
+    fn with_max_concurrency(self, max_concurrency: usize) -> Self::O;
+}
+
+pub trait MaxConcurrencyOption {
+    fn max_concurrency(&self) -> usize;
+}
+
+pub trait MaxRetriesSupport {
+    type O;
+    fn with_max_retries(self, max_retries: u32) -> Self::O;
+}
+
+pub trait MaxRetriesOption {
+    fn max_retries(&self) -> u32;
+}
+
+pub trait ResumeSupport {
+    type O;
+    fn with_resume(self, resume: bool) -> Self::O;
+}
+
+pub trait ResumeOption {
+    fn resume(&self) -> bool;
+}
+
 create_enum!(
     BlobType,
     (BlockBlob, "BlockBlob"),
@@ -476,6 +538,13 @@ where
     }
 }
 
+// The id of a block is its offset followed by its MD5: the ids of the blocks of a blob
+// must have the same length and this way the id only depends on the position and on the
+// content of the block, even if the block size changes between two attempts.
+pub(crate) fn block_id(offset: u64, block_md5: &md5::Digest) -> Vec<u8> {
+    format!("{:015}-{:x}", offset, block_md5).into_bytes()
+}
+
 pub(crate) fn copy_status_from_headers(
     headers: &http::HeaderMap,
 ) -> Result<CopyStatus, AzureError> {
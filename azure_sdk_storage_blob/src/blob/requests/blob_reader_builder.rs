@@ -0,0 +1,408 @@
+use crate::blob::BlobReader;
+use crate::blob::{MaxRetriesOption, MaxRetriesSupport};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::lease::LeaseId;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::{No, ToAssign, Yes};
+use azure_sdk_storage_core::prelude::*;
+use chrono::{DateTime, Utc};
+use std::marker::PhantomData;
+
+const DEFAULT_READ_AHEAD: u64 = 4 * 1024 * 1024;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+#[derive(Debug, Clone)]
+pub struct BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    client: &'a C,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    snapshot: Option<DateTime<Utc>>,
+    chunk_size: u64,
+    max_retries: u32,
+    timeout: Option<u64>,
+    lease_id: Option<&'a LeaseId>,
+    client_request_id: Option<&'a str>,
+}
+
+impl<'a, C> BlobReaderBuilder<'a, C, No, No>
+where
+    C: Client,
+{
+    #[inline]
+    pub(crate) fn new(client: &'a C) -> BlobReaderBuilder<'a, C, No, No> {
+        BlobReaderBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            snapshot: None,
+            chunk_size: DEFAULT_READ_AHEAD,
+            max_retries: DEFAULT_MAX_RETRIES,
+            timeout: None,
+            lease_id: None,
+            client_request_id: None,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequired<'a, C>
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn client(&self) -> &'a C {
+        self.client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, C, BlobNameSet> ContainerNameRequired<'a> for BlobReaderBuilder<'a, C, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, C, ContainerNameSet> BlobNameRequired<'a>
+    for BlobReaderBuilder<'a, C, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> SnapshotOption
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ChunkSizeOption
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn chunk_size(&self) -> u64 {
+        self.chunk_size
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxRetriesOption
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> TimeoutOption
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> LeaseIdOption<'a>
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a>
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, C, BlobNameSet> ContainerNameSupport<'a> for BlobReaderBuilder<'a, C, No, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobReaderBuilder<'a, C, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        BlobReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet> BlobNameSupport<'a> for BlobReaderBuilder<'a, C, ContainerNameSet, No>
+where
+    ContainerNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobReaderBuilder<'a, C, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        BlobReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> SnapshotSupport
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        BlobReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: Some(snapshot),
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ChunkSizeSupport
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_chunk_size(self, chunk_size: u64) -> Self::O {
+        BlobReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size,
+            max_retries: self.max_retries,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxRetriesSupport
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_max_retries(self, max_retries: u32) -> Self::O {
+        BlobReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_retries,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> TimeoutSupport
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        BlobReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            timeout: Some(timeout),
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> LeaseIdSupport<'a>
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        BlobReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            timeout: self.timeout,
+            lease_id: Some(lease_id),
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a>
+    for BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        BlobReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_retries: self.max_retries,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: Some(client_request_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, C> BlobReaderBuilder<'a, C, Yes, Yes>
+where
+    C: Client,
+{
+    /// Creates a `BlobReader` over the blob. The first `chunk_size` bytes are read at
+    /// once, along with the ETag and the length of the blob; the reader then reads
+    /// `chunk_size` bytes ahead. Every request is retried up to `max_retries` times.
+    #[inline]
+    pub async fn finalize(self) -> Result<BlobReader<'a, C>, AzureError> {
+        BlobReader::new(
+            self.client(),
+            self.container_name(),
+            self.blob_name(),
+            self.snapshot(),
+            self.timeout(),
+            self.lease_id().copied(),
+            self.client_request_id(),
+            self.max_retries(),
+            self.chunk_size(),
+        )
+        .await
+    }
+}
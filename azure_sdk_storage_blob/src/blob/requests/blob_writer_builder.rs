@@ -0,0 +1,907 @@
+use crate::blob::requests::{GetBlobBuilder, PutAppendBlobBuilder};
+use crate::blob::retry::with_retries;
+use crate::blob::{BlobType, BlobWriter, BlobWriterOptions};
+use crate::blob::{BlockSizeOption, BlockSizeSupport};
+use crate::blob::{MaxRetriesOption, MaxRetriesSupport};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::lease::LeaseId;
+use azure_sdk_core::modify_conditions::IfMatchCondition;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::range::Range;
+use azure_sdk_core::{No, ToAssign, Yes};
+use azure_sdk_storage_core::prelude::*;
+use hyper::StatusCode;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+// 4 MiB, the block size of the other Azure Storage SDKs.
+const DEFAULT_BLOCK_SIZE: u64 = 4 * 1024 * 1024;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+// Limits of the service for the version in use (2019-07-07).
+const MAX_BLOCK_SIZE: u64 = 100 * 1024 * 1024;
+const MAX_APPEND_BLOCK_SIZE: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    client: &'a C,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    block_size: u64,
+    max_retries: u32,
+    max_size: Option<u64>,
+    timeout: Option<u64>,
+    content_type: Option<&'a str>,
+    content_encoding: Option<&'a str>,
+    content_language: Option<&'a str>,
+    cache_control: Option<&'a str>,
+    content_disposition: Option<&'a str>,
+    metadata: Option<&'a HashMap<&'a str, &'a str>>,
+    lease_id: Option<&'a LeaseId>,
+    client_request_id: Option<&'a str>,
+}
+
+impl<'a, C> BlobWriterBuilder<'a, C, No, No>
+where
+    C: Client,
+{
+    #[inline]
+    pub(crate) fn new(client: &'a C) -> BlobWriterBuilder<'a, C, No, No> {
+        BlobWriterBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            block_size: DEFAULT_BLOCK_SIZE,
+            max_retries: DEFAULT_MAX_RETRIES,
+            max_size: None,
+            timeout: None,
+            content_type: None,
+            content_encoding: None,
+            content_language: None,
+            cache_control: None,
+            content_disposition: None,
+            metadata: None,
+            lease_id: None,
+            client_request_id: None,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequired<'a, C>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn client(&self) -> &'a C {
+        self.client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, C, BlobNameSet> ContainerNameRequired<'a> for BlobWriterBuilder<'a, C, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, C, ContainerNameSet> BlobNameRequired<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> BlockSizeOption
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn block_size(&self) -> u64 {
+        self.block_size
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxRetriesOption
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxSizeOption
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> TimeoutOption
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentTypeOption<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn content_type(&self) -> Option<&'a str> {
+        self.content_type
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentEncodingOption<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn content_encoding(&self) -> Option<&'a str> {
+        self.content_encoding
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentLanguageOption<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn content_language(&self) -> Option<&'a str> {
+        self.content_language
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> CacheControlOption<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn cache_control(&self) -> Option<&'a str> {
+        self.cache_control
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentDispositionOption<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn content_disposition(&self) -> Option<&'a str> {
+        self.content_disposition
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MetadataOption<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn metadata(&self) -> Option<&'a HashMap<&'a str, &'a str>> {
+        self.metadata
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> LeaseIdOption<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, C, BlobNameSet> ContainerNameSupport<'a> for BlobWriterBuilder<'a, C, No, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet> BlobNameSupport<'a> for BlobWriterBuilder<'a, C, ContainerNameSet, No>
+where
+    ContainerNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> BlockSizeSupport
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_block_size(self, block_size: u64) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxRetriesSupport
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_max_retries(self, max_retries: u32) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxSizeSupport
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_max_size(self, max_size: u64) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: Some(max_size),
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> TimeoutSupport
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: Some(timeout),
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentTypeSupport<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_type(self, content_type: &'a str) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: Some(content_type),
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentEncodingSupport<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_encoding(self, content_encoding: &'a str) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: Some(content_encoding),
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentLanguageSupport<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_language(self, content_language: &'a str) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: Some(content_language),
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> CacheControlSupport<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_cache_control(self, cache_control: &'a str) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: Some(cache_control),
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentDispositionSupport<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_disposition(self, content_disposition: &'a str) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: Some(content_disposition),
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MetadataSupport<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_metadata(self, metadata: &'a HashMap<&'a str, &'a str>) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: Some(metadata),
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> LeaseIdSupport<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: Some(lease_id),
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a>
+    for BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = BlobWriterBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        BlobWriterBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_retries: self.max_retries,
+            max_size: self.max_size,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: Some(client_request_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, C> BlobWriterBuilder<'a, C, Yes, Yes>
+where
+    C: Client,
+{
+    /// Creates a `BlobWriter` that writes a block blob, replacing the blob if it exists
+    /// once the writer is closed. The content settings and the metadata are set when the
+    /// blocks are committed, along with the MD5 of the whole content.
+    #[inline]
+    pub fn finalize_block_blob(self) -> Result<BlobWriter<'a, C>, AzureError> {
+        if self.block_size() == 0 || self.block_size() > MAX_BLOCK_SIZE {
+            return Err(AzureError::InputParametersError(format!(
+                "the block size must be between 1 and {} bytes",
+                MAX_BLOCK_SIZE
+            )));
+        }
+        Ok(BlobWriter::block_blob(
+            self.client(),
+            self.options(),
+            self.block_size(),
+        ))
+    }
+
+    /// Creates a `BlobWriter` that appends to an append blob. The blob is created, with
+    /// the content settings and the metadata, if it does not exist yet; otherwise the
+    /// writer appends after its current end. With `max_size` the appends that would make
+    /// the blob larger fail.
+    #[inline]
+    pub async fn finalize_append_blob(self) -> Result<BlobWriter<'a, C>, AzureError> {
+        if self.block_size() == 0 || self.block_size() > MAX_APPEND_BLOCK_SIZE {
+            return Err(AzureError::InputParametersError(format!(
+                "the block size of an append blob must be between 1 and {} bytes",
+                MAX_APPEND_BLOCK_SIZE
+            )));
+        }
+
+        // The blob is created only if it does not exist: if someone else creates it in the
+        // meantime, or if a retry follows a creation whose response was lost, the writer
+        // appends after its end as it would have done had the blob existed.
+        let position = match self.append_blob_length().await? {
+            Some(length) => length,
+            None => match self.create_append_blob().await {
+                Ok(()) => 0,
+                Err(AzureError::UnexpectedHTTPResult(ref result))
+                    if result.status_code() == StatusCode::CONFLICT
+                        || result.status_code() == StatusCode::PRECONDITION_FAILED =>
+                {
+                    self.append_blob_length().await?.ok_or_else(|| {
+                        AzureError::GenericErrorWithText(format!(
+                            "blob {}/{} has been deleted while being created",
+                            self.container_name(),
+                            self.blob_name()
+                        ))
+                    })?
+                }
+                Err(error) => return Err(error),
+            },
+        };
+
+        Ok(BlobWriter::append_blob(
+            self.client(),
+            self.options(),
+            self.block_size(),
+            position,
+        ))
+    }
+
+    // The length of the append blob, or `None` if it does not exist. The first byte tells
+    // the type and the length of the blob; a range cannot be requested from an empty blob,
+    // which is then read as a whole.
+    async fn append_blob_length(&self) -> Result<Option<u64>, AzureError> {
+        let first_byte = Range::new(0, 1);
+        let (blob_type, length) = match with_retries(self.max_retries(), || {
+            self.get_blob(Some(&first_byte)).finalize()
+        })
+        .await
+        {
+            Ok(response) => (response.blob.blob_type, response.blob_content_length),
+            Err(AzureError::UnexpectedHTTPResult(ref result))
+                if result.status_code() == StatusCode::RANGE_NOT_SATISFIABLE =>
+            {
+                let response =
+                    with_retries(self.max_retries(), || self.get_blob(None).finalize()).await?;
+                (response.blob.blob_type, response.data.len() as u64)
+            }
+            Err(AzureError::UnexpectedHTTPResult(ref result))
+                if result.status_code() == StatusCode::NOT_FOUND =>
+            {
+                return Ok(None)
+            }
+            Err(error) => return Err(error),
+        };
+
+        if blob_type != BlobType::AppendBlob {
+            return Err(AzureError::InputParametersError(format!(
+                "blob {}/{} is not an append blob",
+                self.container_name(),
+                self.blob_name()
+            )));
+        }
+        Ok(Some(length))
+    }
+
+    fn get_blob<'b>(&'b self, range: Option<&'b Range>) -> GetBlobBuilder<'b, C, Yes, Yes> {
+        let mut request = GetBlobBuilder::new(self.client())
+            .with_container_name(self.container_name())
+            .with_blob_name(self.blob_name());
+        if let Some(range) = range {
+            request = request.with_range(range);
+        }
+        if let Some(timeout) = self.timeout() {
+            request = request.with_timeout(timeout);
+        }
+        if let Some(lease_id) = self.lease_id() {
+            request = request.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = self.client_request_id() {
+            request = request.with_client_request_id(client_request_id);
+        }
+        request
+    }
+
+    // Fails with a conflict or a failed precondition if the blob already exists.
+    async fn create_append_blob(&self) -> Result<(), AzureError> {
+        with_retries(self.max_retries(), || {
+            let mut request = PutAppendBlobBuilder::new(self.client())
+                .with_container_name(self.container_name())
+                .with_blob_name(self.blob_name())
+                .with_if_match_condition(IfMatchCondition::NotMatch("*"));
+            if let Some(timeout) = self.timeout() {
+                request = request.with_timeout(timeout);
+            }
+            if let Some(content_type) = self.content_type() {
+                request = request.with_content_type(content_type);
+            }
+            if let Some(content_encoding) = self.content_encoding() {
+                request = request.with_content_encoding(content_encoding);
+            }
+            if let Some(content_language) = self.content_language() {
+                request = request.with_content_language(content_language);
+            }
+            if let Some(cache_control) = self.cache_control() {
+                request = request.with_cache_control(cache_control);
+            }
+            if let Some(content_disposition) = self.content_disposition() {
+                request = request.with_content_disposition(content_disposition);
+            }
+            if let Some(metadata) = self.metadata() {
+                request = request.with_metadata(metadata);
+            }
+            if let Some(client_request_id) = self.client_request_id() {
+                request = request.with_client_request_id(client_request_id);
+            }
+            request.finalize()
+        })
+        .await?;
+        Ok(())
+    }
+
+    fn options(&self) -> BlobWriterOptions<'a> {
+        BlobWriterOptions {
+            container_name: self.container_name(),
+            blob_name: self.blob_name(),
+            max_retries: self.max_retries(),
+            max_size: self.max_size(),
+            timeout: self.timeout(),
+            content_type: self.content_type(),
+            content_encoding: self.content_encoding(),
+            content_language: self.content_language(),
+            cache_control: self.cache_control(),
+            content_disposition: self.content_disposition(),
+            metadata: self.metadata(),
+            lease_id: self.lease_id(),
+            client_request_id: self.client_request_id(),
+        }
+    }
+}
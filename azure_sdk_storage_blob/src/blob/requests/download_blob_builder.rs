@@ -0,0 +1,832 @@
+use crate::blob::requests::GetBlobBuilder;
+use crate::blob::responses::{DownloadBlobResponse, GetBlobResponse};
+use crate::blob::retry::with_retries;
+use crate::blob::{MaxConcurrencyOption, MaxConcurrencySupport};
+use crate::blob::{MaxRetriesOption, MaxRetriesSupport};
+use crate::blob::{ResumeOption, ResumeSupport};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::lease::LeaseId;
+use azure_sdk_core::modify_conditions::IfMatchCondition;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::range::Range;
+use azure_sdk_core::{No, ToAssign, Yes};
+use azure_sdk_storage_core::prelude::*;
+use chrono::{DateTime, Utc};
+use futures::io::{AllowStdIo, AsyncWrite, AsyncWriteExt};
+use futures::stream::StreamExt;
+use hyper::StatusCode;
+use std::fs::OpenOptions;
+use std::marker::PhantomData;
+use std::path::Path;
+
+// 4 MiB, the largest range for which the service returns the MD5.
+const DEFAULT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const MAX_RANGE_MD5_LENGTH: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    client: &'a C,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    snapshot: Option<DateTime<Utc>>,
+    chunk_size: u64,
+    max_concurrency: usize,
+    max_retries: u32,
+    resume: bool,
+    if_match_condition: Option<IfMatchCondition<'a>>,
+    timeout: Option<u64>,
+    lease_id: Option<&'a LeaseId>,
+    client_request_id: Option<&'a str>,
+}
+
+impl<'a, C> DownloadBlobBuilder<'a, C, No, No>
+where
+    C: Client,
+{
+    #[inline]
+    pub(crate) fn new(client: &'a C) -> DownloadBlobBuilder<'a, C, No, No> {
+        DownloadBlobBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            snapshot: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            max_retries: DEFAULT_MAX_RETRIES,
+            resume: false,
+            if_match_condition: None,
+            timeout: None,
+            lease_id: None,
+            client_request_id: None,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequired<'a, C>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn client(&self) -> &'a C {
+        self.client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, C, BlobNameSet> ContainerNameRequired<'a> for DownloadBlobBuilder<'a, C, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, C, ContainerNameSet> BlobNameRequired<'a>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> SnapshotOption
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn snapshot(&self) -> Option<DateTime<Utc>> {
+        self.snapshot
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ChunkSizeOption
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn chunk_size(&self) -> u64 {
+        self.chunk_size
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxConcurrencyOption
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxRetriesOption
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ResumeOption
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn resume(&self) -> bool {
+        self.resume
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> IfMatchConditionOption<'a>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> TimeoutOption
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> LeaseIdOption<'a>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, C, BlobNameSet> ContainerNameSupport<'a> for DownloadBlobBuilder<'a, C, No, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet> BlobNameSupport<'a>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, No>
+where
+    ContainerNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> SnapshotSupport
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_snapshot(self, snapshot: DateTime<Utc>) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: Some(snapshot),
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ChunkSizeSupport
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_chunk_size(self, chunk_size: u64) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxConcurrencySupport
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_max_concurrency(self, max_concurrency: usize) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxRetriesSupport
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_max_retries(self, max_retries: u32) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ResumeSupport
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_resume(self, resume: bool) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> IfMatchConditionSupport<'a>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: Some(if_match_condition),
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> TimeoutSupport
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: Some(timeout),
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> LeaseIdSupport<'a>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: Some(lease_id),
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a>
+    for DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = DownloadBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        DownloadBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            chunk_size: self.chunk_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            if_match_condition: self.if_match_condition,
+            timeout: self.timeout,
+            lease_id: self.lease_id,
+            client_request_id: Some(client_request_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, C> DownloadBlobBuilder<'a, C, Yes, Yes>
+where
+    C: Client,
+{
+    /// Downloads the blob into `writer`. The blob is fetched in ranges of `chunk_size`
+    /// bytes, up to `max_concurrency` of them at the same time, and every range is written
+    /// at its offset as soon as the ranges before it have been written. All the ranges are
+    /// requested with the ETag of the blob at the start of the download, so a blob replaced
+    /// in the meantime makes the download fail instead of mixing two versions.
+    ///
+    /// The MD5 of every range is checked when the service returns it (ranges of at most
+    /// 4 MiB), and the MD5 of the whole content is checked against the Content-MD5 of the
+    /// blob when it has one.
+    #[inline]
+    pub async fn finalize_to_writer<W>(self, writer: W) -> Result<DownloadBlobResponse, AzureError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        self.download(writer, 0, md5::Context::new()).await
+    }
+
+    /// Downloads the blob into the file at `path`, as `finalize_to_writer` does. The file
+    /// is created if needed. With `resume` the content already in the file is kept and only
+    /// the rest of the blob is downloaded, otherwise the file is truncated first. The file
+    /// is written with blocking writes.
+    ///
+    /// Resuming a download needs the ETag of the blob the file comes from, given with
+    /// `with_if_match_condition(IfMatchCondition::Match(etag))`: it is the `etag` of the
+    /// `DownloadBlobResponse` (or of any response) of the interrupted download. The
+    /// download fails if the blob has changed since, and a non empty file is never resumed
+    /// without it because its content could not be verified.
+    #[inline]
+    pub async fn finalize_to_file<P>(self, path: P) -> Result<DownloadBlobResponse, AzureError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        // the content already downloaded is part of the MD5 of the whole blob
+        let mut content_md5 = md5::Context::new();
+        let offset = if self.resume() {
+            let offset = std::io::copy(&mut file, &mut content_md5)?;
+            match self.if_match_condition() {
+                Some(IfMatchCondition::Match(_)) => {}
+                _ if offset == 0 => {}
+                _ => {
+                    return Err(AzureError::InputParametersError(
+                        "resuming a download requires the ETag of the blob already partially \
+                         downloaded as if match condition"
+                            .to_owned(),
+                    ))
+                }
+            }
+            offset
+        } else {
+            file.set_len(0)?;
+            0
+        };
+
+        self.download(AllowStdIo::new(file), offset, content_md5)
+            .await
+    }
+
+    async fn download<W>(
+        &self,
+        mut writer: W,
+        offset: u64,
+        mut content_md5: md5::Context,
+    ) -> Result<DownloadBlobResponse, AzureError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let chunk_size = self.chunk_size();
+        if chunk_size == 0 {
+            return Err(AzureError::InputParametersError(
+                "the chunk size must be at least 1 byte".to_owned(),
+            ));
+        }
+        if self.max_concurrency() == 0 {
+            return Err(AzureError::InputParametersError(
+                "the maximum concurrency must be at least 1".to_owned(),
+            ));
+        }
+
+        // The first byte gives the ETag, the length and the MD5 of the blob. A range
+        // cannot be requested from an empty blob, which is then downloaded as a whole.
+        // Both are requested with the if match condition, if any, so a resumed download
+        // fails if the blob is not the one the content already written comes from.
+        let first_byte = Range::new(0, 1);
+        let if_match_condition = self.if_match_condition();
+        let probe = match with_retries(self.max_retries(), || {
+            self.get_blob(Some(&first_byte), if_match_condition)
+                .finalize()
+        })
+        .await
+        {
+            Err(AzureError::UnexpectedHTTPResult(ref result))
+                if result.status_code() == StatusCode::RANGE_NOT_SATISFIABLE =>
+            {
+                let response = with_retries(self.max_retries(), || {
+                    self.get_blob(None, if_match_condition).finalize()
+                })
+                .await?;
+                return self
+                    .write_whole_blob(response, writer, offset, content_md5)
+                    .await;
+            }
+            probe => probe?,
+        };
+
+        let content_length = probe.blob_content_length;
+        if offset > content_length {
+            return Err(AzureError::InputParametersError(format!(
+                "the file already has {} bytes but the blob only {}",
+                offset, content_length
+            )));
+        }
+        let etag = probe
+            .blob
+            .etag
+            .clone()
+            .ok_or_else(|| AzureError::MissingHeaderError("ETag".to_owned()))?;
+
+        {
+            let mut chunks =
+                futures::stream::iter((offset..content_length).step_by(chunk_size as usize))
+                    .map(|start| {
+                        let range =
+                            Range::new(start, std::cmp::min(start + chunk_size, content_length));
+                        self.get_range(range, &etag)
+                    })
+                    .buffered(self.max_concurrency());
+            while let Some(chunk) = chunks.next().await {
+                let chunk = chunk?;
+                content_md5.consume(&chunk);
+                writer.write_all(&chunk).await?;
+            }
+        }
+        writer.flush().await?;
+
+        let content_md5 = content_md5.compute();
+        let content_md5_checked = match probe.blob_content_md5 {
+            Some(ref expected) => {
+                check_md5(expected, &content_md5.0)?;
+                true
+            }
+            None => false,
+        };
+
+        Ok(DownloadBlobResponse {
+            etag,
+            last_modified: probe.blob.last_modified,
+            content_length,
+            content_md5: content_md5.0,
+            content_md5_checked,
+            resumed_length: offset,
+        })
+    }
+
+    async fn write_whole_blob<W>(
+        &self,
+        response: GetBlobResponse,
+        mut writer: W,
+        offset: u64,
+        mut content_md5: md5::Context,
+    ) -> Result<DownloadBlobResponse, AzureError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let content_length = response.data.len() as u64;
+        if offset > content_length {
+            return Err(AzureError::InputParametersError(format!(
+                "the file already has {} bytes but the blob only {}",
+                offset, content_length
+            )));
+        }
+        let data = &response.data[offset as usize..];
+        content_md5.consume(data);
+        writer.write_all(data).await?;
+        writer.flush().await?;
+
+        let content_md5 = content_md5.compute();
+        let content_md5_checked = match response.blob.content_md5 {
+            Some(ref expected) => {
+                check_md5(expected, &content_md5.0)?;
+                true
+            }
+            None => false,
+        };
+
+        Ok(DownloadBlobResponse {
+            etag: response
+                .blob
+                .etag
+                .ok_or_else(|| AzureError::MissingHeaderError("ETag".to_owned()))?,
+            last_modified: response.blob.last_modified,
+            content_length,
+            content_md5: content_md5.0,
+            content_md5_checked,
+            resumed_length: offset,
+        })
+    }
+
+    async fn get_range(&self, range: Range, etag: &str) -> Result<Vec<u8>, AzureError> {
+        let response = with_retries(self.max_retries(), || {
+            self.get_blob(Some(&range), Some(IfMatchCondition::Match(etag)))
+                .finalize()
+        })
+        .await?;
+
+        if response.data.len() as u64 != range.len() {
+            return Err(AzureError::GenericErrorWithText(format!(
+                "received {} bytes for the range {}-{} of {} bytes",
+                response.data.len(),
+                range.start,
+                range.end,
+                range.len()
+            )));
+        }
+        if range.len() <= MAX_RANGE_MD5_LENGTH {
+            if let Some(ref expected) = response.blob.content_md5 {
+                check_md5(expected, &md5::compute(&response.data).0)?;
+            }
+        }
+        Ok(response.data)
+    }
+
+    fn get_blob<'b>(
+        &'b self,
+        range: Option<&'b Range>,
+        if_match_condition: Option<IfMatchCondition<'b>>,
+    ) -> GetBlobBuilder<'b, C, Yes, Yes> {
+        let mut request = GetBlobBuilder::new(self.client())
+            .with_container_name(self.container_name())
+            .with_blob_name(self.blob_name());
+        if let Some(range) = range {
+            request = request.with_range(range);
+        }
+        if let Some(if_match_condition) = if_match_condition {
+            request = request.with_if_match_condition(if_match_condition);
+        }
+        if let Some(snapshot) = self.snapshot() {
+            request = request.with_snapshot(snapshot);
+        }
+        if let Some(timeout) = self.timeout() {
+            request = request.with_timeout(timeout);
+        }
+        if let Some(lease_id) = self.lease_id() {
+            request = request.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = self.client_request_id() {
+            request = request.with_client_request_id(client_request_id);
+        }
+        request
+    }
+}
+
+// `expected` is the base64 encoded MD5 returned by the service.
+fn check_md5(expected: &str, actual: &[u8]) -> Result<(), AzureError> {
+    if base64::decode(expected)? != actual {
+        return Err(AzureError::GenericErrorWithText(format!(
+            "MD5 mismatch: expected {}, computed {}",
+            expected,
+            base64::encode(actual)
+        )));
+    }
+    Ok(())
+}
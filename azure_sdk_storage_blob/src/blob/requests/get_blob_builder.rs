@@ -3,6 +3,7 @@ use crate::blob::{generate_blob_uri, Blob};
 use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
 use azure_sdk_core::headers::RANGE_GET_CONTENT_MD5;
 use azure_sdk_core::lease::LeaseId;
+use azure_sdk_core::modify_conditions::IfMatchCondition;
 use azure_sdk_core::prelude::*;
 use azure_sdk_core::range::Range;
 use azure_sdk_core::util::RequestBuilderExt;
@@ -28,6 +29,7 @@ where
     timeout: Option<u64>,
     range: Option<&'a Range>,
     lease_id: Option<&'a LeaseId>,
+    if_match_condition: Option<IfMatchCondition<'a>>,
     client_request_id: Option<&'a str>,
 }
 
@@ -47,6 +49,7 @@ where
             timeout: None,
             range: None,
             lease_id: None,
+            if_match_condition: None,
             client_request_id: None,
         }
     }
@@ -142,6 +145,19 @@ where
     }
 }
 
+impl<'a, C, ContainerNameSet, BlobNameSet> IfMatchConditionOption<'a>
+    for GetBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn if_match_condition(&self) -> Option<IfMatchCondition<'a>> {
+        self.if_match_condition
+    }
+}
+
 impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a>
     for GetBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
 where
@@ -174,6 +190,7 @@ where
             timeout: self.timeout,
             range: self.range,
             lease_id: self.lease_id,
+            if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
         }
     }
@@ -198,6 +215,7 @@ where
             timeout: self.timeout,
             range: self.range,
             lease_id: self.lease_id,
+            if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
         }
     }
@@ -224,6 +242,7 @@ where
             timeout: self.timeout,
             range: self.range,
             lease_id: self.lease_id,
+            if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
         }
     }
@@ -250,6 +269,7 @@ where
             timeout: Some(timeout),
             range: self.range,
             lease_id: self.lease_id,
+            if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
         }
     }
@@ -276,6 +296,7 @@ where
             timeout: self.timeout,
             range: Some(range),
             lease_id: self.lease_id,
+            if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
         }
     }
@@ -302,6 +323,34 @@ where
             timeout: self.timeout,
             range: self.range,
             lease_id: Some(lease_id),
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> IfMatchConditionSupport<'a>
+    for GetBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = GetBlobBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_if_match_condition(self, if_match_condition: IfMatchCondition<'a>) -> Self::O {
+        GetBlobBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            snapshot: self.snapshot,
+            timeout: self.timeout,
+            range: self.range,
+            lease_id: self.lease_id,
+            if_match_condition: Some(if_match_condition),
             client_request_id: self.client_request_id,
         }
     }
@@ -328,6 +377,7 @@ where
             timeout: self.timeout,
             range: self.range,
             lease_id: self.lease_id,
+            if_match_condition: self.if_match_condition,
             client_request_id: Some(client_request_id),
         }
     }
@@ -369,6 +419,7 @@ where
                         request = request.header_static(RANGE_GET_CONTENT_MD5, "true");
                     }
                 }
+                request = IfMatchConditionOption::add_header(&self, request);
                 request
             },
             None,
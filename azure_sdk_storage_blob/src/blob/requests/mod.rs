@@ -1,11 +1,14 @@
 mod acquire_blob_lease_builder;
+mod blob_reader_builder;
 mod blob_stream_builder;
+mod blob_writer_builder;
 mod break_blob_lease_builder;
 mod change_blob_lease_builder;
 mod clear_page_builder;
 mod copy_blob_from_url_builder;
 mod delete_blob_builder;
 mod delete_blob_snapshot_builder;
+mod download_blob_builder;
 mod get_blob_builder;
 mod get_block_list_builder;
 mod put_append_blob_builder;
@@ -18,13 +21,17 @@ mod release_blob_lease_builder;
 mod renew_blob_lease_builder;
 mod signed_url_builder;
 mod update_page_builder;
+mod upload_blob_from_reader_builder;
 pub use self::acquire_blob_lease_builder::AcquireBlobLeaseBuilder;
+pub use self::blob_reader_builder::BlobReaderBuilder;
 pub use self::blob_stream_builder::BlobStreamBuilder;
+pub use self::blob_writer_builder::BlobWriterBuilder;
 pub use self::break_blob_lease_builder::BreakBlobLeaseBuilder;
 pub use self::change_blob_lease_builder::ChangeBlobLeaseBuilder;
 pub use self::clear_page_builder::ClearPageBuilder;
 pub use self::delete_blob_builder::DeleteBlobBuilder;
 pub use self::delete_blob_snapshot_builder::DeleteBlobSnapshotBuilder;
+pub use self::download_blob_builder::DownloadBlobBuilder;
 pub use self::get_blob_builder::GetBlobBuilder;
 pub use self::get_block_list_builder::GetBlockListBuilder;
 pub use self::put_append_blob_builder::PutAppendBlobBuilder;
@@ -37,4 +44,5 @@ pub use self::release_blob_lease_builder::ReleaseBlobLeaseBuilder;
 pub use self::renew_blob_lease_builder::RenewBlobLeaseBuilder;
 pub use self::signed_url_builder::SignedUrlBuilder;
 pub use self::update_page_builder::UpdatePageBuilder;
+pub use self::upload_blob_from_reader_builder::UploadBlobFromReaderBuilder;
 pub use copy_blob_from_url_builder::CopyBlobFromUrlBuilder;
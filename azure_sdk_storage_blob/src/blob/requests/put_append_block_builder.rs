@@ -29,7 +29,8 @@ where
     lease_id: Option<&'a LeaseId>,
     if_match_condition: Option<IfMatchCondition<'a>>,
     client_request_id: Option<&'a str>,
-    append_position: Option<u32>,
+    append_position: Option<u64>,
+    max_size: Option<u64>,
 }
 
 impl<'a, C> PutAppendBlockBuilder<'a, C, No, No, No>
@@ -52,6 +53,7 @@ where
             if_match_condition: None,
             client_request_id: None,
             append_position: None,
+            max_size: None,
         }
     }
 }
@@ -191,11 +193,25 @@ where
     C: Client,
 {
     #[inline]
-    fn append_position(&self) -> Option<u32> {
+    fn append_position(&self) -> Option<u64> {
         self.append_position
     }
 }
 
+impl<'a, C, ContainerNameSet, BlobNameSet, BodySet> MaxSizeOption
+    for PutAppendBlockBuilder<'a, C, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+}
+
 impl<'a, C, BlobNameSet, BodySet> ContainerNameSupport<'a>
     for PutAppendBlockBuilder<'a, C, No, BlobNameSet, BodySet>
 where
@@ -221,6 +237,7 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             append_position: self.append_position,
+            max_size: self.max_size,
         }
     }
 }
@@ -250,6 +267,7 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             append_position: self.append_position,
+            max_size: self.max_size,
         }
     }
 }
@@ -279,6 +297,7 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             append_position: self.append_position,
+            max_size: self.max_size,
         }
     }
 }
@@ -309,6 +328,7 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             append_position: self.append_position,
+            max_size: self.max_size,
         }
     }
 }
@@ -339,6 +359,7 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             append_position: self.append_position,
+            max_size: self.max_size,
         }
     }
 }
@@ -369,6 +390,7 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             append_position: self.append_position,
+            max_size: self.max_size,
         }
     }
 }
@@ -399,6 +421,7 @@ where
             if_match_condition: Some(if_match_condition),
             client_request_id: self.client_request_id,
             append_position: self.append_position,
+            max_size: self.max_size,
         }
     }
 }
@@ -429,6 +452,7 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: Some(client_request_id),
             append_position: self.append_position,
+            max_size: self.max_size,
         }
     }
 }
@@ -444,7 +468,7 @@ where
     type O = PutAppendBlockBuilder<'a, C, ContainerNameSet, BlobNameSet, BodySet>;
 
     #[inline]
-    fn with_append_position(self, append_position: u32) -> Self::O {
+    fn with_append_position(self, append_position: u64) -> Self::O {
         PutAppendBlockBuilder {
             client: self.client,
             p_container_name: PhantomData {},
@@ -459,6 +483,38 @@ where
             if_match_condition: self.if_match_condition,
             client_request_id: self.client_request_id,
             append_position: Some(append_position),
+            max_size: self.max_size,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet, BodySet> MaxSizeSupport
+    for PutAppendBlockBuilder<'a, C, ContainerNameSet, BlobNameSet, BodySet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BodySet: ToAssign,
+    C: Client,
+{
+    type O = PutAppendBlockBuilder<'a, C, ContainerNameSet, BlobNameSet, BodySet>;
+
+    #[inline]
+    fn with_max_size(self, max_size: u64) -> Self::O {
+        PutAppendBlockBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_body: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            body: self.body,
+            timeout: self.timeout,
+            content_md5: self.content_md5,
+            lease_id: self.lease_id,
+            if_match_condition: self.if_match_condition,
+            client_request_id: self.client_request_id,
+            append_position: self.append_position,
+            max_size: Some(max_size),
         }
     }
 }
@@ -492,6 +548,7 @@ where
                 request = IfMatchConditionOption::add_header(&self, request);
                 request = ClientRequestIdOption::add_header(&self, request);
                 request = AppendPositionOption::add_header(&self, request);
+                request = MaxSizeOption::add_header(&self, request);
                 request
             },
             Some(self.body()),
@@ -1,7 +1,9 @@
 use crate::blob::generate_blob_uri;
 use crate::blob::responses::PutBlockListResponse;
 use crate::blob::BlockList;
-use crate::blob::{BlockListRequired, BlockListSupport};
+use crate::blob::{
+    BlobContentMD5Option, BlobContentMD5Support, BlockListRequired, BlockListSupport,
+};
 use azure_sdk_core::add_content_md5_header;
 use azure_sdk_core::errors::{check_status_extract_headers_and_body, AzureError};
 use azure_sdk_core::lease::LeaseId;
@@ -35,6 +37,7 @@ where
     content_language: Option<&'a str>,
     cache_control: Option<&'a str>,
     content_disposition: Option<&'a str>,
+    blob_content_md5: Option<&'a [u8]>,
     metadata: Option<&'a HashMap<&'a str, &'a str>>,
     lease_id: Option<&'a LeaseId>,
     client_request_id: Option<&'a str>,
@@ -61,6 +64,7 @@ where
             content_language: None,
             cache_control: None,
             content_disposition: None,
+            blob_content_md5: None,
             metadata: None,
             lease_id: None,
             client_request_id: None,
@@ -218,6 +222,21 @@ where
     }
 }
 
+impl<'a, C, T, ContainerNameSet, BlobNameSet, BlockListSet> BlobContentMD5Option<'a>
+    for PutBlockListBuilder<'a, C, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    C: Client,
+    T: Borrow<[u8]> + 'a,
+{
+    #[inline]
+    fn blob_content_md5(&self) -> Option<&'a [u8]> {
+        self.blob_content_md5
+    }
+}
+
 impl<'a, C, T, ContainerNameSet, BlobNameSet, BlockListSet> MetadataOption<'a>
     for PutBlockListBuilder<'a, C, T, ContainerNameSet, BlobNameSet, BlockListSet>
 where
@@ -289,6 +308,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -322,6 +342,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -355,6 +376,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -389,6 +411,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -423,6 +446,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -457,6 +481,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -491,6 +516,7 @@ where
             content_language: Some(content_language),
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -525,6 +551,7 @@ where
             content_language: self.content_language,
             cache_control: Some(cache_control),
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -559,6 +586,42 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: Some(content_disposition),
+            blob_content_md5: self.blob_content_md5,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, T, ContainerNameSet, BlobNameSet, BlockListSet> BlobContentMD5Support<'a>
+    for PutBlockListBuilder<'a, C, T, ContainerNameSet, BlobNameSet, BlockListSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    BlockListSet: ToAssign,
+    C: Client,
+    T: Borrow<[u8]> + 'a,
+{
+    type O = PutBlockListBuilder<'a, C, T, ContainerNameSet, BlobNameSet, BlockListSet>;
+
+    #[inline]
+    fn with_blob_content_md5(self, blob_content_md5: &'a [u8]) -> Self::O {
+        PutBlockListBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            p_block_list: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_list: self.block_list,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            blob_content_md5: Some(blob_content_md5),
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -593,6 +656,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: Some(metadata),
             lease_id: self.lease_id,
             client_request_id: self.client_request_id,
@@ -627,6 +691,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: Some(lease_id),
             client_request_id: self.client_request_id,
@@ -661,6 +726,7 @@ where
             content_language: self.content_language,
             cache_control: self.cache_control,
             content_disposition: self.content_disposition,
+            blob_content_md5: self.blob_content_md5,
             metadata: self.metadata,
             lease_id: self.lease_id,
             client_request_id: Some(client_request_id),
@@ -711,6 +777,7 @@ where
                 request = add_content_md5_header(&md5[..], request);
                 request = CacheControlOption::add_header(&self, request);
                 request = ContentDispositionOption::add_header(&self, request);
+                request = BlobContentMD5Option::add_header(&self, request);
                 request = MetadataOption::add_header(&self, request);
                 request = LeaseIdOption::add_header(&self, request);
                 request = ClientRequestIdOption::add_header(&self, request);
@@ -0,0 +1,1026 @@
+use crate::blob::block_id;
+use crate::blob::requests::GetBlockListBuilder;
+use crate::blob::requests::{PutBlockBuilder, PutBlockListBuilder};
+use crate::blob::responses::UploadBlobFromReaderResponse;
+use crate::blob::retry::with_retries;
+use crate::blob::{BlobBlockType, BlockList};
+use crate::blob::{BlobContentMD5Support, BlockListSupport};
+use crate::blob::{BlockListType, BlockListTypeSupport, BlockWithSizeList};
+use crate::blob::{BlockSizeOption, BlockSizeSupport};
+use crate::blob::{MaxConcurrencyOption, MaxConcurrencySupport};
+use crate::blob::{MaxRetriesOption, MaxRetriesSupport};
+use crate::blob::{ResumeOption, ResumeSupport};
+use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::lease::LeaseId;
+use azure_sdk_core::prelude::*;
+use azure_sdk_core::{No, ToAssign, Yes};
+use azure_sdk_storage_core::prelude::*;
+use futures::io::{AllowStdIo, AsyncRead, AsyncReadExt};
+use futures::stream::{FuturesUnordered, StreamExt};
+use hyper::StatusCode;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::path::Path;
+
+// 4 MiB, the block size of the other Azure Storage SDKs.
+const DEFAULT_BLOCK_SIZE: u64 = 4 * 1024 * 1024;
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+// Limits of the service for the version in use (2019-07-07).
+const MAX_BLOCK_SIZE: u64 = 100 * 1024 * 1024;
+const MAX_BLOCK_COUNT: usize = 50_000;
+
+#[derive(Debug, Clone)]
+pub struct UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    client: &'a C,
+    p_container_name: PhantomData<ContainerNameSet>,
+    p_blob_name: PhantomData<BlobNameSet>,
+    container_name: Option<&'a str>,
+    blob_name: Option<&'a str>,
+    block_size: u64,
+    max_concurrency: usize,
+    max_retries: u32,
+    resume: bool,
+    timeout: Option<u64>,
+    content_type: Option<&'a str>,
+    content_encoding: Option<&'a str>,
+    content_language: Option<&'a str>,
+    cache_control: Option<&'a str>,
+    content_disposition: Option<&'a str>,
+    metadata: Option<&'a HashMap<&'a str, &'a str>>,
+    lease_id: Option<&'a LeaseId>,
+    client_request_id: Option<&'a str>,
+}
+
+impl<'a, C> UploadBlobFromReaderBuilder<'a, C, No, No>
+where
+    C: Client,
+{
+    #[inline]
+    pub(crate) fn new(client: &'a C) -> UploadBlobFromReaderBuilder<'a, C, No, No> {
+        UploadBlobFromReaderBuilder {
+            client,
+            p_container_name: PhantomData {},
+            container_name: None,
+            p_blob_name: PhantomData {},
+            blob_name: None,
+            block_size: DEFAULT_BLOCK_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            max_retries: DEFAULT_MAX_RETRIES,
+            resume: false,
+            timeout: None,
+            content_type: None,
+            content_encoding: None,
+            content_language: None,
+            cache_control: None,
+            content_disposition: None,
+            metadata: None,
+            lease_id: None,
+            client_request_id: None,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequired<'a, C>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn client(&self) -> &'a C {
+        self.client
+    }
+}
+
+//get mandatory no traits methods
+
+//set mandatory no traits methods
+impl<'a, C, BlobNameSet> ContainerNameRequired<'a>
+    for UploadBlobFromReaderBuilder<'a, C, Yes, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn container_name(&self) -> &'a str {
+        self.container_name.unwrap()
+    }
+}
+
+impl<'a, C, ContainerNameSet> BlobNameRequired<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, Yes>
+where
+    ContainerNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn blob_name(&self) -> &'a str {
+        self.blob_name.unwrap()
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> BlockSizeOption
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn block_size(&self) -> u64 {
+        self.block_size
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxConcurrencyOption
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxRetriesOption
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ResumeOption
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn resume(&self) -> bool {
+        self.resume
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> TimeoutOption
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentTypeOption<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn content_type(&self) -> Option<&'a str> {
+        self.content_type
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentEncodingOption<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn content_encoding(&self) -> Option<&'a str> {
+        self.content_encoding
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentLanguageOption<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn content_language(&self) -> Option<&'a str> {
+        self.content_language
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> CacheControlOption<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn cache_control(&self) -> Option<&'a str> {
+        self.cache_control
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentDispositionOption<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn content_disposition(&self) -> Option<&'a str> {
+        self.content_disposition
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MetadataOption<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn metadata(&self) -> Option<&'a HashMap<&'a str, &'a str>> {
+        self.metadata
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> LeaseIdOption<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn lease_id(&self) -> Option<&'a LeaseId> {
+        self.lease_id
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdOption<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    #[inline]
+    fn client_request_id(&self) -> Option<&'a str> {
+        self.client_request_id
+    }
+}
+
+impl<'a, C, BlobNameSet> ContainerNameSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, No, BlobNameSet>
+where
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, Yes, BlobNameSet>;
+
+    #[inline]
+    fn with_container_name(self, container_name: &'a str) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: Some(container_name),
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet> BlobNameSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, No>
+where
+    ContainerNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, Yes>;
+
+    #[inline]
+    fn with_blob_name(self, blob_name: &'a str) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: Some(blob_name),
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> BlockSizeSupport
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_block_size(self, block_size: u64) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxConcurrencySupport
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_max_concurrency(self, max_concurrency: usize) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MaxRetriesSupport
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_max_retries(self, max_retries: u32) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ResumeSupport
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_resume(self, resume: bool) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> TimeoutSupport
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_timeout(self, timeout: u64) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: Some(timeout),
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentTypeSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_type(self, content_type: &'a str) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: Some(content_type),
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentEncodingSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_encoding(self, content_encoding: &'a str) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: Some(content_encoding),
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentLanguageSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_language(self, content_language: &'a str) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: Some(content_language),
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> CacheControlSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_cache_control(self, cache_control: &'a str) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: Some(cache_control),
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ContentDispositionSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_content_disposition(self, content_disposition: &'a str) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: Some(content_disposition),
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> MetadataSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_metadata(self, metadata: &'a HashMap<&'a str, &'a str>) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: Some(metadata),
+            lease_id: self.lease_id,
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> LeaseIdSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_lease_id(self, lease_id: &'a LeaseId) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: Some(lease_id),
+            client_request_id: self.client_request_id,
+        }
+    }
+}
+
+impl<'a, C, ContainerNameSet, BlobNameSet> ClientRequestIdSupport<'a>
+    for UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>
+where
+    ContainerNameSet: ToAssign,
+    BlobNameSet: ToAssign,
+    C: Client,
+{
+    type O = UploadBlobFromReaderBuilder<'a, C, ContainerNameSet, BlobNameSet>;
+
+    #[inline]
+    fn with_client_request_id(self, client_request_id: &'a str) -> Self::O {
+        UploadBlobFromReaderBuilder {
+            client: self.client,
+            p_container_name: PhantomData {},
+            p_blob_name: PhantomData {},
+            container_name: self.container_name,
+            blob_name: self.blob_name,
+            block_size: self.block_size,
+            max_concurrency: self.max_concurrency,
+            max_retries: self.max_retries,
+            resume: self.resume,
+            timeout: self.timeout,
+            content_type: self.content_type,
+            content_encoding: self.content_encoding,
+            content_language: self.content_language,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            metadata: self.metadata,
+            lease_id: self.lease_id,
+            client_request_id: Some(client_request_id),
+        }
+    }
+}
+
+// methods callable only when every mandatory field has been filled
+impl<'a, C> UploadBlobFromReaderBuilder<'a, C, Yes, Yes>
+where
+    C: Client,
+{
+    /// Uploads the content of `reader` as a block blob, replacing the blob if it exists.
+    ///
+    /// The content is split in blocks of `block_size` bytes and up to `max_concurrency`
+    /// blocks are uploaded at once, each with its MD5 so that the service checks it. A block
+    /// that fails with a transient error is sent again, up to `max_retries` times. The
+    /// blocks are then committed with a single `put_block_list`, which also stores the MD5
+    /// of the whole content as the Content-MD5 of the blob. At most `max_concurrency`
+    /// blocks are kept in memory.
+    ///
+    /// The id of a block is made of its offset and of its MD5. With `resume` the
+    /// uncommitted blocks of the blob are listed first and the blocks found there are not
+    /// sent again, so an upload that was interrupted can be restarted with the same content
+    /// without uploading everything again. The service discards the uncommitted blocks
+    /// after a week.
+    #[inline]
+    pub async fn finalize_with_reader<R>(
+        self,
+        mut reader: R,
+    ) -> Result<UploadBlobFromReaderResponse, AzureError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let block_size = self.block_size();
+        if block_size == 0 || block_size > MAX_BLOCK_SIZE {
+            return Err(AzureError::InputParametersError(format!(
+                "the block size must be between 1 and {} bytes",
+                MAX_BLOCK_SIZE
+            )));
+        }
+        if self.max_concurrency() == 0 {
+            return Err(AzureError::InputParametersError(
+                "the maximum concurrency must be at least 1".to_owned(),
+            ));
+        }
+
+        let uploaded = if self.resume() {
+            self.uncommitted_blocks().await?
+        } else {
+            BlockWithSizeList::default()
+        };
+
+        let mut block_list = BlockList { blocks: Vec::new() };
+        let mut skipped_block_count = 0;
+        let mut content_md5 = md5::Context::new();
+        let mut content_length = 0;
+        let mut uploads = FuturesUnordered::new();
+        let mut end_of_content = false;
+
+        loop {
+            while !end_of_content && uploads.len() < self.max_concurrency() {
+                let mut block = Vec::with_capacity(block_size as usize);
+                (&mut reader)
+                    .take(block_size)
+                    .read_to_end(&mut block)
+                    .await?;
+                end_of_content = (block.len() as u64) < block_size;
+                if block.is_empty() {
+                    break;
+                }
+                if block_list.blocks.len() == MAX_BLOCK_COUNT {
+                    return Err(AzureError::InputParametersError(format!(
+                        "the content does not fit in {} blocks of {} bytes",
+                        MAX_BLOCK_COUNT, block_size
+                    )));
+                }
+
+                let offset = content_length;
+                content_md5.consume(&block);
+                content_length += block.len() as u64;
+                let block_md5 = md5::compute(&block);
+                let block_id = block_id(offset, &block_md5);
+                block_list
+                    .blocks
+                    .push(BlobBlockType::Uncommitted(block_id.clone()));
+                if uploaded.contains_uncommitted(&block_id, block.len() as u64) {
+                    debug!("block at offset {} already uploaded", offset);
+                    skipped_block_count += 1;
+                } else {
+                    uploads.push(self.put_block(block_id, block, block_md5));
+                }
+            }
+
+            match uploads.next().await {
+                Some(result) => result?,
+                None => break,
+            }
+        }
+
+        let content_md5 = content_md5.compute();
+        let response = with_retries(self.max_retries(), || {
+            self.put_block_list(&block_list, &content_md5[..])
+                .finalize()
+        })
+        .await?;
+        Ok(UploadBlobFromReaderResponse::new(
+            response,
+            content_length,
+            content_md5.0,
+            block_list.blocks.len(),
+            skipped_block_count,
+        ))
+    }
+
+    /// Uploads the file at `path` as a block blob, as `finalize_with_reader` does. The file
+    /// is read with blocking reads, one block at a time.
+    #[inline]
+    pub async fn finalize_with_file<P>(
+        self,
+        path: P,
+    ) -> Result<UploadBlobFromReaderResponse, AzureError>
+    where
+        P: AsRef<Path>,
+    {
+        let file = std::fs::File::open(path)?;
+        self.finalize_with_reader(AllowStdIo::new(file)).await
+    }
+
+    fn put_block_list<'b>(
+        &'b self,
+        block_list: &'b BlockList<Vec<u8>>,
+        content_md5: &'b [u8],
+    ) -> PutBlockListBuilder<'b, C, Vec<u8>, Yes, Yes, Yes> {
+        let mut request = PutBlockListBuilder::new(self.client())
+            .with_container_name(self.container_name())
+            .with_blob_name(self.blob_name())
+            .with_block_list(block_list)
+            .with_blob_content_md5(content_md5);
+        if let Some(timeout) = self.timeout() {
+            request = request.with_timeout(timeout);
+        }
+        if let Some(content_type) = self.content_type() {
+            request = request.with_content_type(content_type);
+        }
+        if let Some(content_encoding) = self.content_encoding() {
+            request = request.with_content_encoding(content_encoding);
+        }
+        if let Some(content_language) = self.content_language() {
+            request = request.with_content_language(content_language);
+        }
+        if let Some(cache_control) = self.cache_control() {
+            request = request.with_cache_control(cache_control);
+        }
+        if let Some(content_disposition) = self.content_disposition() {
+            request = request.with_content_disposition(content_disposition);
+        }
+        if let Some(metadata) = self.metadata() {
+            request = request.with_metadata(metadata);
+        }
+        if let Some(lease_id) = self.lease_id() {
+            request = request.with_lease_id(lease_id);
+        }
+        if let Some(client_request_id) = self.client_request_id() {
+            request = request.with_client_request_id(client_request_id);
+        }
+        request
+    }
+
+    // The uncommitted blocks of the blob, none if the blob does not exist.
+    async fn uncommitted_blocks(&self) -> Result<BlockWithSizeList<Vec<u8>>, AzureError> {
+        let response = with_retries(self.max_retries(), || {
+            let mut request = GetBlockListBuilder::new(self.client())
+                .with_container_name(self.container_name())
+                .with_blob_name(self.blob_name())
+                .with_block_list_type(BlockListType::Uncommitted);
+            if let Some(timeout) = self.timeout() {
+                request = request.with_timeout(timeout);
+            }
+            if let Some(lease_id) = self.lease_id() {
+                request = request.with_lease_id(lease_id);
+            }
+            if let Some(client_request_id) = self.client_request_id() {
+                request = request.with_client_request_id(client_request_id);
+            }
+            request.finalize()
+        })
+        .await;
+
+        match response {
+            Ok(response) => Ok(response.block_with_size_list),
+            Err(AzureError::UnexpectedHTTPResult(ref result))
+                if result.status_code() == StatusCode::NOT_FOUND =>
+            {
+                Ok(BlockWithSizeList::default())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn put_block(
+        &self,
+        block_id: Vec<u8>,
+        block: Vec<u8>,
+        block_md5: md5::Digest,
+    ) -> Result<(), AzureError> {
+        with_retries(self.max_retries(), || {
+            let mut request = PutBlockBuilder::new(self.client())
+                .with_container_name(self.container_name())
+                .with_blob_name(self.blob_name())
+                .with_block_id(&block_id)
+                .with_body(&block)
+                .with_content_md5(&block_md5[..]);
+            if let Some(timeout) = self.timeout() {
+                request = request.with_timeout(timeout);
+            }
+            if let Some(lease_id) = self.lease_id() {
+                request = request.with_lease_id(lease_id);
+            }
+            if let Some(client_request_id) = self.client_request_id() {
+                request = request.with_client_request_id(client_request_id);
+            }
+            request.finalize()
+        })
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadBlobResponse {
+    pub etag: String,
+    pub last_modified: Option<DateTime<Utc>>,
+    /// Size of the blob, in bytes.
+    pub content_length: u64,
+    /// MD5 of the whole content, including the part already there when resuming.
+    pub content_md5: [u8; 16],
+    /// True if the blob has a Content-MD5 and it matches `content_md5`.
+    pub content_md5_checked: bool,
+    /// Number of bytes found in the file and not downloaded again.
+    pub resumed_length: u64,
+}
@@ -1,7 +1,10 @@
 use crate::blob::Blob;
 use azure_sdk_core::errors::AzureError;
+use azure_sdk_core::headers::BLOB_CONTENT_MD5;
+use azure_sdk_core::util::HeaderMapExt;
 use azure_sdk_core::{date_from_headers, request_id_from_headers, RequestId};
 use chrono::{DateTime, Utc};
+use http::header::CONTENT_RANGE;
 use http::HeaderMap;
 
 #[derive(Debug, Clone)]
@@ -10,6 +13,10 @@ pub struct GetBlobResponse {
     pub request_id: RequestId,
     pub data: Vec<u8>,
     pub date: DateTime<Utc>,
+    /// Length of the whole blob, even when only a range has been requested.
+    pub blob_content_length: u64,
+    /// MD5 of the whole blob, returned when only a range has been requested.
+    pub blob_content_md5: Option<String>,
 }
 
 impl GetBlobResponse {
@@ -23,11 +30,40 @@ impl GetBlobResponse {
         let request_id = request_id_from_headers(headers)?;
         let date = date_from_headers(headers)?;
 
+        let blob_content_length = match headers.get_as_str(CONTENT_RANGE) {
+            Some(content_range) => total_length_from_content_range(content_range)?,
+            None => blob.content_length,
+        };
+        let blob_content_md5 = headers.get_as_string(BLOB_CONTENT_MD5);
+
         Ok(GetBlobResponse {
             blob,
             request_id,
             data: body.to_vec(),
             date,
+            blob_content_length,
+            blob_content_md5,
         })
     }
 }
+
+// The Content-Range header has the form "bytes <start>-<end>/<total length>".
+fn total_length_from_content_range(content_range: &str) -> Result<u64, AzureError> {
+    let total_length = content_range.rsplit('/').next().unwrap_or_default();
+    Ok(total_length.parse::<u64>()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_content_range() {
+        assert_eq!(
+            total_length_from_content_range("bytes 0-1023/4096").unwrap(),
+            4096
+        );
+        assert_eq!(total_length_from_content_range("bytes 0-0/1").unwrap(), 1);
+        assert!(total_length_from_content_range("bytes 0-1023/*").is_err());
+    }
+}
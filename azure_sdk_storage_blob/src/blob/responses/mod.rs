@@ -30,3 +30,7 @@ mod break_blob_lease_response;
 pub use self::break_blob_lease_response::BreakBlobLeaseResponse;
 mod copy_blob_from_url_response;
 pub use copy_blob_from_url_response::CopyBlobFromUrlResponse;
+mod upload_blob_from_reader_response;
+pub use self::upload_blob_from_reader_response::UploadBlobFromReaderResponse;
+mod download_blob_response;
+pub use self::download_blob_response::DownloadBlobResponse;
@@ -0,0 +1,42 @@
+use crate::blob::responses::PutBlockListResponse;
+use azure_sdk_core::RequestId;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadBlobFromReaderResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub request_id: RequestId,
+    pub date: DateTime<Utc>,
+    pub request_server_encrypted: bool,
+    /// Size of the uploaded blob, in bytes.
+    pub content_length: u64,
+    /// MD5 of the whole content, also stored as the Content-MD5 of the blob.
+    pub content_md5: [u8; 16],
+    /// Number of blocks the blob is made of.
+    pub block_count: usize,
+    /// Number of blocks not sent because a previous upload left them on the service.
+    pub skipped_block_count: usize,
+}
+
+impl UploadBlobFromReaderResponse {
+    pub(crate) fn new(
+        response: PutBlockListResponse,
+        content_length: u64,
+        content_md5: [u8; 16],
+        block_count: usize,
+        skipped_block_count: usize,
+    ) -> UploadBlobFromReaderResponse {
+        UploadBlobFromReaderResponse {
+            etag: response.etag,
+            last_modified: response.last_modified,
+            request_id: response.request_id,
+            date: response.date,
+            request_server_encrypted: response.request_server_encrypted,
+            content_length,
+            content_md5,
+            block_count,
+            skipped_block_count,
+        }
+    }
+}
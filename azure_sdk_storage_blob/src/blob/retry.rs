@@ -0,0 +1,80 @@
+use async_timer::new_timer;
+use azure_sdk_core::errors::AzureError;
+use hyper::StatusCode;
+use std::future::Future;
+use std::time::Duration;
+
+// Wait before the first retry, doubled at every retry.
+const BASE_WAIT: Duration = Duration::from_millis(500);
+const MAX_WAIT: Duration = Duration::from_secs(30);
+
+// Runs `operation` and runs it again, up to `max_retries` times, while it fails with an
+// error that can be transient: a connection error, a timeout, throttling or a server
+// error. The other errors are returned at once.
+pub(crate) async fn with_retries<F, Fut, T>(max_retries: u32, operation: F) -> Result<T, AzureError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, AzureError>>,
+{
+    let mut retry = 0;
+    loop {
+        match operation().await {
+            Err(error) if retry < max_retries && is_transient(&error) => {
+                let wait = (BASE_WAIT * 2u32.saturating_pow(retry)).min(MAX_WAIT);
+                warn!(
+                    "retrying in {:?} ({} of {}) after error: {}",
+                    wait,
+                    retry + 1,
+                    max_retries,
+                    error
+                );
+                new_timer(wait).await;
+                retry += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_transient(error: &AzureError) -> bool {
+    match error {
+        AzureError::HyperError(_) | AzureError::IOError(_) => true,
+        AzureError::UnexpectedHTTPResult(result) => {
+            let status = result.status_code();
+            status == StatusCode::REQUEST_TIMEOUT
+                || status == StatusCode::TOO_MANY_REQUESTS
+                || status.is_server_error()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use azure_sdk_core::errors::UnexpectedHTTPResult;
+    use std::cell::Cell;
+
+    fn http_error(status: StatusCode) -> AzureError {
+        AzureError::UnexpectedHTTPResult(UnexpectedHTTPResult::new(StatusCode::OK, status, ""))
+    }
+
+    #[test]
+    fn transient_errors() {
+        assert!(is_transient(&http_error(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(is_transient(&http_error(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(!is_transient(&http_error(StatusCode::BAD_REQUEST)));
+        assert!(!is_transient(&http_error(StatusCode::NOT_FOUND)));
+    }
+
+    #[test]
+    fn non_transient_errors_are_not_retried() {
+        let attempts = Cell::new(0);
+        let result: Result<(), _> = futures::executor::block_on(with_retries(3, || {
+            attempts.set(attempts.get() + 1);
+            async { Err(http_error(StatusCode::CONFLICT)) }
+        }));
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+    }
+}
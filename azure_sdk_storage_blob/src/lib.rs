@@ -52,6 +52,12 @@ where
     fn generate_signed_blob_url<'a>(
         &'a self,
     ) -> blob::requests::SignedUrlBuilder<'a, C, No, No, No>;
+    fn upload_blob_from_reader<'a>(
+        &'a self,
+    ) -> blob::requests::UploadBlobFromReaderBuilder<'a, C, No, No>;
+    fn download_blob<'a>(&'a self) -> blob::requests::DownloadBlobBuilder<'a, C, No, No>;
+    fn blob_reader<'a>(&'a self) -> blob::requests::BlobReaderBuilder<'a, C, No, No>;
+    fn blob_writer<'a>(&'a self) -> blob::requests::BlobWriterBuilder<'a, C, No, No>;
 }
 
 pub trait Container<C>
@@ -175,6 +181,24 @@ where
     ) -> blob::requests::SignedUrlBuilder<'a, C, No, No, No> {
         blob::requests::SignedUrlBuilder::new(self)
     }
+
+    fn upload_blob_from_reader<'a>(
+        &'a self,
+    ) -> blob::requests::UploadBlobFromReaderBuilder<'a, C, No, No> {
+        blob::requests::UploadBlobFromReaderBuilder::new(self)
+    }
+
+    fn download_blob<'a>(&'a self) -> blob::requests::DownloadBlobBuilder<'a, C, No, No> {
+        blob::requests::DownloadBlobBuilder::new(self)
+    }
+
+    fn blob_reader<'a>(&'a self) -> blob::requests::BlobReaderBuilder<'a, C, No, No> {
+        blob::requests::BlobReaderBuilder::new(self)
+    }
+
+    fn blob_writer<'a>(&'a self) -> blob::requests::BlobWriterBuilder<'a, C, No, No> {
+        blob::requests::BlobWriterBuilder::new(self)
+    }
 }
 
 impl<C> Container<C> for C
@@ -1,6 +1,8 @@
 pub use crate::blob::{
-    BlobBlockType, BlockList, BlockListRequired, BlockListSupport, BlockListType,
-    BlockListTypeRequired, BlockListTypeSupport,
+    BlobBlockType, BlobContentMD5Option, BlobContentMD5Support, BlockList, BlockListRequired,
+    BlockListSupport, BlockListType, BlockListTypeRequired, BlockListTypeSupport, BlockSizeOption,
+    BlockSizeSupport, MaxConcurrencyOption, MaxConcurrencySupport, MaxRetriesOption,
+    MaxRetriesSupport, ResumeOption, ResumeSupport,
 };
 pub use crate::container::{
     PublicAccess, PublicAccessRequired, PublicAccessSupport, StoredAccessPolicyListOption,
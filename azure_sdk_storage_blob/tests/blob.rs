@@ -269,6 +269,419 @@ async fn put_and_get_block_list() {
     println!("container {} deleted!", container.name);
 }
 
+#[tokio::test]
+async fn upload_blob_from_reader() {
+    let u = Uuid::new_v4();
+    let container = Container::new(&format!("sdkrust{}", u));
+    let name = "uploaded.bin";
+
+    let client = initialize();
+
+    client
+        .create_container()
+        .with_container_name(&container.name)
+        .with_public_access(PublicAccess::None)
+        .finalize()
+        .await
+        .unwrap();
+
+    // three full blocks and a partial one
+    let data = (0..3 * 1024 * 1024 + 1000)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<_>>();
+    let res = client
+        .upload_blob_from_reader()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_block_size(1024 * 1024)
+        .with_max_concurrency(2)
+        .with_content_type("application/octet-stream")
+        .finalize_with_reader(futures::io::Cursor::new(data.clone()))
+        .await
+        .unwrap();
+    assert_eq!(res.content_length, data.len() as u64);
+    assert_eq!(res.block_count, 4);
+    assert_eq!(res.content_md5, md5::compute(&data).0);
+
+    let blob = client
+        .get_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .finalize()
+        .await
+        .unwrap();
+    assert_eq!(blob.data, data);
+    assert_eq!(
+        blob.blob.content_md5,
+        Some(base64::encode(&res.content_md5[..]))
+    );
+
+    client
+        .delete_container()
+        .with_container_name(&container.name)
+        .finalize()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn upload_blob_from_reader_resume() {
+    let u = Uuid::new_v4();
+    let container = Container::new(&format!("sdkrust{}", u));
+    let name = "resumed.bin";
+
+    let client = initialize();
+
+    client
+        .create_container()
+        .with_container_name(&container.name)
+        .with_public_access(PublicAccess::None)
+        .finalize()
+        .await
+        .unwrap();
+
+    let data = (0..2 * 1024 * 1024 + 1000)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<_>>();
+
+    // the first block left over by an interrupted upload
+    let first_block = &data[..1024 * 1024];
+    let block_id = format!("{:015}-{:x}", 0, md5::compute(first_block));
+    client
+        .put_block()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_body(first_block)
+        .with_block_id(block_id.as_bytes())
+        .finalize()
+        .await
+        .unwrap();
+
+    let res = client
+        .upload_blob_from_reader()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_block_size(1024 * 1024)
+        .with_resume(true)
+        .finalize_with_reader(futures::io::Cursor::new(data.clone()))
+        .await
+        .unwrap();
+    assert_eq!(res.block_count, 3);
+    assert_eq!(res.skipped_block_count, 1);
+
+    let blob = client
+        .get_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .finalize()
+        .await
+        .unwrap();
+    assert_eq!(blob.data, data);
+
+    client
+        .delete_container()
+        .with_container_name(&container.name)
+        .finalize()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn download_blob() {
+    let u = Uuid::new_v4();
+    let container = Container::new(&format!("sdkrust{}", u));
+    let name = "downloaded.bin";
+    let empty_name = "empty.bin";
+
+    let client = initialize();
+
+    client
+        .create_container()
+        .with_container_name(&container.name)
+        .with_public_access(PublicAccess::None)
+        .finalize()
+        .await
+        .unwrap();
+
+    let data = (0..3 * 1024 * 1024 + 1000)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<_>>();
+    client
+        .upload_blob_from_reader()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_block_size(1024 * 1024)
+        .finalize_with_reader(futures::io::Cursor::new(data.clone()))
+        .await
+        .unwrap();
+
+    let mut downloaded = futures::io::Cursor::new(Vec::new());
+    let res = client
+        .download_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_chunk_size(1024 * 1024)
+        .with_max_concurrency(2)
+        .finalize_to_writer(&mut downloaded)
+        .await
+        .unwrap();
+    assert_eq!(downloaded.into_inner(), data);
+    assert_eq!(res.content_length, data.len() as u64);
+    assert!(res.content_md5_checked);
+
+    // a file with the beginning of the blob, as left by an interrupted download
+    let path = std::env::temp_dir().join(format!("sdkrust{}.bin", u));
+    std::fs::write(&path, &data[..1500 * 1024]).unwrap();
+    // its content cannot be verified without the ETag of the blob it comes from
+    assert!(client
+        .download_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_resume(true)
+        .finalize_to_file(&path)
+        .await
+        .is_err());
+    assert_eq!(std::fs::read(&path).unwrap().len(), 1500 * 1024);
+    let res = client
+        .download_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_chunk_size(1024 * 1024)
+        .with_resume(true)
+        .with_if_match_condition(IfMatchCondition::Match(&res.etag))
+        .finalize_to_file(&path)
+        .await
+        .unwrap();
+    assert_eq!(res.resumed_length, 1500 * 1024);
+    assert!(res.content_md5_checked);
+    assert_eq!(std::fs::read(&path).unwrap(), data);
+    std::fs::remove_file(&path).unwrap();
+
+    client
+        .put_block_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(empty_name)
+        .with_body(&[])
+        .finalize()
+        .await
+        .unwrap();
+    let mut downloaded = futures::io::Cursor::new(Vec::new());
+    let res = client
+        .download_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(empty_name)
+        .finalize_to_writer(&mut downloaded)
+        .await
+        .unwrap();
+    assert!(downloaded.into_inner().is_empty());
+    assert_eq!(res.content_length, 0);
+
+    client
+        .delete_container()
+        .with_container_name(&container.name)
+        .finalize()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn blob_reader() {
+    use futures::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+
+    let u = Uuid::new_v4();
+    let container = Container::new(&format!("sdkrust{}", u));
+    let name = "read.bin";
+
+    let client = initialize();
+
+    client
+        .create_container()
+        .with_container_name(&container.name)
+        .with_public_access(PublicAccess::None)
+        .finalize()
+        .await
+        .unwrap();
+
+    let data = (0..3 * 1024 * 1024 + 1000)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<_>>();
+    client
+        .upload_blob_from_reader()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .finalize_with_reader(futures::io::Cursor::new(data.clone()))
+        .await
+        .unwrap();
+
+    let mut reader = client
+        .blob_reader()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_chunk_size(1024 * 1024)
+        .finalize()
+        .await
+        .unwrap();
+    assert_eq!(reader.content_length(), data.len() as u64);
+
+    let mut read = Vec::new();
+    reader.read_to_end(&mut read).await.unwrap();
+    assert_eq!(read, data);
+
+    let mut tail = [0; 1000];
+    let position = reader.seek(SeekFrom::End(-1000)).await.unwrap();
+    reader.read_exact(&mut tail).await.unwrap();
+    assert_eq!(&tail[..], &data[position as usize..]);
+
+    let mut middle = [0; 10];
+    reader.seek(SeekFrom::Start(1500 * 1024)).await.unwrap();
+    reader.read_exact(&mut middle).await.unwrap();
+    assert_eq!(&middle[..], &data[1500 * 1024..1500 * 1024 + 10]);
+
+    // the blob changes: the next range that is not buffered cannot be read
+    client
+        .put_block_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(name)
+        .with_body(&data)
+        .finalize()
+        .await
+        .unwrap();
+    reader.seek(SeekFrom::Start(0)).await.unwrap();
+    assert!(reader.read_exact(&mut middle).await.is_err());
+
+    client
+        .delete_container()
+        .with_container_name(&container.name)
+        .finalize()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn blob_writer() {
+    use futures::io::AsyncWriteExt;
+
+    let u = Uuid::new_v4();
+    let container = Container::new(&format!("sdkrust{}", u));
+    let block_blob_name = "written.bin";
+    let append_blob_name = "appended.log";
+
+    let client = initialize();
+
+    client
+        .create_container()
+        .with_container_name(&container.name)
+        .with_public_access(PublicAccess::None)
+        .finalize()
+        .await
+        .unwrap();
+
+    let data = (0..2 * 1024 * 1024 + 1000)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<_>>();
+
+    let mut writer = client
+        .blob_writer()
+        .with_container_name(&container.name)
+        .with_blob_name(block_blob_name)
+        .with_block_size(1024 * 1024)
+        .with_content_type("application/octet-stream")
+        .finalize_block_blob()
+        .unwrap();
+    for chunk in data.chunks(100_000) {
+        writer.write_all(chunk).await.unwrap();
+    }
+    writer.close().await.unwrap();
+    assert_eq!(writer.position(), data.len() as u64);
+
+    let blob = client
+        .get_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(block_blob_name)
+        .finalize()
+        .await
+        .unwrap();
+    assert_eq!(blob.data, data);
+    assert_eq!(
+        blob.blob.content_md5,
+        Some(base64::encode(&md5::compute(&data)[..]))
+    );
+
+    // the first writer creates the append blob, the second one appends after it
+    let mut writer = client
+        .blob_writer()
+        .with_container_name(&container.name)
+        .with_blob_name(append_blob_name)
+        .with_block_size(1000)
+        .finalize_append_blob()
+        .await
+        .unwrap();
+    writer.write_all(b"first line\n").await.unwrap();
+    writer.flush().await.unwrap();
+    writer.write_all(&data[..2500]).await.unwrap();
+    writer.close().await.unwrap();
+
+    let mut writer = client
+        .blob_writer()
+        .with_container_name(&container.name)
+        .with_blob_name(append_blob_name)
+        .with_max_size(3000)
+        .finalize_append_blob()
+        .await
+        .unwrap();
+    assert_eq!(writer.position(), 2511);
+    writer.write_all(b"last line\n").await.unwrap();
+    writer.close().await.unwrap();
+
+    let blob = client
+        .get_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(append_blob_name)
+        .finalize()
+        .await
+        .unwrap();
+    let mut expected = b"first line\n".to_vec();
+    expected.extend_from_slice(&data[..2500]);
+    expected.extend_from_slice(b"last line\n");
+    assert_eq!(blob.data, expected);
+
+    // appending past the maximum size fails
+    let mut writer = client
+        .blob_writer()
+        .with_container_name(&container.name)
+        .with_blob_name(append_blob_name)
+        .with_max_size(3000)
+        .finalize_append_blob()
+        .await
+        .unwrap();
+    writer.write_all(&data[..1000]).await.unwrap();
+    assert!(writer.close().await.is_err());
+
+    // an empty block blob is not appended to either
+    client
+        .put_block_blob()
+        .with_container_name(&container.name)
+        .with_blob_name(block_blob_name)
+        .with_body(&[])
+        .finalize()
+        .await
+        .unwrap();
+    assert!(client
+        .blob_writer()
+        .with_container_name(&container.name)
+        .with_blob_name(block_blob_name)
+        .finalize_append_blob()
+        .await
+        .is_err());
+
+    client
+        .delete_container()
+        .with_container_name(&container.name)
+        .finalize()
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn list_containers() {
     let client = initialize();